@@ -1,5 +1,4 @@
-// Currently `stacks-encoding-native-js` does not support creating/serializing Clarity values, so use the `stacks.js` library instead
-import * as stacksJs from '@stacks/transactions';
+import { encodeClarityValue, ClarityTypeID } from 'stacks-encoding-native-js';
 import * as assert from 'node:assert';
 
 /*
@@ -13,24 +12,32 @@ import * as assert from 'node:assert';
   (tags (list u"contributor" u"og \u{e2ad90}" u"clarity"))
 )
 */
-const cvTuple = stacksJs.tupleCV({
-  active: stacksJs.trueCV(),
-  address: stacksJs.standardPrincipalCV('SPA2MZWV9N67TBYVWTE0PSSKMJ2F6YXW7CBE6YPW'),
-  alias: stacksJs.stringAsciiCV('Alice'),
-  balance: stacksJs.uintCV(2000n),
-  ping: stacksJs.responseOkCV(stacksJs.intCV(250)),
-  public_key: stacksJs.someCV(
-    stacksJs.bufferCV(Buffer.from('02d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e58', 'hex'))
-  ),
-  tags: stacksJs.listCV([
-    stacksJs.stringUtf8CV('contributor'),
-    stacksJs.stringUtf8CV('og ⭐'),
-    stacksJs.stringUtf8CV('clarity'),
-  ]),
+const cvHex = encodeClarityValue({
+  type_id: ClarityTypeID.Tuple,
+  data: {
+    active: { type_id: ClarityTypeID.BoolTrue },
+    address: { type_id: ClarityTypeID.PrincipalStandard, address: 'SPA2MZWV9N67TBYVWTE0PSSKMJ2F6YXW7CBE6YPW' },
+    alias: { type_id: ClarityTypeID.StringAscii, data: 'Alice' },
+    balance: { type_id: ClarityTypeID.UInt, value: '2000' },
+    ping: { type_id: ClarityTypeID.ResponseOk, value: { type_id: ClarityTypeID.Int, value: '250' } },
+    public_key: {
+      type_id: ClarityTypeID.OptionalSome,
+      value: {
+        type_id: ClarityTypeID.Buffer,
+        buffer: '0x02d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e58',
+      },
+    },
+    tags: {
+      type_id: ClarityTypeID.List,
+      list: [
+        { type_id: ClarityTypeID.StringUtf8, data: 'contributor' },
+        { type_id: ClarityTypeID.StringUtf8, data: 'og ⭐' },
+        { type_id: ClarityTypeID.StringUtf8, data: 'clarity' },
+      ],
+    },
+  },
 });
 
-const cvHex = stacksJs.cvToHex(cvTuple);
-
 const expected = '0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479';
 assert.strictEqual(cvHex, expected);
 
@@ -0,0 +1,75 @@
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#ifndef STACKS_CODEC_H
+#define STACKS_CODEC_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Free a string returned by any `stacks_codec_*` function. Passing null is a no-op.
+void stacks_codec_string_free(char *s);
+
+// The git version of the library.
+char *stacks_codec_version(void);
+
+// Decode a serialized transaction to JSON, with its `tx_id` alongside the transaction fields.
+char *stacks_codec_decode_transaction(const uint8_t *data,
+                                      size_t len,
+                                      char **error_out);
+
+// Decode a serialized Clarity value to JSON.
+char *stacks_codec_decode_clarity_value(const uint8_t *data,
+                                        size_t len,
+                                        char **error_out);
+
+// Decode a serialized Clarity value to its Clarity repr string (not JSON).
+char *stacks_codec_decode_clarity_value_to_repr(const uint8_t *data,
+                                                size_t len,
+                                                char **error_out);
+
+// Decode serialized post conditions, prefixed with the post condition mode byte and a 4 byte
+// count, to JSON.
+char *stacks_codec_decode_post_conditions(const uint8_t *data,
+                                          size_t len,
+                                          char **error_out);
+
+// Decode a serialized Stacks 2.x block to JSON.
+char *stacks_codec_decode_stacks_block(const uint8_t *data,
+                                       size_t len,
+                                       char **error_out);
+
+// Decode a serialized Nakamoto block (Stacks 3.x+) to JSON.
+char *stacks_codec_decode_nakamoto_block(const uint8_t *data,
+                                         size_t len,
+                                         char **error_out);
+
+// Decode a serialized microblock to JSON.
+char *stacks_codec_decode_microblock(const uint8_t *data,
+                                     size_t len,
+                                     char **error_out);
+
+// Whether a NUL-terminated string is a valid c32 Stacks address.
+bool stacks_codec_is_valid_stacks_address(const char *address);
+
+// Decode a c32 Stacks address to JSON `{"version": number, "hash160": "0x.."}`.
+char *stacks_codec_decode_stacks_address(const char *address,
+                                         char **error_out);
+
+// Encode a c32 Stacks address from a version byte and a 20 byte hash160.
+char *stacks_codec_stacks_address_from_parts(uint8_t version,
+                                             const uint8_t *hash160,
+                                             size_t len,
+                                             char **error_out);
+
+// Convert a c32 Stacks address to the equivalent base58check Bitcoin address.
+char *stacks_codec_stacks_to_bitcoin_address(const char *address,
+                                             char **error_out);
+
+// Convert a base58check Bitcoin address to the equivalent c32 Stacks address.
+char *stacks_codec_bitcoin_to_stacks_address(const char *address,
+                                             char **error_out);
+
+#endif /* STACKS_CODEC_H */
@@ -3,6 +3,17 @@ export * from './loader';
 export const StacksNativeEncodingBindings = bindings;
 export default StacksNativeEncodingBindings;
 
+/** The `code` of a `DeserializeError`. */
+export type DeserializeErrorCode =
+  | 'UnexpectedEof'
+  | 'BadTypePrefix'
+  | 'TooDeep'
+  | 'IllegalLength'
+  | 'InvalidName'
+  | 'UnknownPayloadId'
+  | 'NonCanonical'
+  | 'InvalidValue';
+
 export type TxPostCondition = PostConditionStx | PostConditionFungible | PostConditionNonfungible;
 
 export interface DecodedPostConditionsResult {
@@ -348,6 +359,23 @@ export interface TxAuthFieldSignature {
     signature: string;
 }
 
+export interface TxSpendingConditionVerification {
+    /** True if the public keys hash to the spending condition's `signer`. */
+    valid: boolean;
+    /** Hex encoded public keys in field order; for single-sig, the key recovered from the signature. */
+    public_keys: string[];
+    /** Reason verification failed, or null if valid. */
+    error: string | null;
+}
+
+export interface TxVerificationResult {
+    /** True if the origin and (if sponsored) sponsor conditions are both valid. */
+    valid: boolean;
+    origin: TxSpendingConditionVerification;
+    /** Null for non-sponsored transactions. */
+    sponsor: TxSpendingConditionVerification | null;
+}
+
 export interface TxMicroblockHeader {
     /** Hex string */
     buffer: string;
@@ -401,6 +429,140 @@ export interface ClarityValueCommon {
   hex: string;
 }
 
+export interface DecodeNumbersOptions {
+    /**
+     * Output Clarity `int`/`uint` values and transaction amounts, fees and nonces as decimal
+     * strings (the default) or as native `bigint` values.
+     */
+    numbers?: 'string' | 'bigint';
+}
+
+/**
+ * The shape of a decoded result when decoded with `numbers: 'bigint'`: Clarity `int`/`uint`
+ * values and the `amount`, `nonce` and `tx_fee` properties are `bigint` rather than strings.
+ */
+export type BigIntNumbers<T> =
+    T extends { type_id: ClarityTypeID.Int | ClarityTypeID.UInt; value: string } ? Omit<T, 'value'> & { value: bigint }
+    : T extends Error ? T
+    : T extends (infer U)[] ? BigIntNumbers<U>[]
+    : T extends object ? {
+        [K in keyof T]: K extends 'amount' | 'nonce' | 'tx_fee'
+            ? (T[K] extends string ? bigint : BigIntNumbers<T[K]>)
+            : BigIntNumbers<T[K]>
+    }
+    : T;
+
+/** The decoded result type `T` for the given options. */
+export type DecodedNumbers<T, O> = O extends { numbers: 'bigint' } ? BigIntNumbers<T> : T;
+
+export interface DecodeOutputOptions extends DecodeNumbersOptions {
+    /**
+     * The shape of decoded Clarity values:
+     * - `full` (the default): `repr`, `hex` and `type_id` on every value, as in `ClarityValue`
+     * - `minimal`: no `repr` and `hex` on any value, see `ClarityValueMinimal`. Much faster for
+     *   large values. Transaction function args and post-condition asset values are decoded deep.
+     * - `json`: the `{type, value}` convention of the Stacks API, see `ClarityValueJson`
+     */
+    profile?: 'full' | 'minimal' | 'json';
+}
+
+/**
+ * A Clarity value decoded with `profile: 'json'`, in the `{type, value}` convention of the Stacks
+ * API (`cvToJSON` in stacks.js).
+ */
+export interface ClarityValueJson {
+    /** Clarity type, e.g. `uint`, `(buff 32)` or `(list 2 (optional uint))` */
+    type: string;
+    /**
+     * Integers are string-quoted (or `bigint`), buffers are 0x-prefixed hex strings, principals
+     * are addresses, optionals and responses hold the inner value (`null` for `none`), lists an
+     * array and tuples an object of values.
+     */
+    value: string | bigint | boolean | null | ClarityValueJson | ClarityValueJson[] | { [key: string]: ClarityValueJson };
+    /** Only on responses: true for `ok`, false for `err` */
+    success?: boolean;
+}
+
+/** A Clarity value decoded with `profile: 'minimal'`: no `repr` and `hex` on any value. */
+export type ClarityValueMinimal<T = ClarityValue> =
+    T extends (infer U)[] ? ClarityValueMinimal<U>[]
+    : T extends object ? { [K in keyof T as Exclude<K, 'repr' | 'hex'>]: ClarityValueMinimal<T[K]> }
+    : T;
+
+type WithProfileValues<T, P> =
+    T extends ClarityValueAbstract
+        ? (P extends 'json' ? ClarityValueJson : ClarityValueMinimal<T extends ClarityValue ? T : ClarityValue>)
+    : T extends (infer U)[] ? WithProfileValues<U, P>[]
+    : T extends object ? { [K in keyof T]: WithProfileValues<T[K], P> }
+    : T;
+
+/** The decoded result type `T` with its Clarity values in the shape of the options' `profile`. */
+export type DecodedProfile<T, O> =
+    O extends { profile: 'json' | 'minimal' } ? WithProfileValues<T, O['profile']> : T;
+
+export interface DecodeClarityValueOptions extends DecodeOutputOptions {
+    /**
+     * Only accept canonical encodings, rejecting everything the stacks-core consensus deserializer
+     * rejects: empty tuples, duplicate or out-of-order tuple fields, invalid tuple field and
+     * contract names, invalid UTF-8, non-printable ASCII, lists whose items have no common type,
+     * and trailing bytes. Principal versions of 32 or more are rejected in every mode. Errors
+     * include the byte offset of the offending input.
+     */
+    strict?: boolean;
+}
+
+/**
+ * Path to a nested Clarity value, e.g. `data.amount` or `items[2].some`, or as an array of tuple
+ * field names and list indexes, e.g. `['items', 2, 'some']`. Use `some`, `ok` and `err` to unwrap
+ * optionals and responses.
+ */
+export type ClarityValuePath = string | (string | number)[];
+
+export interface DecodeClarityValueAtPathOptions extends DecodeNumbersOptions {
+    /**
+     * Return the selected value as a decoded object (the default, as in `decodeClarityValue`), a
+     * repr string (as in `decodeClarityValueToRepr`), or its serialized hex string.
+     */
+    format?: 'object' | 'repr' | 'hex';
+}
+
+export interface DecodeClarityValueToNativeOptions {
+    /**
+     * Output `int`/`uint` values as native `bigint` values (the default) or as decimal strings.
+     */
+    numbers?: 'string' | 'bigint';
+    /**
+     * Output buffers as 0x-prefixed hex strings (the default) or as `Uint8Array`s.
+     */
+    buffers?: 'hex' | 'uint8array';
+    /**
+     * Output the inner value of `ok` and `err` responses directly rather than as `{ ok: value }` or
+     * `{ err: value }`. Note that this loses the distinction between `ok` and `err`.
+     */
+    unwrapResponses?: boolean;
+}
+
+/**
+ * A Clarity value decoded to a plain JS value by `decodeClarityValueToNative`:
+ * - `int`, `uint`: `bigint`, or a decimal string with `numbers: 'string'`
+ * - `bool`: `boolean`
+ * - `buff`: 0x-prefixed hex string, or `Uint8Array` with `buffers: 'uint8array'`
+ * - `string-ascii`, `string-utf8`: `string`
+ * - `principal`: c32 address string, e.g. `SP000000000000000000002Q6VF78.pox-4`
+ * - `optional`: the inner value for `some`, `null` for `none`
+ * - `response`: `{ ok: value }` or `{ err: value }`, or the inner value with `unwrapResponses`
+ * - `list`: array
+ * - `tuple`: object
+ */
+export type ClarityNativeValue =
+    | bigint
+    | string
+    | boolean
+    | Uint8Array
+    | null
+    | ClarityNativeValue[]
+    | { [key: string]: ClarityNativeValue };
+
 export interface ClarityValueAbstract extends ClarityValueCommon {
   type_id: number;
 }
@@ -540,15 +702,63 @@ export type ClarityValueOptionalBool = ClarityValueOptional<ClarityValueBool>;
  */
 export type ClarityValueOptionalUInt = ClarityValueOptional<ClarityValueUInt>;
 
+/**
+ * Clarity value object accepted by `encodeClarityValue`. This is the same shape returned by
+ * `decodeClarityValue`, except the `repr` and `hex` properties are not required.
+ */
+export type ClarityValueInput =
+    | { type_id: ClarityTypeID.Int | ClarityTypeID.UInt; value: string | number | bigint }
+    | { type_id: ClarityTypeID.BoolTrue | ClarityTypeID.BoolFalse }
+    | { type_id: ClarityTypeID.Buffer; buffer: string | Buffer }
+    | { type_id: ClarityTypeID.List; list: ClarityValueInput[] }
+    | { type_id: ClarityTypeID.StringAscii | ClarityTypeID.StringUtf8; data: string }
+    | { type_id: ClarityTypeID.PrincipalStandard; address: string }
+    | { type_id: ClarityTypeID.PrincipalContract; address: string; contract_name: string }
+    | { type_id: ClarityTypeID.Tuple; data: { [key: string]: ClarityValueInput } }
+    | { type_id: ClarityTypeID.OptionalNone }
+    | {
+        type_id: ClarityTypeID.OptionalSome | ClarityTypeID.ResponseOk | ClarityTypeID.ResponseError;
+        value: ClarityValueInput;
+      };
+
+/**
+ * A Clarity type in the JSON format of a contract ABI, as returned by `/v2/contracts/interface`.
+ * `none` is the type of a branch that can never hold a value, e.g. `(response bool none)`.
+ */
+export type ClarityAbiType =
+    | 'int128'
+    | 'uint128'
+    | 'bool'
+    | 'principal'
+    | 'trait_reference'
+    | 'none'
+    | { buffer: { length: number } }
+    | { 'string-ascii': { length: number } }
+    | { 'string-utf8': { length: number } }
+    | { optional: ClarityAbiType }
+    | { response: { ok: ClarityAbiType; error: ClarityAbiType } }
+    | { list: { type: ClarityAbiType; length: number } }
+    | { tuple: { name: string; type: ClarityAbiType }[] };
+
+/**
+ * A value decoded by `decodeClarityValueWithType`. Each nested value also has a `type` property.
+ */
+export type ClarityValueWithType<T extends ClarityValue = ClarityValue> = T & {
+    /** The declared Clarity type signature of the value, e.g. `(optional uint)` */
+    type: string;
+};
+
 // ============================================================================
 // Nakamoto Block Types (Stacks 3.x+)
 // ============================================================================
 
-export interface DecodedNakamotoBlockResult {
+export interface DecodedNakamotoBlockResult<Tx = DecodedTxResult> {
     /** Hex encoded string of the block ID (index block hash) */
     block_id: string;
+    /** Whether `header.tx_merkle_root` matches the transactions. Only set if `verifyMerkleRoot` is true. */
+    merkle_root_valid?: boolean;
     header: NakamotoBlockHeader;
-    txs: DecodedTxResult[];
+    txs: Tx[];
 }
 
 export interface NakamotoBlockHeader {
@@ -579,6 +789,56 @@ export interface NakamotoBlockHeader {
     index_block_hash: string;
 }
 
+export interface TxMerkleProof {
+    /** Index of the transaction in the block */
+    tx_index: number;
+    /** Hex string (32 bytes) - the block's tx_merkle_root */
+    merkle_root: string;
+    /** Sibling hashes from the leaf up to the root */
+    path: TxMerklePathPoint[];
+}
+
+export interface TxMerklePathPoint {
+    /** Whether the sibling hash is hashed to the left or the right of the running hash */
+    order: 'left' | 'right';
+    /** Hex string (32 bytes) */
+    hash: string;
+}
+
+export interface RewardSetSigner {
+    /** Hex string or Buffer (33 bytes) - compressed secp256k1 signing key */
+    signing_key: string | Buffer;
+    weight: number;
+}
+
+export interface SignerSignaturesVerificationResult {
+    /** True if every signature is from a known signer, in order, and the signed weight meets the threshold */
+    threshold_met: boolean;
+    /** Sum of the weights of all signers in the reward set */
+    total_weight: number;
+    /** Sum of the weights of the signers that signed */
+    signed_weight: number;
+    /** 70% of `total_weight`, rounded up */
+    threshold: number;
+    /** The reward set signers that signed, in signature order */
+    signers: {
+        /** Index of the signer in the reward set */
+        index: number;
+        /** Hex string (33 bytes) */
+        signing_key: string;
+        weight: number;
+    }[];
+    /** The first unrecoverable, unknown, duplicate, or out-of-order signature, or null */
+    error: string | null;
+}
+
+export interface MinerSignatureVerificationResult {
+    /** True if the recovered miner public key hashes to the given hash160 */
+    valid: boolean;
+    /** Hex string (33 bytes) - compressed public key recovered from `miner_signature` */
+    miner_public_key: string;
+}
+
 export interface BitVec {
     /** Number of bits */
     len: number;
@@ -592,11 +852,13 @@ export interface BitVec {
 // Stacks 2.x Block Types
 // ============================================================================
 
-export interface DecodedStacksBlockResult {
+export interface DecodedStacksBlockResult<Tx = DecodedTxResult> {
     /** Hex encoded string of the block hash */
     block_hash: string;
+    /** Whether `header.tx_merkle_root` matches the transactions. Only set if `verifyMerkleRoot` is true. */
+    merkle_root_valid?: boolean;
     header: StacksBlockHeader;
-    txs: DecodedTxResult[];
+    txs: Tx[];
 }
 
 export interface StacksBlockHeader {
@@ -627,6 +889,25 @@ export interface StacksWorkScore {
     work: string;
 }
 
+export interface DecodedMicroblockResult<Tx = DecodedTxResult> {
+    /** Hex encoded string of the microblock hash */
+    block_hash: string;
+    /** Hex string (20 bytes) - hash160 of the public key recovered from the signature, or null if the signature is invalid */
+    signer_pubkey_hash: string | null;
+    /** Whether `header.tx_merkle_root` matches the transactions. Only set if `verifyMerkleRoot` is true. */
+    merkle_root_valid?: boolean;
+    header: TxMicroblockHeader;
+    txs: Tx[];
+}
+
+export interface DecodedMicroblockStreamResult<Tx = DecodedTxResult> {
+    /** True if the stream has no errors */
+    valid: boolean;
+    /** Description of each sequence, chaining, signer, or merkle root problem found in the stream */
+    errors: string[];
+    microblocks: DecodedMicroblockResult<Tx>[];
+}
+
 // ============================================================================
 // PoX Synthetic Event Types
 // ============================================================================
@@ -854,3 +1135,153 @@ export type DecodedPoxSyntheticEvent =
     | PoxEventStackAggregationCommitIndexed
     | PoxEventStackAggregationIncrease
     | PoxEventRevokeDelegateStx;
+
+/**
+ * A transaction of a stacks-node event observer `/new_block` message, see `decodeNewBlockMessage`.
+ * Other properties of the message are passed through as-is.
+ */
+export interface NewBlockTransaction<Tx = DecodedTxResult, Value = ClarityValue> {
+    txid: string;
+    tx_index: number;
+    status: string;
+    /** Hex encoded transaction, `0x00` for burnchain operations */
+    raw_tx: string;
+    /** The decoded `raw_tx`, or null for burnchain operations */
+    decoded_tx: Tx | null;
+    /** Hex encoded Clarity value of the transaction result */
+    raw_result: string;
+    decoded_result: Value;
+    [key: string]: unknown;
+}
+
+/** An event with a serialized Clarity value, e.g. a `contract_event` or `nft_mint_event`. */
+export interface NewBlockEventValue<Value = ClarityValue> {
+    /** Hex encoded Clarity value */
+    raw_value: string;
+    /** The decoded `raw_value` */
+    decoded_value: Value;
+    [key: string]: unknown;
+}
+
+/**
+ * An event of a `/new_block` message. Each `*_event` object with a `raw_value` (contract and NFT
+ * events) has the decoded value added as `decoded_value`.
+ */
+export interface NewBlockEvent<Value = ClarityValue> {
+    txid: string;
+    event_index: number;
+    committed: boolean;
+    type: string;
+    contract_event?: NewBlockEventValue<Value> & { contract_identifier: string; topic: string };
+    nft_transfer_event?: NewBlockEventValue<Value> & { asset_identifier: string; sender: string; recipient: string };
+    nft_mint_event?: NewBlockEventValue<Value> & { asset_identifier: string; recipient: string };
+    nft_burn_event?: NewBlockEventValue<Value> & { asset_identifier: string; sender: string };
+    [key: string]: unknown;
+}
+
+/** A stacks-node event observer `/new_block` message, with its transactions and Clarity values decoded. */
+export interface DecodedNewBlockMessage<Tx = DecodedTxResult, Value = ClarityValue> {
+    transactions: NewBlockTransaction<Tx, Value>[];
+    events: NewBlockEvent<Value>[];
+    [key: string]: unknown;
+}
+
+// ============================================================================
+// Token Event Types
+// ============================================================================
+
+export enum TokenEventName {
+    FtTransfer = 'ft_transfer',
+    FtMint = 'ft_mint',
+    FtBurn = 'ft_burn',
+    NftTransfer = 'nft_transfer',
+    NftMint = 'nft_mint',
+    NftBurn = 'nft_burn',
+}
+
+/** The parts of an asset identifier such as `SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex` */
+export interface TokenAssetIdentifier {
+    /** The contract principal defining the token */
+    contract_id: string;
+    contract_address: string;
+    contract_name: string;
+    asset_name: string;
+}
+
+export interface FtTransferEvent {
+    name: TokenEventName.FtTransfer;
+    asset: TokenAssetIdentifier;
+    sender: string;
+    recipient: string;
+    /** String-quoted unsigned integer */
+    amount: string;
+}
+
+export interface FtMintEvent {
+    name: TokenEventName.FtMint;
+    asset: TokenAssetIdentifier;
+    recipient: string;
+    /** String-quoted unsigned integer */
+    amount: string;
+}
+
+export interface FtBurnEvent {
+    name: TokenEventName.FtBurn;
+    asset: TokenAssetIdentifier;
+    sender: string;
+    /** String-quoted unsigned integer */
+    amount: string;
+}
+
+export interface NftTransferEvent {
+    name: TokenEventName.NftTransfer;
+    asset: TokenAssetIdentifier;
+    sender: string;
+    recipient: string;
+    /** The decoded `raw_value` identifying the token */
+    value: ClarityValue;
+}
+
+export interface NftMintEvent {
+    name: TokenEventName.NftMint;
+    asset: TokenAssetIdentifier;
+    recipient: string;
+    /** The decoded `raw_value` identifying the token */
+    value: ClarityValue;
+}
+
+export interface NftBurnEvent {
+    name: TokenEventName.NftBurn;
+    asset: TokenAssetIdentifier;
+    sender: string;
+    /** The decoded `raw_value` identifying the token */
+    value: ClarityValue;
+}
+
+export type DecodedTokenEvent =
+    | FtTransferEvent
+    | FtMintEvent
+    | FtBurnEvent
+    | NftTransferEvent
+    | NftMintEvent
+    | NftBurnEvent;
+
+/** The arguments of a SIP-010 `transfer` contract call */
+export interface DecodedSip010Transfer {
+    /** String-quoted unsigned integer */
+    amount: string;
+    sender: string;
+    recipient: string;
+    /** The memo normalized to a printable string as with `memoToString`, or null if no memo was given */
+    memo: string | null;
+    /** Hex string or null */
+    memo_hex: string | null;
+}
+
+/** A memo printed by a SIP-010 `transfer` */
+export interface DecodedSip010Memo {
+    /** The memo normalized to a printable string as with `memoToString` */
+    memo: string;
+    /** Hex string */
+    memo_hex: string;
+}
@@ -1,28 +1,175 @@
-import type { DecodedPostConditionsResult, DecodedTxResult, DecodedNakamotoBlockResult, DecodedStacksBlockResult, ClarityValue, ClarityValueAbstract, DecodedPoxSyntheticEvent } from ".";
+import type { DecodedPostConditionsResult, DecodedTxResult, TxVerificationResult, DecodedNakamotoBlockResult, DecodedStacksBlockResult, DecodedMicroblockResult, DecodedMicroblockStreamResult, TxMerkleProof, RewardSetSigner, SignerSignaturesVerificationResult, MinerSignatureVerificationResult, ClarityValue, ClarityValueAbstract, DecodeClarityValueOptions, DecodeNumbersOptions, DecodeOutputOptions, DecodedNumbers, DecodedProfile, BigIntNumbers, ClarityValuePath, DecodeClarityValueAtPathOptions, DecodeClarityValueToNativeOptions, ClarityNativeValue, ClarityValueInput, ClarityValueWithType, ClarityAbiType, DecodedPoxSyntheticEvent, DecodedNewBlockMessage, DecodedTokenEvent, DecodedSip010Transfer, DecodedSip010Memo, DeserializeErrorCode, TxSpendingConditionSingleSigHashMode, TxSpendingConditionMultiSigHashMode } from ".";
 
 export function getVersion(): string;
 
-export function decodeTransaction(arg: string | Buffer): DecodedTxResult;
+/**
+ * Thrown by the decode functions when the input is malformed.
+ */
+export declare class DeserializeError extends Error {
+  name: 'DeserializeError';
+  /** The kind of failure, e.g. `UnexpectedEof` for truncated input. */
+  code: DeserializeErrorCode;
+  /** Byte offset of the decoder's cursor in the input when the failure occurred. */
+  offset: number | null;
+  /** Path to the failing value, e.g. `payload.function_args[2].tuple.amount`, or an empty string for the top-level value. */
+  path: string;
+}
+
+/**
+ * @param options - Set `numbers: 'bigint'` to output the amount, fee and nonce fields as `bigint`,
+ * and `profile` for the shape of the Clarity function args, see `DecodeOutputOptions`
+ */
+export function decodeTransaction<O extends DecodeOutputOptions = {}>(arg: string | Buffer, options?: O): DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>;
+
+/**
+ * Like `decodeTransaction`, but the transaction is deserialized on the libuv threadpool rather
+ * than on the main thread. The promise rejects with a `DeserializeError` for malformed input.
+ */
+export function decodeTransactionAsync<O extends DecodeOutputOptions = {}>(arg: string | Buffer, options?: O): Promise<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>;
+
+/**
+ * Decode many transactions at once, deserializing them in parallel on a Rust thread pool.
+ * @returns An array with, for each input, either the decoded transaction or the `DeserializeError`
+ * it failed with. Malformed transactions don't fail the whole batch.
+ */
+export function decodeTransactions<O extends DecodeOutputOptions = {}>(txs: (string | Buffer)[], options?: O): (DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O> | DeserializeError)[];
+
+/**
+ * Like `decodeTransactions`, but the input is a single buffer: a u32 count followed by each
+ * transaction prefixed with its u32 byte length. Throws a `DeserializeError` if the framing itself
+ * is truncated.
+ */
+export function decodeTransactionList<O extends DecodeOutputOptions = {}>(arg: string | Buffer, options?: O): (DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O> | DeserializeError)[];
+
+/**
+ * Serialize a transaction from the object shape returned by `decodeTransaction`.
+ * Derived properties (`tx_id`, `post_conditions_buffer`, `function_args_buffer`, Clarity `repr`)
+ * are ignored. Clarity values are read from their `hex` property when present, otherwise from
+ * the deep shape accepted by `encodeClarityValue`.
+ * @returns 0x-prefixed hex string of the serialized transaction
+ */
+export function encodeTransaction(tx: DecodedTxResult | BigIntNumbers<DecodedTxResult>): string;
+
+/**
+ * Verify the origin (and sponsor) signatures of a serialized transaction. The sighash chain is
+ * recomputed, the public key is recovered from each signature, and the keys are hashed under the
+ * declared hash mode and compared against the spending condition's `signer`.
+ */
+export function verifyTransaction(arg: string | Buffer): TxVerificationResult;
 
 /**
  * Decode a Nakamoto block (Stacks 3.x+).
  * The input should be the raw binary block data as returned by /v3/blocks/{block_id} endpoint.
  * @param arg - Hex string or Buffer containing the raw block data
+ * @param verifyMerkleRoot - If true, compute the merkle root over the txids and set `merkle_root_valid`
+ * @param options - Output options for the decoded transactions, as for `decodeTransaction`
+ */
+export function decodeNakamotoBlock<O extends DecodeOutputOptions = {}>(arg: string | Buffer, verifyMerkleRoot?: boolean, options?: O): DecodedNakamotoBlockResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>;
+
+/**
+ * Like `decodeNakamotoBlock`, but the block is deserialized (and its merkle root verified) on the
+ * libuv threadpool, so large blocks don't block the event loop.
  */
-export function decodeNakamotoBlock(arg: string | Buffer): DecodedNakamotoBlockResult;
+export function decodeNakamotoBlockAsync<O extends DecodeOutputOptions = {}>(arg: string | Buffer, verifyMerkleRoot?: boolean, options?: O): Promise<DecodedNakamotoBlockResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>>;
 
 /**
  * Decode a Stacks 2.x block.
  * The input should be the raw binary block data as returned by /v2/blocks/{block_id} endpoint.
  * @param arg - Hex string or Buffer containing the raw block data
+ * @param verifyMerkleRoot - If true, compute the merkle root over the txids and set `merkle_root_valid`
+ * @param options - Output options for the decoded transactions, as for `decodeTransaction`
+ */
+export function decodeStacksBlock<O extends DecodeOutputOptions = {}>(arg: string | Buffer, verifyMerkleRoot?: boolean, options?: O): DecodedStacksBlockResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>;
+
+/**
+ * Like `decodeStacksBlock`, but the block is deserialized (and its merkle root verified) on the
+ * libuv threadpool, so large blocks don't block the event loop.
+ */
+export function decodeStacksBlockAsync<O extends DecodeOutputOptions = {}>(arg: string | Buffer, verifyMerkleRoot?: boolean, options?: O): Promise<DecodedStacksBlockResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>>;
+
+/**
+ * Decode a Stacks 2.x microblock, compute its hash, and recover the hash160 of its signer's public
+ * key (to compare with the anchor block's `microblock_pubkey_hash`).
+ * @param arg - Hex string or Buffer containing the raw microblock data
+ * @param verifyMerkleRoot - If true, compute the merkle root over the txids and set `merkle_root_valid`
+ * @param options - Output options for the decoded transactions, as for `decodeTransaction`
+ */
+export function decodeMicroblock<O extends DecodeOutputOptions = {}>(arg: string | Buffer, verifyMerkleRoot?: boolean, options?: O): DecodedMicroblockResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>;
+
+/**
+ * Decode a stream of Stacks 2.x microblocks, serialized as a u32 count followed by the microblocks.
+ * The stream is checked for `sequence` numbers counting up from 0, `prev_block` chaining, a single
+ * signer, and valid tx merkle roots. Problems are reported in `errors` rather than thrown.
+ * @param arg - Hex string or Buffer containing the serialized microblock stream
+ * @param parentBlockHash - If given, the first microblock's `prev_block` must match this anchor block hash
+ * @param microblockPubkeyHash - If given, the anchor block's `microblock_pubkey_hash` that each microblock must be signed by
+ * @param options - Output options for the decoded transactions, as for `decodeTransaction`
+ */
+export function decodeMicroblockStream<O extends DecodeOutputOptions = {}>(
+  arg: string | Buffer,
+  parentBlockHash?: string | Buffer | null,
+  microblockPubkeyHash?: string | Buffer | null,
+  options?: O
+): DecodedMicroblockStreamResult<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>>;
+
+/**
+ * Build a merkle inclusion proof for a transaction in a block. Throws if the txid is not in the
+ * block, or if the block's `tx_merkle_root` does not match its transactions.
+ * @param block - Hex string or Buffer containing the raw block data
+ * @param txid - Hex string or Buffer of the 32-byte txid
+ * @param blockType - `nakamoto` for Stacks 3.x+ blocks, `stacks` for Stacks 2.x blocks
+ */
+export function getTxMerkleProof(block: string | Buffer, txid: string | Buffer, blockType: 'nakamoto' | 'stacks'): TxMerkleProof;
+
+/**
+ * Check a merkle inclusion proof produced by `getTxMerkleProof` against its `merkle_root`.
+ */
+export function verifyTxMerkleProof(txid: string | Buffer, proof: TxMerkleProof): boolean;
+
+/**
+ * Recover each signer key of a Nakamoto block from its signer signature hash (the block hash),
+ * map it to the reward set, and check whether the signed weight meets the 70% threshold.
+ * As in stacks-core, signatures must appear in reward set order.
+ * @param block - Hex string or Buffer containing a raw Nakamoto block or block header
+ * @param rewardSet - The signers of the block's reward cycle, e.g. from /v3/stacker_set
+ */
+export function verifyNakamotoBlockSignerSignatures(block: string | Buffer, rewardSet: RewardSetSigner[]): SignerSignaturesVerificationResult;
+
+/**
+ * Recover the miner public key of a Nakamoto block from its `miner_signature`, and check that it
+ * hashes to the given hash160 (e.g. of the key that signed the tenure's `TenureChange` tx).
+ * @param block - Hex string or Buffer containing a raw Nakamoto block or block header
+ * @param minerPubkeyHash - Hex string or Buffer of the 20-byte miner public key hash160
  */
-export function decodeStacksBlock(arg: string | Buffer): DecodedStacksBlockResult;
+export function verifyNakamotoBlockMinerSignature(block: string | Buffer, minerPubkeyHash: string | Buffer): MinerSignatureVerificationResult;
 
 export function decodeClarityValueToRepr(arg: string | Buffer): string;
 
+/**
+ * Infer the type signature of a serialized Clarity value, e.g. `(list 2 (optional uint))`.
+ * List item types are the least supertype of the items; `none` and the missing branch of a
+ * response are `UnknownType`. Throws if a list has items with no common type.
+ */
 export function decodeClarityValueToTypeName(arg: string | Buffer): string;
 
-export function decodeClarityValue<T extends ClarityValue = ClarityValue>(arg: string | Buffer): T;
+/**
+ * Decode a serialized Clarity value to an object.
+ * @param arg - Hex string or Buffer containing the serialized Clarity value
+ * @param options - See `DecodeClarityValueOptions`
+ */
+export function decodeClarityValue<T extends ClarityValue = ClarityValue, O extends DecodeClarityValueOptions = {}>(arg: string | Buffer, options?: O): DecodedNumbers<DecodedProfile<T, O>, O>;
+
+/**
+ * Decode a serialized Clarity value against a declared type, e.g. a function's argument or return
+ * type from a contract ABI. Throws if the value is not admitted by the type (wrong type, lengths
+ * over the declared maximums, or tuple fields that differ). Each decoded value gets a `type`
+ * property with its declared type, so `none` and `err` branches carry the full type.
+ * @param arg - Hex string or Buffer containing the serialized Clarity value
+ * @param typeSignature - A type in Clarity syntax, e.g. `(list 10 (optional uint))`, or an ABI
+ * type object (or its JSON string), e.g. `{ list: { type: { optional: 'uint128' }, length: 10 } }`
+ * @param options - See `DecodeNumbersOptions`
+ */
+export function decodeClarityValueWithType<T extends ClarityValue = ClarityValue, O extends DecodeNumbersOptions = {}>(arg: string | Buffer, typeSignature: string | ClarityAbiType, options?: O): DecodedNumbers<ClarityValueWithType<T>, O>;
 
 /**
  * 
@@ -32,7 +179,7 @@ export function decodeClarityValue<T extends ClarityValue = ClarityValue>(arg: s
  * not contain decoded children.
  * TODO: fix the clarity result type definition to be more accurate.
  */
-export function decodeClarityValueList(arg: string | Buffer, deep?: false | undefined): ClarityValueAbstract[];
+export function decodeClarityValueList(arg: string | Buffer, deep?: false | undefined, options?: DecodeNumbersOptions): ClarityValueAbstract[];
 
 /**
  * 
@@ -42,9 +189,49 @@ export function decodeClarityValueList(arg: string | Buffer, deep?: false | unde
  * not contain decoded children.
  * TODO: fix the clarity result type definition to be more accurate.
  */
-export function decodeClarityValueList(arg: string | Buffer, deep: true): ClarityValue[];
+export function decodeClarityValueList<O extends DecodeNumbersOptions = {}>(arg: string | Buffer, deep: true, options?: O): DecodedNumbers<ClarityValue, O>[];
 
-export function decodePostConditions(arg: string | Buffer): DecodedPostConditionsResult;
+/**
+ * Decode only the nested value at a path, e.g. the `amount` field of a print event tuple. Values
+ * before it are skipped without being decoded, and anything after it is not read.
+ * @param arg - Hex string or Buffer containing the serialized Clarity value
+ * @param path - E.g. `data.amount`, `items[2]` or `['items', 2]`, see `ClarityValuePath`
+ * @param options - See `DecodeClarityValueAtPathOptions`
+ * @returns The selected value, or null if there is no value at the path (e.g. a missing tuple
+ * field, an out-of-range list index, or `some` on a `none`)
+ */
+export function decodeClarityValueAtPath<T extends ClarityValue = ClarityValue, O extends DecodeClarityValueAtPathOptions & { format?: 'object' } = {}>(arg: string | Buffer, path: ClarityValuePath, options?: O): DecodedNumbers<T, O> | null;
+export function decodeClarityValueAtPath(arg: string | Buffer, path: ClarityValuePath, options: DecodeClarityValueAtPathOptions & { format: 'repr' | 'hex' }): string | null;
+
+/**
+ * Decode a serialized Clarity value to plain JS values, e.g. tuples to objects and lists to arrays,
+ * without the `type_id`, `repr` and `hex` properties of `decodeClarityValue`.
+ * @param arg - Hex string or Buffer containing the serialized Clarity value
+ * @param options - See `DecodeClarityValueToNativeOptions`
+ * @returns See `ClarityNativeValue`
+ */
+export function decodeClarityValueToNative<T extends ClarityNativeValue = ClarityNativeValue>(arg: string | Buffer, options?: DecodeClarityValueToNativeOptions): T;
+
+/**
+ * Serialize a Clarity value to its consensus wire format.
+ * @param value - A Clarity value object in the same shape returned by `decodeClarityValue`.
+ * The `repr` and `hex` properties are ignored and may be omitted.
+ * @returns 0x-prefixed hex string of the serialized Clarity value
+ */
+export function encodeClarityValue(value: ClarityValueInput): string;
+
+/**
+ * Parse a Clarity literal in the format returned by `decodeClarityValueToRepr`, e.g.
+ * `(tuple (active true) (tags (list u"a" u"b")))`, and serialize it.
+ * Errors include the line and column of the offending input.
+ * @returns 0x-prefixed hex string of the serialized Clarity value
+ */
+export function parseClarityRepr(repr: string): string;
+
+/**
+ * @param options - Set `numbers: 'bigint'` to output the post-condition amounts as `bigint`
+ */
+export function decodePostConditions<O extends DecodeNumbersOptions = {}>(arg: string | Buffer, options?: O): DecodedNumbers<DecodedPostConditionsResult, O>;
 
 export function stacksToBitcoinAddress(stackAddress: string): string;
 
@@ -58,6 +245,27 @@ export function decodeClarityValueToPrincipal(clarityValue: string | Buffer) : s
 
 export function stacksAddressFromParts(version: number, hash160: string | Buffer): string;
 
+/**
+ * Derive the Stacks address of a single-sig public key (compressed or uncompressed; P2WPKH
+ * requires compressed).
+ */
+export function publicKeyToStacksAddress(
+  publicKey: string | Buffer,
+  hashMode: TxSpendingConditionSingleSigHashMode,
+  network: 'mainnet' | 'testnet' | 'devnet' | 'mocknet'
+): string;
+
+/**
+ * Derive the Stacks address of an m-of-n multisig key set. Keys are hashed in the given order,
+ * which must match the order of the `PublicKey`/`Signature` auth fields.
+ */
+export function multisigToStacksAddress(
+  publicKeys: (string | Buffer)[],
+  signaturesRequired: number,
+  hashMode: TxSpendingConditionMultiSigHashMode,
+  network: 'mainnet' | 'testnet' | 'devnet' | 'mocknet'
+): string;
+
 export function memoToString(memo: string | Buffer): string;
 
 /**
@@ -71,6 +279,49 @@ export function decodePoxSyntheticEvent(
   network: 'mainnet' | 'testnet' | 'devnet' | 'mocknet'
 ): DecodedPoxSyntheticEvent | null;
 
+/**
+ * Decode a stacks-node event observer `/new_block` message in one call. The JSON is parsed in Rust
+ * and returned as an object with every serialized value decoded alongside it: `decoded_tx` for
+ * each `transactions[].raw_tx`, `decoded_result` for each `transactions[].raw_result`, and
+ * `decoded_value` for the `raw_value` of contract and NFT events. Throws a `DeserializeError`
+ * whose `path` points at the failing value, e.g. `events[3].contract_event.raw_value`.
+ * @param message - The request body of the `/new_block` event observer endpoint
+ * @param options - Output options for the decoded transactions and Clarity values, as for
+ * `decodeTransaction`
+ */
+export function decodeNewBlockMessage<O extends DecodeOutputOptions = {}>(
+  message: string,
+  options?: O
+): DecodedNewBlockMessage<DecodedNumbers<DecodedProfile<DecodedTxResult, O>, O>, DecodedNumbers<DecodedProfile<ClarityValue, O>, O>>;
+
+/**
+ * Decode a fungible or non-fungible token event of a `/new_block` message into a typed event,
+ * splitting the `asset_identifier` and decoding the NFT `raw_value`.
+ * @param event - An item of the `events` array, e.g. `{ type: 'ft_transfer_event', ft_transfer_event: {...} }`
+ * @param options - Output options for the FT `amount` and the NFT value, as for `decodeClarityValue`
+ * @returns The decoded token event, or null if the event is not an `ft_*` or `nft_*` event
+ */
+export function decodeTokenEvent<O extends DecodeOutputOptions = {}>(
+  event: { type: string; [key: string]: unknown },
+  options?: O
+): DecodedNumbers<DecodedProfile<DecodedTokenEvent, O>, O> | null;
+
+/**
+ * Decode the arguments of a SIP-010 `transfer` contract call,
+ * `(amount uint) (sender principal) (recipient principal) (memo (optional (buff 34)))`.
+ * Throws if the arguments do not match this signature.
+ * @param arg - Hex string or Buffer of the serialized arguments, i.e. the `function_args_buffer`
+ * of a decoded contract call payload
+ */
+export function decodeSip010TransferArgs(arg: string | Buffer): DecodedSip010Transfer;
+
+/**
+ * Decode the memo printed by a SIP-010 `transfer`, given as a `(buff 34)` or `(some (buff 34))`.
+ * @param arg - Hex string or Buffer containing the serialized Clarity value of the `print` event
+ * @returns The decoded memo, or null if the value is not a memo buffer
+ */
+export function decodeSip010PrintMemo(arg: string | Buffer): DecodedSip010Memo | null;
+
 export function startProfiler(): string;
 
 export function stopProfiler(): Buffer;
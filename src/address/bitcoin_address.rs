@@ -5,11 +5,15 @@ pub const ADDRESS_VERSION_MAINNET_MULTISIG: u8 = 5;
 pub const ADDRESS_VERSION_TESTNET_SINGLESIG: u8 = 111;
 pub const ADDRESS_VERSION_TESTNET_MULTISIG: u8 = 196;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BitcoinAddressType {
     PublicKeyHash,
     ScriptHash,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BitcoinNetworkType {
     Mainnet,
     Testnet,
@@ -17,9 +21,12 @@ pub enum BitcoinNetworkType {
     Regtest,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BitcoinAddress {
     pub addrtype: BitcoinAddressType,
     pub network_id: BitcoinNetworkType,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub hash160_bytes: [u8; 20],
 }
 
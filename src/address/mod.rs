@@ -1,22 +1,30 @@
+#[cfg(feature = "nodejs")]
+use std::convert::TryFrom;
 use std::io::{Cursor, Read};
 
 use byteorder::ReadBytesExt;
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
 #[cfg(feature = "profiling")]
 use neon::types::buffer::TypedArray;
 
 use crate::clarity_value::deserialize::TypePrefix;
 use crate::clarity_value::types::{ClarityName, StandardPrincipalData};
+#[cfg(feature = "nodejs")]
 use crate::hex::encode_hex;
-use crate::neon_util::{arg_as_bytes, arg_as_bytes_copied};
+#[cfg(feature = "nodejs")]
+use crate::neon_util::{arg_as_bytes, arg_as_bytes_copied, arg_as_uint, value_as_bytes_copied};
+#[cfg(feature = "nodejs")]
+use crate::pox_events::types::StacksNetwork;
 
 use self::bitcoin_address::{
     BitcoinAddress, ADDRESS_VERSION_MAINNET_MULTISIG, ADDRESS_VERSION_MAINNET_SINGLESIG,
     ADDRESS_VERSION_TESTNET_MULTISIG, ADDRESS_VERSION_TESTNET_SINGLESIG,
 };
 use self::c32::c32_address;
+#[cfg(feature = "nodejs")]
 use self::c32::c32_address_decode;
-use self::stacks_address::StacksAddress;
+use self::stacks_address::{AddressHashMode, StacksAddress};
 use self::stacks_address::{
     C32_ADDRESS_VERSION_MAINNET_MULTISIG, C32_ADDRESS_VERSION_MAINNET_SINGLESIG,
     C32_ADDRESS_VERSION_TESTNET_MULTISIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
@@ -58,7 +66,8 @@ fn btc_addr_to_stx_addr_version(addr: &BitcoinAddress) -> Result<u8, String> {
     })
 }
 
-fn btc_addr_to_stx_addr(addr: &BitcoinAddress) -> Result<StacksAddress, String> {
+/// Convert a Bitcoin address to the Stacks address with the same hash160 and equivalent version.
+pub fn btc_addr_to_stx_addr(addr: &BitcoinAddress) -> Result<StacksAddress, String> {
     let version = btc_addr_to_stx_addr_version(addr)?;
     Ok(StacksAddress {
         version: version,
@@ -66,7 +75,9 @@ fn btc_addr_to_stx_addr(addr: &BitcoinAddress) -> Result<StacksAddress, String>
     })
 }
 
-fn stx_addr_to_btc_addr(addr: &StacksAddress) -> String {
+/// Convert a Stacks address to the base58check Bitcoin address with the same hash160 and
+/// equivalent version.
+pub fn stx_addr_to_btc_addr(addr: &StacksAddress) -> String {
     let btc_version = stx_to_btc_version_byte(addr.version)
         // fallback to version
         .unwrap_or(addr.version);
@@ -75,6 +86,7 @@ fn stx_addr_to_btc_addr(addr: &StacksAddress) -> String {
     b58::check_encode_slice(&all_bytes)
 }
 
+#[cfg(feature = "nodejs")]
 pub fn is_valid_stacks_address(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     let address_string = cx.argument::<JsString>(0)?.value(&mut cx);
     let address = c32_address_decode(&address_string);
@@ -84,6 +96,7 @@ pub fn is_valid_stacks_address(mut cx: FunctionContext) -> JsResult<JsBoolean> {
     }
 }
 
+#[cfg(feature = "nodejs")]
 pub fn decode_stacks_address(mut cx: FunctionContext) -> JsResult<JsArray> {
     let address_string = cx.argument::<JsString>(0)?.value(&mut cx);
     let address = c32_address_decode(&address_string)
@@ -98,7 +111,9 @@ pub fn decode_stacks_address(mut cx: FunctionContext) -> JsResult<JsArray> {
     Ok(array_resp)
 }
 
-fn decode_clarity_value_to_principal_inner(arg_bytes: &[u8]) -> Result<String, String> {
+/// Decode a serialized Clarity principal value to its c32 address string, e.g.
+/// `SP000000000000000000002Q6VF78.pox-4` for a contract principal.
+pub fn decode_clarity_value_to_principal_inner(arg_bytes: &[u8]) -> Result<String, String> {
     let mut cursor: Cursor<&[u8]> = Cursor::new(arg_bytes);
     let prefix_byte = cursor
         .read_u8()
@@ -155,6 +170,7 @@ fn decode_clarity_value_to_principal_inner(arg_bytes: &[u8]) -> Result<String, S
     Ok(addr)
 }
 
+#[cfg(feature = "nodejs")]
 pub fn decode_clarity_value_to_principal(mut cx: FunctionContext) -> JsResult<JsString> {
     let arg_bytes = arg_as_bytes_copied(&mut cx, 0)?;
 
@@ -168,24 +184,108 @@ pub fn decode_clarity_value_to_principal(mut cx: FunctionContext) -> JsResult<Js
     Ok(cx.string(addr))
 }
 
+#[cfg(feature = "nodejs")]
 pub fn stacks_address_from_parts(mut cx: FunctionContext) -> JsResult<JsString> {
-    let version = cx.argument::<JsNumber>(0)?.value(&mut cx);
+    let version = arg_as_uint(&mut cx, 0, "version", u8::MAX as u32)?;
     let stacks_address = arg_as_bytes(&mut cx, 1, |bytes| {
         let addr = c32_address(version as u8, bytes)
             .or_else(|e| Err(format!("Error converting to C32 address: {}", e)))?;
         Ok(addr)
     })
-    .or_else(|e| cx.throw_error(e)?)?;
+    .or_else(|e: String| cx.throw_error(e)?)?;
     let resp = cx.string(stacks_address);
     Ok(resp)
 }
 
-fn stacks_to_bitcoin_address_internal(input: String) -> Result<String, String> {
+#[cfg(feature = "nodejs")]
+fn address_hash_mode_arg(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+) -> NeonResult<AddressHashMode> {
+    let hash_mode = arg_as_uint(cx, arg_index, "hashMode", u8::MAX as u32)?;
+    AddressHashMode::try_from(hash_mode as u8)
+        .or_else(|e| cx.throw_error(format!("Invalid hash mode: {}", e)))
+}
+
+#[cfg(feature = "nodejs")]
+fn address_version_arg(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+    hash_mode: &AddressHashMode,
+) -> NeonResult<u8> {
+    let network_str = cx.argument::<JsString>(arg_index)?.value(cx);
+    let network = StacksNetwork::from_str(&network_str).or_else(|e| cx.throw_error(e))?;
+    if network.is_mainnet() {
+        Ok(hash_mode.to_version_mainnet())
+    } else {
+        Ok(hash_mode.to_version_testnet())
+    }
+}
+
+/// Derive the c32 Stacks address for a set of public keys and hash mode.
+pub fn public_keys_to_stacks_address(
+    version: u8,
+    hash_mode: &AddressHashMode,
+    num_sigs: usize,
+    public_keys: &[Vec<u8>],
+) -> Result<String, String> {
+    let address = StacksAddress::from_public_keys(version, hash_mode, num_sigs, public_keys)?;
+    c32_address(address.version, &address.hash160_bytes)
+        .map_err(|e| format!("Error converting to C32 address: {}", e))
+}
+
+#[cfg(feature = "nodejs")]
+pub fn public_key_to_stacks_address(mut cx: FunctionContext) -> JsResult<JsString> {
+    let public_key = arg_as_bytes_copied(&mut cx, 0)?;
+    let hash_mode = address_hash_mode_arg(&mut cx, 1)?;
+    let version = address_version_arg(&mut cx, 2, &hash_mode)?;
+    if !matches!(
+        hash_mode,
+        AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH
+    ) {
+        return cx.throw_error(format!(
+            "Hash mode {} is not a single-sig hash mode",
+            hash_mode as u8
+        ));
+    }
+    let address = public_keys_to_stacks_address(version, &hash_mode, 1, &[public_key.into()])
+        .or_else(|e| cx.throw_error(e))?;
+    Ok(cx.string(address))
+}
+
+#[cfg(feature = "nodejs")]
+pub fn multisig_to_stacks_address(mut cx: FunctionContext) -> JsResult<JsString> {
+    let public_keys_arg = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let mut public_keys = Vec::with_capacity(public_keys_arg.len());
+    for public_key in public_keys_arg {
+        public_keys.push(value_as_bytes_copied(&mut cx, public_key)?.into());
+    }
+    let num_sigs = arg_as_uint(&mut cx, 1, "signaturesRequired", u16::MAX as u32)?;
+    let hash_mode = address_hash_mode_arg(&mut cx, 2)?;
+    let version = address_version_arg(&mut cx, 3, &hash_mode)?;
+    if matches!(
+        hash_mode,
+        AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH
+    ) {
+        return cx.throw_error(format!(
+            "Hash mode {} is not a multisig hash mode",
+            hash_mode as u8
+        ));
+    }
+    let address =
+        public_keys_to_stacks_address(version, &hash_mode, num_sigs as usize, &public_keys)
+            .or_else(|e| cx.throw_error(e))?;
+    Ok(cx.string(address))
+}
+
+/// Convert a c32 Stacks address string to the equivalent base58check Bitcoin address.
+pub fn stacks_to_bitcoin_address_internal(input: String) -> Result<String, String> {
     let stacks_address = StacksAddress::from_string(&input)?;
     let bitcoin_address = stx_addr_to_btc_addr(&stacks_address);
     Ok(bitcoin_address)
 }
 
+#[cfg(feature = "nodejs")]
 pub fn stacks_to_bitcoin_address(mut cx: FunctionContext) -> JsResult<JsString> {
     let stacks_address_arg = cx.argument::<JsString>(0)?.value(&mut cx);
     let btc_address =
@@ -194,6 +294,7 @@ pub fn stacks_to_bitcoin_address(mut cx: FunctionContext) -> JsResult<JsString>
     Ok(btc_address)
 }
 
+#[cfg(feature = "nodejs")]
 pub fn bitcoin_to_stacks_address(mut cx: FunctionContext) -> JsResult<JsString> {
     let bitcoin_address_arg = cx.argument::<JsString>(0)?.value(&mut cx);
     let bitcoin_address = bitcoin_address::from_b58(&bitcoin_address_arg)
@@ -302,6 +403,72 @@ mod tests {
         assert_eq!(output, "SP2GKVKM12JZ0YW3ZJH3GMBJYGVNM0BS94ERA45AM");
     }
 
+    #[test]
+    fn test_public_keys_to_stacks_address() {
+        let public_keys: Vec<Vec<u8>> = [
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        ]
+        .iter()
+        .map(|key| decode_hex(key).unwrap().into())
+        .collect();
+        let cases = [
+            (
+                AddressHashMode::SerializeP2PKH,
+                C32_ADDRESS_VERSION_MAINNET_SINGLESIG,
+                1,
+                "SP1THWXQ8368SDN2MJGE4BMDKMCHZ2GSVTS1X0BPM",
+            ),
+            (
+                AddressHashMode::SerializeP2WPKH,
+                C32_ADDRESS_VERSION_TESTNET_MULTISIG,
+                1,
+                "SN2YFXDS8PP22AFAZ7XRBSDW0X7QJ32K8YGY8YW81",
+            ),
+            (
+                AddressHashMode::SerializeP2SH,
+                C32_ADDRESS_VERSION_MAINNET_MULTISIG,
+                2,
+                "SMAZR1TMWWZBGQ8WQKG8F1QTVDSJ1V5RVGMRGMFW",
+            ),
+            (
+                AddressHashMode::SerializeP2SHNonSequential,
+                C32_ADDRESS_VERSION_MAINNET_MULTISIG,
+                2,
+                "SMAZR1TMWWZBGQ8WQKG8F1QTVDSJ1V5RVGMRGMFW",
+            ),
+            (
+                AddressHashMode::SerializeP2WSH,
+                C32_ADDRESS_VERSION_TESTNET_MULTISIG,
+                2,
+                "SN34NXXY925X5CNRW5QE49SFXHEH9THCRJC52J7J4",
+            ),
+        ];
+        for (hash_mode, version, num_sigs, expected) in cases {
+            let keys = if num_sigs == 1 {
+                &public_keys[..1]
+            } else {
+                &public_keys[..]
+            };
+            let output =
+                public_keys_to_stacks_address(version, &hash_mode, num_sigs, keys).unwrap();
+            assert_eq!(output, expected);
+        }
+
+        let err = public_keys_to_stacks_address(
+            C32_ADDRESS_VERSION_MAINNET_MULTISIG,
+            &AddressHashMode::SerializeP2SH,
+            4,
+            &public_keys,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "Invalid number of required signatures 4 for 3 public keys"
+        );
+    }
+
     /*
     #[test]
     fn test_bitcoin_to_stacks_address_mainnet() {
@@ -1,14 +1,20 @@
 use std::convert::TryFrom;
 
+use sha2::{Digest, Sha256};
+
 use super::c32::c32_address_decode;
+use crate::crypto::{hash160, parse_public_key};
 
 pub const C32_ADDRESS_VERSION_MAINNET_SINGLESIG: u8 = 22; // P
 pub const C32_ADDRESS_VERSION_MAINNET_MULTISIG: u8 = 20; // M
 pub const C32_ADDRESS_VERSION_TESTNET_SINGLESIG: u8 = 26; // T
 pub const C32_ADDRESS_VERSION_TESTNET_MULTISIG: u8 = 21; // N
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksAddress {
     pub version: u8,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub hash160_bytes: [u8; 20],
 }
 
@@ -33,10 +39,43 @@ impl StacksAddress {
             hash160_bytes: bytes,
         })
     }
+
+    /// Derive the address of a spending condition from its serialized public keys, the same way
+    /// stacks-core does for each hash mode. Single-sig modes take exactly one key.
+    pub fn from_public_keys(
+        version: u8,
+        hash_mode: &AddressHashMode,
+        num_sigs: usize,
+        public_keys: &[Vec<u8>],
+    ) -> Result<StacksAddress, String> {
+        for public_key in public_keys {
+            parse_public_key(public_key)?;
+        }
+        match *hash_mode {
+            AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH => {}
+            AddressHashMode::SerializeP2WSH | AddressHashMode::SerializeP2WSHNonSequential
+                if public_keys.iter().any(|key| key.len() != 33) =>
+            {
+                return Err("P2WSH requires compressed public keys".to_string());
+            }
+            _ => {
+                if num_sigs == 0 || num_sigs > public_keys.len() {
+                    return Err(format!(
+                        "Invalid number of required signatures {} for {} public keys",
+                        num_sigs,
+                        public_keys.len()
+                    ));
+                }
+            }
+        }
+        let hash160_bytes = hash_mode.public_keys_to_hash160(num_sigs, public_keys)?;
+        Ok(StacksAddress::new(version, hash160_bytes))
+    }
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AddressHashMode {
     // serialization modes for public keys to addresses.
     // We support four different modes due to legacy compatibility with Stacks v1 addresses:
@@ -62,6 +101,44 @@ impl AddressHashMode {
             _ => C32_ADDRESS_VERSION_TESTNET_MULTISIG,
         }
     }
+
+    /// Compute the hash160 of an address with this hash mode, given the serialized (compressed or
+    /// uncompressed) public keys and the number of signatures required.
+    pub fn public_keys_to_hash160(
+        &self,
+        num_sigs: usize,
+        public_keys: &[Vec<u8>],
+    ) -> Result<[u8; 20], String> {
+        match *self {
+            AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH => {
+                if num_sigs != 1 || public_keys.len() != 1 {
+                    return Err(format!(
+                        "Single-sig hash mode requires exactly one public key, received {}",
+                        public_keys.len()
+                    ));
+                }
+                let key_hash = hash160(&public_keys[0]);
+                if *self == AddressHashMode::SerializeP2PKH {
+                    return Ok(key_hash);
+                }
+                if public_keys[0].len() != 33 {
+                    return Err("P2WPKH requires a compressed public key".to_string());
+                }
+                let mut witness_program = vec![0x00, 0x14];
+                witness_program.extend_from_slice(&key_hash);
+                Ok(hash160(&witness_program))
+            }
+            AddressHashMode::SerializeP2SH | AddressHashMode::SerializeP2SHNonSequential => {
+                Ok(hash160(&multisig_redeem_script(num_sigs, public_keys)))
+            }
+            AddressHashMode::SerializeP2WSH | AddressHashMode::SerializeP2WSHNonSequential => {
+                let script = multisig_redeem_script(num_sigs, public_keys);
+                let mut witness_program = vec![0x00, 0x20];
+                witness_program.extend_from_slice(&Sha256::digest(&script));
+                Ok(hash160(&witness_program))
+            }
+        }
+    }
 }
 
 /// Given the u8 of an AddressHashMode, deduce the AddressHashNode
@@ -86,3 +163,37 @@ impl TryFrom<u8> for AddressHashMode {
         }
     }
 }
+
+/// Bitcoin `OP_m <pubkey>... OP_n OP_CHECKMULTISIG` redeem script
+fn multisig_redeem_script(num_sigs: usize, public_keys: &[Vec<u8>]) -> Vec<u8> {
+    let mut script = vec![];
+    push_script_int(&mut script, num_sigs);
+    for public_key in public_keys {
+        script.push(public_key.len() as u8);
+        script.extend_from_slice(public_key);
+    }
+    push_script_int(&mut script, public_keys.len());
+    script.push(0xae); // OP_CHECKMULTISIG
+    script
+}
+
+fn push_script_int(script: &mut Vec<u8>, n: usize) {
+    match n {
+        0 => script.push(0x00),                // OP_0
+        1..=16 => script.push(0x50 + n as u8), // OP_1 - OP_16
+        _ => {
+            // minimally-encoded little-endian script number
+            let mut bytes = vec![];
+            let mut x = n;
+            while x > 0 {
+                bytes.push((x & 0xff) as u8);
+                x >>= 8;
+            }
+            if bytes[bytes.len() - 1] & 0x80 != 0 {
+                bytes.push(0);
+            }
+            script.push(bytes.len() as u8);
+            script.extend_from_slice(&bytes);
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use serde_json::{json, Value};
+
+use stacks_encoding_native_js::address::c32::{c32_address, c32_address_decode};
+use stacks_encoding_native_js::address::{
+    bitcoin_address, btc_addr_to_stx_addr, stacks_to_bitcoin_address_internal,
+};
+use stacks_encoding_native_js::clarity_value::value_ref::ClarityValueRef;
+use stacks_encoding_native_js::hex::decode_hex;
+use stacks_encoding_native_js::json::{
+    self, nakamoto_block_to_json, pox_event_to_json, stacks_block_to_json, DecodedPostConditions,
+    DecodedStacksAddress, DecodedTransaction,
+};
+use stacks_encoding_native_js::output_options::{
+    DecodeClarityValueOptions, NumberFormat, OutputOptions,
+};
+use stacks_encoding_native_js::pox_events::decode::decode_pox_synthetic_event;
+use stacks_encoding_native_js::pox_events::types::StacksNetwork;
+use stacks_encoding_native_js::stacks_block::deserialize::{NakamotoBlock, StacksBlock};
+use stacks_encoding_native_js::GIT_VERSION;
+
+/// Decode Stacks blockchain wire formats to JSON
+#[derive(Parser)]
+#[command(name = "stacks-codec", version = GIT_VERSION)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode a serialized transaction
+    Tx(InputArgs),
+    /// Decode a serialized Clarity value
+    Clarity(InputArgs),
+    /// Decode a serialized Clarity value to its repr string
+    Repr(InputArgs),
+    /// Decode serialized post conditions (mode byte, 4 byte count, then the items)
+    PostConditions(InputArgs),
+    /// Decode a serialized Stacks 2.x block
+    Block(InputArgs),
+    /// Decode a serialized Nakamoto block (Stacks 3.x+)
+    NakamotoBlock(InputArgs),
+    /// Decode a c32 Stacks address, or convert a base58 Bitcoin address to one
+    Address(InputArgs),
+    /// Decode a serialized Clarity value from a PoX synthetic print event
+    PoxEvent {
+        #[command(flatten)]
+        input: InputArgs,
+        /// The network of the event, `mainnet` or `testnet`
+        #[arg(long, default_value = "mainnet")]
+        network: String,
+    },
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Hex encoded inputs, with or without a 0x prefix (addresses for `address`). Stdin is read
+    /// if no inputs or files are given.
+    inputs: Vec<String>,
+    /// Read an input from a file containing hex or raw binary
+    #[arg(short, long = "file")]
+    files: Vec<PathBuf>,
+    /// Print one compact JSON value per line, reading files and stdin as one input per line.
+    /// Failed inputs are printed as `{"error": "..."}`.
+    #[arg(long)]
+    ndjson: bool,
+}
+
+enum Input {
+    /// A command-line argument or an `--ndjson` line
+    Text(String),
+    /// The whole contents of a file or stdin, which is read as text or bytes by the subcommand
+    Contents(Vec<u8>),
+}
+
+impl Input {
+    /// Whole contents are hex if they decode as such, otherwise raw binary.
+    fn bytes(&self) -> Result<Box<[u8]>, String> {
+        match self {
+            Input::Text(text) => decode_hex(text).map_err(|e| format!("Hex parsing error: {}", e)),
+            Input::Contents(contents) => match std::str::from_utf8(contents) {
+                Ok(text) => Ok(decode_hex(text.trim()).unwrap_or_else(|_| contents[..].into())),
+                Err(_) => Ok(contents[..].into()),
+            },
+        }
+    }
+
+    fn text(&self) -> Result<&str, String> {
+        match self {
+            Input::Text(text) => Ok(text),
+            Input::Contents(contents) => std::str::from_utf8(contents)
+                .map(str::trim)
+                .map_err(|_| "Expected text input".to_string()),
+        }
+    }
+}
+
+fn to_json(value: Result<Value, String>, pretty: bool) -> Result<String, String> {
+    let value = value?;
+    let json = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+    json.map_err(|e| format!("Error serializing JSON: {}", e))
+}
+
+fn decode_address(input: &str, pretty: bool) -> Result<String, String> {
+    let address = if c32_address_decode(input).is_ok() {
+        input.to_string()
+    } else {
+        let bitcoin_address = bitcoin_address::from_b58(input)
+            .map_err(|e| format!("Error parsing Stacks or Bitcoin address: {}", e))?;
+        let stacks_address = btc_addr_to_stx_addr(&bitcoin_address)?;
+        c32_address(stacks_address.version, &stacks_address.hash160_bytes)?
+    };
+    let (version, hash160) = c32_address_decode(&address)?;
+    let bitcoin_address = stacks_to_bitcoin_address_internal(address.clone())?;
+    let mut json = DecodedStacksAddress { version, hash160 }.to_json();
+    json["address"] = address.into();
+    json["bitcoin_address"] = bitcoin_address.into();
+    to_json(Ok(json), pretty)
+}
+
+fn decode(command: &Command, input: &Input, pretty: bool) -> Result<String, String> {
+    match command {
+        Command::Tx(_) => {
+            let tx = DecodedTransaction::decode(&input.bytes()?)
+                .map_err(|e| format!("Failed to decode transaction: {}", e))?;
+            to_json(tx.to_json(OutputOptions::default()), pretty)
+        }
+        Command::Clarity(_) => to_json(
+            json::decode_clarity_value(&input.bytes()?, DecodeClarityValueOptions::default()),
+            pretty,
+        ),
+        Command::Repr(_) => {
+            let bytes = input.bytes()?;
+            let value = ClarityValueRef::deserialize(&mut io::Cursor::new(&bytes))
+                .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+            to_json(Ok(value.repr_string().into()), pretty)
+        }
+        Command::PostConditions(_) => {
+            let post_conditions = DecodedPostConditions::decode(&input.bytes()?)
+                .map_err(|e| format!("Error deserializing post condition: {}", e))?;
+            to_json(post_conditions.to_json(NumberFormat::default()), pretty)
+        }
+        Command::Block(_) => {
+            let bytes = input.bytes()?;
+            let block = StacksBlock::deserialize(&mut io::Cursor::new(&bytes))
+                .map_err(|e| format!("Failed to decode Stacks block: {}", e))?;
+            to_json(
+                stacks_block_to_json(&block, None, OutputOptions::default()),
+                pretty,
+            )
+        }
+        Command::NakamotoBlock(_) => {
+            let bytes = input.bytes()?;
+            let block = NakamotoBlock::deserialize(&mut io::Cursor::new(&bytes))
+                .map_err(|e| format!("Failed to decode Nakamoto block: {}", e))?;
+            to_json(
+                nakamoto_block_to_json(&block, None, OutputOptions::default()),
+                pretty,
+            )
+        }
+        Command::Address(_) => decode_address(input.text()?.trim(), pretty),
+        Command::PoxEvent { network, .. } => {
+            let network = StacksNetwork::from_str(network)?;
+            let bytes = input.bytes()?;
+            let value = ClarityValueRef::deserialize(&mut io::Cursor::new(&bytes))
+                .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+            let event = decode_pox_synthetic_event(&value, network)?;
+            to_json(
+                Ok(event.as_ref().map_or(Value::Null, pox_event_to_json)),
+                pretty,
+            )
+        }
+    }
+}
+
+/// Call `f` with each input, reading files and stdin line by line in `--ndjson` mode so that
+/// large inputs are decoded as they stream in.
+fn for_each_input<F>(args: &InputArgs, mut f: F) -> io::Result<()>
+where
+    F: FnMut(Input) -> io::Result<()>,
+{
+    for input in &args.inputs {
+        f(Input::Text(input.clone()))?;
+    }
+    let mut readers: Vec<Box<dyn Read>> = Vec::new();
+    for path in &args.files {
+        let file = fs::File::open(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        readers.push(Box::new(file));
+    }
+    if args.inputs.is_empty() && args.files.is_empty() {
+        readers.push(Box::new(io::stdin()));
+    }
+    for mut reader in readers {
+        if args.ndjson {
+            for line in BufReader::new(reader).lines() {
+                let line = line?;
+                if !line.trim().is_empty() {
+                    f(Input::Text(line.trim().to_string()))?;
+                }
+            }
+        } else {
+            let mut contents = Vec::new();
+            reader.read_to_end(&mut contents)?;
+            f(Input::Contents(contents))?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let args = match &cli.command {
+        Command::Tx(args)
+        | Command::Clarity(args)
+        | Command::Repr(args)
+        | Command::PostConditions(args)
+        | Command::Block(args)
+        | Command::NakamotoBlock(args)
+        | Command::Address(args)
+        | Command::PoxEvent { input: args, .. } => args,
+    };
+
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    let mut failed = false;
+    let result = for_each_input(args, |input| {
+        let result = decode(&cli.command, &input, !args.ndjson);
+        failed |= result.is_err();
+        match (result, args.ndjson) {
+            (Ok(json), _) => writeln!(out, "{}", json),
+            (Err(e), true) => writeln!(out, "{}", json!({ "error": e })),
+            (Err(e), false) => {
+                eprintln!("{}", e);
+                Ok(())
+            }
+        }
+    })
+    .and_then(|_| out.flush());
+    match result {
+        // a closed pipe, e.g. when piped to `head`
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+        Ok(_) if failed => ExitCode::FAILURE,
+        Ok(_) => ExitCode::SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str]) -> Command {
+        let args = ["stacks-codec"].iter().chain(args);
+        Cli::try_parse_from(args).unwrap().command
+    }
+
+    #[test]
+    fn test_input_contents() {
+        let input = Input::Contents(b"0x0809\n".to_vec());
+        assert_eq!(input.bytes().unwrap().as_ref(), &[0x08, 0x09]);
+        let input = Input::Contents(vec![0x08, 0x09]);
+        assert_eq!(input.bytes().unwrap().as_ref(), &[0x08, 0x09]);
+        let input = Input::Contents(vec![0xff, 0x09]);
+        assert_eq!(input.bytes().unwrap().as_ref(), &[0xff, 0x09]);
+        assert!(input.text().is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        let repr = command(&["repr", "0x0809"]);
+        let input = Input::Text("0809".to_string());
+        assert_eq!(decode(&repr, &input, false).unwrap(), r#""(err none)""#);
+        let clarity = command(&["clarity", "--ndjson"]);
+        let json: Value = serde_json::from_str(&decode(&clarity, &input, false).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "repr": "(err none)",
+                "hex": "0x0809",
+                "type_id": 8,
+                "value": { "repr": "none", "hex": "0x09", "type_id": 9, "value": null },
+            })
+        );
+        let err = decode(&clarity, &Input::Text("0x08".to_string()), false).unwrap_err();
+        assert!(err.starts_with("Error deserializing Clarity value: "));
+    }
+
+    #[test]
+    fn test_decode_address() {
+        let address = command(&["address"]);
+        let json = json!({
+            "address": "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B",
+            "version": 22,
+            "hash160": "0xa5d9d331000f5b79578ce56bd157f29a9056f0d6",
+            "bitcoin_address": "1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB",
+        });
+        for input in [
+            Input::Text("SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B".to_string()),
+            Input::Text("1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB".to_string()),
+            // stdin or `-f`, e.g. `echo SP2J.. | stacks-codec address`
+            Input::Contents(b"SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B\n".to_vec()),
+            Input::Contents(b"1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB\n".to_vec()),
+        ] {
+            let decoded = decode(&address, &input, false).unwrap();
+            assert_eq!(serde_json::from_str::<Value>(&decoded).unwrap(), json);
+        }
+    }
+}
@@ -4,7 +4,9 @@ use super::types::*;
 use std::collections::BTreeMap;
 use std::io::{Cursor, Read};
 
-use crate::serialize_util::DeserializeError;
+use crate::serialize_util::{DeserializeError, DeserializeErrorKind};
+
+use super::type_signature::TypeSignature;
 
 macro_rules! define_u8_enum {
     ($Name:ident { $($Variant:ident = $Val:literal),+ }) =>
@@ -59,19 +61,19 @@ impl ContractName {
         if (len_byte as usize) < CONTRACT_MIN_NAME_LENGTH
             || (len_byte as usize) > CONTRACT_MAX_NAME_LENGTH
         {
-            return Err(format!(
+            return Err(DeserializeErrorKind::InvalidName(format!(
                 "Failed to deserialize contract name: too short or too long: {}",
                 len_byte
-            ))?;
+            )))?;
         }
         let mut bytes = vec![0u8; len_byte as usize];
         fd.read_exact(&mut bytes)?;
 
         let s = String::from_utf8(bytes).map_err(|e| {
-            format!(
+            DeserializeErrorKind::InvalidName(format!(
                 "Failed to parse Contract name: could not construct from utf8: {}",
                 e
-            )
+            ))
         })?;
 
         Ok(ContractName(s))
@@ -80,30 +82,68 @@ impl ContractName {
 
 impl ClarityName {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
-        let len_byte = fd.read_u8()?;
-        if len_byte > MAX_STRING_LEN {
-            return Err(format!(
-                "Failed to deserialize clarity name: too long: {}",
-                len_byte,
-            ))?;
-        }
-        let mut bytes = vec![0u8; len_byte as usize];
-        fd.read_exact(&mut bytes)?;
+        read_clarity_name(fd).map(|name| ClarityName(name.to_string()))
+    }
+}
 
-        let s = String::from_utf8(bytes).map_err(|e| {
-            format!(
-                "Failed to parse Clarity name: could not contruct from utf8: {}",
-                e
-            )
-        })?;
+/// Read a length-prefixed Clarity name, borrowing it from the input.
+pub(crate) fn read_clarity_name<'a>(
+    fd: &mut Cursor<&'a [u8]>,
+) -> Result<&'a str, DeserializeError> {
+    let len_byte = fd.read_u8()?;
+    if len_byte > MAX_STRING_LEN {
+        return Err(DeserializeErrorKind::InvalidName(format!(
+            "Failed to deserialize clarity name: too long: {}",
+            len_byte,
+        ))
+        .into());
+    }
+    let bytes = read_slice(fd, len_byte as usize)?;
+
+    std::str::from_utf8(bytes).map_err(|e| {
+        DeserializeErrorKind::InvalidName(format!(
+            "Failed to parse Clarity name: could not contruct from utf8: {}",
+            e
+        ))
+        .into()
+    })
+}
+
+/// Borrow the next `len` bytes of the input. Like `read_exact`, moves the cursor to the end of
+/// the input if there are not enough bytes left.
+pub(crate) fn read_slice<'a>(
+    fd: &mut Cursor<&'a [u8]>,
+    len: usize,
+) -> Result<&'a [u8], DeserializeError> {
+    let input: &'a [u8] = fd.get_ref();
+    let start = (fd.position() as usize).min(input.len());
+    if input.len() - start < len {
+        fd.set_position(input.len() as u64);
+        return Err(DeserializeErrorKind::UnexpectedEof.into());
+    }
+    fd.set_position((start + len) as u64);
+    Ok(&input[start..start + len])
+}
 
-        Ok(ClarityName(s))
+/// c32 address versions are 5 bits, stacks-core rejects principals with a larger version byte.
+pub(crate) fn check_principal_version(version: u8, offset: u64) -> Result<(), DeserializeError> {
+    if version >= 32 {
+        return Err(
+            DeserializeError::new(DeserializeErrorKind::InvalidValue(format!(
+                "Invalid principal version {}, must be less than 32",
+                version
+            )))
+            .at_offset(offset),
+        );
     }
+    Ok(())
 }
 
 impl StandardPrincipalData {
     pub fn deserialize(r: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        let offset = r.position();
         let version = r.read_u8()?;
+        check_principal_version(version, offset)?;
         let mut data = [0; 20];
         r.read_exact(&mut data)?;
         Ok(StandardPrincipalData(version, data))
@@ -115,26 +155,42 @@ impl ClarityValue {
         r: &mut Cursor<&[u8]>,
         with_bytes: bool,
     ) -> Result<ClarityValue, DeserializeError> {
-        Self::inner_deserialize_read(r, 0, with_bytes)
+        Self::inner_deserialize_read(r, 0, with_bytes, false).map_err(|e| e.at_offset(r.position()))
+    }
+
+    /// Deserialize only canonical encodings, rejecting everything the stacks-core consensus
+    /// deserializer rejects: empty tuples, tuple fields that are duplicated, out of order, or not
+    /// valid Clarity names, invalid contract names, invalid UTF-8, non-printable ASCII, and lists whose items
+    /// have no common type. Errors include the byte offset of the offending input.
+    pub fn deserialize_strict(
+        r: &mut Cursor<&[u8]>,
+        with_bytes: bool,
+    ) -> Result<ClarityValue, DeserializeError> {
+        Self::inner_deserialize_read(r, 0, with_bytes, true).map_err(|e| e.at_offset(r.position()))
     }
 
     fn inner_deserialize_read(
         r: &mut Cursor<&[u8]>,
         depth: u8,
         with_bytes: bool,
+        strict: bool,
     ) -> Result<ClarityValue, DeserializeError> {
         use super::types::Value::*;
 
+        let cursor_start = r.position() as usize;
+
         if depth >= 16 {
-            return Err(format!("TypeSignatureTooDeep: {}", depth).into());
+            return Err(DeserializeError::new(DeserializeErrorKind::TooDeep(depth))
+                .at_offset(cursor_start as u64));
         }
 
-        let cursor_start = r.position() as usize;
-
         let mut header = [0];
         r.read_exact(&mut header)?;
 
-        let prefix = TypePrefix::from_u8(header[0]).ok_or_else(|| "Bad type prefix")?;
+        let prefix = TypePrefix::from_u8(header[0]).ok_or_else(|| {
+            DeserializeError::new(DeserializeErrorKind::BadTypePrefix(header[0]))
+                .at_offset(cursor_start as u64)
+        })?;
 
         let clarity_value = match prefix {
             TypePrefix::Int => {
@@ -152,7 +208,7 @@ impl ClarityValue {
                 r.read_exact(&mut buffer_len)?;
                 let buffer_len = u32::from_be_bytes(buffer_len);
                 if buffer_len > MAX_VALUE_SIZE {
-                    return Err("Illegal buffer type size".into());
+                    return Err(illegal_length("buffer", buffer_len, cursor_start));
                 }
                 let mut data = vec![0; buffer_len as usize];
                 r.read_exact(&mut data[..])?;
@@ -166,20 +222,30 @@ impl ClarityValue {
             }
             TypePrefix::PrincipalContract => {
                 let issuer = StandardPrincipalData::deserialize(r)?;
+                let name_offset = r.position();
                 let name = ClarityName::deserialize(r)?;
+                if strict && !CONTRACT_NAME_REGEX.is_match(&name) {
+                    return Err(non_canonical(
+                        name_offset,
+                        format!("invalid contract name `{}`", name),
+                    ));
+                }
                 Value::PrincipalContract(QualifiedContractIdentifier { issuer, name })
             }
             TypePrefix::ResponseOk => {
-                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes)?;
+                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes, strict)
+                    .map_err(|e| e.in_field("ok"))?;
                 Value::ResponseOk(Box::new(value))
             }
             TypePrefix::ResponseErr => {
-                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes)?;
+                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes, strict)
+                    .map_err(|e| e.in_field("err"))?;
                 Value::ResponseErr(Box::new(value))
             }
             TypePrefix::OptionalNone => Value::OptionalNone,
             TypePrefix::OptionalSome => {
-                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes)?;
+                let value = Self::inner_deserialize_read(r, depth + 1, with_bytes, strict)
+                    .map_err(|e| e.in_field("some"))?;
                 Value::OptionalSome(Box::new(value))
             }
             TypePrefix::List => {
@@ -187,26 +253,66 @@ impl ClarityValue {
                 r.read_exact(&mut len)?;
                 let len = u32::from_be_bytes(len);
                 if len > MAX_VALUE_SIZE {
-                    return Err("Illegal list type size".into());
+                    return Err(illegal_length("list", len, cursor_start));
                 }
                 let mut items = Vec::with_capacity(len as usize);
-                for _i in 0..len {
-                    let value = Self::inner_deserialize_read(r, depth + 1, with_bytes)?;
+                for i in 0..len {
+                    let value = Self::inner_deserialize_read(r, depth + 1, with_bytes, strict)
+                        .map_err(|e| e.in_element("list", i as usize))?;
                     items.push(value);
                 }
-                Value::List(items)
+                let list = Value::List(items);
+                if strict {
+                    TypeSignature::type_of(&list)
+                        .map_err(|e| non_canonical(cursor_start as u64, e))?;
+                }
+                list
             }
             TypePrefix::Tuple => {
                 let mut len = [0; 4];
                 r.read_exact(&mut len)?;
                 let len = u32::from_be_bytes(len);
                 if len > MAX_VALUE_SIZE {
-                    return Err("Illegal tuple type size".into());
+                    return Err(illegal_length("tuple", len, cursor_start));
+                }
+                if strict && len == 0 {
+                    return Err(non_canonical(
+                        cursor_start as u64,
+                        "empty tuples are not allowed".to_string(),
+                    ));
                 }
                 let mut data = BTreeMap::new();
+                let mut prev_key: Option<ClarityName> = None;
                 for _i in 0..len {
+                    let key_offset = r.position();
                     let key = ClarityName::deserialize(r)?;
-                    let value = Self::inner_deserialize_read(r, depth + 1, with_bytes)?;
+                    if strict {
+                        if !CLARITY_NAME_REGEX.is_match(&key) {
+                            return Err(non_canonical(
+                                key_offset,
+                                format!("invalid tuple field name `{}`", key),
+                            ));
+                        }
+                        if let Some(prev_key) = &prev_key {
+                            if key == *prev_key {
+                                return Err(non_canonical(
+                                    key_offset,
+                                    format!("duplicate tuple field `{}`", key),
+                                ));
+                            } else if key < *prev_key {
+                                return Err(non_canonical(
+                                    key_offset,
+                                    format!(
+                                        "tuple field `{}` is out of order after `{}`",
+                                        key, prev_key
+                                    ),
+                                ));
+                            }
+                        }
+                        prev_key = Some(key.clone());
+                    }
+                    let value = Self::inner_deserialize_read(r, depth + 1, with_bytes, strict)
+                        .map_err(|e| e.in_field(&key).in_field("tuple"))?;
                     data.insert(key, value);
                 }
                 Value::Tuple(data)
@@ -216,10 +322,24 @@ impl ClarityValue {
                 r.read_exact(&mut buffer_len)?;
                 let buffer_len = u32::from_be_bytes(buffer_len);
                 if buffer_len > MAX_VALUE_SIZE {
-                    return Err("Illegal string-ascii type size".into());
+                    return Err(illegal_length("string-ascii", buffer_len, cursor_start));
                 }
+                let data_offset = r.position();
                 let mut data = vec![0; buffer_len as usize];
                 r.read_exact(&mut data[..])?;
+                if strict {
+                    let invalid = data.iter().position(|b| {
+                        !b.is_ascii_alphanumeric()
+                            && !b.is_ascii_punctuation()
+                            && !b.is_ascii_whitespace()
+                    });
+                    if let Some(i) = invalid {
+                        return Err(non_canonical(
+                            data_offset + i as u64,
+                            format!("invalid string-ascii character 0x{:02x}", data[i]),
+                        ));
+                    }
+                }
                 Value::StringASCII(data)
             }
             TypePrefix::StringUTF8 => {
@@ -227,10 +347,19 @@ impl ClarityValue {
                 r.read_exact(&mut total_len)?;
                 let total_len = u32::from_be_bytes(total_len);
                 if total_len > MAX_VALUE_SIZE {
-                    return Err("Illegal string-utf8 type size".into());
+                    return Err(illegal_length("string-utf8", total_len, cursor_start));
                 }
+                let data_offset = r.position();
                 let mut data: Vec<u8> = vec![0; total_len as usize];
                 r.read_exact(&mut data[..])?;
+                if strict {
+                    if let Err(e) = std::str::from_utf8(&data) {
+                        return Err(non_canonical(
+                            data_offset + e.valid_up_to() as u64,
+                            "invalid UTF-8 in string-utf8".to_string(),
+                        ));
+                    }
+                }
                 Value::string_utf8(data)
             }
         };
@@ -243,3 +372,170 @@ impl ClarityValue {
         }
     }
 }
+
+fn non_canonical(offset: u64, message: String) -> DeserializeError {
+    DeserializeError::new(DeserializeErrorKind::NonCanonical(message)).at_offset(offset)
+}
+
+pub(crate) fn illegal_length(
+    type_name: &'static str,
+    length: u32,
+    offset: usize,
+) -> DeserializeError {
+    DeserializeError::new(DeserializeErrorKind::IllegalLength { type_name, length })
+        .at_offset(offset as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::decode_hex;
+
+    fn deserialize_strict(hex: &str) -> Result<ClarityValue, String> {
+        let bytes = decode_hex(hex).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        ClarityValue::deserialize_strict(&mut cursor, false).map_err(|e| e.as_string())
+    }
+
+    fn deserialize_lenient(hex: &str) -> ClarityValue {
+        let bytes = decode_hex(hex).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        ClarityValue::deserialize(&mut cursor, false).unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_strict_canonical() {
+        let hex = "0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479";
+        assert!(deserialize_strict(hex).is_ok());
+        // (list none (some u1))
+        assert!(deserialize_strict("0x0b00000002090a0100000000000000000000000000000001").is_ok());
+        // 'SP000000000000000000002Q6VF78.pox-4
+        assert!(
+            deserialize_strict("0x0616000000000000000000000000000000000000000005706f782d34")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_tuples() {
+        // (tuple (b true) (a true))
+        let hex = "0x0c00000002016203016103";
+        assert_eq!(
+            deserialize_lenient(hex).value.repr_string(),
+            "(tuple (a true) (b true))"
+        );
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: tuple field `a` is out of order after `b` (at byte offset 8)"
+        );
+
+        // (tuple (a true) (a false))
+        let hex = "0x0c00000002016103016104";
+        assert_eq!(
+            deserialize_lenient(hex).value.repr_string(),
+            "(tuple (a false))"
+        );
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: duplicate tuple field `a` (at byte offset 8)"
+        );
+
+        // (tuple (1 true))
+        assert_eq!(
+            deserialize_strict("0x0c00000001013103").err().unwrap(),
+            "Non-canonical Clarity value: invalid tuple field name `1` (at byte offset 5)"
+        );
+
+        // (tuple)
+        let hex = "0x0c00000000";
+        assert_eq!(deserialize_lenient(hex).value.repr_string(), "(tuple)");
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: empty tuples are not allowed (at byte offset 0)"
+        );
+        // (list (tuple))
+        assert_eq!(
+            deserialize_strict("0x0b000000010c00000000").err().unwrap(),
+            "Non-canonical Clarity value: empty tuples are not allowed (at `list[0]`, byte offset 5)"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_principals() {
+        // standard principal with the largest c32 version, 31
+        assert!(deserialize_strict("0x051f0000000000000000000000000000000000000000").is_ok());
+        for hex in [
+            "0x05200000000000000000000000000000000000000000",
+            "0x06ff0000000000000000000000000000000000000000016161",
+        ] {
+            let err = deserialize_strict(hex).err().unwrap();
+            assert!(
+                err.starts_with("Invalid principal version ")
+                    && err.ends_with(", must be less than 32 (at byte offset 1)"),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_strict_strings() {
+        // "a\x07"
+        let hex = "0x0d000000026107";
+        assert!(deserialize_strict("0x0d00000003610a09").is_ok());
+        assert_eq!(deserialize_lenient(hex).value.repr_string(), "\"a\\x07\"");
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: invalid string-ascii character 0x07 (at byte offset 6)"
+        );
+
+        // u"a\xffb"
+        let hex = "0x0e0000000361ff62";
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: invalid UTF-8 in string-utf8 (at byte offset 6)"
+        );
+
+        // 'SP000000000000000000002Q6VF78.a.b
+        let hex = "0x0616000000000000000000000000000000000000000003612e62";
+        assert!(deserialize_lenient(hex)
+            .value
+            .repr_string()
+            .ends_with(".a.b"));
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: invalid contract name `a.b` (at byte offset 22)"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_strict_list_types() {
+        // (some (list true u1))
+        let hex = "0x0a0b00000002030100000000000000000000000000000001";
+        assert_eq!(
+            deserialize_strict(hex).err().unwrap(),
+            "Non-canonical Clarity value: Invalid list: item 1 does not match the list type: types bool and uint are incompatible (at `some`, byte offset 1)"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_json() {
+        // (ok (tuple (amount u100) (memo (some 0x0102)) (sender 'SP000000000000000000002Q6VF78) (text u"h\u{e9}")))
+        let hex = "0x070c0000000406616d6f756e740100000000000000000000000000000064046d656d6f0a020000000201020673656e6465720516000000000000000000000000000000000000000004746578740e0000000368c3a9";
+        let json = serde_json::to_value(deserialize_lenient(hex)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "ResponseOk": {
+                    "Tuple": {
+                        "amount": { "UInt": "100" },
+                        "memo": { "OptionalSome": { "Buffer": "0x0102" } },
+                        "sender": { "PrincipalStandard": "SP000000000000000000002Q6VF78" },
+                        "text": { "StringUTF8": "h\u{e9}" },
+                    }
+                }
+            })
+        );
+    }
+}
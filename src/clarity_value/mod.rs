@@ -1,84 +1,241 @@
-use std::{convert::TryInto, io::Cursor};
+use std::io::Cursor;
 
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
 
-use crate::neon_util::{arg_as_bytes, arg_as_bytes_copied};
+#[cfg(feature = "nodejs")]
+use crate::hex::encode_hex;
+#[cfg(feature = "nodejs")]
+use crate::neon_util::{
+    arg_as_bytes, arg_as_bytes_copied, arg_options_obj, get_obj_prop_opt, throw_deserialize_error,
+    NumberFormat, OutputOptions,
+};
+#[cfg(feature = "nodejs")]
+use crate::output_options::{DecodeClarityValueOptions, NativeValueOptions, SelectedValueFormat};
+use crate::serialize_util::{DeserializeError, DeserializeErrorKind};
 
-use self::{neon_encoder::decode_clarity_val, types::ClarityValue};
+#[cfg(feature = "nodejs")]
+use self::{
+    neon_decoder::{clarity_val_from_js, path_from_js, type_signature_from_js},
+    neon_encoder::{clarity_val_to_native, decode_clarity_val, decode_clarity_val_typed},
+    types::Value,
+};
+use self::{types::ClarityValue, value_ref::ClarityValueRef};
 
 pub mod deserialize;
+#[cfg(feature = "nodejs")]
+pub mod neon_decoder;
+#[cfg(feature = "nodejs")]
 pub mod neon_encoder;
+pub mod repr_parser;
+pub mod serialize;
+pub mod type_signature;
 pub mod types;
+pub mod value_ref;
 
+/// Deserialize the Clarity value at the start of `input` as for `decodeClarityValue`, returning
+/// it with its length in bytes. With `strict`, non-canonical encodings and bytes after the value
+/// are rejected, see `ClarityValue::deserialize_strict`.
+pub fn deserialize_clarity_value(
+    input: &[u8],
+    strict: bool,
+) -> Result<(ClarityValueRef<'_>, usize), DeserializeError> {
+    let mut cursor = Cursor::new(input);
+    if strict {
+        // Strict decoding checks the whole value up front, the lenient checks are a subset.
+        ClarityValue::deserialize_strict(&mut cursor, false)?;
+        if cursor.position() as usize != input.len() {
+            return Err(DeserializeError::new(DeserializeErrorKind::NonCanonical(
+                "trailing bytes".to_string(),
+            ))
+            .at_offset(cursor.position()));
+        }
+        cursor.set_position(0);
+    }
+    let value = ClarityValueRef::deserialize(&mut cursor)?;
+    Ok((value, cursor.position() as usize))
+}
+
+#[cfg(feature = "nodejs")]
+impl DecodeClarityValueOptions {
+    /// Read the options argument of `decodeClarityValue`.
+    fn from_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<Self> {
+        let mut options = DecodeClarityValueOptions::default();
+        if let Some(obj) = arg_options_obj(cx, arg_index)? {
+            if let Some(strict) = get_obj_prop_opt::<JsBoolean>(cx, &obj, "strict")? {
+                options.strict = strict.value(cx);
+            }
+            options.output = OutputOptions::from_options(cx, &obj)?;
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(feature = "nodejs")]
 pub fn decode_clarity_value(mut cx: FunctionContext) -> JsResult<JsObject> {
     let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let options = DecodeClarityValueOptions::from_arg(&mut cx, 1)?;
 
-    let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
-    let clarity_value = ClarityValue::deserialize(&mut cursor, true)
-        .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
+    let (clarity_value, len) = deserialize_clarity_value(&val_bytes, options.strict)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
 
     let root_obj = cx.empty_object();
-    decode_clarity_val(&mut cx, &root_obj, &clarity_value, true, val_bytes)?;
+    decode_clarity_val(&mut cx, &root_obj, &clarity_value, true, options.output)?;
+    if len != val_bytes.len() {
+        // The root `hex` is the whole input, including any bytes after the value.
+        let hex = cx.string(encode_hex(&val_bytes));
+        root_obj.set(&mut cx, "hex", hex)?;
+    }
 
     return Ok(root_obj);
 }
 
+/// Decode a Clarity value against a declared type signature, validating the value and adding the
+/// declared type to each decoded value.
+#[cfg(feature = "nodejs")]
+pub fn decode_clarity_value_with_type(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let type_arg = cx.argument::<JsValue>(1)?;
+    let type_sig = type_signature_from_js(&mut cx, type_arg)?;
+    let numbers = NumberFormat::from_arg(&mut cx, 2)?;
+
+    let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
+    let clarity_value = ClarityValueRef::deserialize(&mut cursor)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    type_sig
+        .admits(&clarity_value)
+        .or_else(|e| cx.throw_error(e))?;
+
+    let root_obj = cx.empty_object();
+    decode_clarity_val_typed(&mut cx, &root_obj, &clarity_value, &type_sig, numbers)?;
+    Ok(root_obj)
+}
+
+/// Decode a Clarity value to plain JS values, e.g. `(tuple (a u1) (b (some "x")))` to
+/// `{a: 1n, b: "x"}`.
+#[cfg(feature = "nodejs")]
+pub fn decode_clarity_value_to_native(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let options = NativeValueOptions::from_arg(&mut cx, 1)?;
+
+    let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
+    let clarity_value = ClarityValue::deserialize(&mut cursor, false)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    clarity_val_to_native(&mut cx, &clarity_value.value, &options)
+}
+
+#[cfg(feature = "nodejs")]
+pub fn encode_clarity_value(mut cx: FunctionContext) -> JsResult<JsString> {
+    let input_obj = cx.argument::<JsObject>(0)?;
+    let clarity_value = clarity_val_from_js(&mut cx, &input_obj)?;
+    let serialized = clarity_value
+        .serialize_to_vec()
+        .or_else(|e| cx.throw_error(format!("Error serializing Clarity value: {}", e)))?;
+    Ok(cx.string(encode_hex(&serialized)))
+}
+
+#[cfg(feature = "nodejs")]
+pub fn parse_clarity_repr(mut cx: FunctionContext) -> JsResult<JsString> {
+    let repr = cx.argument::<JsString>(0)?.value(&mut cx);
+    let value = Value::parse_repr(&repr)
+        .or_else(|e| cx.throw_error(format!("Error parsing Clarity repr: {}", e)))?;
+    let serialized = value
+        .serialize_to_vec()
+        .or_else(|e| cx.throw_error(format!("Error serializing Clarity value: {}", e)))?;
+    Ok(cx.string(encode_hex(&serialized)))
+}
+
+#[cfg(feature = "nodejs")]
+impl SelectedValueFormat {
+    /// Read the options argument of `decodeClarityValueAtPath`.
+    fn from_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<Self> {
+        let obj = match arg_options_obj(cx, arg_index)? {
+            Some(obj) => obj,
+            None => return Ok(SelectedValueFormat::Object(NumberFormat::String)),
+        };
+        let numbers = NumberFormat::from_options(cx, &obj)?;
+        match get_obj_prop_opt::<JsString>(cx, &obj, "format")? {
+            None => Ok(SelectedValueFormat::Object(numbers)),
+            Some(format) => match format.value(cx).as_str() {
+                "object" => Ok(SelectedValueFormat::Object(numbers)),
+                "repr" => Ok(SelectedValueFormat::Repr),
+                "hex" => Ok(SelectedValueFormat::Hex),
+                other => cx.throw_error(format!("Unknown format `{}`", other)),
+            },
+        }
+    }
+}
+
+/// Decode only the nested value at a path, e.g. `data.amount`, skipping over everything else.
+/// Returns null if there is no value at the path.
+#[cfg(feature = "nodejs")]
+pub fn decode_clarity_value_at_path(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let path_arg = cx.argument::<JsValue>(1)?;
+    let path = path_from_js(&mut cx, path_arg)?;
+    let format = SelectedValueFormat::from_arg(&mut cx, 2)?;
+
+    let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
+    let selected = ClarityValueRef::deserialize_at_path(&mut cursor, &path)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    let value = match selected {
+        Some(value) => value,
+        None => return Ok(cx.null().upcast()),
+    };
+    match format {
+        SelectedValueFormat::Object(numbers) => {
+            let obj = cx.empty_object();
+            decode_clarity_val(&mut cx, &obj, &value, true, numbers.into())?;
+            Ok(obj.upcast())
+        }
+        SelectedValueFormat::Repr => Ok(cx.string(value.repr_string()).upcast()),
+        SelectedValueFormat::Hex => Ok(cx.string(encode_hex(value.serialized_bytes())).upcast()),
+    }
+}
+
+#[cfg(feature = "nodejs")]
 pub fn decode_clarity_value_type_name(mut cx: FunctionContext) -> JsResult<JsString> {
-    let type_string = arg_as_bytes(&mut cx, 0, |val_bytes| {
+    let clarity_value = arg_as_bytes(&mut cx, 0, |val_bytes| {
         let mut cursor = Cursor::new(val_bytes);
         ClarityValue::deserialize(&mut cursor, false)
-            .map_err(|err| err.as_string())
-            .map(|val| val.value.type_signature())
     })
-    .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
+    .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    let type_string = clarity_value
+        .value
+        .type_signature()
+        .or_else(|e| cx.throw_error(format!("Error inferring Clarity type: {}", e)))?;
     Ok(cx.string(type_string))
 }
 
+#[cfg(feature = "nodejs")]
 pub fn decode_clarity_value_to_repr(mut cx: FunctionContext) -> JsResult<JsString> {
     let repr_string = arg_as_bytes(&mut cx, 0, |val_bytes| {
         let mut cursor = Cursor::new(val_bytes);
-        ClarityValue::deserialize(&mut cursor, false)
-            .map_err(|err| err.as_string())
-            .map(|val| val.value.repr_string())
+        ClarityValueRef::deserialize(&mut cursor).map(|val| val.repr_string())
     })
-    .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
+    .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
     Ok(cx.string(repr_string))
 }
 
+#[cfg(feature = "nodejs")]
 pub fn decode_clarity_value_array(mut cx: FunctionContext) -> JsResult<JsArray> {
     let input_bytes = arg_as_bytes_copied(&mut cx, 0)?;
 
-    let result_length = if input_bytes.len() >= 4 {
-        u32::from_be_bytes(input_bytes[..4].try_into().unwrap())
-    } else {
-        0
-    };
-
-    let array_result = JsArray::new(&mut cx, result_length as usize);
-
     let deep: bool = match cx.argument_opt(1) {
-        Some(arg) => arg
+        Some(arg) if !arg.is_a::<JsUndefined, _>(&mut cx) => arg
             .downcast_or_throw::<JsBoolean, _>(&mut cx)?
             .value(&mut cx),
-        None => false,
+        _ => false,
     };
+    let numbers = NumberFormat::from_arg(&mut cx, 2)?;
 
-    if input_bytes.len() > 4 {
-        let val_slice = &input_bytes[4..];
-        let mut byte_cursor = std::io::Cursor::new(val_slice);
-        let val_len = val_slice.len() as u64;
-        let mut i: u32 = 0;
-        while byte_cursor.position() < val_len {
-            let cursor_pos = byte_cursor.position();
-            let clarity_value = ClarityValue::deserialize(&mut byte_cursor, deep)
-                .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
-            let decoded_bytes =
-                &byte_cursor.get_ref()[cursor_pos as usize..byte_cursor.position() as usize];
-            let value_obj = cx.empty_object();
-            decode_clarity_val(&mut cx, &value_obj, &clarity_value, deep, decoded_bytes)?;
-            array_result.set(&mut cx, i, value_obj)?;
-            i = i + 1;
-        }
+    let values = ClarityValueRef::deserialize_list(&input_bytes)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    let array_result = JsArray::new(&mut cx, values.len());
+    for (i, clarity_value) in values.iter().enumerate() {
+        let value_obj = cx.empty_object();
+        decode_clarity_val(&mut cx, &value_obj, clarity_value, deep, numbers.into())?;
+        array_result.set(&mut cx, i as u32, value_obj)?;
     }
     Ok(array_result)
 }
@@ -0,0 +1,266 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use neon::prelude::*;
+
+use crate::address::c32::c32_address_decode;
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::clarity_value::type_signature::{TypeSignature, MAX_TYPE_DEPTH};
+use crate::clarity_value::types::{
+    ClarityName, ClarityValue, QualifiedContractIdentifier, StandardPrincipalData, Value,
+};
+use crate::clarity_value::value_ref::parse_path;
+use crate::hex::decode_hex;
+use crate::neon_util::{
+    get_obj_prop, get_obj_prop_bytes, get_obj_prop_integer_string, get_obj_prop_number,
+    get_obj_prop_string, json_parse,
+};
+use crate::serialize_util::PathSegment;
+
+/// Build a Clarity `Value` from an object in the shape produced by `decode_clarity_val`
+/// (i.e. the deep `decodeClarityValue` output). The `repr` and `hex` properties are ignored.
+pub fn clarity_val_from_js(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<Value> {
+    let type_id = get_obj_prop_number(cx, obj, "type_id")?;
+    let prefix = match TypePrefix::from_u8(type_id as u8) {
+        Some(prefix) if type_id.fract() == 0.0 && (0.0..=255.0).contains(&type_id) => prefix,
+        _ => cx.throw_error(format!("Bad Clarity type_id: {}", type_id))?,
+    };
+
+    let value = match prefix {
+        TypePrefix::Int => {
+            let val_str = get_obj_prop_integer_string(cx, obj, "value")?;
+            let val = val_str
+                .parse::<i128>()
+                .or_else(|e| cx.throw_error(format!("Invalid Clarity int `{}`: {}", val_str, e)))?;
+            Value::Int(val)
+        }
+        TypePrefix::UInt => {
+            let val_str = get_obj_prop_integer_string(cx, obj, "value")?;
+            let val = val_str.parse::<u128>().or_else(|e| {
+                cx.throw_error(format!("Invalid Clarity uint `{}`: {}", val_str, e))
+            })?;
+            Value::UInt(val)
+        }
+        TypePrefix::BoolTrue => Value::Bool(true),
+        TypePrefix::BoolFalse => Value::Bool(false),
+        TypePrefix::Buffer => {
+            let bytes = get_obj_prop_bytes(cx, obj, "buffer")?;
+            Value::Buffer(bytes.into_vec())
+        }
+        TypePrefix::PrincipalStandard => {
+            let principal = standard_principal_from_js(cx, obj)?;
+            Value::PrincipalStandard(principal)
+        }
+        TypePrefix::PrincipalContract => {
+            let issuer = standard_principal_from_js(cx, obj)?;
+            let contract_name = get_obj_prop_string(cx, obj, "contract_name")?;
+            let name = ClarityName::try_from(contract_name).or_else(|e| cx.throw_error(e))?;
+            Value::PrincipalContract(QualifiedContractIdentifier { issuer, name })
+        }
+        TypePrefix::ResponseOk => Value::ResponseOk(Box::new(inner_val_from_js(cx, obj)?)),
+        TypePrefix::ResponseErr => Value::ResponseErr(Box::new(inner_val_from_js(cx, obj)?)),
+        TypePrefix::OptionalNone => Value::OptionalNone,
+        TypePrefix::OptionalSome => Value::OptionalSome(Box::new(inner_val_from_js(cx, obj)?)),
+        TypePrefix::List => {
+            let list = get_obj_prop::<JsArray>(cx, obj, "list")?.to_vec(cx)?;
+            let mut items = Vec::with_capacity(list.len());
+            for item in list {
+                let item_obj = item.downcast_or_throw::<JsObject, _>(cx)?;
+                items.push(ClarityValue::new(clarity_val_from_js(cx, &item_obj)?));
+            }
+            Value::List(items)
+        }
+        TypePrefix::Tuple => {
+            let tuple_obj = get_obj_prop::<JsObject>(cx, obj, "data")?;
+            let keys = tuple_obj.get_own_property_names(cx)?.to_vec(cx)?;
+            let mut data = BTreeMap::new();
+            for key in keys {
+                let key = key.downcast_or_throw::<JsString, _>(cx)?.value(cx);
+                let val_obj = get_obj_prop::<JsObject>(cx, &tuple_obj, &key)?;
+                let val = clarity_val_from_js(cx, &val_obj)?;
+                let name = ClarityName::try_from(key).or_else(|e| cx.throw_error(e))?;
+                data.insert(name, ClarityValue::new(val));
+            }
+            Value::Tuple(data)
+        }
+        TypePrefix::StringASCII => {
+            let data = get_obj_prop_string(cx, obj, "data")?;
+            if !data.is_ascii() {
+                cx.throw_error("Clarity string-ascii data contains non-ASCII characters")?;
+            }
+            Value::StringASCII(data.into_bytes())
+        }
+        TypePrefix::StringUTF8 => {
+            let data = get_obj_prop_string(cx, obj, "data")?;
+            Value::string_utf8(data.into_bytes())
+        }
+    };
+    Ok(value)
+}
+
+/// Build a Clarity value from either a shallow object (as found in `decodeTransaction` function
+/// args and post-conditions) using its serialized `hex` property, or else from a deep object
+/// as accepted by `clarity_val_from_js`.
+pub fn clarity_val_from_js_hex_or_deep(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<ClarityValue> {
+    let hex_prop: Handle<JsValue> = obj.get_value(cx, "hex")?;
+    if let Ok(hex) = hex_prop.downcast::<JsString, _>(cx) {
+        let hex = hex.value(cx);
+        let bytes = decode_hex(&hex)
+            .or_else(|e| cx.throw_error(format!("Hex parsing error for Clarity value: {}", e)))?;
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let value = ClarityValue::deserialize(&mut cursor, false)
+            .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
+        if cursor.position() as usize != bytes.len() {
+            cx.throw_error(format!("Trailing bytes in Clarity value hex {}", hex))?;
+        }
+        return Ok(value);
+    }
+    let value = clarity_val_from_js(cx, obj)?;
+    Ok(ClarityValue::new(value))
+}
+
+/// Build a `TypeSignature` from either a string in Clarity syntax (e.g. `(list 10 uint)`), or a
+/// type in the JSON format of a contract ABI from `/v2/contracts/interface`
+/// (e.g. `{ "list": { "type": "uint128", "length": 10 } }`), given as an object or JSON string.
+pub fn type_signature_from_js(
+    cx: &mut FunctionContext,
+    val: Handle<JsValue>,
+) -> NeonResult<TypeSignature> {
+    abi_type_from_js(cx, val, 0)
+}
+
+/// Build a path for `ClarityValueRef::deserialize_at_path` from either a string such as
+/// `data.items[2]`, or an array of tuple field names and list indexes such as
+/// `["data", "items", 2]`.
+pub fn path_from_js(
+    cx: &mut FunctionContext,
+    val: Handle<JsValue>,
+) -> NeonResult<Vec<PathSegment>> {
+    if let Ok(path) = val.downcast::<JsString, _>(cx) {
+        let path = path.value(cx);
+        return parse_path(&path).or_else(|e| cx.throw_error(e));
+    }
+    let segments = val
+        .downcast::<JsArray, _>(cx)
+        .or_else(|_| cx.throw_type_error("Path must be a string or an array"))?
+        .to_vec(cx)?;
+    let mut path = Vec::with_capacity(segments.len());
+    for segment in segments {
+        if let Ok(name) = segment.downcast::<JsString, _>(cx) {
+            path.push(PathSegment::Field(name.value(cx)));
+        } else if let Ok(index) = segment.downcast::<JsNumber, _>(cx) {
+            let index = index.value(cx);
+            if index < 0.0 || index.fract() != 0.0 || index > u32::MAX as f64 {
+                return cx.throw_range_error(format!("Invalid list index in path: {}", index));
+            }
+            path.push(PathSegment::Index(index as usize));
+        } else {
+            return cx.throw_type_error("Path segments must be strings or numbers");
+        }
+    }
+    Ok(path)
+}
+
+fn abi_type_from_js(
+    cx: &mut FunctionContext,
+    val: Handle<JsValue>,
+    depth: u8,
+) -> NeonResult<TypeSignature> {
+    if depth >= MAX_TYPE_DEPTH {
+        return cx.throw_error(format!("TypeSignatureTooDeep: {}", depth));
+    }
+    if let Ok(handle) = val.downcast::<JsString, _>(cx) {
+        let input = handle.value(cx);
+        let trimmed = input.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('"') {
+            let parsed = json_parse(cx, &input)?;
+            return abi_type_from_js(cx, parsed, depth);
+        }
+        return TypeSignature::parse(&input)
+            .or_else(|e| cx.throw_error(format!("Error parsing type signature: {}", e)));
+    }
+    let obj = val.downcast::<JsObject, _>(cx).or_else(|_| {
+        cx.throw_type_error("Type signature must be a string or an ABI type object")
+    })?;
+    let keys = obj.get_own_property_names(cx)?.to_vec(cx)?;
+    if keys.len() != 1 {
+        return cx.throw_error("ABI type object must have exactly one property");
+    }
+    let key = keys[0].downcast_or_throw::<JsString, _>(cx)?.value(cx);
+    let type_sig = match key.as_str() {
+        "buffer" | "string-ascii" | "string-utf8" => {
+            let inner_obj = get_obj_prop::<JsObject>(cx, &obj, &key)?;
+            let len = abi_length_from_js(cx, &inner_obj)?;
+            match key.as_str() {
+                "buffer" => TypeSignature::Buffer(len),
+                "string-ascii" => TypeSignature::StringASCII(len),
+                _ => TypeSignature::StringUTF8(len),
+            }
+        }
+        "optional" => {
+            let inner: Handle<JsValue> = obj.get_value(cx, "optional")?;
+            TypeSignature::Optional(Box::new(abi_type_from_js(cx, inner, depth + 1)?))
+        }
+        "response" => {
+            let inner_obj = get_obj_prop::<JsObject>(cx, &obj, "response")?;
+            let ok: Handle<JsValue> = inner_obj.get_value(cx, "ok")?;
+            let ok = abi_type_from_js(cx, ok, depth + 1)?;
+            let err: Handle<JsValue> = inner_obj.get_value(cx, "error")?;
+            let err = abi_type_from_js(cx, err, depth + 1)?;
+            TypeSignature::Response(Box::new(ok), Box::new(err))
+        }
+        "list" => {
+            let inner_obj = get_obj_prop::<JsObject>(cx, &obj, "list")?;
+            let len = abi_length_from_js(cx, &inner_obj)?;
+            let item_type: Handle<JsValue> = inner_obj.get_value(cx, "type")?;
+            let item_type = abi_type_from_js(cx, item_type, depth + 1)?;
+            TypeSignature::List(Box::new(item_type), len)
+        }
+        "tuple" => {
+            let fields = get_obj_prop::<JsArray>(cx, &obj, "tuple")?.to_vec(cx)?;
+            let mut field_types = BTreeMap::new();
+            for field in fields {
+                let field_obj = field.downcast_or_throw::<JsObject, _>(cx)?;
+                let name = get_obj_prop_string(cx, &field_obj, "name")?;
+                let name = ClarityName::try_from(name).or_else(|e| cx.throw_error(e))?;
+                if field_types.contains_key(&name) {
+                    return cx.throw_error(format!("Duplicate tuple field `{}`", name));
+                }
+                let field_type: Handle<JsValue> = field_obj.get_value(cx, "type")?;
+                let field_type = abi_type_from_js(cx, field_type, depth + 1)?;
+                field_types.insert(name, field_type);
+            }
+            TypeSignature::Tuple(field_types)
+        }
+        _ => cx.throw_error(format!("Unknown ABI type `{}`", key))?,
+    };
+    Ok(type_sig)
+}
+
+fn abi_length_from_js(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<u32> {
+    let len = get_obj_prop_number(cx, obj, "length")?;
+    if len.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&len) {
+        return cx.throw_type_error("Property `length` must be a u32 integer");
+    }
+    Ok(len as u32)
+}
+
+fn inner_val_from_js(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<ClarityValue> {
+    let inner_obj = get_obj_prop::<JsObject>(cx, obj, "value")?;
+    let inner = clarity_val_from_js(cx, &inner_obj)?;
+    Ok(ClarityValue::new(inner))
+}
+
+fn standard_principal_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<StandardPrincipalData> {
+    let address = get_obj_prop_string(cx, obj, "address")?;
+    let (version, hash160) = c32_address_decode(&address)
+        .or_else(|e| cx.throw_error(format!("Error parsing Stacks address {}", e)))?;
+    Ok(StandardPrincipalData(version, hash160))
+}
@@ -1,37 +1,52 @@
 use crate::address::c32::c32_address;
 use crate::clarity_value;
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::clarity_value::type_signature::TypeSignature;
+use crate::clarity_value::value_ref::{ClarityValueRef, ValueRef};
 use crate::hex::encode_hex;
+use crate::neon_util::{
+    arg_options_obj, get_obj_prop_opt, NumberFormat, OutputOptions, OutputProfile,
+};
+use crate::output_options::{BufferFormat, NativeValueOptions};
 use neon::prelude::*;
 
-pub fn decode_clarity_val<T: AsRef<[u8]>>(
-    cx: &mut FunctionContext,
+/// Set the properties of a decoded Clarity value on `cur_obj`. Unless `deep` is set, or with the
+/// `json` profile, only the top-level `repr`, `hex` and `type_id` are set.
+pub fn decode_clarity_val<'cx, C: Context<'cx>>(
+    cx: &mut C,
     cur_obj: &Handle<JsObject>,
-    val: &clarity_value::types::ClarityValue,
+    val: &ClarityValueRef,
     deep: bool,
-    bytes: T,
+    options: OutputOptions,
 ) -> NeonResult<()> {
-    let repr_string = cx.string(val.value.repr_string());
-    cur_obj.set(cx, "repr", repr_string)?;
+    match options.profile {
+        OutputProfile::Json => return decode_clarity_val_json(cx, cur_obj, val, options.numbers),
+        OutputProfile::Minimal => {}
+        OutputProfile::Full => {
+            let repr_string = cx.string(val.repr_string());
+            cur_obj.set(cx, "repr", repr_string)?;
 
-    let hex = cx.string(encode_hex(bytes.as_ref()));
-    cur_obj.set(cx, "hex", hex)?;
+            let hex = cx.string(encode_hex(val.serialized_bytes()));
+            cur_obj.set(cx, "hex", hex)?;
+        }
+    }
 
-    let type_id = cx.number(val.value.type_prefix().to_u8());
+    let type_id = cx.number(val.type_prefix().to_u8());
     cur_obj.set(cx, "type_id", type_id)?;
 
     if deep {
-        use clarity_value::types::Value::*;
-        match &val.value {
+        use ValueRef::*;
+        match val.value() {
             Int(val) => {
-                let val_string = cx.string(val.to_string());
-                cur_obj.set(cx, "value", val_string)?;
+                let val_number = options.numbers.int(cx, val);
+                cur_obj.set(cx, "value", val_number)?;
             }
             UInt(val) => {
-                let val_string = cx.string(val.to_string());
-                cur_obj.set(cx, "value", val_string)?;
+                let val_number = options.numbers.uint(cx, val);
+                cur_obj.set(cx, "value", val_number)?;
             }
             Bool(val) => {
-                let val_boolean = cx.boolean(*val);
+                let val_boolean = cx.boolean(val);
                 cur_obj.set(cx, "value", val_boolean)?;
             }
             Buffer(buff) => {
@@ -42,85 +57,58 @@ pub fn decode_clarity_val<T: AsRef<[u8]>>(
                 let list_obj = JsArray::new(cx, data.len());
                 for (i, x) in data.iter().enumerate() {
                     let item_obj = cx.empty_object();
-                    decode_clarity_val(
-                        cx,
-                        &item_obj,
-                        x,
-                        deep,
-                        x.serialized_bytes.as_ref().unwrap(),
-                    )?;
+                    decode_clarity_val(cx, &item_obj, &x, deep, options)?;
                     list_obj.set(cx, i as u32, item_obj)?;
                 }
                 cur_obj.set(cx, "list", list_obj)?;
             }
-            StringASCII(str_data) => {
+            StringASCII(str_data) | StringUTF8(str_data) => {
                 let data = cx.string(String::from_utf8_lossy(str_data));
                 cur_obj.set(cx, "data", data)?;
             }
-            StringUTF8(str_data) => {
-                let utf8_bytes: Vec<u8> = str_data.iter().cloned().flatten().collect();
-                let utf8_str = String::from_utf8_lossy(&utf8_bytes);
-                let data = cx.string(utf8_str);
-                cur_obj.set(cx, "data", data)?;
-            }
-            PrincipalStandard(standard_principal) => {
-                let address_version = cx.number(standard_principal.0);
+            PrincipalStandard(version, hash_bytes) => {
+                let address_version = cx.number(version);
                 cur_obj.set(cx, "address_version", address_version)?;
 
-                let address_hash_bytes = cx.string(encode_hex(&standard_principal.1));
+                let address_hash_bytes = cx.string(encode_hex(hash_bytes));
                 cur_obj.set(cx, "address_hash_bytes", address_hash_bytes)?;
 
-                let address_string = c32_address(standard_principal.0, &standard_principal.1)
-                    .or_else(|e| {
-                        cx.throw_error(format!("Error converting to C32 address: {}", e))
-                    })?;
+                let address_string = c32_address(version, hash_bytes).or_else(|e| {
+                    cx.throw_error(format!("Error converting to C32 address: {}", e))
+                })?;
 
                 let address = cx.string(address_string);
                 cur_obj.set(cx, "address", address)?;
             }
-            PrincipalContract(contract_identifier) => {
-                let address_version = cx.number(contract_identifier.issuer.0);
+            PrincipalContract(version, hash_bytes, name) => {
+                let address_version = cx.number(version);
                 cur_obj.set(cx, "address_version", address_version)?;
 
-                let address_hash_bytes = cx.string(encode_hex(&contract_identifier.issuer.1));
+                let address_hash_bytes = cx.string(encode_hex(hash_bytes));
                 cur_obj.set(cx, "address_hash_bytes", address_hash_bytes)?;
 
-                let address_string =
-                    c32_address(contract_identifier.issuer.0, &contract_identifier.issuer.1)
-                        .or_else(|e| {
-                            cx.throw_error(format!("Error converting to C32 address: {}", e))
-                        })?;
+                let address_string = c32_address(version, hash_bytes).or_else(|e| {
+                    cx.throw_error(format!("Error converting to C32 address: {}", e))
+                })?;
 
                 let address = cx.string(address_string);
                 cur_obj.set(cx, "address", address)?;
 
-                let contract_name = cx.string(contract_identifier.name.as_str());
+                let contract_name = cx.string(name);
                 cur_obj.set(cx, "contract_name", contract_name)?;
             }
             Tuple(val) => {
                 let tuple_obj = cx.empty_object();
-                for (key, value) in val.iter() {
+                for (key, value) in val.sorted_fields() {
                     let val_obj = cx.empty_object();
-                    decode_clarity_val(
-                        cx,
-                        &val_obj,
-                        value,
-                        deep,
-                        value.serialized_bytes.as_ref().unwrap(),
-                    )?;
-                    tuple_obj.set(cx, key.as_str(), val_obj)?;
+                    decode_clarity_val(cx, &val_obj, &value, deep, options)?;
+                    tuple_obj.set(cx, key, val_obj)?;
                 }
                 cur_obj.set(cx, "data", tuple_obj)?;
             }
             OptionalSome(data) => {
                 let option_obj = cx.empty_object();
-                decode_clarity_val(
-                    cx,
-                    &option_obj,
-                    data,
-                    deep,
-                    data.serialized_bytes.as_ref().unwrap(),
-                )?;
+                decode_clarity_val(cx, &option_obj, &data, deep, options)?;
                 cur_obj.set(cx, "value", option_obj)?;
             }
             OptionalNone => {
@@ -129,16 +117,243 @@ pub fn decode_clarity_val<T: AsRef<[u8]>>(
             }
             ResponseOk(val) | ResponseErr(val) => {
                 let response_obj = cx.empty_object();
-                decode_clarity_val(
-                    cx,
-                    &response_obj,
-                    &val,
-                    deep,
-                    val.serialized_bytes.as_ref().unwrap(),
-                )?;
+                decode_clarity_val(cx, &response_obj, &val, deep, options)?;
                 cur_obj.set(cx, "value", response_obj)?;
             }
         };
     }
     Ok(())
 }
+
+/// A Clarity value in the `{type, value}` JSON convention of the Stacks API (`cvToJSON` in
+/// stacks.js). Responses also get a `success` property.
+fn decode_clarity_val_json<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    cur_obj: &Handle<JsObject>,
+    val: &ClarityValueRef,
+    numbers: NumberFormat,
+) -> NeonResult<()> {
+    let type_string = cx.string(val.json_type_string());
+    cur_obj.set(cx, "type", type_string)?;
+
+    use ValueRef::*;
+    let value: Handle<JsValue> = match val.value() {
+        Int(val) => numbers.int(cx, val),
+        UInt(val) => numbers.uint(cx, val),
+        Bool(val) => cx.boolean(val).upcast(),
+        Buffer(buff) => cx.string(encode_hex(buff)).upcast(),
+        StringASCII(str_data) | StringUTF8(str_data) => {
+            cx.string(String::from_utf8_lossy(str_data)).upcast()
+        }
+        PrincipalStandard(version, hash_bytes) => {
+            let address = c32_address(version, hash_bytes)
+                .or_else(|e| cx.throw_error(format!("Error converting to C32 address: {}", e)))?;
+            cx.string(address).upcast()
+        }
+        PrincipalContract(version, hash_bytes, name) => {
+            let address = c32_address(version, hash_bytes)
+                .or_else(|e| cx.throw_error(format!("Error converting to C32 address: {}", e)))?;
+            cx.string(format!("{}.{}", address, name)).upcast()
+        }
+        List(data) => {
+            let list_obj = JsArray::new(cx, data.len());
+            for (i, x) in data.iter().enumerate() {
+                let item_obj = cx.empty_object();
+                decode_clarity_val_json(cx, &item_obj, &x, numbers)?;
+                list_obj.set(cx, i as u32, item_obj)?;
+            }
+            list_obj.upcast()
+        }
+        Tuple(val) => {
+            let tuple_obj = cx.empty_object();
+            for (key, value) in val.sorted_fields() {
+                let val_obj = cx.empty_object();
+                decode_clarity_val_json(cx, &val_obj, &value, numbers)?;
+                tuple_obj.set(cx, key, val_obj)?;
+            }
+            tuple_obj.upcast()
+        }
+        OptionalSome(data) => {
+            let option_obj = cx.empty_object();
+            decode_clarity_val_json(cx, &option_obj, &data, numbers)?;
+            option_obj.upcast()
+        }
+        OptionalNone => cx.null().upcast(),
+        ResponseOk(data) | ResponseErr(data) => {
+            let response_obj = cx.empty_object();
+            decode_clarity_val_json(cx, &response_obj, &data, numbers)?;
+            response_obj.upcast()
+        }
+    };
+    cur_obj.set(cx, "value", value)?;
+
+    let type_prefix = val.type_prefix();
+    if type_prefix == TypePrefix::ResponseOk || type_prefix == TypePrefix::ResponseErr {
+        let success = cx.boolean(type_prefix == TypePrefix::ResponseOk);
+        cur_obj.set(cx, "success", success)?;
+    }
+    Ok(())
+}
+
+/// Like a deep `decode_clarity_val`, but each value also gets a `type` property with its declared
+/// type, so that e.g. a `none` carries `(optional uint)` rather than `(optional UnknownType)`.
+/// The value must already have been checked with `TypeSignature::admits`.
+pub fn decode_clarity_val_typed<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    cur_obj: &Handle<JsObject>,
+    val: &ClarityValueRef,
+    type_sig: &TypeSignature,
+    numbers: NumberFormat,
+) -> NeonResult<()> {
+    let value = val.value();
+    let is_compound = matches!(
+        value,
+        ValueRef::List(_)
+            | ValueRef::Tuple(_)
+            | ValueRef::OptionalSome(_)
+            | ValueRef::ResponseOk(_)
+            | ValueRef::ResponseErr(_)
+    );
+    decode_clarity_val(cx, cur_obj, val, !is_compound, numbers.into())?;
+
+    let type_string = cx.string(type_sig.to_string());
+    cur_obj.set(cx, "type", type_string)?;
+
+    match (value, type_sig) {
+        (ValueRef::List(data), TypeSignature::List(item_type, _)) => {
+            let list_obj = JsArray::new(cx, data.len());
+            for (i, x) in data.iter().enumerate() {
+                let item_obj = cx.empty_object();
+                decode_clarity_val_typed(cx, &item_obj, &x, item_type, numbers)?;
+                list_obj.set(cx, i as u32, item_obj)?;
+            }
+            cur_obj.set(cx, "list", list_obj)?;
+        }
+        (ValueRef::Tuple(data), TypeSignature::Tuple(field_types)) => {
+            let tuple_obj = cx.empty_object();
+            for (key, value) in data.sorted_fields() {
+                let field_type = match field_types.get(key) {
+                    Some(field_type) => field_type,
+                    None => {
+                        return cx.throw_error(format!(
+                            "Clarity value {} does not match type {}",
+                            val.repr_string(),
+                            type_sig
+                        ))
+                    }
+                };
+                let val_obj = cx.empty_object();
+                decode_clarity_val_typed(cx, &val_obj, &value, field_type, numbers)?;
+                tuple_obj.set(cx, key, val_obj)?;
+            }
+            cur_obj.set(cx, "data", tuple_obj)?;
+        }
+        (ValueRef::OptionalSome(data), TypeSignature::Optional(inner_type))
+        | (ValueRef::ResponseOk(data), TypeSignature::Response(inner_type, _))
+        | (ValueRef::ResponseErr(data), TypeSignature::Response(_, inner_type)) => {
+            let inner_obj = cx.empty_object();
+            decode_clarity_val_typed(cx, &inner_obj, &data, inner_type, numbers)?;
+            cur_obj.set(cx, "value", inner_obj)?;
+        }
+        (_, _) if is_compound => {
+            return cx.throw_error(format!(
+                "Clarity value {} does not match type {}",
+                val.repr_string(),
+                type_sig
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+impl NativeValueOptions {
+    /// Read the options argument of `decodeClarityValueToNative`.
+    pub fn from_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<Self> {
+        let mut options = NativeValueOptions::default();
+        if let Some(obj) = arg_options_obj(cx, arg_index)? {
+            options.numbers = NumberFormat::from_options_or(cx, &obj, NumberFormat::BigInt)?;
+            if let Some(buffers) = get_obj_prop_opt::<JsString>(cx, &obj, "buffers")? {
+                options.buffers = match buffers.value(cx).as_str() {
+                    "hex" => BufferFormat::Hex,
+                    "uint8array" => BufferFormat::Uint8Array,
+                    other => return cx.throw_error(format!("Unknown buffers format `{}`", other)),
+                };
+            }
+            if let Some(unwrap) = get_obj_prop_opt::<JsBoolean>(cx, &obj, "unwrapResponses")? {
+                options.unwrap_responses = unwrap.value(cx);
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Convert a Clarity value to a plain JS value, like `cvToValue` in stacks.js: tuples become
+/// objects, lists arrays, `(some x)` the value of `x` and `none` null, responses `{ok: x}` or
+/// `{err: x}`, and principals their address strings.
+pub fn clarity_val_to_native<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    val: &clarity_value::types::Value,
+    options: &NativeValueOptions,
+) -> JsResult<'cx, JsValue> {
+    use clarity_value::types::Value::*;
+    let native: Handle<JsValue> = match val {
+        Int(val) => options.numbers.int(cx, *val),
+        UInt(val) => options.numbers.uint(cx, *val),
+        Bool(val) => cx.boolean(*val).upcast(),
+        Buffer(buff) => match options.buffers {
+            BufferFormat::Hex => cx.string(encode_hex(buff)).upcast(),
+            BufferFormat::Uint8Array => JsUint8Array::from_slice(cx, buff)?.upcast(),
+        },
+        StringASCII(str_data) => cx.string(String::from_utf8_lossy(str_data)).upcast(),
+        StringUTF8(str_data) => {
+            let str_bytes = str_data.concat();
+            cx.string(String::from_utf8_lossy(&str_bytes)).upcast()
+        }
+        PrincipalStandard(principal) => {
+            let address = c32_address(principal.0, &principal.1)
+                .or_else(|e| cx.throw_error(format!("Error converting to C32 address: {}", e)))?;
+            cx.string(address).upcast()
+        }
+        PrincipalContract(contract) => {
+            let address = c32_address(contract.issuer.0, &contract.issuer.1)
+                .or_else(|e| cx.throw_error(format!("Error converting to C32 address: {}", e)))?;
+            cx.string(format!("{}.{}", address, contract.name.as_str()))
+                .upcast()
+        }
+        List(data) => {
+            let list = JsArray::new(cx, data.len());
+            for (i, x) in data.iter().enumerate() {
+                let item = clarity_val_to_native(cx, &x.value, options)?;
+                list.set(cx, i as u32, item)?;
+            }
+            list.upcast()
+        }
+        Tuple(data) => {
+            let tuple_obj = cx.empty_object();
+            for (key, value) in data.iter() {
+                let field = clarity_val_to_native(cx, &value.value, options)?;
+                tuple_obj.set(cx, key.as_str(), field)?;
+            }
+            tuple_obj.upcast()
+        }
+        OptionalSome(data) => clarity_val_to_native(cx, &data.value, options)?,
+        OptionalNone => cx.null().upcast(),
+        ResponseOk(data) | ResponseErr(data) => {
+            let inner = clarity_val_to_native(cx, &data.value, options)?;
+            if options.unwrap_responses {
+                inner
+            } else {
+                let response_obj = cx.empty_object();
+                let key = if matches!(val, ResponseOk(_)) {
+                    "ok"
+                } else {
+                    "err"
+                };
+                response_obj.set(cx, key, inner)?;
+                response_obj.upcast()
+            }
+        }
+    };
+    Ok(native)
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::address::c32::c32_address_decode;
+use crate::hex::decode_hex;
+
+use super::types::*;
+
+/// Maximum nesting of parenthesized expressions, matching the deserializer's depth limit.
+const MAX_REPR_DEPTH: u8 = 16;
+
+#[derive(Debug, PartialEq)]
+pub struct ReprParseError {
+    pub message: String,
+    /// 1-based line number of the offending input
+    pub line: usize,
+    /// 1-based column (in characters) of the offending input
+    pub column: usize,
+}
+
+impl fmt::Display for ReprParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Value {
+    /// Parse a Clarity literal in the format produced by `Value::repr_string`, e.g.
+    /// `(tuple (active true) (address 'SP...) (tags (list u"a" u"b")))`.
+    pub fn parse_repr(input: &str) -> Result<Value, ReprParseError> {
+        let mut parser = ReprParser::new(input);
+        parser.skip_whitespace();
+        let value = parser.parse_value(0)?;
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return Err(parser.error("Unexpected input after Clarity value"));
+        }
+        Ok(value)
+    }
+}
+
+struct ReprParser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Snapshot of a location in the input, used to report errors at the start of a token.
+#[derive(Clone, Copy)]
+struct Location {
+    line: usize,
+    column: usize,
+}
+
+impl ReprParser {
+    fn new(input: &str) -> Self {
+        ReprParser {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn location(&self) -> Location {
+        Location {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> ReprParseError {
+        self.error_at(self.location(), message)
+    }
+
+    fn error_at<S: Into<String>>(&self, loc: Location, message: S) -> ReprParseError {
+        ReprParseError {
+            message: message.into(),
+            line: loc.line,
+            column: loc.column,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ReprParseError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("Expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("Expected `{}`, found end of input", expected))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.next();
+            } else if c == ';' && self.peek_at(1) == Some(';') {
+                // Clarity line comment
+                while let Some(c) = self.next() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Read characters up to the next delimiter (whitespace, parenthesis, or quote).
+    fn read_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' || c == '"' || c == ';' {
+                break;
+            }
+            word.push(c);
+            self.next();
+        }
+        word
+    }
+
+    fn parse_value(&mut self, depth: u8) -> Result<Value, ReprParseError> {
+        let start = self.location();
+        match self.peek() {
+            None => Err(self.error("Expected Clarity value, found end of input")),
+            Some('(') => {
+                if depth + 1 > MAX_REPR_DEPTH {
+                    return Err(self.error(format!("TypeSignatureTooDeep: {}", depth + 1)));
+                }
+                self.next();
+                self.skip_whitespace();
+                self.parse_expression(start, depth + 1)
+            }
+            Some(')') => Err(self.error("Unexpected `)`")),
+            Some('"') => self.parse_ascii_string(),
+            Some('u') if self.peek_at(1) == Some('"') => {
+                self.next();
+                self.parse_utf8_string()
+            }
+            Some('\'') => {
+                self.next();
+                self.parse_principal(start)
+            }
+            Some(_) => {
+                let word = self.read_word();
+                parse_atom(&word).map_err(|e| self.error_at(start, e))
+            }
+        }
+    }
+
+    fn parse_expression(&mut self, start: Location, depth: u8) -> Result<Value, ReprParseError> {
+        let keyword_loc = self.location();
+        let keyword = self.read_word();
+        self.skip_whitespace();
+        let value = match keyword.as_str() {
+            "some" | "ok" | "err" => {
+                let inner = ClarityValue::new(self.parse_value(depth)?);
+                self.skip_whitespace();
+                match keyword.as_str() {
+                    "some" => Value::OptionalSome(Box::new(inner)),
+                    "ok" => Value::ResponseOk(Box::new(inner)),
+                    _ => Value::ResponseErr(Box::new(inner)),
+                }
+            }
+            "list" => {
+                let mut items = vec![];
+                while !matches!(self.peek(), Some(')') | None) {
+                    items.push(ClarityValue::new(self.parse_value(depth)?));
+                    self.skip_whitespace();
+                }
+                Value::List(items)
+            }
+            "tuple" => {
+                let mut data = BTreeMap::new();
+                while !matches!(self.peek(), Some(')') | None) {
+                    self.expect('(')?;
+                    self.skip_whitespace();
+                    let name_loc = self.location();
+                    let name = ClarityName::try_from(self.read_word())
+                        .map_err(|e| self.error_at(name_loc, e))?;
+                    if data.contains_key(&name) {
+                        return Err(
+                            self.error_at(name_loc, format!("Duplicate tuple field `{}`", name))
+                        );
+                    }
+                    self.skip_whitespace();
+                    let value = self.parse_value(depth)?;
+                    self.skip_whitespace();
+                    self.expect(')')?;
+                    self.skip_whitespace();
+                    data.insert(name, ClarityValue::new(value));
+                }
+                Value::Tuple(data)
+            }
+            "" => return Err(self.error_at(keyword_loc, "Expected expression keyword")),
+            _ => {
+                return Err(self.error_at(keyword_loc, format!("Unknown expression `{}`", keyword)))
+            }
+        };
+        if self.peek().is_none() {
+            return Err(self.error_at(start, "Unclosed `(`"));
+        }
+        self.expect(')')?;
+        Ok(value)
+    }
+
+    fn parse_ascii_string(&mut self) -> Result<Value, ReprParseError> {
+        self.expect('"')?;
+        let mut data = vec![];
+        loop {
+            let loc = self.location();
+            match self.next() {
+                None => return Err(self.error("Unterminated string literal")),
+                Some('"') => break,
+                Some('\\') => data.push(self.parse_escape(loc)?),
+                Some(c) if c.is_ascii() && !c.is_ascii_control() => data.push(c as u8),
+                Some(c) => {
+                    return Err(self.error_at(
+                        loc,
+                        format!("Invalid character `{}` in string-ascii literal", c),
+                    ))
+                }
+            }
+        }
+        Ok(Value::StringASCII(data))
+    }
+
+    fn parse_utf8_string(&mut self) -> Result<Value, ReprParseError> {
+        self.expect('"')?;
+        let mut data = vec![];
+        loop {
+            let loc = self.location();
+            match self.next() {
+                None => return Err(self.error("Unterminated string literal")),
+                Some('"') => break,
+                Some('\\') if self.peek() == Some('u') => {
+                    self.next();
+                    data.push(self.parse_unicode_escape(loc)?);
+                }
+                Some('\\') => data.push(vec![self.parse_escape(loc)?]),
+                Some(c) => {
+                    let mut encoded_char = vec![0; c.len_utf8()];
+                    c.encode_utf8(&mut encoded_char);
+                    data.push(encoded_char);
+                }
+            }
+        }
+        Ok(Value::StringUTF8(data))
+    }
+
+    /// Parse the remainder of an escape sequence as produced by `std::ascii::escape_default`.
+    fn parse_escape(&mut self, loc: Location) -> Result<u8, ReprParseError> {
+        match self.next() {
+            Some('n') => Ok(b'\n'),
+            Some('r') => Ok(b'\r'),
+            Some('t') => Ok(b'\t'),
+            Some('0') => Ok(0),
+            Some('\\') => Ok(b'\\'),
+            Some('\'') => Ok(b'\''),
+            Some('"') => Ok(b'"'),
+            Some('x') => {
+                let hex: String = [self.next(), self.next()].iter().flatten().collect();
+                u8::from_str_radix(&hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 2)
+                    .ok_or_else(|| self.error_at(loc, format!("Invalid escape `\\x{}`", hex)))
+            }
+            Some(c) => Err(self.error_at(loc, format!("Invalid escape `\\{}`", c))),
+            None => Err(self.error("Unterminated string literal")),
+        }
+    }
+
+    /// Parse a `\u{...}` escape holding the hex of the UTF-8 encoded bytes of one char, as
+    /// written by `Value::repr_string` (e.g. `\u{e2ad90}` for U+2B50).
+    fn parse_unicode_escape(&mut self, loc: Location) -> Result<Vec<u8>, ReprParseError> {
+        self.expect('{')?;
+        let mut hex = String::new();
+        loop {
+            match self.next() {
+                Some('}') => break,
+                Some(c) if c.is_ascii_hexdigit() && hex.len() < 8 => hex.push(c),
+                _ => return Err(self.error_at(loc, "Invalid unicode escape")),
+            }
+        }
+        decode_hex(&hex)
+            .ok()
+            .filter(|bytes| match std::str::from_utf8(bytes) {
+                Ok(s) => s.chars().count() == 1,
+                Err(_) => false,
+            })
+            .map(|bytes| bytes.into_vec())
+            .ok_or_else(|| {
+                self.error_at(
+                    loc,
+                    format!(
+                        "Invalid unicode escape `\\u{{{}}}`, expected the UTF-8 bytes of one char",
+                        hex
+                    ),
+                )
+            })
+    }
+
+    fn parse_principal(&mut self, start: Location) -> Result<Value, ReprParseError> {
+        let word = self.read_word();
+        let (address, contract_name) = match word.split_once('.') {
+            Some((address, name)) => (address, Some(name)),
+            None => (word.as_str(), None),
+        };
+        let (version, hash160) = c32_address_decode(address)
+            .map_err(|e| self.error_at(start, format!("Invalid principal: {}", e)))?;
+        let issuer = StandardPrincipalData(version, hash160);
+        match contract_name {
+            None => Ok(Value::PrincipalStandard(issuer)),
+            Some(name) => {
+                if name.len() < CONTRACT_MIN_NAME_LENGTH || name.len() > CONTRACT_MAX_NAME_LENGTH {
+                    return Err(self.error_at(
+                        start,
+                        format!("Invalid contract name length: {}", name.len()),
+                    ));
+                }
+                let name =
+                    ClarityName::try_from(name.to_string()).map_err(|e| self.error_at(start, e))?;
+                Ok(Value::PrincipalContract(QualifiedContractIdentifier {
+                    issuer,
+                    name,
+                }))
+            }
+        }
+    }
+}
+
+fn parse_atom(word: &str) -> Result<Value, String> {
+    match word {
+        "true" => Ok(Value::Bool(true)),
+        "false" => Ok(Value::Bool(false)),
+        "none" => Ok(Value::OptionalNone),
+        _ if word.starts_with("0x") => decode_hex(word)
+            .map(|bytes| Value::Buffer(bytes.into_vec()))
+            .map_err(|e| format!("Invalid buffer literal `{}`: {}", word, e)),
+        _ if word.starts_with('u') => word[1..]
+            .parse::<u128>()
+            .map(Value::UInt)
+            .map_err(|e| format!("Invalid uint literal `{}`: {}", word, e)),
+        _ if word.starts_with('-') || word.starts_with(|c: char| c.is_ascii_digit()) => word
+            .parse::<i128>()
+            .map(Value::Int)
+            .map_err(|e| format!("Invalid int literal `{}`: {}", word, e)),
+        _ => Err(format!("Unexpected token `{}`", word)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::hex::encode_hex;
+
+    fn assert_roundtrip(hex: &str) {
+        let bytes = decode_hex(hex).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let repr = ClarityValue::deserialize(&mut cursor, false)
+            .unwrap()
+            .value
+            .repr_string();
+        let parsed = Value::parse_repr(&repr).unwrap();
+        assert_eq!(
+            encode_hex(&parsed.serialize_to_vec().unwrap()).as_ref(),
+            hex
+        );
+    }
+
+    #[test]
+    fn test_parse_repr_roundtrip() {
+        assert_roundtrip("0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479");
+        assert_roundtrip("0x00fffffffffffffffffffffffffffffffe");
+        assert_roundtrip("0x080616a13dce8114be0f707f94470a2e5e86eb402f29230474657374");
+        assert_roundtrip("0x0d000000040a22095c");
+        assert_roundtrip("0x0b000000020a0909");
+    }
+
+    fn parse_err(input: &str) -> ReprParseError {
+        Value::parse_repr(input).expect_err("expected parse error")
+    }
+
+    fn utf8_chars(input: &str) -> Vec<Vec<u8>> {
+        match Value::parse_repr(input).unwrap() {
+            Value::StringUTF8(chars) => chars,
+            _ => panic!("expected a string-utf8 value"),
+        }
+    }
+
+    #[test]
+    fn test_parse_repr_unicode_escape() {
+        assert_eq!(
+            utf8_chars(r#"u"og \u{e2ad90}""#),
+            vec![
+                b"o".to_vec(),
+                b"g".to_vec(),
+                b" ".to_vec(),
+                "\u{2b50}".as_bytes().to_vec()
+            ]
+        );
+        assert_eq!(
+            utf8_chars(r#"u"\u{c3a9}""#),
+            vec!["\u{e9}".as_bytes().to_vec()]
+        );
+
+        // Code points are not accepted, the escape must hold the UTF-8 bytes of one char
+        let err = parse_err(r#"u"\u{2b50}""#);
+        assert_eq!(
+            err.message,
+            "Invalid unicode escape `\\u{2b50}`, expected the UTF-8 bytes of one char"
+        );
+        assert!(Value::parse_repr(r#"u"\u{e9}""#).is_err());
+        assert!(Value::parse_repr(r#"u"\u{6162}""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_repr_depth() {
+        // An empty list nested in 15 optionals is 16 levels deep, the most the serializer accepts
+        let repr = format!("{}(list){}", "(some ".repeat(15), ")".repeat(15));
+        let parsed = Value::parse_repr(&repr).unwrap();
+        assert!(parsed.serialize_to_vec().is_ok());
+
+        let repr = format!("{}(list){}", "(some ".repeat(16), ")".repeat(16));
+        assert_eq!(parse_err(&repr).message, "TypeSignatureTooDeep: 17");
+    }
+
+    #[test]
+    fn test_parse_repr_errors() {
+        let err = parse_err("(tuple\n  (a u1)\n  (b u-1))");
+        assert_eq!((err.line, err.column), (3, 6));
+
+        let err = parse_err("(list u1 u2");
+        assert_eq!(err.message, "Unclosed `(`");
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = parse_err("(tuple (a u1) (a u2))");
+        assert_eq!(err.message, "Duplicate tuple field `a`");
+        assert_eq!((err.line, err.column), (1, 16));
+
+        let err = parse_err("(foo u1)");
+        assert_eq!(
+            err.to_string(),
+            "Unknown expression `foo` at line 1, column 2"
+        );
+
+        assert!(Value::parse_repr("'SP000").is_err());
+        assert!(Value::parse_repr("u1 u2").is_err());
+        assert!(Value::parse_repr("\"abc").is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use std::io::Write;
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use super::types::*;
+use crate::serialize_util::SerializeError;
+
+impl ContractName {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        let len = self.0.len();
+        if !(CONTRACT_MIN_NAME_LENGTH..=CONTRACT_MAX_NAME_LENGTH).contains(&len) {
+            return Err(format!(
+                "Failed to serialize contract name: too short or too long: {}",
+                len
+            ))?;
+        }
+        fd.write_u8(len as u8)?;
+        fd.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ClarityName {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        let len = self.0.len();
+        if len > MAX_STRING_LEN as usize {
+            return Err(format!(
+                "Failed to serialize clarity name: too long: {}",
+                len
+            ))?;
+        }
+        fd.write_u8(len as u8)?;
+        fd.write_all(self.0.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl StandardPrincipalData {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u8(self.0)?;
+        fd.write_all(&self.1)?;
+        Ok(())
+    }
+}
+
+impl ClarityValue {
+    /// Serialize the wrapped value. The cached `serialized_bytes` are not used, so the output
+    /// always reflects the current contents of `value`.
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        self.value.serialize(fd)
+    }
+}
+
+impl Value {
+    /// Serialize to the consensus wire format, enforcing the same size and nesting depth limits
+    /// that `ClarityValue::deserialize` applies.
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        self.inner_serialize_write(fd, 0)
+    }
+
+    pub fn serialize_to_vec(&self) -> Result<Vec<u8>, SerializeError> {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn inner_serialize_write<W: Write>(&self, w: &mut W, depth: u8) -> Result<(), SerializeError> {
+        use super::types::Value::*;
+
+        if depth >= 16 {
+            return Err(format!("TypeSignatureTooDeep: {}", depth).into());
+        }
+
+        w.write_u8(self.type_prefix().to_u8())?;
+
+        match self {
+            Int(value) => w.write_all(&value.to_be_bytes())?,
+            UInt(value) => w.write_all(&value.to_be_bytes())?,
+            Bool(_) | OptionalNone => {}
+            Buffer(data) => {
+                write_length_prefix(w, data.len(), "buffer")?;
+                w.write_all(data)?;
+            }
+            PrincipalStandard(principal) => principal.serialize(w)?,
+            PrincipalContract(contract_identifier) => {
+                contract_identifier.issuer.serialize(w)?;
+                contract_identifier.name.serialize(w)?;
+            }
+            ResponseOk(value) | ResponseErr(value) | OptionalSome(value) => {
+                value.value.inner_serialize_write(w, depth + 1)?
+            }
+            List(items) => {
+                write_length_prefix(w, items.len(), "list")?;
+                for item in items {
+                    item.value.inner_serialize_write(w, depth + 1)?;
+                }
+            }
+            Tuple(data) => {
+                write_length_prefix(w, data.len(), "tuple")?;
+                for (key, value) in data {
+                    key.serialize(w)?;
+                    value.value.inner_serialize_write(w, depth + 1)?;
+                }
+            }
+            StringASCII(data) => {
+                write_length_prefix(w, data.len(), "string-ascii")?;
+                w.write_all(data)?;
+            }
+            StringUTF8(data) => {
+                let total_len = data.iter().map(|c| c.len()).sum();
+                write_length_prefix(w, total_len, "string-utf8")?;
+                for c in data {
+                    w.write_all(c)?;
+                }
+            }
+        };
+        Ok(())
+    }
+}
+
+fn write_length_prefix<W: Write>(
+    w: &mut W,
+    len: usize,
+    type_name: &str,
+) -> Result<(), SerializeError> {
+    if len > MAX_VALUE_SIZE as usize {
+        return Err(format!("Illegal {} type size", type_name).into());
+    }
+    w.write_u32::<BigEndian>(len as u32)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::hex::{decode_hex, encode_hex};
+
+    fn roundtrip(input: &str) -> String {
+        let bytes = decode_hex(input).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let val = ClarityValue::deserialize(&mut cursor, false).unwrap();
+        encode_hex(&val.value.serialize_to_vec().unwrap()).to_string()
+    }
+
+    #[test]
+    fn test_serialize_roundtrip_tuple() {
+        let input = "0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479";
+        assert_eq!(roundtrip(input), input);
+    }
+
+    #[test]
+    fn test_serialize_roundtrip_primitives() {
+        let inputs = [
+            "0x00fffffffffffffffffffffffffffffffe",
+            "0x0100000000000000000000000000000001",
+            "0x03",
+            "0x04",
+            "0x09",
+            "0x0a03",
+            "0x080100000000000000000000000000000001",
+            "0x0200000000",
+            "0x0b00000000",
+            "0x0c00000000",
+            "0x0d00000000",
+            "0x0e00000000",
+            "0x0616a13dce8114be0f707f94470a2e5e86eb402f29230474657374",
+        ];
+        for input in inputs {
+            assert_eq!(roundtrip(input), input);
+        }
+    }
+
+    #[test]
+    fn test_serialize_too_deep() {
+        let mut value = Value::Bool(true);
+        for _ in 0..16 {
+            value = Value::OptionalSome(Box::new(ClarityValue::new(value)));
+        }
+        let err = value.serialize_to_vec().unwrap_err();
+        assert_eq!(err.as_string(), "TypeSignatureTooDeep: 16");
+    }
+
+    #[test]
+    fn test_serialize_bad_clarity_name() {
+        let name = ClarityName("a".repeat(129));
+        assert!(name.serialize(&mut vec![]).is_err());
+    }
+}
@@ -0,0 +1,543 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::types::*;
+use super::value_ref::{ClarityValueRef, ValueRef};
+
+/// Maximum nesting of type signatures, matching the deserializer's depth limit.
+pub const MAX_TYPE_DEPTH: u8 = 16;
+
+/// A declared Clarity type, e.g. a function argument or return type from a contract ABI.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeSignature {
+    /// The type of a branch that can never hold a value, e.g. the `err` type of
+    /// `(response bool none)` in an ABI. Written as `UnknownType`.
+    NoType,
+    Int,
+    UInt,
+    Bool,
+    Principal,
+    Buffer(u32),
+    StringASCII(u32),
+    /// Maximum length in characters
+    StringUTF8(u32),
+    Optional(Box<TypeSignature>),
+    Response(Box<TypeSignature>, Box<TypeSignature>),
+    List(Box<TypeSignature>, u32),
+    Tuple(BTreeMap<ClarityName, TypeSignature>),
+}
+
+impl fmt::Display for TypeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TypeSignature::*;
+        match self {
+            NoType => write!(f, "UnknownType"),
+            Int => write!(f, "int"),
+            UInt => write!(f, "uint"),
+            Bool => write!(f, "bool"),
+            Principal => write!(f, "principal"),
+            Buffer(len) => write!(f, "(buff {})", len),
+            StringASCII(len) => write!(f, "(string-ascii {})", len),
+            StringUTF8(len) => write!(f, "(string-utf8 {})", len),
+            Optional(inner) => write!(f, "(optional {})", inner),
+            Response(ok, err) => write!(f, "(response {} {})", ok, err),
+            List(item, len) => write!(f, "(list {} {})", len, item),
+            Tuple(fields) => {
+                write!(f, "(tuple")?;
+                for (name, field_type) in fields.iter() {
+                    write!(f, " ({} {})", name, field_type)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl TypeSignature {
+    /// Parse a type signature in Clarity syntax, e.g. `(response (list 10 principal) uint)`.
+    /// The ABI atom names `int128`, `uint128`, `trait_reference`, and `none` are also accepted.
+    pub fn parse(input: &str) -> Result<TypeSignature, String> {
+        let tokens = tokenize(input);
+        let mut pos = 0;
+        let type_sig = parse_type(&tokens, &mut pos, 0)?;
+        if let Some(token) = tokens.get(pos) {
+            return Err(format!(
+                "Unexpected `{}` after type signature at column {}",
+                token.text, token.column
+            ));
+        }
+        Ok(type_sig)
+    }
+
+    /// Check that a value is admitted by this type: matching variants, lengths within the
+    /// declared maximums, and tuples with exactly the declared field names.
+    pub fn admits(&self, value: &ClarityValueRef) -> Result<(), String> {
+        self.admits_at(value, "value")
+    }
+
+    fn admits_at(&self, value: &ClarityValueRef, path: &str) -> Result<(), String> {
+        use TypeSignature::*;
+        let mismatch = || {
+            Err(format!(
+                "Type mismatch at `{}`: expected {}, found {}",
+                path,
+                self,
+                value.repr_string()
+            ))
+        };
+        match (self, value.value()) {
+            (Int, ValueRef::Int(_)) | (UInt, ValueRef::UInt(_)) | (Bool, ValueRef::Bool(_)) => {
+                Ok(())
+            }
+            (Principal, ValueRef::PrincipalStandard(..)) => Ok(()),
+            (Principal, ValueRef::PrincipalContract(..)) => Ok(()),
+            (Buffer(max_len), ValueRef::Buffer(data)) => {
+                check_len(path, "buff", data.len(), *max_len)
+            }
+            (StringASCII(max_len), ValueRef::StringASCII(data)) => {
+                check_len(path, "string-ascii", data.len(), *max_len)
+            }
+            (StringUTF8(max_len), ValueRef::StringUTF8(data)) => {
+                // Counted in characters, the way `Value::string_utf8` splits the string
+                let len = String::from_utf8_lossy(data).chars().count();
+                check_len(path, "string-utf8", len, *max_len)
+            }
+            (Optional(_), ValueRef::OptionalNone) => Ok(()),
+            (Optional(inner), ValueRef::OptionalSome(data)) => {
+                inner.admits_at(&data, &format!("{}.some", path))
+            }
+            (Response(ok, _), ValueRef::ResponseOk(data)) => {
+                ok.admits_at(&data, &format!("{}.ok", path))
+            }
+            (Response(_, err), ValueRef::ResponseErr(data)) => {
+                err.admits_at(&data, &format!("{}.err", path))
+            }
+            (List(item_type, max_len), ValueRef::List(items)) => {
+                check_len(path, "list", items.len(), *max_len)?;
+                for (i, item) in items.iter().enumerate() {
+                    item_type.admits_at(&item, &format!("{}[{}]", path, i))?;
+                }
+                Ok(())
+            }
+            (Tuple(field_types), ValueRef::Tuple(data)) => {
+                for (name, _) in data.sorted_fields() {
+                    if !field_types.contains_key(name) {
+                        return Err(format!(
+                            "Type mismatch at `{}`: unexpected tuple field `{}`",
+                            path, name
+                        ));
+                    }
+                }
+                for (name, field_type) in field_types.iter() {
+                    match data.get(name) {
+                        Some(field) => {
+                            field_type.admits_at(&field, &format!("{}.{}", path, name))?
+                        }
+                        None => {
+                            return Err(format!(
+                                "Type mismatch at `{}`: missing tuple field `{}`",
+                                path, name
+                            ))
+                        }
+                    }
+                }
+                Ok(())
+            }
+            _ => mismatch(),
+        }
+    }
+}
+
+impl TypeSignature {
+    /// Infer the type of a value. `none` and the missing branch of a response are `NoType`, and
+    /// the item type of a list is the least supertype of its items. Fails if the list items have
+    /// no common type, which is not a valid Clarity value.
+    pub fn type_of(value: &Value) -> Result<TypeSignature, String> {
+        let type_sig = match value {
+            Value::Int(_) => TypeSignature::Int,
+            Value::UInt(_) => TypeSignature::UInt,
+            Value::Bool(_) => TypeSignature::Bool,
+            Value::PrincipalStandard(_) | Value::PrincipalContract(_) => TypeSignature::Principal,
+            Value::Buffer(data) => TypeSignature::Buffer(data.len() as u32),
+            Value::StringASCII(data) => TypeSignature::StringASCII(data.len() as u32),
+            // A UTF-8 char may be up to 4 bytes
+            Value::StringUTF8(data) => TypeSignature::StringUTF8(data.len() as u32 * 4),
+            Value::OptionalNone => TypeSignature::Optional(Box::new(TypeSignature::NoType)),
+            Value::OptionalSome(data) => {
+                TypeSignature::Optional(Box::new(TypeSignature::type_of(&data.value)?))
+            }
+            Value::ResponseOk(data) => TypeSignature::Response(
+                Box::new(TypeSignature::type_of(&data.value)?),
+                Box::new(TypeSignature::NoType),
+            ),
+            Value::ResponseErr(data) => TypeSignature::Response(
+                Box::new(TypeSignature::NoType),
+                Box::new(TypeSignature::type_of(&data.value)?),
+            ),
+            Value::List(items) => {
+                let mut item_type = TypeSignature::NoType;
+                for (i, item) in items.iter().enumerate() {
+                    let next_type = TypeSignature::type_of(&item.value)?;
+                    item_type = item_type.least_supertype(&next_type).map_err(|e| {
+                        format!(
+                            "Invalid list: item {} does not match the list type: {}",
+                            i, e
+                        )
+                    })?;
+                }
+                TypeSignature::List(Box::new(item_type), items.len() as u32)
+            }
+            Value::Tuple(data) => {
+                let mut field_types = BTreeMap::new();
+                for (name, field) in data.iter() {
+                    field_types.insert(name.clone(), TypeSignature::type_of(&field.value)?);
+                }
+                TypeSignature::Tuple(field_types)
+            }
+        };
+        Ok(type_sig)
+    }
+
+    /// The smallest type that admits every value of both types, following Clarity's type
+    /// admission rules: `NoType` is replaced by the other type, optional and response branches
+    /// are merged, and buffer, string, and list lengths take the maximum.
+    pub fn least_supertype(&self, other: &TypeSignature) -> Result<TypeSignature, String> {
+        use TypeSignature::*;
+        let type_sig = match (self, other) {
+            (NoType, other) | (other, NoType) => other.clone(),
+            (Int, Int) => Int,
+            (UInt, UInt) => UInt,
+            (Bool, Bool) => Bool,
+            (Principal, Principal) => Principal,
+            (Buffer(a), Buffer(b)) => Buffer(*a.max(b)),
+            (StringASCII(a), StringASCII(b)) => StringASCII(*a.max(b)),
+            (StringUTF8(a), StringUTF8(b)) => StringUTF8(*a.max(b)),
+            (Optional(a), Optional(b)) => Optional(Box::new(a.least_supertype(b)?)),
+            (Response(ok_a, err_a), Response(ok_b, err_b)) => Response(
+                Box::new(ok_a.least_supertype(ok_b)?),
+                Box::new(err_a.least_supertype(err_b)?),
+            ),
+            (List(item_a, len_a), List(item_b, len_b)) => {
+                List(Box::new(item_a.least_supertype(item_b)?), *len_a.max(len_b))
+            }
+            (Tuple(fields_a), Tuple(fields_b))
+                if fields_a.len() == fields_b.len()
+                    && fields_a.keys().all(|name| fields_b.contains_key(name)) =>
+            {
+                let mut field_types = BTreeMap::new();
+                for (name, field_type) in fields_a.iter() {
+                    field_types.insert(name.clone(), field_type.least_supertype(&fields_b[name])?);
+                }
+                Tuple(field_types)
+            }
+            _ => return Err(format!("types {} and {} are incompatible", self, other)),
+        };
+        Ok(type_sig)
+    }
+}
+
+fn check_len(path: &str, type_name: &str, len: usize, max_len: u32) -> Result<(), String> {
+    if len > max_len as usize {
+        return Err(format!(
+            "Type mismatch at `{}`: {} length {} exceeds maximum {}",
+            path, type_name, len, max_len
+        ));
+    }
+    Ok(())
+}
+
+struct Token {
+    text: String,
+    /// 1-based column (in characters) of the start of the token
+    column: usize,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut word = String::new();
+    let mut word_column = 0;
+    for (i, c) in input.chars().enumerate() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if !word.is_empty() {
+                tokens.push(Token {
+                    text: std::mem::take(&mut word),
+                    column: word_column,
+                });
+            }
+            if !c.is_whitespace() {
+                tokens.push(Token {
+                    text: c.to_string(),
+                    column: i + 1,
+                });
+            }
+        } else {
+            if word.is_empty() {
+                word_column = i + 1;
+            }
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(Token {
+            text: word,
+            column: word_column,
+        });
+    }
+    tokens
+}
+
+fn next_token<'a>(tokens: &'a [Token], pos: &mut usize) -> Result<&'a Token, String> {
+    let token = tokens
+        .get(*pos)
+        .ok_or_else(|| "Unexpected end of type signature".to_string())?;
+    *pos += 1;
+    Ok(token)
+}
+
+fn expect_token(tokens: &[Token], pos: &mut usize, expected: &str) -> Result<(), String> {
+    let token = next_token(tokens, pos)?;
+    if token.text != expected {
+        return Err(format!(
+            "Expected `{}`, found `{}` at column {}",
+            expected, token.text, token.column
+        ));
+    }
+    Ok(())
+}
+
+fn parse_length(tokens: &[Token], pos: &mut usize) -> Result<u32, String> {
+    let token = next_token(tokens, pos)?;
+    token
+        .text
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid length `{}` at column {}", token.text, token.column))
+}
+
+fn parse_type(tokens: &[Token], pos: &mut usize, depth: u8) -> Result<TypeSignature, String> {
+    if depth >= MAX_TYPE_DEPTH {
+        return Err(format!("TypeSignatureTooDeep: {}", depth));
+    }
+    let token = next_token(tokens, pos)?;
+    let type_sig = match token.text.as_str() {
+        "int" | "int128" => TypeSignature::Int,
+        "uint" | "uint128" => TypeSignature::UInt,
+        "bool" => TypeSignature::Bool,
+        "principal" | "trait_reference" => TypeSignature::Principal,
+        "none" | "UnknownType" => TypeSignature::NoType,
+        "(" => {
+            let keyword = next_token(tokens, pos)?;
+            let type_sig = match keyword.text.as_str() {
+                "buff" => TypeSignature::Buffer(parse_length(tokens, pos)?),
+                "string-ascii" => TypeSignature::StringASCII(parse_length(tokens, pos)?),
+                "string-utf8" => TypeSignature::StringUTF8(parse_length(tokens, pos)?),
+                "optional" => {
+                    TypeSignature::Optional(Box::new(parse_type(tokens, pos, depth + 1)?))
+                }
+                "response" => {
+                    let ok = parse_type(tokens, pos, depth + 1)?;
+                    let err = parse_type(tokens, pos, depth + 1)?;
+                    TypeSignature::Response(Box::new(ok), Box::new(err))
+                }
+                "list" => {
+                    let max_len = parse_length(tokens, pos)?;
+                    let item_type = parse_type(tokens, pos, depth + 1)?;
+                    TypeSignature::List(Box::new(item_type), max_len)
+                }
+                "tuple" => {
+                    let mut fields = BTreeMap::new();
+                    while tokens.get(*pos).map(|t| t.text.as_str()) == Some("(") {
+                        *pos += 1;
+                        let name_token = next_token(tokens, pos)?;
+                        let name = ClarityName::try_from(name_token.text.clone())?;
+                        if fields.contains_key(&name) {
+                            return Err(format!(
+                                "Duplicate tuple field `{}` at column {}",
+                                name, name_token.column
+                            ));
+                        }
+                        let field_type = parse_type(tokens, pos, depth + 1)?;
+                        expect_token(tokens, pos, ")")?;
+                        fields.insert(name, field_type);
+                    }
+                    TypeSignature::Tuple(fields)
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown type `{}` at column {}",
+                        keyword.text, keyword.column
+                    ))
+                }
+            };
+            expect_token(tokens, pos, ")")?;
+            type_sig
+        }
+        _ => {
+            return Err(format!(
+                "Unknown type `{}` at column {}",
+                token.text, token.column
+            ))
+        }
+    };
+    Ok(type_sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::hex::decode_hex;
+
+    fn deserialize(hex: &str) -> SerializedClarityValue {
+        let bytes = decode_hex(hex).unwrap();
+        SerializedClarityValue::deserialize(&mut Cursor::new(bytes.as_ref())).unwrap()
+    }
+
+    #[test]
+    fn test_parse_type_signature() {
+        for input in [
+            "int",
+            "(buff 32)",
+            "(optional (string-utf8 40))",
+            "(response (list 10 principal) UnknownType)",
+            "(tuple (amount uint) (memo (optional (buff 34))) (to principal))",
+        ] {
+            assert_eq!(TypeSignature::parse(input).unwrap().to_string(), input);
+        }
+        assert_eq!(
+            TypeSignature::parse(" (response  uint128 none)\n").unwrap(),
+            TypeSignature::Response(
+                Box::new(TypeSignature::UInt),
+                Box::new(TypeSignature::NoType)
+            )
+        );
+
+        assert_eq!(
+            TypeSignature::parse("(list 10 uint").unwrap_err(),
+            "Unexpected end of type signature"
+        );
+        assert_eq!(
+            TypeSignature::parse("(buff -1)").unwrap_err(),
+            "Invalid length `-1` at column 7"
+        );
+        assert_eq!(
+            TypeSignature::parse("(tuple (a int) (a uint))").unwrap_err(),
+            "Duplicate tuple field `a` at column 17"
+        );
+        assert_eq!(
+            TypeSignature::parse("uint int").unwrap_err(),
+            "Unexpected `int` after type signature at column 6"
+        );
+        assert!(TypeSignature::parse("(optional foo)").is_err());
+    }
+
+    #[test]
+    fn test_type_of() {
+        let type_of = |repr: &str| {
+            TypeSignature::type_of(&Value::parse_repr(repr).unwrap()).map(|t| t.to_string())
+        };
+
+        assert_eq!(
+            type_of("(list none (some u1))").unwrap(),
+            "(list 2 (optional uint))"
+        );
+        assert_eq!(
+            type_of("(list (ok 0x01) (err u1) (ok 0x0203))").unwrap(),
+            "(list 3 (response (buff 2) uint))"
+        );
+        assert_eq!(
+            type_of(r#"(list (list) (list "a") (list "abc" "d"))"#).unwrap(),
+            "(list 3 (list 2 (string-ascii 3)))"
+        );
+        assert_eq!(
+            type_of("(tuple (a (list (some (err 1)) none)))").unwrap(),
+            "(tuple (a (list 2 (optional (response UnknownType int)))))"
+        );
+        assert_eq!(type_of("(list)").unwrap(), "(list 0 UnknownType)");
+
+        assert_eq!(
+            type_of("(list 1 u1)").unwrap_err(),
+            "Invalid list: item 1 does not match the list type: types int and uint are incompatible"
+        );
+        assert!(type_of("(list (tuple (a 1)) (tuple (b 1)))").is_err());
+        assert!(type_of("(list (tuple (a 1)) (tuple (a 1) (b 1)))").is_err());
+        assert!(type_of("(some (list (ok u1) (ok 1)))").is_err());
+    }
+
+    #[test]
+    fn test_admits() {
+        let type_sig = TypeSignature::parse("(list 2 (optional uint))").unwrap();
+        // (list none (some u1))
+        let value = deserialize("0x0b00000002090a0100000000000000000000000000000001");
+        assert!(type_sig.admits(&value.as_value_ref()).is_ok());
+
+        let type_sig = TypeSignature::parse("(list 1 (optional uint))").unwrap();
+        assert_eq!(
+            type_sig.admits(&value.as_value_ref()).unwrap_err(),
+            "Type mismatch at `value`: list length 2 exceeds maximum 1"
+        );
+
+        let type_sig = TypeSignature::parse("(list 2 (optional int))").unwrap();
+        assert_eq!(
+            type_sig.admits(&value.as_value_ref()).unwrap_err(),
+            "Type mismatch at `value[1].some`: expected int, found u1"
+        );
+
+        // (tuple (a u1))
+        let value = deserialize("0x0c0000000101610100000000000000000000000000000001");
+        assert!(TypeSignature::parse("(tuple (a uint))")
+            .unwrap()
+            .admits(&value.as_value_ref())
+            .is_ok());
+        assert_eq!(
+            TypeSignature::parse("(tuple (a uint) (b int))")
+                .unwrap()
+                .admits(&value.as_value_ref())
+                .unwrap_err(),
+            "Type mismatch at `value`: missing tuple field `b`"
+        );
+        assert_eq!(
+            TypeSignature::parse("(tuple (b uint))")
+                .unwrap()
+                .admits(&value.as_value_ref())
+                .unwrap_err(),
+            "Type mismatch at `value`: unexpected tuple field `a`"
+        );
+
+        // (tuple (a u1) (a 1)) is only accepted by the lenient deserializer, the last field wins
+        let value = deserialize(
+            "0x0c000000020161010000000000000000000000000000000101610000000000000000000000000000000001",
+        );
+        assert!(TypeSignature::parse("(tuple (a int))")
+            .unwrap()
+            .admits(&value.as_value_ref())
+            .is_ok());
+        assert_eq!(
+            TypeSignature::parse("(tuple (a uint))")
+                .unwrap()
+                .admits(&value.as_value_ref())
+                .unwrap_err(),
+            "Type mismatch at `value.a`: expected uint, found 1"
+        );
+
+        // (err u1) against a response whose err branch can never hold a value
+        let value = deserialize("0x080100000000000000000000000000000001");
+        assert_eq!(
+            TypeSignature::parse("(response bool none)")
+                .unwrap()
+                .admits(&value.as_value_ref())
+                .unwrap_err(),
+            "Type mismatch at `value.err`: expected UnknownType, found u1"
+        );
+
+        // u"og \u{2b50}" is 4 characters
+        let value = deserialize("0x0e000000066f6720e2ad90");
+        assert!(TypeSignature::StringUTF8(4)
+            .admits(&value.as_value_ref())
+            .is_ok());
+        assert!(TypeSignature::StringUTF8(3)
+            .admits(&value.as_value_ref())
+            .is_err());
+    }
+}
@@ -11,6 +11,7 @@ use crate::address::c32::c32_address;
 use crate::hex::{encode_hex, encode_hex_no_prefix};
 
 use super::deserialize::TypePrefix;
+use super::type_signature::TypeSignature;
 
 pub const MAX_STRING_LEN: u8 = 128;
 pub const MAX_VALUE_SIZE: u32 = 1024 * 1024; // 1MB
@@ -19,7 +20,10 @@ pub const MAX_VALUE_SIZE: u32 = 1024 * 1024; // 1MB
 pub const CONTRACT_MIN_NAME_LENGTH: usize = 1;
 pub const CONTRACT_MAX_NAME_LENGTH: usize = 40;
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
 pub struct ClarityValue {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub serialized_bytes: Option<Vec<u8>>,
     pub value: Value,
 }
@@ -40,14 +44,35 @@ impl ClarityValue {
     }
 }
 
+/// The consensus serialization of a Clarity value that has been validated but not decoded, e.g.
+/// a contract call argument. Read it with `as_value_ref`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct SerializedClarityValue {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// A decoded Clarity value. With the `serde` feature, integers serialize as decimal strings,
+/// buffers as 0x-prefixed hex strings, Clarity strings as strings, and standard principals as
+/// c32 addresses.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Value {
-    Int(i128),
-    UInt(u128),
+    Int(#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_integer_string"))] i128),
+    UInt(#[cfg_attr(feature = "serde", serde(serialize_with = "serialize_integer_string"))] u128),
     Bool(bool),
-    Buffer(Vec<u8>),
+    Buffer(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] Vec<u8>,
+    ),
     List(Vec<ClarityValue>),
-    StringUTF8(Vec<Vec<u8>>),
-    StringASCII(Vec<u8>),
+    StringUTF8(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_utf8_string"))]
+        Vec<Vec<u8>>,
+    ),
+    StringASCII(
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_ascii_string"))] Vec<u8>,
+    ),
     PrincipalStandard(StandardPrincipalData),
     PrincipalContract(QualifiedContractIdentifier),
     Tuple(BTreeMap<ClarityName, ClarityValue>),
@@ -173,70 +198,50 @@ impl Value {
         }
     }
 
-    pub fn type_signature(&self) -> String {
-        let mut w: Vec<u8> = Vec::new();
-        Value::type_signature_to_buffer(self, &mut w).unwrap();
-        let string_result = unsafe { String::from_utf8_unchecked(w) };
-        string_result
+    /// The inferred Clarity type of the value, e.g. `(list 2 (optional uint))`.
+    /// Fails if the value contains a list whose items have no common type.
+    pub fn type_signature(&self) -> Result<String, String> {
+        TypeSignature::type_of(self).map(|type_sig| type_sig.to_string())
     }
+}
 
-    fn type_signature_to_buffer(val: &Value, w: &mut Vec<u8>) -> std::io::Result<()> {
-        use Value::*;
-        match val {
-            Int(_) => write!(w, "int"),
-            UInt(_) => write!(w, "uint"),
-            Bool(_) => write!(w, "bool"),
-            OptionalSome(data) => {
-                write!(w, "(optional ")?;
-                Value::type_signature_to_buffer(&data.value, w)?;
-                write!(w, ")")
-            }
-            OptionalNone => write!(w, "(optional UnknownType)"),
-            ResponseOk(data) => {
-                write!(w, "(response ")?;
-                Value::type_signature_to_buffer(&data.value, w)?;
-                write!(w, " UnknownType)")
-            }
-            ResponseErr(data) => {
-                write!(w, "(response UnknownType ")?;
-                Value::type_signature_to_buffer(&data.value, w)?;
-                write!(w, ")")
-            }
-            Tuple(data) => {
-                write!(w, "(tuple")?;
-                for (name, value) in data.iter() {
-                    write!(w, " ({} ", name)?;
-                    Value::type_signature_to_buffer(&value.value, w)?;
-                    write!(w, ")")?;
-                }
-                write!(w, ")")
-            }
-            PrincipalStandard(_) | PrincipalContract(_) => write!(w, "principal"),
-            Buffer(value) => write!(w, "(buff {})", value.len()),
-            List(value) => {
-                write!(w, "(list {} ", value.len())?;
-                if value.len() > 0 {
-                    // TODO: this should use the least common supertype
-                    Value::type_signature_to_buffer(&value[0].value, w)?;
-                } else {
-                    write!(w, "UnknownType")?;
-                }
-                write!(w, ")")
-            }
-            StringASCII(data) => {
-                write!(w, "(string-ascii {})", data.len())
-            }
-            StringUTF8(data) => {
-                write!(w, "(string-utf8 {})", data.len() * 4)
-            }
-        }
-    }
+#[cfg(feature = "serde")]
+fn serialize_integer_string<T: fmt::Display, S: serde::Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[cfg(feature = "serde")]
+fn serialize_utf8_string<S: serde::Serializer>(
+    chars: &[Vec<u8>],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(&chars.concat()))
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[cfg(feature = "serde")]
+fn serialize_ascii_string<S: serde::Serializer>(
+    data: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(data))
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct StandardPrincipalData(pub u8, pub [u8; 20]);
 
-#[derive(Clone, Eq, PartialEq)]
+#[cfg(feature = "serde")]
+impl serde::Serialize for StandardPrincipalData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let address = c32_address(self.0, &self.1).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&address)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QualifiedContractIdentifier {
     pub issuer: StandardPrincipalData,
     pub name: ClarityName,
@@ -246,6 +251,7 @@ pub struct QualifiedContractIdentifier {
 macro_rules! guarded_string {
     ($Name:ident, $Label:literal, $Regex:expr) => {
         #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
         pub struct $Name(pub String);
         impl TryFrom<String> for $Name {
             type Error = String;
@@ -0,0 +1,895 @@
+use std::convert::TryInto;
+use std::fmt::Write;
+use std::io::Cursor;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::address::c32::c32_address;
+use crate::hex::{encode_hex, encode_hex_no_prefix};
+use crate::serialize_util::{DeserializeError, DeserializeErrorKind, PathSegment, SerializeError};
+
+use super::deserialize::{
+    check_principal_version, illegal_length, read_clarity_name, read_slice, TypePrefix,
+};
+use super::types::{ClarityValue, SerializedClarityValue, Value, MAX_VALUE_SIZE};
+
+/// A Clarity value borrowed from its consensus serialization. The input is validated once by
+/// `deserialize`, which accepts exactly what `ClarityValue::deserialize` accepts, but nothing is
+/// copied: nested values, strings and buffers are read from the input as they are accessed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClarityValueRef<'a> {
+    bytes: &'a [u8],
+}
+
+/// The top level of a `ClarityValueRef`, decoded on demand.
+pub enum ValueRef<'a> {
+    Int(i128),
+    UInt(u128),
+    Bool(bool),
+    Buffer(&'a [u8]),
+    List(ListRef<'a>),
+    /// The raw string bytes, which the lenient deserializer does not check for valid UTF-8.
+    StringUTF8(&'a [u8]),
+    StringASCII(&'a [u8]),
+    PrincipalStandard(u8, &'a [u8; 20]),
+    PrincipalContract(u8, &'a [u8; 20], &'a str),
+    Tuple(TupleRef<'a>),
+    OptionalSome(ClarityValueRef<'a>),
+    OptionalNone,
+    ResponseOk(ClarityValueRef<'a>),
+    ResponseErr(ClarityValueRef<'a>),
+}
+
+#[derive(Clone, Copy)]
+pub struct ListRef<'a> {
+    len: u32,
+    items: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+pub struct TupleRef<'a> {
+    len: u32,
+    fields: &'a [u8],
+}
+
+impl<'a> ClarityValueRef<'a> {
+    pub fn deserialize(r: &mut Cursor<&'a [u8]>) -> Result<Self, DeserializeError> {
+        let start = r.position() as usize;
+        validate(r, 0).map_err(|e| e.at_offset(r.position()))?;
+        let input: &'a [u8] = r.get_ref();
+        Ok(ClarityValueRef {
+            bytes: &input[start..r.position() as usize],
+        })
+    }
+
+    /// Like `deserialize`, but only reads as much of the input as is needed to find the nested
+    /// value at `path`: values before it are validated and skipped without being decoded, and
+    /// anything after it is not read at all. Returns `None` if there is no value at the path,
+    /// e.g. a missing tuple field, an out-of-range list index, or `some` on a `none`.
+    pub fn deserialize_at_path(
+        r: &mut Cursor<&'a [u8]>,
+        path: &[PathSegment],
+    ) -> Result<Option<Self>, DeserializeError> {
+        select(r, 0, path).map_err(|e| e.at_offset(r.position()))
+    }
+
+    /// Read values serialized as a 4 byte count followed by the values, e.g. the
+    /// `function_args_buffer` of a contract call or the input of `decodeClarityValueList`. The
+    /// count is not trusted, values are read until the end of the input.
+    pub fn deserialize_list(input: &'a [u8]) -> Result<Vec<Self>, DeserializeError> {
+        let mut values = Vec::new();
+        if input.len() > 4 {
+            let val_slice = &input[4..];
+            let mut cursor = Cursor::new(val_slice);
+            while cursor.position() < val_slice.len() as u64 {
+                let value = ClarityValueRef::deserialize(&mut cursor)
+                    .map_err(|e| e.in_index(values.len()).shift_offset(4))?;
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// The nested value at `path`, see `deserialize_at_path`.
+    pub fn select(&self, path: &[PathSegment]) -> Option<ClarityValueRef<'a>> {
+        let (segment, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return Some(*self),
+        };
+        let value = match (self.value(), segment) {
+            (ValueRef::OptionalSome(value), PathSegment::Field(name)) if name == "some" => value,
+            (ValueRef::ResponseOk(value), PathSegment::Field(name)) if name == "ok" => value,
+            (ValueRef::ResponseErr(value), PathSegment::Field(name)) if name == "err" => value,
+            (ValueRef::List(list), PathSegment::Index(index)) => list.iter().nth(*index)?,
+            (ValueRef::Tuple(tuple), PathSegment::Field(name)) => tuple.get(name)?,
+            _ => return None,
+        };
+        value.select(rest)
+    }
+
+    /// The consensus serialization of this value.
+    pub fn serialized_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn type_prefix(&self) -> TypePrefix {
+        TypePrefix::from_u8(self.bytes[0]).unwrap()
+    }
+
+    pub fn value(&self) -> ValueRef<'a> {
+        let body = &self.bytes[1..];
+        match self.type_prefix() {
+            TypePrefix::Int => ValueRef::Int(i128::from_be_bytes(body[..16].try_into().unwrap())),
+            TypePrefix::UInt => ValueRef::UInt(u128::from_be_bytes(body[..16].try_into().unwrap())),
+            TypePrefix::BoolTrue => ValueRef::Bool(true),
+            TypePrefix::BoolFalse => ValueRef::Bool(false),
+            TypePrefix::Buffer => ValueRef::Buffer(&body[4..]),
+            TypePrefix::StringASCII => ValueRef::StringASCII(&body[4..]),
+            TypePrefix::StringUTF8 => ValueRef::StringUTF8(&body[4..]),
+            TypePrefix::PrincipalStandard => {
+                ValueRef::PrincipalStandard(body[0], body[1..21].try_into().unwrap())
+            }
+            TypePrefix::PrincipalContract => ValueRef::PrincipalContract(
+                body[0],
+                body[1..21].try_into().unwrap(),
+                std::str::from_utf8(&body[22..]).unwrap(),
+            ),
+            TypePrefix::List => ValueRef::List(ListRef {
+                len: be_u32(body),
+                items: &body[4..],
+            }),
+            TypePrefix::Tuple => ValueRef::Tuple(TupleRef {
+                len: be_u32(body),
+                fields: &body[4..],
+            }),
+            TypePrefix::OptionalSome => ValueRef::OptionalSome(ClarityValueRef { bytes: body }),
+            TypePrefix::OptionalNone => ValueRef::OptionalNone,
+            TypePrefix::ResponseOk => ValueRef::ResponseOk(ClarityValueRef { bytes: body }),
+            TypePrefix::ResponseErr => ValueRef::ResponseErr(ClarityValueRef { bytes: body }),
+        }
+    }
+
+    /// Same output as `Value::repr_string`.
+    pub fn repr_string(&self) -> String {
+        let mut w = String::new();
+        self.repr_string_to_buffer(&mut w).unwrap();
+        w
+    }
+
+    fn repr_string_to_buffer(&self, w: &mut String) -> std::fmt::Result {
+        use ValueRef::*;
+        match self.value() {
+            Int(data) => write!(w, "{}", data),
+            UInt(data) => write!(w, "u{}", data),
+            Bool(data) => write!(w, "{}", data),
+            OptionalSome(data) => {
+                write!(w, "(some ")?;
+                data.repr_string_to_buffer(w)?;
+                write!(w, ")")
+            }
+            OptionalNone => write!(w, "none"),
+            ResponseOk(data) => {
+                write!(w, "(ok ")?;
+                data.repr_string_to_buffer(w)?;
+                write!(w, ")")
+            }
+            ResponseErr(data) => {
+                write!(w, "(err ")?;
+                data.repr_string_to_buffer(w)?;
+                write!(w, ")")
+            }
+            Tuple(data) => {
+                write!(w, "(tuple")?;
+                for (name, value) in data.sorted_fields() {
+                    write!(w, " ({} ", name)?;
+                    value.repr_string_to_buffer(w)?;
+                    write!(w, ")")?;
+                }
+                write!(w, ")")
+            }
+            // Principal versions are checked by `validate`, so the addresses can always be encoded
+            PrincipalStandard(version, hash) => {
+                write!(w, "'{}", c32_address(version, hash).unwrap())
+            }
+            PrincipalContract(version, hash, name) => {
+                write!(w, "'{}.{}", c32_address(version, hash).unwrap(), name)
+            }
+            Buffer(value) => write!(w, "{}", encode_hex(value)),
+            List(value) => {
+                write!(w, "(list")?;
+                for val in value.iter() {
+                    write!(w, " ")?;
+                    val.repr_string_to_buffer(w)?;
+                }
+                write!(w, ")")
+            }
+            StringASCII(data) => {
+                write!(w, "\"")?;
+                for c in data.iter() {
+                    write!(w, "{}", std::ascii::escape_default(*c))?;
+                }
+                write!(w, "\"")
+            }
+            StringUTF8(data) => {
+                write!(w, "u\"")?;
+                for c in String::from_utf8_lossy(data).chars() {
+                    if c.len_utf8() > 1 {
+                        // We escape extended charset
+                        let mut encoded_char = [0; 4];
+                        let encoded_char = c.encode_utf8(&mut encoded_char);
+                        write!(
+                            w,
+                            "\\u{{{}}}",
+                            encode_hex_no_prefix(encoded_char.as_bytes())
+                        )?;
+                    } else {
+                        // We render an ASCII char, escaped
+                        write!(w, "{}", std::ascii::escape_default(c as u8))?;
+                    }
+                }
+                write!(w, "\"")
+            }
+        }
+    }
+
+    /// The type of the value as in the Stacks API `{type, value}` JSON convention (`cvToJSON` in
+    /// stacks.js). Unlike `Value::type_signature` this never fails: a list has the type of its first
+    /// item, `none` is `(optional none)` and the other branch of a response is `UnknownType`.
+    pub fn json_type_string(&self) -> String {
+        let mut w = String::new();
+        self.json_type_string_to_buffer(&mut w).unwrap();
+        w
+    }
+
+    fn json_type_string_to_buffer(&self, w: &mut String) -> std::fmt::Result {
+        use ValueRef::*;
+        match self.value() {
+            Int(_) => write!(w, "int"),
+            UInt(_) => write!(w, "uint"),
+            Bool(_) => write!(w, "bool"),
+            PrincipalStandard(..) | PrincipalContract(..) => write!(w, "principal"),
+            Buffer(data) => write!(w, "(buff {})", data.len()),
+            StringASCII(data) => write!(w, "(string-ascii {})", data.len()),
+            StringUTF8(data) => write!(w, "(string-utf8 {})", data.len()),
+            OptionalNone => write!(w, "(optional none)"),
+            OptionalSome(data) => {
+                write!(w, "(optional ")?;
+                data.json_type_string_to_buffer(w)?;
+                write!(w, ")")
+            }
+            ResponseOk(data) => {
+                write!(w, "(response ")?;
+                data.json_type_string_to_buffer(w)?;
+                write!(w, " UnknownType)")
+            }
+            ResponseErr(data) => {
+                write!(w, "(response UnknownType ")?;
+                data.json_type_string_to_buffer(w)?;
+                write!(w, ")")
+            }
+            List(data) => {
+                write!(w, "(list {} ", data.len())?;
+                match data.iter().next() {
+                    Some(first) => first.json_type_string_to_buffer(w)?,
+                    None => write!(w, "UnknownType")?,
+                }
+                write!(w, ")")
+            }
+            Tuple(data) => {
+                write!(w, "(tuple")?;
+                for (name, value) in data.sorted_fields() {
+                    write!(w, " ({} ", name)?;
+                    value.json_type_string_to_buffer(w)?;
+                    write!(w, ")")?;
+                }
+                write!(w, ")")
+            }
+        }
+    }
+}
+
+impl<'a> ValueRef<'a> {
+    pub fn type_prefix(&self) -> TypePrefix {
+        use ValueRef::*;
+        match self {
+            Int(_) => TypePrefix::Int,
+            UInt(_) => TypePrefix::UInt,
+            Bool(true) => TypePrefix::BoolTrue,
+            Bool(false) => TypePrefix::BoolFalse,
+            PrincipalStandard(..) => TypePrefix::PrincipalStandard,
+            PrincipalContract(..) => TypePrefix::PrincipalContract,
+            ResponseOk(_) => TypePrefix::ResponseOk,
+            ResponseErr(_) => TypePrefix::ResponseErr,
+            OptionalSome(_) => TypePrefix::OptionalSome,
+            OptionalNone => TypePrefix::OptionalNone,
+            Tuple(_) => TypePrefix::Tuple,
+            Buffer(_) => TypePrefix::Buffer,
+            List(_) => TypePrefix::List,
+            StringASCII(_) => TypePrefix::StringASCII,
+            StringUTF8(_) => TypePrefix::StringUTF8,
+        }
+    }
+}
+
+impl<'a> ListRef<'a> {
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> ListIter<'a> {
+        ListIter {
+            remaining: self.len,
+            bytes: self.items,
+        }
+    }
+}
+
+pub struct ListIter<'a> {
+    remaining: u32,
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ListIter<'a> {
+    type Item = ClarityValueRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (item, rest) = self.bytes.split_at(value_len(self.bytes));
+        self.bytes = rest;
+        Some(ClarityValueRef { bytes: item })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<'a> TupleRef<'a> {
+    /// The number of serialized fields, including any duplicates.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The fields in serialized order.
+    pub fn iter(&self) -> TupleIter<'a> {
+        TupleIter {
+            remaining: self.len,
+            bytes: self.fields,
+        }
+    }
+
+    /// Looks up a field. Like `ClarityValue`'s tuple map, the last of duplicate fields wins.
+    pub fn get(&self, key: &str) -> Option<ClarityValueRef<'a>> {
+        self.iter()
+            .filter(|(name, _)| *name == key)
+            .last()
+            .map(|(_, value)| value)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.iter().any(|(name, _)| name == key)
+    }
+
+    /// The fields in the order `ClarityValue`'s tuple map iterates them: sorted by name, with the
+    /// last of duplicate fields winning. Canonical tuples are already in this order.
+    pub fn sorted_fields(&self) -> Vec<(&'a str, ClarityValueRef<'a>)> {
+        let mut fields: Vec<_> = self.iter().collect();
+        if !fields.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+            // The sort is stable, so after reversing, the first field of each run of
+            // duplicates is the last one in the input.
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            fields.reverse();
+            fields.dedup_by_key(|(name, _)| *name);
+            fields.reverse();
+        }
+        fields
+    }
+}
+
+pub struct TupleIter<'a> {
+    remaining: u32,
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for TupleIter<'a> {
+    type Item = (&'a str, ClarityValueRef<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let name_len = self.bytes[0] as usize;
+        let name = std::str::from_utf8(&self.bytes[1..1 + name_len]).unwrap();
+        let rest = &self.bytes[1 + name_len..];
+        let (value, rest) = rest.split_at(value_len(rest));
+        self.bytes = rest;
+        Some((name, ClarityValueRef { bytes: value }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ClarityValue {
+    /// Borrow the bytes this value was deserialized from, if they were kept.
+    pub fn as_value_ref(&self) -> Option<ClarityValueRef<'_>> {
+        self.serialized_bytes
+            .as_ref()
+            .map(|bytes| ClarityValueRef { bytes })
+    }
+}
+
+impl SerializedClarityValue {
+    /// Validate and copy the value at the cursor.
+    pub fn deserialize(r: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        ClarityValueRef::deserialize(r).map(|val| SerializedClarityValue {
+            bytes: val.bytes.to_vec(),
+        })
+    }
+
+    /// Serialize a value, which is always valid.
+    pub fn from_value(value: &Value) -> Result<Self, SerializeError> {
+        value
+            .serialize_to_vec()
+            .map(|bytes| SerializedClarityValue { bytes })
+    }
+
+    pub fn as_value_ref(&self) -> ClarityValueRef<'_> {
+        ClarityValueRef { bytes: &self.bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Checks the value at the cursor and moves past it, with the same errors as
+/// `ClarityValue::deserialize`.
+fn validate(r: &mut Cursor<&[u8]>, depth: u8) -> Result<(), DeserializeError> {
+    let cursor_start = r.position() as usize;
+    let prefix = read_type_prefix(r, depth)?;
+
+    match prefix {
+        TypePrefix::Int | TypePrefix::UInt => {
+            read_slice(r, 16)?;
+        }
+        TypePrefix::BoolTrue | TypePrefix::BoolFalse | TypePrefix::OptionalNone => {}
+        TypePrefix::Buffer | TypePrefix::StringASCII | TypePrefix::StringUTF8 => {
+            let len = r.read_u32::<BigEndian>()?;
+            if len > MAX_VALUE_SIZE {
+                let type_name = match prefix {
+                    TypePrefix::Buffer => "buffer",
+                    TypePrefix::StringASCII => "string-ascii",
+                    _ => "string-utf8",
+                };
+                return Err(illegal_length(type_name, len, cursor_start));
+            }
+            read_slice(r, len as usize)?;
+        }
+        TypePrefix::PrincipalStandard => {
+            let principal = read_slice(r, 21)?;
+            check_principal_version(principal[0], cursor_start as u64 + 1)?;
+        }
+        TypePrefix::PrincipalContract => {
+            let issuer = read_slice(r, 21)?;
+            check_principal_version(issuer[0], cursor_start as u64 + 1)?;
+            read_clarity_name(r)?;
+        }
+        TypePrefix::ResponseOk => validate(r, depth + 1).map_err(|e| e.in_field("ok"))?,
+        TypePrefix::ResponseErr => validate(r, depth + 1).map_err(|e| e.in_field("err"))?,
+        TypePrefix::OptionalSome => validate(r, depth + 1).map_err(|e| e.in_field("some"))?,
+        TypePrefix::List => {
+            let len = read_length(r, "list", cursor_start)?;
+            for i in 0..len {
+                validate(r, depth + 1).map_err(|e| e.in_element("list", i as usize))?;
+            }
+        }
+        TypePrefix::Tuple => {
+            let len = read_length(r, "tuple", cursor_start)?;
+            for _i in 0..len {
+                let key = read_clarity_name(r)?;
+                validate(r, depth + 1).map_err(|e| e.in_field(key).in_field("tuple"))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads the type prefix of a value nested `depth` levels deep.
+fn read_type_prefix(r: &mut Cursor<&[u8]>, depth: u8) -> Result<TypePrefix, DeserializeError> {
+    let cursor_start = r.position();
+
+    if depth >= 16 {
+        return Err(
+            DeserializeError::new(DeserializeErrorKind::TooDeep(depth)).at_offset(cursor_start)
+        );
+    }
+
+    let header = r.read_u8()?;
+    TypePrefix::from_u8(header).ok_or_else(|| {
+        DeserializeError::new(DeserializeErrorKind::BadTypePrefix(header)).at_offset(cursor_start)
+    })
+}
+
+/// Reads the length prefix of a list or tuple.
+fn read_length(
+    r: &mut Cursor<&[u8]>,
+    type_name: &'static str,
+    cursor_start: usize,
+) -> Result<u32, DeserializeError> {
+    let len = r.read_u32::<BigEndian>()?;
+    if len > MAX_VALUE_SIZE {
+        return Err(illegal_length(type_name, len, cursor_start));
+    }
+    Ok(len)
+}
+
+/// Finds the value at `path` while validating only what it passes over, see
+/// `ClarityValueRef::deserialize_at_path`.
+fn select<'a>(
+    r: &mut Cursor<&'a [u8]>,
+    depth: u8,
+    path: &[PathSegment],
+) -> Result<Option<ClarityValueRef<'a>>, DeserializeError> {
+    let input: &'a [u8] = r.get_ref();
+    let cursor_start = r.position() as usize;
+    let (segment, rest) = match path.split_first() {
+        Some(split) => split,
+        None => {
+            validate(r, depth)?;
+            return Ok(Some(ClarityValueRef {
+                bytes: &input[cursor_start..r.position() as usize],
+            }));
+        }
+    };
+
+    match (read_type_prefix(r, depth)?, segment) {
+        (TypePrefix::OptionalSome, PathSegment::Field(name)) if name == "some" => {
+            select(r, depth + 1, rest).map_err(|e| e.in_field("some"))
+        }
+        (TypePrefix::ResponseOk, PathSegment::Field(name)) if name == "ok" => {
+            select(r, depth + 1, rest).map_err(|e| e.in_field("ok"))
+        }
+        (TypePrefix::ResponseErr, PathSegment::Field(name)) if name == "err" => {
+            select(r, depth + 1, rest).map_err(|e| e.in_field("err"))
+        }
+        (TypePrefix::List, PathSegment::Index(index)) => {
+            let len = read_length(r, "list", cursor_start)?;
+            if *index >= len as usize {
+                return Ok(None);
+            }
+            for i in 0..*index {
+                validate(r, depth + 1).map_err(|e| e.in_element("list", i))?;
+            }
+            select(r, depth + 1, rest).map_err(|e| e.in_element("list", *index))
+        }
+        (TypePrefix::Tuple, PathSegment::Field(name)) => {
+            let len = read_length(r, "tuple", cursor_start)?;
+            // All fields are read, since the last of duplicate fields wins.
+            let mut found = None;
+            for _i in 0..len {
+                let key = read_clarity_name(r)?;
+                let value_start = r.position() as usize;
+                validate(r, depth + 1).map_err(|e| e.in_field(key).in_field("tuple"))?;
+                if key == name {
+                    found = Some(ClarityValueRef {
+                        bytes: &input[value_start..r.position() as usize],
+                    });
+                }
+            }
+            Ok(found.and_then(|value| value.select(rest)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parses a path such as `data.amount`, `items[2]` or `items.2.some` into segments. Numeric
+/// segments are list indexes, everything else is a tuple field name, or `some`, `ok` or `err` to
+/// unwrap an optional or response.
+pub fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let mut segments = vec![];
+    if path.is_empty() {
+        return Ok(segments);
+    }
+    let invalid = || format!("Invalid path `{}`", path);
+    for part in path.split('.') {
+        let (name, mut indexes) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !name.is_empty() {
+            match name.parse::<usize>() {
+                Ok(index) => segments.push(PathSegment::Index(index)),
+                Err(_) => segments.push(PathSegment::Field(name.to_string())),
+            }
+        } else if indexes.is_empty() {
+            return Err(invalid());
+        }
+        while !indexes.is_empty() {
+            let end = indexes.find(']').ok_or_else(invalid)?;
+            let index = indexes[1..end].parse::<usize>().map_err(|_| invalid())?;
+            segments.push(PathSegment::Index(index));
+            indexes = &indexes[end + 1..];
+            if !indexes.is_empty() && !indexes.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+    Ok(segments)
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().unwrap())
+}
+
+/// The length of the validated value at the start of `bytes`.
+fn value_len(bytes: &[u8]) -> usize {
+    match TypePrefix::from_u8(bytes[0]).unwrap() {
+        TypePrefix::Int | TypePrefix::UInt => 17,
+        TypePrefix::BoolTrue | TypePrefix::BoolFalse | TypePrefix::OptionalNone => 1,
+        TypePrefix::Buffer | TypePrefix::StringASCII | TypePrefix::StringUTF8 => {
+            5 + be_u32(&bytes[1..]) as usize
+        }
+        TypePrefix::PrincipalStandard => 22,
+        TypePrefix::PrincipalContract => 23 + bytes[22] as usize,
+        TypePrefix::OptionalSome | TypePrefix::ResponseOk | TypePrefix::ResponseErr => {
+            1 + value_len(&bytes[1..])
+        }
+        TypePrefix::List => {
+            let mut pos = 5;
+            for _ in 0..be_u32(&bytes[1..]) {
+                pos += value_len(&bytes[pos..]);
+            }
+            pos
+        }
+        TypePrefix::Tuple => {
+            let mut pos = 5;
+            for _ in 0..be_u32(&bytes[1..]) {
+                pos += 1 + bytes[pos] as usize;
+                pos += value_len(&bytes[pos..]);
+            }
+            pos
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::decode_hex;
+
+    fn deserialize_both(hex: &str) -> (Result<String, String>, Result<String, String>) {
+        let bytes = decode_hex(hex).unwrap();
+        let owned = ClarityValue::deserialize(&mut Cursor::new(bytes.as_ref()), false)
+            .map(|val| val.value.repr_string())
+            .map_err(|e| e.as_string());
+        let borrowed = ClarityValueRef::deserialize(&mut Cursor::new(bytes.as_ref()))
+            .map(|val| val.repr_string())
+            .map_err(|e| e.as_string());
+        (owned, borrowed)
+    }
+
+    #[test]
+    fn test_repr_matches_owned_value() {
+        let hexes = [
+            // (tuple (active true) (address 'SP...) (alias "Alice") ... (tags (list ...)))
+            "0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479",
+            // (list none (some u1))
+            "0x0b00000002090a0100000000000000000000000000000001",
+            // (err -2)
+            "0x0800fffffffffffffffffffffffffffffffe",
+            // 'SP000000000000000000002Q6VF78.pox-4
+            "0x0616000000000000000000000000000000000000000005706f782d34",
+            // (tuple (b true) (a true)), out of order
+            "0x0c00000002016203016103",
+            // (tuple (a true) (a false)), duplicated
+            "0x0c00000002016103016104",
+            // "a\x07"
+            "0x0d000000026107",
+            // u"a\xffb", invalid UTF-8
+            "0x0e0000000361ff62",
+            // (list)
+            "0x0b00000000",
+        ];
+        for hex in hexes.iter() {
+            let (owned, borrowed) = deserialize_both(hex);
+            assert!(owned.is_ok());
+            assert_eq!(owned, borrowed, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_errors_match_owned_value() {
+        let hexes = [
+            // truncated uint
+            "0x0100000000",
+            // bad type prefix inside a tuple field
+            "0x0c0000000106616d6f756e7463",
+            // list longer than MAX_VALUE_SIZE
+            "0x0b00100001",
+            // clarity name that is not valid UTF-8
+            "0x0c0000000101ff03",
+            // (some (some ... 16 levels deep
+            "0x0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a09",
+            // empty input
+            "0x",
+            // principal version 255
+            "0x05ff0000000000000000000000000000000000000000",
+        ];
+        for hex in hexes.iter() {
+            let (owned, borrowed) = deserialize_both(hex);
+            assert!(owned.is_err());
+            assert_eq!(owned, borrowed, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_principal_version_out_of_range() {
+        let hexes = [
+            "0x05ff0000000000000000000000000000000000000000",
+            "0x05200000000000000000000000000000000000000000",
+            // (some 'xx.a) with version 32
+            "0x0a0620000000000000000000000000000000000000000161",
+        ];
+        for hex in hexes.iter() {
+            let bytes = decode_hex(hex).unwrap();
+            let err = ClarityValueRef::deserialize(&mut Cursor::new(bytes.as_ref()))
+                .err()
+                .unwrap();
+            assert_eq!(err.code(), "InvalidValue", "{}", hex);
+            assert!(err.to_string().contains("must be less than 32"), "{}", err);
+        }
+        // version 31 is the largest c32 version
+        let bytes = decode_hex("0x051f0000000000000000000000000000000000000000").unwrap();
+        let value = ClarityValueRef::deserialize(&mut Cursor::new(bytes.as_ref())).unwrap();
+        assert!(value.repr_string().starts_with("'SZ"));
+    }
+
+    #[test]
+    fn test_lazy_access() {
+        // (tuple (a (list u1 u2)) (b none))
+        let bytes =
+            decode_hex("0x0c0000000201610b000000020100000000000000000000000000000001010000000000000000000000000000000201620900")
+                .unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let val = ClarityValueRef::deserialize(&mut cursor).unwrap();
+        assert_eq!(cursor.position() as usize, bytes.len() - 1);
+        assert_eq!(val.serialized_bytes(), &bytes[..bytes.len() - 1]);
+
+        let tuple = match val.value() {
+            ValueRef::Tuple(tuple) => tuple,
+            _ => panic!("expected tuple"),
+        };
+        assert_eq!(tuple.len(), 2);
+        assert!(tuple.contains_key("b"));
+        assert!(tuple.get("c").is_none());
+        assert_eq!(
+            tuple.get("b").unwrap().type_prefix(),
+            TypePrefix::OptionalNone
+        );
+
+        let items: Vec<u128> = match tuple.get("a").unwrap().value() {
+            ValueRef::List(list) => list
+                .iter()
+                .map(|item| match item.value() {
+                    ValueRef::UInt(v) => v,
+                    _ => panic!("expected uint"),
+                })
+                .collect(),
+            _ => panic!("expected list"),
+        };
+        assert_eq!(items, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        use PathSegment::*;
+        assert_eq!(parse_path("").unwrap(), vec![]);
+        assert_eq!(
+            parse_path("data.amount").unwrap(),
+            vec![Field("data".into()), Field("amount".into())]
+        );
+        assert_eq!(
+            parse_path("items[2].some").unwrap(),
+            parse_path("items.2.some").unwrap()
+        );
+        assert_eq!(parse_path("[1][0]").unwrap(), vec![Index(1), Index(0)]);
+        for path in ["a..b", "a[", "a[x]", "a[1]b", "."].iter() {
+            assert_eq!(
+                parse_path(path).unwrap_err(),
+                format!("Invalid path `{}`", path)
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_at_path() {
+        // (ok (tuple (items (list u1 u2 u3)) (name "a")))
+        let bytes = decode_hex("0x070c00000002056974656d730b00000003010000000000000000000000000000000101000000000000000000000000000000020100000000000000000000000000000003046e616d650d0000000161").unwrap();
+        let select = |path: &str| {
+            let path = parse_path(path).unwrap();
+            let mut cursor = Cursor::new(bytes.as_ref());
+            let selected = ClarityValueRef::deserialize_at_path(&mut cursor, &path).unwrap();
+            let whole = ClarityValueRef::deserialize(&mut Cursor::new(bytes.as_ref())).unwrap();
+            let repr = selected.map(|val| val.repr_string());
+            assert_eq!(repr, whole.select(&path).map(|val| val.repr_string()));
+            repr
+        };
+        assert_eq!(select("ok.items[1]").unwrap(), "u2");
+        assert_eq!(select("ok.name").unwrap(), "\"a\"");
+        assert_eq!(select("ok.items").unwrap(), "(list u1 u2 u3)");
+        assert_eq!(select("").unwrap(), whole_repr(&bytes));
+        assert!(select("err").is_none());
+        assert!(select("ok.items[3]").is_none());
+        assert!(select("ok.missing").is_none());
+        assert!(select("ok.name.x").is_none());
+        assert!(select("ok[0]").is_none());
+    }
+
+    #[test]
+    fn test_deserialize_at_path_skips_trailing_values() {
+        // (list u1 <bad type prefix>): selecting the first item does not read the second
+        let bytes = decode_hex("0x0b00000002010000000000000000000000000000000163").unwrap();
+        let path = parse_path("[0]").unwrap();
+        let selected =
+            ClarityValueRef::deserialize_at_path(&mut Cursor::new(bytes.as_ref()), &path);
+        assert_eq!(selected.unwrap().unwrap().repr_string(), "u1");
+
+        let path = parse_path("[1]").unwrap();
+        let err = ClarityValueRef::deserialize_at_path(&mut Cursor::new(bytes.as_ref()), &path)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.as_string(),
+            "Bad type prefix 0x63 (at `list[1]`, byte offset 22)"
+        );
+    }
+
+    #[test]
+    fn test_json_type_string() {
+        let type_string = |hex: &str| {
+            let bytes = decode_hex(hex).unwrap();
+            ClarityValueRef::deserialize(&mut Cursor::new(bytes.as_ref()))
+                .unwrap()
+                .json_type_string()
+        };
+        // (list none (some u1))
+        assert_eq!(
+            type_string("0x0b00000002090a0100000000000000000000000000000001"),
+            "(list 2 (optional none))"
+        );
+        // (err -2)
+        assert_eq!(
+            type_string("0x0800fffffffffffffffffffffffffffffffe"),
+            "(response UnknownType int)"
+        );
+        // (ok (tuple (items (list u1 u2 u3)) (name "a")))
+        assert_eq!(
+            type_string("0x070c00000002056974656d730b00000003010000000000000000000000000000000101000000000000000000000000000000020100000000000000000000000000000003046e616d650d0000000161"),
+            "(response (tuple (items (list 3 uint)) (name (string-ascii 1))) UnknownType)"
+        );
+        // (list u"og \u{e2ad90}" 0x0102)
+        assert_eq!(
+            type_string("0x0b000000020e000000066f6720e2ad9002000000020102"),
+            "(list 2 (string-utf8 6))"
+        );
+        // (list)
+        assert_eq!(type_string("0x0b00000000"), "(list 0 UnknownType)");
+    }
+
+    fn whole_repr(bytes: &[u8]) -> String {
+        ClarityValueRef::deserialize(&mut Cursor::new(bytes))
+            .unwrap()
+            .repr_string()
+    }
+}
@@ -0,0 +1,53 @@
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::PublicKey;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+/// RIPEMD160(SHA256(data)), as used for Stacks and Bitcoin address hashes.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
+/// Recover the public key that produced `signature` over the 32-byte `message_hash`.
+/// The signature uses the Stacks `MessageSignature` layout: a recovery ID byte followed by the
+/// 64-byte compact `r || s` encoding. High-S signatures are accepted, as in stacks-core.
+pub fn recover_public_key(message_hash: &[u8], signature: &[u8; 65]) -> Result<PublicKey, String> {
+    let recovery_id = RecoveryId::from_byte(signature[0])
+        .ok_or_else(|| format!("Invalid signature recovery ID {}", signature[0]))?;
+    let sig = Signature::from_slice(&signature[1..])
+        .map_err(|_| "Invalid signature: failed to decode recoverable signature".to_string())?;
+    // k256 only verifies low-S signatures; negating S flips the parity of the recovered R point
+    let (sig, recovery_id) = match sig.normalize_s() {
+        Some(normalized) => (
+            normalized,
+            RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced()),
+        ),
+        None => (sig, recovery_id),
+    };
+    let key = VerifyingKey::recover_from_prehash(message_hash, &sig, recovery_id)
+        .map_err(|_| "Invalid signature: failed to recover public key".to_string())?;
+    Ok(PublicKey::from(&key))
+}
+
+pub fn parse_public_key(bytes: &[u8]) -> Result<PublicKey, String> {
+    PublicKey::from_sec1_bytes(bytes).map_err(|_| "Invalid secp256k1 public key".to_string())
+}
+
+pub fn serialize_public_key(key: &PublicKey, compressed: bool) -> Vec<u8> {
+    key.to_encoded_point(compressed).as_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::encode_hex;
+
+    #[test]
+    fn test_hash160() {
+        assert_eq!(
+            encode_hex(&hash160(b"")).as_ref(),
+            "0xb472a266d0bd89c13706a4132ccfb16f7c3b9fcb"
+        );
+    }
+}
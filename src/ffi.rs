@@ -0,0 +1,426 @@
+//! C ABI over the core deserializers, for consumers other than Node.js.
+//!
+//! Decoders take a pointer and length of the serialized bytes and return a NUL-terminated JSON
+//! string, in the same shape as the Node addon's decoders with the default options (see the
+//! `json` module), e.g. integers wider than 32 bits are decimal strings. On failure they return
+//! null and, if `error_out` is not null, store an error message in it. Every string returned by
+//! this module is owned by the caller and must be released with `stacks_codec_string_free`.
+//!
+//! # Safety
+//!
+//! Byte inputs must point to `len` readable bytes (or may be null when `len` is 0), string
+//! inputs must be NUL-terminated, and `error_out` must be null or point to a writable
+//! `char *`. Strings must be freed exactly once, and only with `stacks_codec_string_free`.
+//!
+//! The header at `include/stacks_codec.h` is generated with
+//! `cbindgen --config cbindgen.toml --output include/stacks_codec.h`.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{CStr, CString};
+use std::io::Cursor;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, UnwindSafe};
+use std::ptr;
+
+use crate::address::bitcoin_address;
+use crate::address::c32::{c32_address, c32_address_decode};
+use crate::address::{btc_addr_to_stx_addr, stacks_to_bitcoin_address_internal};
+use crate::clarity_value::value_ref::ClarityValueRef;
+use crate::json::{
+    self, microblock_to_json, nakamoto_block_to_json, stacks_block_to_json, DecodedPostConditions,
+    DecodedStacksAddress, DecodedTransaction,
+};
+use crate::output_options::{DecodeClarityValueOptions, NumberFormat, OutputOptions};
+use crate::stacks_block::deserialize::{NakamotoBlock, StacksBlock, StacksMicroblock};
+use crate::GIT_VERSION;
+
+/// Run `f`, converting its result into a caller-owned C string. Errors and panics are reported
+/// through `error_out` and return null.
+fn ffi_result<F>(error_out: *mut *mut c_char, f: F) -> *mut c_char
+where
+    F: FnOnce() -> Result<String, String> + UnwindSafe,
+{
+    let result = catch_unwind(f).unwrap_or_else(|panic| {
+        let msg = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(format!("Panic: {}", msg))
+    });
+    match result {
+        Ok(output) => into_c_string(output),
+        Err(e) => {
+            if !error_out.is_null() {
+                unsafe { *error_out = into_c_string(e) };
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    // Interior NUL bytes cannot be represented; they only occur in Clarity string contents,
+    // which JSON escapes as `\u0000`, so this is a fallback for plain string outputs.
+    CString::new(s)
+        .unwrap_or_else(|e| {
+            let bytes: Vec<u8> = e.into_vec().into_iter().filter(|b| *b != 0).collect();
+            CString::new(bytes).unwrap()
+        })
+        .into_raw()
+}
+
+unsafe fn input_bytes<'a>(data: *const u8, len: usize) -> Result<&'a [u8], String> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err("Input pointer is null".to_string())
+    } else {
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn input_str<'a>(input: *const c_char) -> Result<&'a str, String> {
+    if input.is_null() {
+        return Err("Input pointer is null".to_string());
+    }
+    CStr::from_ptr(input)
+        .to_str()
+        .map_err(|e| format!("Input is not valid UTF-8: {}", e))
+}
+
+fn to_json(value: Result<serde_json::Value, String>) -> Result<String, String> {
+    Ok(value?.to_string())
+}
+
+/// Free a string returned by any `stacks_codec_*` function. Passing null is a no-op.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// The git version of the library.
+#[no_mangle]
+pub extern "C" fn stacks_codec_version() -> *mut c_char {
+    into_c_string(GIT_VERSION.to_string())
+}
+
+/// Decode a serialized transaction to JSON, with its `tx_id` alongside the transaction fields.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_transaction(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let tx = DecodedTransaction::decode(input_bytes(data, len)?)
+            .map_err(|e| format!("Failed to decode transaction: {}", e))?;
+        to_json(tx.to_json(OutputOptions::default()))
+    })
+}
+
+/// Decode a serialized Clarity value to JSON.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_clarity_value(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        to_json(json::decode_clarity_value(
+            input_bytes(data, len)?,
+            DecodeClarityValueOptions::default(),
+        ))
+    })
+}
+
+/// Decode a serialized Clarity value to its Clarity repr string (not JSON).
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_clarity_value_to_repr(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let mut cursor = Cursor::new(input_bytes(data, len)?);
+        let value = ClarityValueRef::deserialize(&mut cursor)
+            .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+        Ok(value.repr_string())
+    })
+}
+
+/// Decode serialized post conditions, prefixed with the post condition mode byte and a 4 byte
+/// count, to JSON.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_post_conditions(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let post_conditions = DecodedPostConditions::decode(input_bytes(data, len)?)
+            .map_err(|e| format!("Error deserializing post condition: {}", e))?;
+        to_json(post_conditions.to_json(NumberFormat::default()))
+    })
+}
+
+/// Decode a serialized Stacks 2.x block to JSON.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_stacks_block(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let mut cursor = Cursor::new(input_bytes(data, len)?);
+        let block = StacksBlock::deserialize(&mut cursor)
+            .map_err(|e| format!("Failed to decode Stacks block: {}", e))?;
+        to_json(stacks_block_to_json(&block, None, OutputOptions::default()))
+    })
+}
+
+/// Decode a serialized Nakamoto block (Stacks 3.x+) to JSON.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_nakamoto_block(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let mut cursor = Cursor::new(input_bytes(data, len)?);
+        let block = NakamotoBlock::deserialize(&mut cursor)
+            .map_err(|e| format!("Failed to decode Nakamoto block: {}", e))?;
+        to_json(nakamoto_block_to_json(
+            &block,
+            None,
+            OutputOptions::default(),
+        ))
+    })
+}
+
+/// Decode a serialized microblock to JSON.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_microblock(
+    data: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let mut cursor = Cursor::new(input_bytes(data, len)?);
+        let microblock = StacksMicroblock::deserialize(&mut cursor)
+            .map_err(|e| format!("Failed to decode microblock: {}", e))?;
+        to_json(microblock_to_json(
+            &microblock,
+            None,
+            OutputOptions::default(),
+        ))
+    })
+}
+
+/// Whether a NUL-terminated string is a valid c32 Stacks address.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_is_valid_stacks_address(address: *const c_char) -> bool {
+    match input_str(address) {
+        Ok(address) => c32_address_decode(address).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Decode a c32 Stacks address to JSON `{"version": number, "hash160": "0x.."}`.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_decode_stacks_address(
+    address: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let (version, hash160) = c32_address_decode(input_str(address)?)
+            .map_err(|e| format!("Error parsing Stacks address {}", e))?;
+        to_json(Ok(DecodedStacksAddress { version, hash160 }.to_json()))
+    })
+}
+
+/// Encode a c32 Stacks address from a version byte and a 20 byte hash160.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_stacks_address_from_parts(
+    version: u8,
+    hash160: *const u8,
+    len: usize,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        c32_address(version, input_bytes(hash160, len)?)
+            .map_err(|e| format!("Error converting to C32 address: {}", e))
+    })
+}
+
+/// Convert a c32 Stacks address to the equivalent base58check Bitcoin address.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_stacks_to_bitcoin_address(
+    address: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        stacks_to_bitcoin_address_internal(input_str(address)?.to_string())
+    })
+}
+
+/// Convert a base58check Bitcoin address to the equivalent c32 Stacks address.
+#[no_mangle]
+pub unsafe extern "C" fn stacks_codec_bitcoin_to_stacks_address(
+    address: *const c_char,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_result(error_out, || {
+        let bitcoin_address = bitcoin_address::from_b58(input_str(address)?)
+            .map_err(|e| format!("Error parsing Bitcoin address: {}", e))?;
+        let stacks_address = btc_addr_to_stx_addr(&bitcoin_address).map_err(|e| {
+            format!(
+                "Error getting Stacks address version from Bitcoin address: {}",
+                e
+            )
+        })?;
+        c32_address(stacks_address.version, &stacks_address.hash160_bytes)
+            .map_err(|e| format!("Error converting to C32 address: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex::decode_hex;
+    use serde_json::json;
+
+    fn take_string(s: *mut c_char) -> String {
+        assert!(!s.is_null());
+        let string = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { stacks_codec_string_free(s) };
+        string
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        // STX token transfer of 2000000 uSTX
+        let tx = decode_hex("0x00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000").unwrap();
+        let mut error = ptr::null_mut();
+        let json = take_string(unsafe {
+            stacks_codec_decode_transaction(tx.as_ptr(), tx.len(), &mut error)
+        });
+        assert!(error.is_null());
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            json["tx_id"],
+            "0xb81f2fdcc66926d846a4cfdad426a66a2a5a183208bffcbc1e0c1a453e662171"
+        );
+        assert_eq!(json["chain_id"], 1);
+        assert_eq!(json["payload"]["type_id"], 0);
+        assert_eq!(json["payload"]["amount"], "2000000");
+        assert_eq!(
+            json["payload"]["recipient"]["address"],
+            "SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92"
+        );
+    }
+
+    #[test]
+    fn test_decode_error() {
+        let mut error = ptr::null_mut();
+        let result = unsafe { stacks_codec_decode_transaction([0u8; 3].as_ptr(), 3, &mut error) };
+        assert!(result.is_null());
+        assert!(take_string(error).starts_with("Failed to decode transaction: "));
+
+        // a null error_out is allowed
+        let result = unsafe { stacks_codec_decode_clarity_value(ptr::null(), 0, ptr::null_mut()) };
+        assert!(result.is_null());
+
+        // a principal version that can't be c32 encoded is an error rather than a panic
+        let value = decode_hex("0x05ff0000000000000000000000000000000000000000").unwrap();
+        let mut error = ptr::null_mut();
+        let result =
+            unsafe { stacks_codec_decode_clarity_value(value.as_ptr(), value.len(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(
+            take_string(error),
+            "Error deserializing Clarity value: Invalid principal version 255, must be less than 32 (at byte offset 1)"
+        );
+    }
+
+    #[test]
+    fn test_decode_clarity_value() {
+        // (tuple (a u1) (b (some "x")))
+        let value =
+            decode_hex("0x0c000000020161010000000000000000000000000000000101620a0d0000000178")
+                .unwrap();
+        let mut error = ptr::null_mut();
+        let repr = take_string(unsafe {
+            stacks_codec_decode_clarity_value_to_repr(value.as_ptr(), value.len(), &mut error)
+        });
+        assert_eq!(repr, "(tuple (a u1) (b (some \"x\")))");
+        let json = take_string(unsafe {
+            stacks_codec_decode_clarity_value(value.as_ptr(), value.len(), &mut error)
+        });
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["repr"], repr);
+        assert_eq!(json["type_id"], 12);
+        assert_eq!(
+            json["data"]["a"],
+            json!({
+                "repr": "u1",
+                "hex": "0x0100000000000000000000000000000001",
+                "type_id": 1,
+                "value": "1",
+            })
+        );
+        assert_eq!(json["data"]["b"]["value"]["data"], "x");
+    }
+
+    #[test]
+    fn test_addresses() {
+        let address = CString::new("SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B").unwrap();
+        assert!(unsafe { stacks_codec_is_valid_stacks_address(address.as_ptr()) });
+        let mut error = ptr::null_mut();
+        let json = take_string(unsafe {
+            stacks_codec_decode_stacks_address(address.as_ptr(), &mut error)
+        });
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            json!({ "version": 22, "hash160": "0xa5d9d331000f5b79578ce56bd157f29a9056f0d6" })
+        );
+        let btc_address = take_string(unsafe {
+            stacks_codec_stacks_to_bitcoin_address(address.as_ptr(), &mut error)
+        });
+        assert_eq!(btc_address, "1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB");
+        let btc_address = CString::new(btc_address).unwrap();
+        let stx_address = take_string(unsafe {
+            stacks_codec_bitcoin_to_stacks_address(btc_address.as_ptr(), &mut error)
+        });
+        assert_eq!(stx_address, address.to_str().unwrap());
+        assert!(error.is_null());
+    }
+    #[test]
+    fn test_null_strings() {
+        assert!(!unsafe { stacks_codec_is_valid_stacks_address(ptr::null()) });
+        let mut error = ptr::null_mut();
+        let result = unsafe { stacks_codec_decode_stacks_address(ptr::null(), &mut error) };
+        assert!(result.is_null());
+        assert_eq!(take_string(error), "Input pointer is null");
+        let mut error = ptr::null_mut();
+        let result =
+            unsafe { stacks_codec_stacks_address_from_parts(22, ptr::null(), 20, &mut error) };
+        assert!(result.is_null());
+        assert_eq!(take_string(error), "Input pointer is null");
+
+        // a null error_out is allowed
+        let result =
+            unsafe { stacks_codec_stacks_to_bitcoin_address(ptr::null(), ptr::null_mut()) };
+        assert!(result.is_null());
+        let result =
+            unsafe { stacks_codec_bitcoin_to_stacks_address(ptr::null(), ptr::null_mut()) };
+        assert!(result.is_null());
+        let address = CString::new("not an address").unwrap();
+        let result =
+            unsafe { stacks_codec_decode_stacks_address(address.as_ptr(), ptr::null_mut()) };
+        assert!(result.is_null());
+    }
+}
@@ -30,6 +30,15 @@ pub fn encode_hex_no_prefix(data: &[u8]) -> Box<str> {
     hex_simd::encode_to_boxed_str(data, hex_simd::AsciiCase::Lower)
 }
 
+/// Serialize bytes as a 0x-prefixed hex string, for use with `#[serde(serialize_with)]`.
+#[cfg(feature = "serde")]
+pub fn serialize_hex<T: AsRef<[u8]>, S: serde::Serializer>(
+    data: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&encode_hex(data.as_ref()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
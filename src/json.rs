@@ -0,0 +1,1155 @@
+//! The decoders' output as JSON values, in the same shapes as the Node addon's decoders: with
+//! the default options integers wider than 32 bits are decimal strings, buffers are `0x`-prefixed
+//! hex and Clarity values use the `full` profile. Shared by the wasm bindings, the C ABI and the
+//! `stacks-codec` CLI so that all of them produce the same objects as `index.node`.
+//!
+//! JSON has no bigint, so with `numbers: 'bigint'` integers are `{"$bigint": "<decimal>"}`
+//! objects, which the wasm bindings convert to `bigint` values.
+
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use byteorder::ReadBytesExt;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::address::c32::c32_address;
+use crate::address::stacks_address::{AddressHashMode, StacksAddress};
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::clarity_value::deserialize_clarity_value;
+use crate::clarity_value::type_signature::TypeSignature;
+use crate::clarity_value::value_ref::{ClarityValueRef, ValueRef};
+use crate::hex::encode_hex;
+use crate::output_options::{
+    DecodeClarityValueOptions, NumberFormat, OutputOptions, OutputProfile,
+};
+use crate::post_condition::deserialize::{
+    AssetInfo, AssetInfoID, FungibleConditionCode, NonfungibleConditionCode,
+    PostConditionPrincipal, PostConditionPrincipalID, TransactionPostCondition,
+};
+use crate::pox_events::types::{PoxEventData, PoxSyntheticEvent};
+use crate::serialize_util::DeserializeError;
+use crate::stacks_block::deserialize::{
+    BitVec, NakamotoBlock, NakamotoBlockHeader, StacksBlock, StacksBlockHeader, StacksMicroblock,
+};
+use crate::stacks_block::merkle::{MerklePathOrder, TxMerkleProof};
+use crate::stacks_block::signers::{RewardSetSigner, SignerSignatureVerification};
+use crate::stacks_tx::decode_tx_bytes;
+use crate::stacks_tx::deserialize::{
+    PrincipalData, StacksMicroblockHeader, StacksTransaction, StandardPrincipalData,
+    TransactionAuth, TransactionAuthField, TransactionAuthFieldID, TransactionAuthFlags,
+    TransactionPayload, TransactionPayloadID, TransactionPublicKeyEncoding,
+    TransactionSpendingCondition, TransactionVersion,
+};
+use crate::stacks_tx::verify::{SpendingConditionVerification, TransactionVerification};
+
+/// A decoded transaction with its txid alongside the transaction fields.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedTransaction {
+    #[serde(serialize_with = "crate::hex::serialize_hex")]
+    pub tx_id: [u8; 32],
+    #[serde(flatten)]
+    pub tx: StacksTransaction,
+}
+
+impl DecodedTransaction {
+    pub fn decode(input: &[u8]) -> Result<Self, DeserializeError> {
+        let (tx, tx_id) = decode_tx_bytes(input)?;
+        Ok(DecodedTransaction { tx_id, tx })
+    }
+
+    /// The transaction as returned by `decodeTransaction`.
+    pub fn to_json(&self, options: OutputOptions) -> Result<Value, String> {
+        let mut obj = Map::new();
+        obj.insert("tx_id".into(), hex(&self.tx_id).into());
+        set_tx_fields(&mut obj, &self.tx, options)?;
+        Ok(obj.into())
+    }
+}
+
+/// Post conditions serialized as the post condition mode byte, followed by a 4 byte count and
+/// the post condition items, as read by `decodePostConditions`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedPostConditions {
+    pub post_condition_mode: u8,
+    pub post_conditions: Vec<TransactionPostCondition>,
+}
+
+impl DecodedPostConditions {
+    pub fn decode(input: &[u8]) -> Result<Self, DeserializeError> {
+        let post_condition_mode = Cursor::new(input).read_u8()?;
+        let mut post_conditions = Vec::new();
+        if input.len() > 4 {
+            let post_condition_bytes = &input[5..];
+            let mut cursor = Cursor::new(post_condition_bytes);
+            while cursor.position() < post_condition_bytes.len() as u64 {
+                let post_condition =
+                    TransactionPostCondition::deserialize(&mut cursor).map_err(|e| {
+                        e.in_element("post_conditions", post_conditions.len())
+                            .shift_offset(5)
+                    })?;
+                post_conditions.push(post_condition);
+            }
+        }
+        Ok(DecodedPostConditions {
+            post_condition_mode,
+            post_conditions,
+        })
+    }
+
+    /// The post conditions as returned by `decodePostConditions`.
+    pub fn to_json(&self, numbers: NumberFormat) -> Result<Value, String> {
+        let post_conditions = self
+            .post_conditions
+            .iter()
+            .map(|post_condition| post_condition_to_json(post_condition, numbers.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(json!({
+            "post_condition_mode": self.post_condition_mode,
+            "post_conditions": post_conditions,
+        }))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    encode_hex(bytes).into()
+}
+
+fn address(version: u8, hash_bytes: &[u8]) -> Result<String, String> {
+    c32_address(version, hash_bytes).map_err(|e| format!("Error converting to C32 address: {}", e))
+}
+
+fn int(numbers: NumberFormat, val: i128) -> Value {
+    match numbers {
+        NumberFormat::String => val.to_string().into(),
+        NumberFormat::BigInt => json!({ "$bigint": val.to_string() }),
+    }
+}
+
+fn uint(numbers: NumberFormat, val: u128) -> Value {
+    match numbers {
+        NumberFormat::String => val.to_string().into(),
+        NumberFormat::BigInt => json!({ "$bigint": val.to_string() }),
+    }
+}
+
+/// A serialized Clarity value decoded as by `decodeClarityValue`. As in the Node addon, the root
+/// `hex` is the whole input, including any bytes after the value.
+pub fn decode_clarity_value(
+    input: &[u8],
+    options: DecodeClarityValueOptions,
+) -> Result<Value, String> {
+    let (value, len) = deserialize_clarity_value(input, options.strict)
+        .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+    let mut decoded = clarity_value_to_json(&value, true, options.output)?;
+    if len != input.len() {
+        if let Value::Object(obj) = &mut decoded {
+            obj.insert("hex".into(), hex(input).into());
+        }
+    }
+    Ok(decoded)
+}
+
+/// Serialized Clarity values, as a u32 count followed by the values, decoded as by
+/// `decodeClarityValueList`.
+pub fn decode_clarity_value_list(
+    input: &[u8],
+    deep: bool,
+    numbers: NumberFormat,
+) -> Result<Value, String> {
+    let values = ClarityValueRef::deserialize_list(input)
+        .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+    Ok(values
+        .iter()
+        .map(|value| clarity_value_to_json(value, deep, numbers.into()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into())
+}
+
+/// A decoded Clarity value, as for `decodeClarityValue`. Unless `deep` is set, or with the `json`
+/// profile, only the top-level `repr`, `hex` and `type_id` are set.
+pub fn clarity_value_to_json(
+    val: &ClarityValueRef,
+    deep: bool,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    match options.profile {
+        OutputProfile::Json => return clarity_value_to_stacks_api_json(val, options.numbers),
+        OutputProfile::Minimal => {}
+        OutputProfile::Full => {
+            obj.insert("repr".into(), val.repr_string().into());
+            obj.insert("hex".into(), hex(val.serialized_bytes()).into());
+        }
+    }
+    obj.insert("type_id".into(), val.type_prefix().to_u8().into());
+    if !deep {
+        return Ok(obj.into());
+    }
+
+    use ValueRef::*;
+    match val.value() {
+        Int(val) => {
+            obj.insert("value".into(), int(options.numbers, val));
+        }
+        UInt(val) => {
+            obj.insert("value".into(), uint(options.numbers, val));
+        }
+        Bool(val) => {
+            obj.insert("value".into(), val.into());
+        }
+        Buffer(buff) => {
+            obj.insert("buffer".into(), hex(buff).into());
+        }
+        List(data) => {
+            let list = data
+                .iter()
+                .map(|x| clarity_value_to_json(&x, deep, options))
+                .collect::<Result<Vec<_>, _>>()?;
+            obj.insert("list".into(), list.into());
+        }
+        StringASCII(str_data) | StringUTF8(str_data) => {
+            obj.insert("data".into(), String::from_utf8_lossy(str_data).into());
+        }
+        PrincipalStandard(version, hash_bytes) => {
+            obj.insert("address_version".into(), version.into());
+            obj.insert("address_hash_bytes".into(), hex(hash_bytes).into());
+            obj.insert("address".into(), address(version, hash_bytes)?.into());
+        }
+        PrincipalContract(version, hash_bytes, name) => {
+            obj.insert("address_version".into(), version.into());
+            obj.insert("address_hash_bytes".into(), hex(hash_bytes).into());
+            obj.insert("address".into(), address(version, hash_bytes)?.into());
+            obj.insert("contract_name".into(), name.into());
+        }
+        Tuple(val) => {
+            let mut tuple = Map::new();
+            for (key, value) in val.sorted_fields() {
+                tuple.insert(key.into(), clarity_value_to_json(&value, deep, options)?);
+            }
+            obj.insert("data".into(), tuple.into());
+        }
+        OptionalSome(data) | ResponseOk(data) | ResponseErr(data) => {
+            obj.insert("value".into(), clarity_value_to_json(&data, deep, options)?);
+        }
+        OptionalNone => {
+            obj.insert("value".into(), Value::Null);
+        }
+    }
+    Ok(obj.into())
+}
+
+/// A Clarity value in the `{type, value}` JSON convention of the Stacks API (`cvToJSON` in
+/// stacks.js), as for the `json` profile. Responses also get a `success` property.
+fn clarity_value_to_stacks_api_json(
+    val: &ClarityValueRef,
+    numbers: NumberFormat,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    obj.insert("type".into(), val.json_type_string().into());
+
+    use ValueRef::*;
+    let value = match val.value() {
+        Int(val) => int(numbers, val),
+        UInt(val) => uint(numbers, val),
+        Bool(val) => val.into(),
+        Buffer(buff) => hex(buff).into(),
+        StringASCII(str_data) | StringUTF8(str_data) => String::from_utf8_lossy(str_data).into(),
+        PrincipalStandard(version, hash_bytes) => address(version, hash_bytes)?.into(),
+        PrincipalContract(version, hash_bytes, name) => {
+            format!("{}.{}", address(version, hash_bytes)?, name).into()
+        }
+        List(data) => data
+            .iter()
+            .map(|x| clarity_value_to_stacks_api_json(&x, numbers))
+            .collect::<Result<Vec<_>, _>>()?
+            .into(),
+        Tuple(val) => {
+            let mut tuple = Map::new();
+            for (key, value) in val.sorted_fields() {
+                tuple.insert(
+                    key.into(),
+                    clarity_value_to_stacks_api_json(&value, numbers)?,
+                );
+            }
+            tuple.into()
+        }
+        OptionalSome(data) | ResponseOk(data) | ResponseErr(data) => {
+            clarity_value_to_stacks_api_json(&data, numbers)?
+        }
+        OptionalNone => Value::Null,
+    };
+    obj.insert("value".into(), value);
+
+    let type_prefix = val.type_prefix();
+    if type_prefix == TypePrefix::ResponseOk || type_prefix == TypePrefix::ResponseErr {
+        obj.insert(
+            "success".into(),
+            (type_prefix == TypePrefix::ResponseOk).into(),
+        );
+    }
+    Ok(obj.into())
+}
+
+/// A Clarity value decoded as by `decodeClarityValueWithType`: like a deep
+/// `clarity_value_to_json`, but each value also gets a `type` property with its declared type.
+/// The value must already have been checked with `TypeSignature::admits`.
+pub fn clarity_value_typed_to_json(
+    val: &ClarityValueRef,
+    type_sig: &TypeSignature,
+    numbers: NumberFormat,
+) -> Result<Value, String> {
+    let value = val.value();
+    let is_compound = matches!(
+        value,
+        ValueRef::List(_)
+            | ValueRef::Tuple(_)
+            | ValueRef::OptionalSome(_)
+            | ValueRef::ResponseOk(_)
+            | ValueRef::ResponseErr(_)
+    );
+    let mut obj = match clarity_value_to_json(val, !is_compound, numbers.into())? {
+        Value::Object(obj) => obj,
+        _ => unreachable!(),
+    };
+    obj.insert("type".into(), type_sig.to_string().into());
+
+    let mismatch = || {
+        format!(
+            "Clarity value {} does not match type {}",
+            val.repr_string(),
+            type_sig
+        )
+    };
+    match (value, type_sig) {
+        (ValueRef::List(data), TypeSignature::List(item_type, _)) => {
+            let list = data
+                .iter()
+                .map(|x| clarity_value_typed_to_json(&x, item_type, numbers))
+                .collect::<Result<Vec<_>, _>>()?;
+            obj.insert("list".into(), list.into());
+        }
+        (ValueRef::Tuple(data), TypeSignature::Tuple(field_types)) => {
+            let mut tuple = Map::new();
+            for (key, value) in data.sorted_fields() {
+                let field_type = field_types.get(key).ok_or_else(mismatch)?;
+                tuple.insert(
+                    key.into(),
+                    clarity_value_typed_to_json(&value, field_type, numbers)?,
+                );
+            }
+            obj.insert("data".into(), tuple.into());
+        }
+        (ValueRef::OptionalSome(data), TypeSignature::Optional(inner_type))
+        | (ValueRef::ResponseOk(data), TypeSignature::Response(inner_type, _))
+        | (ValueRef::ResponseErr(data), TypeSignature::Response(_, inner_type)) => {
+            obj.insert(
+                "value".into(),
+                clarity_value_typed_to_json(&data, inner_type, numbers)?,
+            );
+        }
+        (_, _) if is_compound => return Err(mismatch()),
+        _ => {}
+    }
+    Ok(obj.into())
+}
+
+/// The fields of a transaction, as for `decodeTransaction` but without the `tx_id`.
+pub fn transaction_to_json(
+    tx: &StacksTransaction,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    set_tx_fields(&mut obj, tx, options)?;
+    Ok(obj.into())
+}
+
+fn set_tx_fields(
+    obj: &mut Map<String, Value>,
+    tx: &StacksTransaction,
+    options: OutputOptions,
+) -> Result<(), String> {
+    obj.insert("version".into(), (tx.version as u8).into());
+    obj.insert("chain_id".into(), tx.chain_id.into());
+    obj.insert(
+        "auth".into(),
+        auth_to_json(&tx.auth, tx.version, options.numbers)?,
+    );
+    obj.insert("anchor_mode".into(), (tx.anchor_mode as u8).into());
+    obj.insert(
+        "post_condition_mode".into(),
+        (tx.post_condition_mode as u8).into(),
+    );
+    let post_conditions = tx
+        .post_conditions
+        .iter()
+        .map(|post_condition| post_condition_to_json(post_condition, options))
+        .collect::<Result<Vec<_>, _>>()?;
+    obj.insert("post_conditions".into(), post_conditions.into());
+    obj.insert(
+        "post_conditions_buffer".into(),
+        hex(&tx.post_conditions_serialized).into(),
+    );
+    obj.insert("payload".into(), payload_to_json(&tx.payload, options)?);
+    Ok(())
+}
+
+fn auth_to_json(
+    auth: &TransactionAuth,
+    version: TransactionVersion,
+    numbers: NumberFormat,
+) -> Result<Value, String> {
+    Ok(match auth {
+        TransactionAuth::Standard(origin_condition) => json!({
+            "type_id": TransactionAuthFlags::AuthStandard as u8,
+            "origin_condition": spending_condition_to_json(origin_condition, version, numbers)?,
+        }),
+        TransactionAuth::Sponsored(origin_condition, sponsor_condition) => json!({
+            "type_id": TransactionAuthFlags::AuthSponsored as u8,
+            "origin_condition": spending_condition_to_json(origin_condition, version, numbers)?,
+            "sponsor_condition": spending_condition_to_json(sponsor_condition, version, numbers)?,
+        }),
+    })
+}
+
+fn signer_to_json(
+    hash_mode: u8,
+    signer: [u8; 20],
+    version: TransactionVersion,
+) -> Result<Value, String> {
+    let hash_mode = AddressHashMode::try_from(hash_mode).unwrap();
+    let address_version = match version {
+        TransactionVersion::Mainnet => hash_mode.to_version_mainnet(),
+        TransactionVersion::Testnet => hash_mode.to_version_testnet(),
+    };
+    stacks_address_to_json(&StacksAddress::new(address_version, signer))
+}
+
+fn spending_condition_to_json(
+    condition: &TransactionSpendingCondition,
+    version: TransactionVersion,
+    numbers: NumberFormat,
+) -> Result<Value, String> {
+    Ok(match condition {
+        TransactionSpendingCondition::Singlesig(data) => json!({
+            "hash_mode": data.hash_mode as u8,
+            "signer": signer_to_json(data.hash_mode as u8, data.signer, version)?,
+            "nonce": uint(numbers, data.nonce.into()),
+            "tx_fee": uint(numbers, data.tx_fee.into()),
+            "key_encoding": data.key_encoding as u8,
+            "signature": hex(&data.signature.0),
+        }),
+        TransactionSpendingCondition::Multisig(data) => json!({
+            "hash_mode": data.hash_mode as u8,
+            "signer": signer_to_json(data.hash_mode as u8, data.signer, version)?,
+            "nonce": uint(numbers, data.nonce.into()),
+            "tx_fee": uint(numbers, data.tx_fee.into()),
+            "fields": data.fields.iter().map(auth_field_to_json).collect::<Vec<_>>(),
+            "signatures_required": data.signatures_required,
+        }),
+    })
+}
+
+fn auth_field_to_json(field: &TransactionAuthField) -> Value {
+    match field {
+        TransactionAuthField::PublicKey(pubkey) => {
+            let field_id = if pubkey.compressed {
+                TransactionAuthFieldID::PublicKeyCompressed
+            } else {
+                TransactionAuthFieldID::PublicKeyUncompressed
+            };
+            json!({ "type_id": field_id as u8, "public_key": hex(&pubkey.key.0) })
+        }
+        TransactionAuthField::Signature(key_encoding, sig) => {
+            let field_id = if *key_encoding == TransactionPublicKeyEncoding::Compressed {
+                TransactionAuthFieldID::SignatureCompressed
+            } else {
+                TransactionAuthFieldID::SignatureUncompressed
+            };
+            json!({ "type_id": field_id as u8, "signature": hex(&sig.0) })
+        }
+    }
+}
+
+fn stacks_address_to_json(address: &StacksAddress) -> Result<Value, String> {
+    let mut obj = Map::new();
+    set_address_fields(&mut obj, address.version, &address.hash160_bytes)?;
+    Ok(obj.into())
+}
+
+fn set_address_fields(
+    obj: &mut Map<String, Value>,
+    version: u8,
+    hash_bytes: &[u8],
+) -> Result<(), String> {
+    obj.insert("address_version".into(), version.into());
+    obj.insert("address_hash_bytes".into(), hex(hash_bytes).into());
+    obj.insert("address".into(), self::address(version, hash_bytes)?.into());
+    Ok(())
+}
+
+/// A post condition, as in the `post_conditions` of `decodePostConditions`. With the `minimal`
+/// profile the NFT `asset_value` is decoded in full, as it has no `repr`.
+pub fn post_condition_to_json(
+    post_condition: &TransactionPostCondition,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    match post_condition {
+        TransactionPostCondition::STX(principal, fungible_condition, amount) => {
+            obj.insert("asset_info_id".into(), (AssetInfoID::STX as u8).into());
+            obj.insert(
+                "principal".into(),
+                post_condition_principal_to_json(principal)?,
+            );
+            set_fungible_condition_fields(&mut obj, *fungible_condition);
+            obj.insert("amount".into(), uint(options.numbers, (*amount).into()));
+        }
+        TransactionPostCondition::Fungible(principal, asset_info, fungible_condition, amount) => {
+            obj.insert(
+                "asset_info_id".into(),
+                (AssetInfoID::FungibleAsset as u8).into(),
+            );
+            obj.insert(
+                "principal".into(),
+                post_condition_principal_to_json(principal)?,
+            );
+            obj.insert("asset".into(), asset_info_to_json(asset_info)?);
+            set_fungible_condition_fields(&mut obj, *fungible_condition);
+            obj.insert("amount".into(), uint(options.numbers, (*amount).into()));
+        }
+        TransactionPostCondition::Nonfungible(
+            principal,
+            asset_info,
+            asset_value,
+            nonfungible_condition,
+        ) => {
+            obj.insert(
+                "asset_info_id".into(),
+                (AssetInfoID::NonfungibleAsset as u8).into(),
+            );
+            obj.insert(
+                "principal".into(),
+                post_condition_principal_to_json(principal)?,
+            );
+            obj.insert("asset".into(), asset_info_to_json(asset_info)?);
+            obj.insert(
+                "asset_value".into(),
+                clarity_value_to_json(
+                    &asset_value.as_value_ref().unwrap(),
+                    options.profile == OutputProfile::Minimal,
+                    options,
+                )?,
+            );
+            let condition_name = match nonfungible_condition {
+                NonfungibleConditionCode::Sent => "sent",
+                NonfungibleConditionCode::NotSent => "not_sent",
+            };
+            obj.insert(
+                "condition_code".into(),
+                (*nonfungible_condition as u8).into(),
+            );
+            obj.insert("condition_name".into(), condition_name.into());
+        }
+    }
+    Ok(obj.into())
+}
+
+fn set_fungible_condition_fields(obj: &mut Map<String, Value>, code: FungibleConditionCode) {
+    let condition_name = match code {
+        FungibleConditionCode::SentEq => "sent_equal_to",
+        FungibleConditionCode::SentGt => "sent_greater_than",
+        FungibleConditionCode::SentGe => "sent_greater_than_or_equal_to",
+        FungibleConditionCode::SentLt => "sent_less_than",
+        FungibleConditionCode::SentLe => "sent_less_than_or_equal_to",
+    };
+    obj.insert("condition_code".into(), (code as u8).into());
+    obj.insert("condition_name".into(), condition_name.into());
+}
+
+fn post_condition_principal_to_json(principal: &PostConditionPrincipal) -> Result<Value, String> {
+    let mut obj = Map::new();
+    match principal {
+        PostConditionPrincipal::Origin => {
+            obj.insert(
+                "type_id".into(),
+                (PostConditionPrincipalID::Origin as u8).into(),
+            );
+        }
+        PostConditionPrincipal::Standard(address) => {
+            obj.insert(
+                "type_id".into(),
+                (PostConditionPrincipalID::Standard as u8).into(),
+            );
+            set_address_fields(&mut obj, address.version, &address.hash160_bytes)?;
+        }
+        PostConditionPrincipal::Contract(address, contract_name) => {
+            obj.insert(
+                "type_id".into(),
+                (PostConditionPrincipalID::Contract as u8).into(),
+            );
+            set_address_fields(&mut obj, address.version, &address.hash160_bytes)?;
+            obj.insert("contract_name".into(), contract_name.as_str().into());
+        }
+    }
+    Ok(obj.into())
+}
+
+fn asset_info_to_json(asset_info: &AssetInfo) -> Result<Value, String> {
+    Ok(json!({
+        "contract_address": address(
+            asset_info.contract_address.version,
+            &asset_info.contract_address.hash160_bytes,
+        )?,
+        "contract_name": asset_info.contract_name.as_str(),
+        "asset_name": asset_info.asset_name.as_str(),
+    }))
+}
+
+fn payload_to_json(payload: &TransactionPayload, options: OutputOptions) -> Result<Value, String> {
+    let mut obj = Map::new();
+    let type_id = match payload {
+        TransactionPayload::TokenTransfer(recipient, amount, memo) => {
+            obj.insert("recipient".into(), principal_to_json(recipient)?);
+            obj.insert("amount".into(), uint(options.numbers, (*amount).into()));
+            obj.insert("memo_hex".into(), hex(&memo.0).into());
+            TransactionPayloadID::TokenTransfer
+        }
+        TransactionPayload::ContractCall(contract_call) => {
+            let address = &contract_call.address;
+            set_address_fields(&mut obj, address.version, &address.hash160_bytes)?;
+            obj.insert(
+                "contract_name".into(),
+                contract_call.contract_name.as_str().into(),
+            );
+            obj.insert(
+                "function_name".into(),
+                contract_call.function_name.as_str().into(),
+            );
+            let mut function_args_raw =
+                u32::to_be_bytes(contract_call.function_args.len() as u32).to_vec();
+            let mut function_args = Vec::with_capacity(contract_call.function_args.len());
+            for arg in &contract_call.function_args {
+                function_args_raw.extend_from_slice(arg.as_bytes());
+                function_args.push(clarity_value_to_json(
+                    &arg.as_value_ref(),
+                    options.profile == OutputProfile::Minimal,
+                    options,
+                )?);
+            }
+            obj.insert("function_args".into(), function_args.into());
+            obj.insert(
+                "function_args_buffer".into(),
+                hex(&function_args_raw).into(),
+            );
+            TransactionPayloadID::ContractCall
+        }
+        TransactionPayload::SmartContract(smart_contract) => {
+            obj.insert("contract_name".into(), smart_contract.name.as_str().into());
+            obj.insert(
+                "code_body".into(),
+                String::from_utf8_lossy(&smart_contract.code_body.0).into(),
+            );
+            TransactionPayloadID::SmartContract
+        }
+        TransactionPayload::PoisonMicroblock(h1, h2) => {
+            obj.insert("microblock_header_1".into(), microblock_header_to_json(h1));
+            obj.insert("microblock_header_2".into(), microblock_header_to_json(h2));
+            TransactionPayloadID::PoisonMicroblock
+        }
+        TransactionPayload::Coinbase(buf) => {
+            obj.insert("payload_buffer".into(), hex(&buf.0).into());
+            TransactionPayloadID::Coinbase
+        }
+        TransactionPayload::CoinbaseToAltRecipient(buf, recipient) => {
+            obj.insert("payload_buffer".into(), hex(&buf.0).into());
+            obj.insert("recipient".into(), principal_to_json(recipient)?);
+            TransactionPayloadID::CoinbaseToAltRecipient
+        }
+        TransactionPayload::VersionedSmartContract(smart_contract, version) => {
+            obj.insert("clarity_version".into(), (*version as u8).into());
+            obj.insert("contract_name".into(), smart_contract.name.as_str().into());
+            obj.insert(
+                "code_body".into(),
+                String::from_utf8_lossy(&smart_contract.code_body.0).into(),
+            );
+            TransactionPayloadID::VersionedSmartContract
+        }
+        TransactionPayload::TenureChange(tenure_change) => {
+            obj.insert(
+                "tenure_consensus_hash".into(),
+                hex(&tenure_change.tenure_consensus_hash).into(),
+            );
+            obj.insert(
+                "prev_tenure_consensus_hash".into(),
+                hex(&tenure_change.prev_tenure_consensus_hash).into(),
+            );
+            obj.insert(
+                "burn_view_consensus_hash".into(),
+                hex(&tenure_change.burn_view_consensus_hash).into(),
+            );
+            obj.insert(
+                "previous_tenure_end".into(),
+                hex(&tenure_change.previous_tenure_end).into(),
+            );
+            obj.insert(
+                "previous_tenure_blocks".into(),
+                tenure_change.previous_tenure_blocks.into(),
+            );
+            obj.insert("cause".into(), (tenure_change.cause as u8).into());
+            obj.insert("pubkey_hash".into(), hex(&tenure_change.pubkey_hash).into());
+            TransactionPayloadID::TenureChange
+        }
+        TransactionPayload::NakamotoCoinbase(buf, recipient, vrf_proof) => {
+            obj.insert("payload_buffer".into(), hex(&buf.0).into());
+            let recipient = match recipient {
+                Some(recipient) => principal_to_json(recipient)?,
+                None => Value::Null,
+            };
+            obj.insert("recipient".into(), recipient);
+            obj.insert("vrf_proof".into(), hex(&vrf_proof.0).into());
+            TransactionPayloadID::NakamotoCoinbase
+        }
+    };
+    obj.insert("type_id".into(), (type_id as u8).into());
+    Ok(obj.into())
+}
+
+fn principal_to_json(principal: &PrincipalData) -> Result<Value, String> {
+    let mut obj = Map::new();
+    match principal {
+        PrincipalData::Standard(StandardPrincipalData(version, hash_bytes)) => {
+            obj.insert(
+                "type_id".into(),
+                TypePrefix::PrincipalStandard.to_u8().into(),
+            );
+            set_address_fields(&mut obj, *version, hash_bytes)?;
+        }
+        PrincipalData::Contract(contract_identifier) => {
+            let StandardPrincipalData(version, hash_bytes) = &contract_identifier.issuer;
+            obj.insert(
+                "type_id".into(),
+                TypePrefix::PrincipalContract.to_u8().into(),
+            );
+            obj.insert(
+                "contract_name".into(),
+                contract_identifier.name.as_str().into(),
+            );
+            set_address_fields(&mut obj, *version, hash_bytes)?;
+        }
+    }
+    Ok(obj.into())
+}
+
+fn microblock_header_to_json(header: &StacksMicroblockHeader) -> Value {
+    json!({
+        "buffer": hex(&header.serialized_bytes),
+        "version": header.version,
+        "sequence": header.sequence,
+        "prev_block": hex(&header.prev_block.0),
+        "tx_merkle_root": hex(&header.tx_merkle_root.0),
+        "signature": hex(&header.signature.0),
+    })
+}
+
+fn transactions_to_json(
+    txs: &[StacksTransaction],
+    options: OutputOptions,
+) -> Result<Value, String> {
+    Ok(txs
+        .iter()
+        .map(|tx| transaction_to_json(tx, options))
+        .collect::<Result<Vec<_>, _>>()?
+        .into())
+}
+
+/// A decoded Nakamoto block, as for `decodeNakamotoBlock`. `merkle_root_valid` is set if the
+/// merkle root was verified.
+pub fn nakamoto_block_to_json(
+    block: &NakamotoBlock,
+    merkle_root_valid: Option<bool>,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    obj.insert("block_id".into(), hex(&block.header.block_id()).into());
+    if let Some(merkle_root_valid) = merkle_root_valid {
+        obj.insert("merkle_root_valid".into(), merkle_root_valid.into());
+    }
+    obj.insert(
+        "header".into(),
+        nakamoto_block_header_to_json(&block.header),
+    );
+    obj.insert("txs".into(), transactions_to_json(&block.txs, options)?);
+    Ok(obj.into())
+}
+
+fn nakamoto_block_header_to_json(header: &NakamotoBlockHeader) -> Value {
+    json!({
+        "version": header.version,
+        "chain_length": header.chain_length.to_string(),
+        "burn_spent": header.burn_spent.to_string(),
+        "consensus_hash": hex(&header.consensus_hash.0),
+        "parent_block_id": hex(&header.parent_block_id.0),
+        "tx_merkle_root": hex(&header.tx_merkle_root.0),
+        "state_index_root": hex(&header.state_index_root.0),
+        "timestamp": header.timestamp.to_string(),
+        "miner_signature": hex(&header.miner_signature.0),
+        "signer_signature": header
+            .signer_signature
+            .iter()
+            .map(|sig| hex(&sig.0))
+            .collect::<Vec<_>>(),
+        "pox_treatment": bitvec_to_json(&header.pox_treatment),
+        "block_hash": hex(&header.block_hash()),
+        "index_block_hash": hex(&header.block_id()),
+    })
+}
+
+fn bitvec_to_json(bitvec: &BitVec) -> Value {
+    json!({
+        "len": bitvec.len,
+        "data": hex(&bitvec.data),
+        "bits": (0..bitvec.len)
+            .map(|i| bitvec.get(i).unwrap_or(false))
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// A decoded Stacks 2.x block, as for `decodeStacksBlock`. `merkle_root_valid` is set if the
+/// merkle root was verified.
+pub fn stacks_block_to_json(
+    block: &StacksBlock,
+    merkle_root_valid: Option<bool>,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    obj.insert("block_hash".into(), hex(&block.header.block_hash()).into());
+    if let Some(merkle_root_valid) = merkle_root_valid {
+        obj.insert("merkle_root_valid".into(), merkle_root_valid.into());
+    }
+    obj.insert("header".into(), stacks_block_header_to_json(&block.header));
+    obj.insert("txs".into(), transactions_to_json(&block.txs, options)?);
+    Ok(obj.into())
+}
+
+fn stacks_block_header_to_json(header: &StacksBlockHeader) -> Value {
+    json!({
+        "version": header.version,
+        "total_work": {
+            "burn": header.total_work.burn.to_string(),
+            "work": header.total_work.work.to_string(),
+        },
+        "proof": hex(&header.proof.0),
+        "parent_block": hex(&header.parent_block.0),
+        "parent_microblock": hex(&header.parent_microblock.0),
+        "parent_microblock_sequence": header.parent_microblock_sequence,
+        "tx_merkle_root": hex(&header.tx_merkle_root.0),
+        "state_index_root": hex(&header.state_index_root.0),
+        "microblock_pubkey_hash": hex(&header.microblock_pubkey_hash),
+        "block_hash": hex(&header.block_hash()),
+    })
+}
+
+/// A decoded Stacks 2.x microblock, as for `decodeMicroblock`. `merkle_root_valid` is set if
+/// the merkle root was verified.
+pub fn microblock_to_json(
+    microblock: &StacksMicroblock,
+    merkle_root_valid: Option<bool>,
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let mut obj = Map::new();
+    if let Some(merkle_root_valid) = merkle_root_valid {
+        obj.insert("merkle_root_valid".into(), merkle_root_valid.into());
+    }
+    obj.insert(
+        "block_hash".into(),
+        hex(&microblock.header.block_hash()).into(),
+    );
+    // Null if the signature is malformed
+    let signer_pubkey_hash = match microblock.header.recover_signer_pubkey_hash() {
+        Ok(pubkey_hash) => hex(&pubkey_hash).into(),
+        Err(_) => Value::Null,
+    };
+    obj.insert("signer_pubkey_hash".into(), signer_pubkey_hash);
+    obj.insert(
+        "header".into(),
+        microblock_header_to_json(&microblock.header),
+    );
+    obj.insert(
+        "txs".into(),
+        transactions_to_json(&microblock.txs, options)?,
+    );
+    Ok(obj.into())
+}
+
+/// A decoded microblock stream and the problems found verifying it, as for
+/// `decodeMicroblockStream`.
+pub fn microblock_stream_to_json(
+    microblocks: &[StacksMicroblock],
+    errors: &[String],
+    options: OutputOptions,
+) -> Result<Value, String> {
+    let microblocks = microblocks
+        .iter()
+        .map(|microblock| microblock_to_json(microblock, None, options))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+        "microblocks": microblocks,
+    }))
+}
+
+/// A merkle inclusion proof, as returned by `getTxMerkleProof`.
+pub fn tx_merkle_proof_to_json(proof: &TxMerkleProof) -> Value {
+    let path = proof
+        .path
+        .iter()
+        .map(|point| {
+            let order = match point.order {
+                MerklePathOrder::Left => "left",
+                MerklePathOrder::Right => "right",
+            };
+            json!({ "order": order, "hash": hex(&point.hash) })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "tx_index": proof.tx_index,
+        "merkle_root": hex(&proof.merkle_root),
+        "path": path,
+    })
+}
+
+/// The result of `verifyTransaction`.
+pub fn transaction_verification_to_json(verification: &TransactionVerification) -> Value {
+    json!({
+        "valid": verification.is_valid(),
+        "origin": spending_condition_verification_to_json(&verification.origin),
+        "sponsor": verification
+            .sponsor
+            .as_ref()
+            .map(spending_condition_verification_to_json),
+    })
+}
+
+fn spending_condition_verification_to_json(verification: &SpendingConditionVerification) -> Value {
+    json!({
+        "valid": verification.error.is_none(),
+        "public_keys": verification
+            .public_keys
+            .iter()
+            .map(|public_key| hex(public_key))
+            .collect::<Vec<_>>(),
+        "error": verification.error,
+    })
+}
+
+/// The result of `verifyNakamotoBlockSignerSignatures` for the given reward set.
+pub fn signer_signature_verification_to_json(
+    verification: &SignerSignatureVerification,
+    reward_set: &[RewardSetSigner],
+) -> Value {
+    let signers = verification
+        .signer_indices
+        .iter()
+        .map(|index| {
+            let signer = &reward_set[*index];
+            json!({
+                "index": index,
+                "signing_key": hex(&signer.signing_key),
+                "weight": signer.weight,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "threshold_met": verification.threshold_met(),
+        "total_weight": verification.total_weight,
+        "signed_weight": verification.signed_weight,
+        "threshold": verification.threshold,
+        "signers": signers,
+        "error": verification.error,
+    })
+}
+
+/// The result of `verifyNakamotoBlockMinerSignature`.
+pub fn miner_signature_verification_to_json(valid: bool, miner_public_key: &[u8]) -> Value {
+    json!({ "valid": valid, "miner_public_key": hex(miner_public_key) })
+}
+
+/// A PoX synthetic event, as for `decodePoxSyntheticEvent`.
+pub fn pox_event_to_json(event: &PoxSyntheticEvent) -> Value {
+    let opt = |value: Option<u128>| value.map(|v| v.to_string());
+    let data = match &event.data {
+        PoxEventData::HandleUnlock {
+            first_cycle_locked,
+            first_unlocked_cycle,
+        } => json!({
+            "first_cycle_locked": first_cycle_locked.to_string(),
+            "first_unlocked_cycle": first_unlocked_cycle.to_string(),
+        }),
+        PoxEventData::StackStx {
+            lock_amount,
+            lock_period,
+            start_burn_height,
+            unlock_burn_height,
+            signer_key,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "lock_amount": lock_amount.to_string(),
+            "lock_period": lock_period.to_string(),
+            "start_burn_height": start_burn_height.to_string(),
+            "unlock_burn_height": unlock_burn_height.to_string(),
+            "signer_key": signer_key,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::StackIncrease {
+            increase_by,
+            total_locked,
+            signer_key,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "increase_by": increase_by.to_string(),
+            "total_locked": total_locked.to_string(),
+            "signer_key": signer_key,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::StackExtend {
+            extend_count,
+            unlock_burn_height,
+            signer_key,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "extend_count": extend_count.to_string(),
+            "unlock_burn_height": unlock_burn_height.to_string(),
+            "signer_key": signer_key,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::DelegateStx {
+            amount_ustx,
+            delegate_to,
+            unlock_burn_height,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "amount_ustx": amount_ustx.to_string(),
+            "delegate_to": delegate_to,
+            "unlock_burn_height": opt(*unlock_burn_height),
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::DelegateStackStx {
+            lock_amount,
+            unlock_burn_height,
+            start_burn_height,
+            lock_period,
+            delegator,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "lock_amount": lock_amount.to_string(),
+            "unlock_burn_height": unlock_burn_height.to_string(),
+            "start_burn_height": start_burn_height.to_string(),
+            "lock_period": lock_period.to_string(),
+            "delegator": delegator,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::DelegateStackIncrease {
+            increase_by,
+            total_locked,
+            delegator,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "increase_by": increase_by.to_string(),
+            "total_locked": total_locked.to_string(),
+            "delegator": delegator,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::DelegateStackExtend {
+            unlock_burn_height,
+            extend_count,
+            delegator,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "unlock_burn_height": unlock_burn_height.to_string(),
+            "extend_count": extend_count.to_string(),
+            "delegator": delegator,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::StackAggregationCommit {
+            reward_cycle,
+            amount_ustx,
+            signer_key,
+            end_cycle_id,
+            start_cycle_id,
+        }
+        | PoxEventData::StackAggregationCommitIndexed {
+            reward_cycle,
+            amount_ustx,
+            signer_key,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "reward_cycle": reward_cycle.to_string(),
+            "amount_ustx": amount_ustx.to_string(),
+            "signer_key": signer_key,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::StackAggregationIncrease {
+            reward_cycle,
+            amount_ustx,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "reward_cycle": reward_cycle.to_string(),
+            "amount_ustx": amount_ustx.to_string(),
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+        PoxEventData::RevokeDelegateStx {
+            delegate_to,
+            end_cycle_id,
+            start_cycle_id,
+        } => json!({
+            "delegate_to": delegate_to,
+            "end_cycle_id": opt(*end_cycle_id),
+            "start_cycle_id": opt(*start_cycle_id),
+        }),
+    };
+    json!({
+        "stacker": event.base.stacker,
+        "locked": event.base.locked.to_string(),
+        "balance": event.base.balance.to_string(),
+        "burnchain_unlock_height": event.base.burnchain_unlock_height.to_string(),
+        "pox_addr": event.base.pox_addr,
+        "pox_addr_raw": event.base.pox_addr_raw,
+        "name": event.name.as_str(),
+        "data": data,
+    })
+}
+
+/// The version and hash160 of a c32 Stacks address.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedStacksAddress {
+    pub version: u8,
+    #[serde(serialize_with = "crate::hex::serialize_hex")]
+    pub hash160: [u8; 20],
+}
+
+impl DecodedStacksAddress {
+    /// The address as `{"version": number, "hash160": "0x.."}`.
+    pub fn to_json(&self) -> Value {
+        json!({ "version": self.version, "hash160": hex(&self.hash160) })
+    }
+}
@@ -0,0 +1,932 @@
+//! Readers for the JSON inputs of the encoders and verifiers, the counterpart of `json.rs`. They
+//! accept the same shapes and give the same errors as the Node addon's `neon_decoder` modules, so
+//! e.g. a decoded transaction can be passed back to `encodeTransaction`. Buffers are `0x`-prefixed
+//! hex strings and integers wider than 32 bits may be decimal strings.
+
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+use std::io::Cursor;
+
+use serde_json::{Map, Value};
+
+use crate::address::c32::c32_address_decode;
+use crate::address::stacks_address::StacksAddress;
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::clarity_value::type_signature::{TypeSignature, MAX_TYPE_DEPTH};
+use crate::clarity_value::types::{self, ClarityName, ClarityValue, SerializedClarityValue};
+use crate::clarity_value::value_ref::parse_path;
+use crate::hex::decode_hex;
+use crate::output_options::{
+    BufferFormat, DecodeClarityValueOptions, NativeValueOptions, NumberFormat, OutputOptions,
+    OutputProfile, SelectedValueFormat,
+};
+use crate::post_condition::deserialize::{
+    AssetInfo, AssetInfoID, FungibleConditionCode, NonfungibleConditionCode,
+    PostConditionPrincipal, PostConditionPrincipalID, TransactionPostCondition,
+};
+use crate::serialize_util::PathSegment;
+use crate::stacks_block::merkle::{MerklePathOrder, MerklePathPoint};
+use crate::stacks_block::signers::RewardSetSigner;
+use crate::stacks_tx::deserialize::*;
+
+type JsonObject = Map<String, Value>;
+
+static NULL: Value = Value::Null;
+
+fn as_object(val: &Value) -> Result<&JsonObject, String> {
+    val.as_object()
+        .ok_or_else(|| "Expected an object".to_string())
+}
+
+fn as_array(val: &Value) -> Result<&Vec<Value>, String> {
+    val.as_array()
+        .ok_or_else(|| "Expected an array".to_string())
+}
+
+fn unexpected_type(key: &str) -> String {
+    format!("Unexpected type for property `{}`", key)
+}
+
+fn get_prop<'a>(obj: &'a JsonObject, key: &str) -> &'a Value {
+    obj.get(key).unwrap_or(&NULL)
+}
+
+fn get_prop_object<'a>(obj: &'a JsonObject, key: &str) -> Result<&'a JsonObject, String> {
+    get_prop(obj, key)
+        .as_object()
+        .ok_or_else(|| unexpected_type(key))
+}
+
+fn get_prop_array<'a>(obj: &'a JsonObject, key: &str) -> Result<&'a Vec<Value>, String> {
+    get_prop(obj, key)
+        .as_array()
+        .ok_or_else(|| unexpected_type(key))
+}
+
+fn get_prop_string<'a>(obj: &'a JsonObject, key: &str) -> Result<&'a str, String> {
+    get_prop(obj, key)
+        .as_str()
+        .ok_or_else(|| unexpected_type(key))
+}
+
+fn get_prop_number(obj: &JsonObject, key: &str) -> Result<f64, String> {
+    get_prop(obj, key)
+        .as_f64()
+        .ok_or_else(|| unexpected_type(key))
+}
+
+/// Read an optional string property, returning `None` if it is missing or null.
+fn get_prop_string_opt<'a>(obj: &'a JsonObject, key: &str) -> Result<Option<&'a str>, String> {
+    match get_prop(obj, key) {
+        Value::Null => Ok(None),
+        Value::String(val) => Ok(Some(val)),
+        _ => Err(unexpected_type(key)),
+    }
+}
+
+/// Read an optional boolean property, returning `None` if it is missing or null.
+fn get_prop_bool_opt(obj: &JsonObject, key: &str) -> Result<Option<bool>, String> {
+    match get_prop(obj, key) {
+        Value::Null => Ok(None),
+        Value::Bool(val) => Ok(Some(*val)),
+        _ => Err(unexpected_type(key)),
+    }
+}
+
+/// Read an integer property that may be given as a string-quoted integer or a number.
+fn get_prop_integer_string(obj: &JsonObject, key: &str) -> Result<String, String> {
+    match get_prop(obj, key) {
+        Value::String(val) => Ok(val.clone()),
+        Value::Number(num) => {
+            if let Some(num) = num.as_u64() {
+                return Ok(num.to_string());
+            }
+            if let Some(num) = num.as_i64() {
+                return Ok(num.to_string());
+            }
+            match num.as_f64() {
+                Some(num) if num.fract() == 0.0 && num.is_finite() => Ok(format!("{:.0}", num)),
+                _ => Err(format!("Property `{}` must be an integer", key)),
+            }
+        }
+        _ => Err(format!(
+            "Property `{}` must be a string-quoted integer, a number or a bigint",
+            key
+        )),
+    }
+}
+
+/// Read a hex string property.
+fn get_prop_bytes(obj: &JsonObject, key: &str) -> Result<Box<[u8]>, String> {
+    match get_prop(obj, key) {
+        Value::String(val) => {
+            decode_hex(val).map_err(|e| format!("Hex parsing error for property `{}`: {}", key, e))
+        }
+        _ => Err(format!(
+            "Property `{}` must be a hex string or a Buffer",
+            key
+        )),
+    }
+}
+
+/// Read a hex string property that must be exactly `N` bytes long.
+fn get_prop_fixed_bytes<const N: usize>(obj: &JsonObject, key: &str) -> Result<[u8; N], String> {
+    let bytes = get_prop_bytes(obj, key)?;
+    bytes.as_ref().try_into().map_err(|_| {
+        format!(
+            "Property `{}` must be {} bytes, received {} bytes",
+            key,
+            N,
+            bytes.len()
+        )
+    })
+}
+
+/// Read a number property that must be an integer in the range of `u8`, e.g. a type ID.
+fn get_prop_u8(obj: &JsonObject, key: &str) -> Result<u8, String> {
+    let num = get_prop_number(obj, key)?;
+    if num.fract() != 0.0 || !(0.0..=255.0).contains(&num) {
+        return Err(format!("Property `{}` must be an integer 0-255", key));
+    }
+    Ok(num as u8)
+}
+
+/// Read a u64 property given as a string-quoted integer or a number, e.g. a nonce.
+fn get_prop_u64(obj: &JsonObject, key: &str) -> Result<u64, String> {
+    let val_str = get_prop_integer_string(obj, key)?;
+    val_str
+        .parse::<u64>()
+        .map_err(|e| format!("Invalid `{}` value `{}`: {}", key, val_str, e))
+}
+
+/// Read a number property that must be an integer in the range of `u32`.
+fn get_prop_u32(obj: &JsonObject, key: &str, name: &str) -> Result<u32, String> {
+    let num = get_prop_number(obj, key)?;
+    if num.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&num) {
+        return Err(format!("Invalid {} {}", name, num));
+    }
+    Ok(num as u32)
+}
+
+/// Get an optional options object argument, returning `None` if it is not given or null.
+fn options_obj(options: &Value) -> Result<Option<&JsonObject>, String> {
+    match options {
+        Value::Null => Ok(None),
+        Value::Object(obj) => Ok(Some(obj)),
+        _ => Err("Options argument must be an object".to_string()),
+    }
+}
+
+fn number_format_from_json(
+    obj: &JsonObject,
+    default: NumberFormat,
+) -> Result<NumberFormat, String> {
+    match get_prop_string_opt(obj, "numbers")? {
+        None => Ok(default),
+        Some("string") => Ok(NumberFormat::String),
+        Some("bigint") => Ok(NumberFormat::BigInt),
+        Some(other) => Err(format!("Unknown numbers format `{}`", other)),
+    }
+}
+
+/// Read the `numbers` property of an optional options argument.
+pub fn numbers_from_json(options: &Value) -> Result<NumberFormat, String> {
+    match options_obj(options)? {
+        Some(obj) => number_format_from_json(obj, NumberFormat::String),
+        None => Ok(NumberFormat::String),
+    }
+}
+
+fn output_options_from_obj(obj: &JsonObject) -> Result<OutputOptions, String> {
+    let numbers = number_format_from_json(obj, NumberFormat::String)?;
+    let profile = match get_prop_string_opt(obj, "profile")? {
+        None | Some("full") => OutputProfile::Full,
+        Some("minimal") => OutputProfile::Minimal,
+        Some("json") => OutputProfile::Json,
+        Some(other) => return Err(format!("Unknown profile `{}`", other)),
+    };
+    Ok(OutputOptions { numbers, profile })
+}
+
+/// Read the `numbers` and `profile` properties of an optional options argument.
+pub fn output_options_from_json(options: &Value) -> Result<OutputOptions, String> {
+    match options_obj(options)? {
+        Some(obj) => output_options_from_obj(obj),
+        None => Ok(OutputOptions::default()),
+    }
+}
+
+/// Read the options argument of `decodeClarityValue`.
+pub fn decode_clarity_value_options_from_json(
+    options: &Value,
+) -> Result<DecodeClarityValueOptions, String> {
+    let mut decode_options = DecodeClarityValueOptions::default();
+    if let Some(obj) = options_obj(options)? {
+        if let Some(strict) = get_prop_bool_opt(obj, "strict")? {
+            decode_options.strict = strict;
+        }
+        decode_options.output = output_options_from_obj(obj)?;
+    }
+    Ok(decode_options)
+}
+
+/// Read the options argument of `decodeClarityValueAtPath`.
+pub fn selected_value_format_from_json(options: &Value) -> Result<SelectedValueFormat, String> {
+    let obj = match options_obj(options)? {
+        Some(obj) => obj,
+        None => return Ok(SelectedValueFormat::default()),
+    };
+    let numbers = number_format_from_json(obj, NumberFormat::String)?;
+    match get_prop_string_opt(obj, "format")? {
+        None | Some("object") => Ok(SelectedValueFormat::Object(numbers)),
+        Some("repr") => Ok(SelectedValueFormat::Repr),
+        Some("hex") => Ok(SelectedValueFormat::Hex),
+        Some(other) => Err(format!("Unknown format `{}`", other)),
+    }
+}
+
+/// Read the options argument of `decodeClarityValueToNative`.
+pub fn native_value_options_from_json(options: &Value) -> Result<NativeValueOptions, String> {
+    let mut native_options = NativeValueOptions::default();
+    if let Some(obj) = options_obj(options)? {
+        native_options.numbers = number_format_from_json(obj, NumberFormat::BigInt)?;
+        native_options.buffers = match get_prop_string_opt(obj, "buffers")? {
+            None | Some("hex") => BufferFormat::Hex,
+            Some("uint8array") => BufferFormat::Uint8Array,
+            Some(other) => return Err(format!("Unknown buffers format `{}`", other)),
+        };
+        if let Some(unwrap) = get_prop_bool_opt(obj, "unwrapResponses")? {
+            native_options.unwrap_responses = unwrap;
+        }
+    }
+    Ok(native_options)
+}
+
+/// Build a Clarity `Value` from an object in the shape of the deep `decodeClarityValue` output.
+/// The `repr` and `hex` properties are ignored.
+pub fn clarity_value_from_json(obj: &JsonObject) -> Result<types::Value, String> {
+    let type_id = get_prop_number(obj, "type_id")?;
+    let prefix = match TypePrefix::from_u8(type_id as u8) {
+        Some(prefix) if type_id.fract() == 0.0 && (0.0..=255.0).contains(&type_id) => prefix,
+        _ => return Err(format!("Bad Clarity type_id: {}", type_id)),
+    };
+
+    let value = match prefix {
+        TypePrefix::Int => {
+            let val_str = get_prop_integer_string(obj, "value")?;
+            let val = val_str
+                .parse::<i128>()
+                .map_err(|e| format!("Invalid Clarity int `{}`: {}", val_str, e))?;
+            types::Value::Int(val)
+        }
+        TypePrefix::UInt => {
+            let val_str = get_prop_integer_string(obj, "value")?;
+            let val = val_str
+                .parse::<u128>()
+                .map_err(|e| format!("Invalid Clarity uint `{}`: {}", val_str, e))?;
+            types::Value::UInt(val)
+        }
+        TypePrefix::BoolTrue => types::Value::Bool(true),
+        TypePrefix::BoolFalse => types::Value::Bool(false),
+        TypePrefix::Buffer => types::Value::Buffer(get_prop_bytes(obj, "buffer")?.into_vec()),
+        TypePrefix::PrincipalStandard => {
+            types::Value::PrincipalStandard(standard_principal_from_json(obj)?)
+        }
+        TypePrefix::PrincipalContract => {
+            let issuer = standard_principal_from_json(obj)?;
+            let name = ClarityName::try_from(get_prop_string(obj, "contract_name")?.to_string())?;
+            types::Value::PrincipalContract(types::QualifiedContractIdentifier { issuer, name })
+        }
+        TypePrefix::ResponseOk => types::Value::ResponseOk(Box::new(inner_value_from_json(obj)?)),
+        TypePrefix::ResponseErr => types::Value::ResponseErr(Box::new(inner_value_from_json(obj)?)),
+        TypePrefix::OptionalNone => types::Value::OptionalNone,
+        TypePrefix::OptionalSome => {
+            types::Value::OptionalSome(Box::new(inner_value_from_json(obj)?))
+        }
+        TypePrefix::List => {
+            let list = get_prop_array(obj, "list")?;
+            let mut items = Vec::with_capacity(list.len());
+            for item in list {
+                items.push(ClarityValue::new(clarity_value_from_json(as_object(
+                    item,
+                )?)?));
+            }
+            types::Value::List(items)
+        }
+        TypePrefix::Tuple => {
+            let tuple_obj = get_prop_object(obj, "data")?;
+            let mut data = BTreeMap::new();
+            for key in tuple_obj.keys() {
+                let val = clarity_value_from_json(get_prop_object(tuple_obj, key)?)?;
+                let name = ClarityName::try_from(key.clone())?;
+                data.insert(name, ClarityValue::new(val));
+            }
+            types::Value::Tuple(data)
+        }
+        TypePrefix::StringASCII => {
+            let data = get_prop_string(obj, "data")?;
+            if !data.is_ascii() {
+                return Err("Clarity string-ascii data contains non-ASCII characters".to_string());
+            }
+            types::Value::StringASCII(data.as_bytes().to_vec())
+        }
+        TypePrefix::StringUTF8 => {
+            let data = get_prop_string(obj, "data")?;
+            types::Value::string_utf8(data.as_bytes().to_vec())
+        }
+    };
+    Ok(value)
+}
+
+/// Build a Clarity value from either a shallow object (as found in `decodeTransaction` function
+/// args and post-conditions) using its serialized `hex` property, or else from a deep object
+/// as accepted by `clarity_value_from_json`.
+pub fn clarity_value_from_json_hex_or_deep(obj: &JsonObject) -> Result<ClarityValue, String> {
+    if let Value::String(hex) = get_prop(obj, "hex") {
+        let bytes =
+            decode_hex(hex).map_err(|e| format!("Hex parsing error for Clarity value: {}", e))?;
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let value = ClarityValue::deserialize(&mut cursor, false)
+            .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+        if cursor.position() as usize != bytes.len() {
+            return Err(format!("Trailing bytes in Clarity value hex {}", hex));
+        }
+        return Ok(value);
+    }
+    Ok(ClarityValue::new(clarity_value_from_json(obj)?))
+}
+
+fn inner_value_from_json(obj: &JsonObject) -> Result<ClarityValue, String> {
+    let inner = clarity_value_from_json(get_prop_object(obj, "value")?)?;
+    Ok(ClarityValue::new(inner))
+}
+
+fn standard_principal_from_json(obj: &JsonObject) -> Result<types::StandardPrincipalData, String> {
+    let (version, hash160) = c32_address_decode(get_prop_string(obj, "address")?)
+        .map_err(|e| format!("Error parsing Stacks address {}", e))?;
+    Ok(types::StandardPrincipalData(version, hash160))
+}
+
+/// Build a `TypeSignature` from either a string in Clarity syntax (e.g. `(list 10 uint)`), or a
+/// type in the JSON format of a contract ABI (e.g. `{ "list": { "type": "uint128", "length": 10 } }`),
+/// given as an object or JSON string.
+pub fn type_signature_from_json(val: &Value) -> Result<TypeSignature, String> {
+    abi_type_from_json(val, 0)
+}
+
+fn abi_type_from_json(val: &Value, depth: u8) -> Result<TypeSignature, String> {
+    if depth >= MAX_TYPE_DEPTH {
+        return Err(format!("TypeSignatureTooDeep: {}", depth));
+    }
+    let obj = match val {
+        Value::String(input) => {
+            let trimmed = input.trim_start();
+            if trimmed.starts_with('{') || trimmed.starts_with('"') {
+                let parsed: Value = serde_json::from_str(input)
+                    .map_err(|e| format!("Error parsing type signature: {}", e))?;
+                return abi_type_from_json(&parsed, depth);
+            }
+            return TypeSignature::parse(input)
+                .map_err(|e| format!("Error parsing type signature: {}", e));
+        }
+        Value::Object(obj) => obj,
+        _ => return Err("Type signature must be a string or an ABI type object".to_string()),
+    };
+    if obj.len() != 1 {
+        return Err("ABI type object must have exactly one property".to_string());
+    }
+    let (key, inner) = obj.iter().next().unwrap();
+    let type_sig = match key.as_str() {
+        "buffer" | "string-ascii" | "string-utf8" => {
+            let len = abi_length_from_json(get_prop_object(obj, key)?)?;
+            match key.as_str() {
+                "buffer" => TypeSignature::Buffer(len),
+                "string-ascii" => TypeSignature::StringASCII(len),
+                _ => TypeSignature::StringUTF8(len),
+            }
+        }
+        "optional" => TypeSignature::Optional(Box::new(abi_type_from_json(inner, depth + 1)?)),
+        "response" => {
+            let inner_obj = get_prop_object(obj, "response")?;
+            let ok = abi_type_from_json(get_prop(inner_obj, "ok"), depth + 1)?;
+            let err = abi_type_from_json(get_prop(inner_obj, "error"), depth + 1)?;
+            TypeSignature::Response(Box::new(ok), Box::new(err))
+        }
+        "list" => {
+            let inner_obj = get_prop_object(obj, "list")?;
+            let len = abi_length_from_json(inner_obj)?;
+            let item_type = abi_type_from_json(get_prop(inner_obj, "type"), depth + 1)?;
+            TypeSignature::List(Box::new(item_type), len)
+        }
+        "tuple" => {
+            let mut field_types = BTreeMap::new();
+            for field in get_prop_array(obj, "tuple")? {
+                let field_obj = as_object(field)?;
+                let name = ClarityName::try_from(get_prop_string(field_obj, "name")?.to_string())?;
+                if field_types.contains_key(&name) {
+                    return Err(format!("Duplicate tuple field `{}`", name));
+                }
+                let field_type = abi_type_from_json(get_prop(field_obj, "type"), depth + 1)?;
+                field_types.insert(name, field_type);
+            }
+            TypeSignature::Tuple(field_types)
+        }
+        _ => return Err(format!("Unknown ABI type `{}`", key)),
+    };
+    Ok(type_sig)
+}
+
+fn abi_length_from_json(obj: &JsonObject) -> Result<u32, String> {
+    let len = get_prop_number(obj, "length")?;
+    if len.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&len) {
+        return Err("Property `length` must be a u32 integer".to_string());
+    }
+    Ok(len as u32)
+}
+
+/// Build a path for `ClarityValueRef::deserialize_at_path` from either a string such as
+/// `data.items[2]`, or an array of tuple field names and list indexes such as
+/// `["data", "items", 2]`.
+pub fn path_from_json(val: &Value) -> Result<Vec<PathSegment>, String> {
+    let segments = match val {
+        Value::String(path) => return parse_path(path),
+        Value::Array(segments) => segments,
+        _ => return Err("Path must be a string or an array".to_string()),
+    };
+    let mut path = Vec::with_capacity(segments.len());
+    for segment in segments {
+        match segment {
+            Value::String(name) => path.push(PathSegment::Field(name.clone())),
+            Value::Number(index) => {
+                let index = index.as_f64().unwrap_or(f64::NAN);
+                if !(0.0..=u32::MAX as f64).contains(&index) || index.fract() != 0.0 {
+                    return Err(format!("Invalid list index in path: {}", index));
+                }
+                path.push(PathSegment::Index(index as usize));
+            }
+            _ => return Err("Path segments must be strings or numbers".to_string()),
+        }
+    }
+    Ok(path)
+}
+
+/// Read the `merkle_root` and `path` of a proof returned by `getTxMerkleProof`.
+pub fn tx_merkle_proof_from_json(
+    proof: &Value,
+) -> Result<([u8; 32], Vec<MerklePathPoint>), String> {
+    let proof_obj = as_object(proof)?;
+    let merkle_root = get_prop_fixed_bytes::<32>(proof_obj, "merkle_root")?;
+    let points = get_prop_array(proof_obj, "path")?;
+    let mut path = Vec::with_capacity(points.len());
+    for point in points {
+        let point_obj = as_object(point)?;
+        let order = match get_prop_string(point_obj, "order")? {
+            "left" => MerklePathOrder::Left,
+            "right" => MerklePathOrder::Right,
+            order => return Err(format!("Invalid merkle path order: {}", order)),
+        };
+        let hash = get_prop_fixed_bytes::<32>(point_obj, "hash")?;
+        path.push(MerklePathPoint { order, hash });
+    }
+    Ok((merkle_root, path))
+}
+
+/// Read the `{signing_key, weight}` signers of a reward set.
+pub fn reward_set_from_json(reward_set: &Value) -> Result<Vec<RewardSetSigner>, String> {
+    let signers = as_array(reward_set)?;
+    let mut reward_set = Vec::with_capacity(signers.len());
+    for signer in signers {
+        let signer_obj = as_object(signer)?;
+        let signing_key = get_prop_fixed_bytes::<33>(signer_obj, "signing_key")?;
+        let weight = get_prop_number(signer_obj, "weight")?;
+        if weight.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&weight) {
+            return Err("Property `weight` must be a u32 integer".to_string());
+        }
+        reward_set.push(RewardSetSigner {
+            signing_key,
+            weight: weight as u32,
+        });
+    }
+    Ok(reward_set)
+}
+
+/// Build a post-condition from an object in the shape produced by `decodePostConditions`.
+/// The `condition_name` property is ignored.
+pub fn post_condition_from_json(obj: &JsonObject) -> Result<TransactionPostCondition, String> {
+    let asset_info_id = get_prop_u8(obj, "asset_info_id")?;
+    let principal = post_condition_principal_from_json(get_prop_object(obj, "principal")?)?;
+    let condition_code = get_prop_u8(obj, "condition_code")?;
+
+    let post_condition = match asset_info_id {
+        x if x == AssetInfoID::STX as u8 => {
+            let condition_code = fungible_condition_code(condition_code)?;
+            let amount = get_prop_u64(obj, "amount")?;
+            TransactionPostCondition::STX(principal, condition_code, amount)
+        }
+        x if x == AssetInfoID::FungibleAsset as u8 => {
+            let asset = asset_info_from_json(obj)?;
+            let condition_code = fungible_condition_code(condition_code)?;
+            let amount = get_prop_u64(obj, "amount")?;
+            TransactionPostCondition::Fungible(principal, asset, condition_code, amount)
+        }
+        x if x == AssetInfoID::NonfungibleAsset as u8 => {
+            let asset = asset_info_from_json(obj)?;
+            let asset_value =
+                clarity_value_from_json_hex_or_deep(get_prop_object(obj, "asset_value")?)?;
+            let condition_code: NonfungibleConditionCode = condition_code
+                .try_into()
+                .map_err(|_| format!("Invalid NonfungibleConditionCode: {}", condition_code))?;
+            TransactionPostCondition::Nonfungible(principal, asset, asset_value, condition_code)
+        }
+        _ => {
+            return Err(format!(
+                "Unknown post condition asset_info_id {}",
+                asset_info_id
+            ))
+        }
+    };
+    Ok(post_condition)
+}
+
+fn post_condition_principal_from_json(obj: &JsonObject) -> Result<PostConditionPrincipal, String> {
+    let type_id = get_prop_u8(obj, "type_id")?;
+    let principal = match type_id {
+        x if x == PostConditionPrincipalID::Origin as u8 => PostConditionPrincipal::Origin,
+        x if x == PostConditionPrincipalID::Standard as u8 => {
+            PostConditionPrincipal::Standard(stacks_address_from_json(obj, "address")?)
+        }
+        x if x == PostConditionPrincipalID::Contract as u8 => PostConditionPrincipal::Contract(
+            stacks_address_from_json(obj, "address")?,
+            clarity_name_from_json(obj, "contract_name")?,
+        ),
+        _ => {
+            return Err(format!(
+                "Unknown post condition principal type_id {}",
+                type_id
+            ))
+        }
+    };
+    Ok(principal)
+}
+
+fn asset_info_from_json(obj: &JsonObject) -> Result<AssetInfo, String> {
+    let asset_obj = get_prop_object(obj, "asset")?;
+    Ok(AssetInfo {
+        contract_address: stacks_address_from_json(asset_obj, "contract_address")?,
+        contract_name: clarity_name_from_json(asset_obj, "contract_name")?,
+        asset_name: clarity_name_from_json(asset_obj, "asset_name")?,
+    })
+}
+
+fn fungible_condition_code(condition_code: u8) -> Result<FungibleConditionCode, String> {
+    FungibleConditionCode::try_from(condition_code)
+        .map_err(|_| format!("Invalid FungibleConditionCode: {}", condition_code))
+}
+
+/// Read a c32check-encoded Stacks address property.
+fn stacks_address_from_json(obj: &JsonObject, key: &str) -> Result<StacksAddress, String> {
+    StacksAddress::from_string(get_prop_string(obj, key)?)
+}
+
+fn clarity_name_from_json(obj: &JsonObject, key: &str) -> Result<ClarityName, String> {
+    ClarityName::try_from(get_prop_string(obj, key)?.to_string())
+}
+
+/// Build a transaction from an object in the shape produced by `decodeTransaction`.
+/// Properties derived from other fields (`tx_id`, `post_conditions_buffer`,
+/// `function_args_buffer`, microblock header `buffer`, and Clarity `repr`) are ignored.
+pub fn tx_from_json(obj: &JsonObject) -> Result<StacksTransaction, String> {
+    let version = match get_prop_u8(obj, "version")? {
+        x if x == TransactionVersion::Mainnet as u8 => TransactionVersion::Mainnet,
+        x if x == TransactionVersion::Testnet as u8 => TransactionVersion::Testnet,
+        x => return Err(format!("Invalid transaction version {}", x)),
+    };
+    let chain_id = get_prop_u32(obj, "chain_id", "chain_id")?;
+    let auth = tx_auth_from_json(get_prop_object(obj, "auth")?)?;
+
+    let anchor_mode = match get_prop_u8(obj, "anchor_mode")? {
+        x if x == TransactionAnchorMode::OnChainOnly as u8 => TransactionAnchorMode::OnChainOnly,
+        x if x == TransactionAnchorMode::OffChainOnly as u8 => TransactionAnchorMode::OffChainOnly,
+        x if x == TransactionAnchorMode::Any as u8 => TransactionAnchorMode::Any,
+        x => return Err(format!("Invalid anchor mode {}", x)),
+    };
+
+    let post_condition_mode = match get_prop_u8(obj, "post_condition_mode")? {
+        x if x == TransactionPostConditionMode::Allow as u8 => TransactionPostConditionMode::Allow,
+        x if x == TransactionPostConditionMode::Deny as u8 => TransactionPostConditionMode::Deny,
+        x => return Err(format!("Invalid post-condition mode {}", x)),
+    };
+
+    let post_conditions = get_prop_array(obj, "post_conditions")?
+        .iter()
+        .map(|item| post_condition_from_json(as_object(item)?))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let payload = tx_payload_from_json(get_prop_object(obj, "payload")?)?;
+
+    Ok(StacksTransaction {
+        version,
+        chain_id,
+        auth,
+        anchor_mode,
+        post_conditions_serialized: vec![],
+        post_condition_mode,
+        post_conditions,
+        payload,
+    })
+}
+
+fn tx_auth_from_json(obj: &JsonObject) -> Result<TransactionAuth, String> {
+    let type_id = get_prop_u8(obj, "type_id")?;
+    let origin_condition = spending_condition_from_json(get_prop_object(obj, "origin_condition")?)?;
+    let auth = match type_id {
+        x if x == TransactionAuthFlags::AuthStandard as u8 => {
+            TransactionAuth::Standard(origin_condition)
+        }
+        x if x == TransactionAuthFlags::AuthSponsored as u8 => {
+            let sponsor_condition =
+                spending_condition_from_json(get_prop_object(obj, "sponsor_condition")?)?;
+            TransactionAuth::Sponsored(origin_condition, sponsor_condition)
+        }
+        _ => return Err(format!("Unrecognized auth type_id {}", type_id)),
+    };
+    Ok(auth)
+}
+
+fn spending_condition_from_json(obj: &JsonObject) -> Result<TransactionSpendingCondition, String> {
+    let hash_mode_u8 = get_prop_u8(obj, "hash_mode")?;
+    let signer =
+        stacks_address_from_json(get_prop_object(obj, "signer")?, "address")?.hash160_bytes;
+    let nonce = get_prop_u64(obj, "nonce")?;
+    let tx_fee = get_prop_u64(obj, "tx_fee")?;
+
+    if let Some(hash_mode) = SinglesigHashMode::from_u8(hash_mode_u8) {
+        let key_encoding_u8 = get_prop_u8(obj, "key_encoding")?;
+        let key_encoding = TransactionPublicKeyEncoding::from_u8(key_encoding_u8)
+            .ok_or_else(|| format!("Unknown key encoding {}", key_encoding_u8))?;
+        let signature = MessageSignature(get_prop_fixed_bytes(obj, "signature")?);
+        Ok(TransactionSpendingCondition::Singlesig(
+            SinglesigSpendingCondition {
+                hash_mode,
+                signer,
+                nonce,
+                tx_fee,
+                key_encoding,
+                signature,
+            },
+        ))
+    } else if let Some(hash_mode) = MultisigHashMode::from_u8(hash_mode_u8) {
+        let fields = get_prop_array(obj, "fields")?
+            .iter()
+            .map(|item| auth_field_from_json(as_object(item)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        let signatures_required = get_prop_number(obj, "signatures_required")?;
+        if signatures_required.fract() != 0.0
+            || !(0.0..=u16::MAX as f64).contains(&signatures_required)
+        {
+            return Err(format!(
+                "Invalid signatures_required {}",
+                signatures_required
+            ));
+        }
+        Ok(TransactionSpendingCondition::Multisig(
+            MultisigSpendingCondition {
+                hash_mode,
+                signer,
+                nonce,
+                tx_fee,
+                fields,
+                signatures_required: signatures_required as u16,
+            },
+        ))
+    } else {
+        Err(format!(
+            "Invalid spending condition hash mode {}",
+            hash_mode_u8
+        ))
+    }
+}
+
+fn auth_field_from_json(obj: &JsonObject) -> Result<TransactionAuthField, String> {
+    let type_id = get_prop_u8(obj, "type_id")?;
+    let field = match type_id {
+        x if x == TransactionAuthFieldID::PublicKeyCompressed as u8
+            || x == TransactionAuthFieldID::PublicKeyUncompressed as u8 =>
+        {
+            let key = StacksPublicKeyBuffer(get_prop_fixed_bytes(obj, "public_key")?);
+            TransactionAuthField::PublicKey(Secp256k1PublicKey {
+                key,
+                compressed: x == TransactionAuthFieldID::PublicKeyCompressed as u8,
+            })
+        }
+        x if x == TransactionAuthFieldID::SignatureCompressed as u8 => {
+            let sig = MessageSignature(get_prop_fixed_bytes(obj, "signature")?);
+            TransactionAuthField::Signature(TransactionPublicKeyEncoding::Compressed, sig)
+        }
+        x if x == TransactionAuthFieldID::SignatureUncompressed as u8 => {
+            let sig = MessageSignature(get_prop_fixed_bytes(obj, "signature")?);
+            TransactionAuthField::Signature(TransactionPublicKeyEncoding::Uncompressed, sig)
+        }
+        _ => return Err(format!("Unknown auth field type_id {}", type_id)),
+    };
+    Ok(field)
+}
+
+fn tx_payload_from_json(obj: &JsonObject) -> Result<TransactionPayload, String> {
+    let type_id = get_prop_u8(obj, "type_id")?;
+    let payload = match type_id {
+        x if x == TransactionPayloadID::TokenTransfer as u8 => {
+            let recipient = principal_data_from_json(obj, "recipient")?;
+            let amount = get_prop_u64(obj, "amount")?;
+            // shorter memos are right-padded with zero bytes
+            let memo_bytes = get_prop_bytes(obj, "memo_hex")?;
+            if memo_bytes.len() > 34 {
+                return Err(format!(
+                    "Token transfer memo must be at most 34 bytes, received {} bytes",
+                    memo_bytes.len()
+                ));
+            }
+            let mut memo = [0u8; 34];
+            memo[..memo_bytes.len()].copy_from_slice(&memo_bytes);
+            TransactionPayload::TokenTransfer(recipient, amount, TokenTransferMemo(memo))
+        }
+        x if x == TransactionPayloadID::ContractCall as u8 => {
+            let address = stacks_address_from_json(obj, "address")?;
+            let contract_name = clarity_name_from_json(obj, "contract_name")?;
+            let function_name = clarity_name_from_json(obj, "function_name")?;
+            let args = get_prop_array(obj, "function_args")?;
+            let mut function_args = Vec::with_capacity(args.len());
+            for (i, item) in args.iter().enumerate() {
+                let arg = clarity_value_from_json_hex_or_deep(as_object(item)?)?;
+                function_args.push(
+                    SerializedClarityValue::from_value(&arg.value)
+                        .map_err(|e| format!("Invalid function arg {}: {}", i, e))?,
+                );
+            }
+            TransactionPayload::ContractCall(TransactionContractCall {
+                address,
+                contract_name,
+                function_name,
+                function_args,
+            })
+        }
+        x if x == TransactionPayloadID::SmartContract as u8 => {
+            TransactionPayload::SmartContract(smart_contract_from_json(obj)?)
+        }
+        x if x == TransactionPayloadID::PoisonMicroblock as u8 => {
+            let h1 = microblock_header_from_json(get_prop_object(obj, "microblock_header_1")?)?;
+            let h2 = microblock_header_from_json(get_prop_object(obj, "microblock_header_2")?)?;
+            TransactionPayload::PoisonMicroblock(h1, h2)
+        }
+        x if x == TransactionPayloadID::Coinbase as u8 => {
+            let payload = CoinbasePayload(get_prop_fixed_bytes(obj, "payload_buffer")?);
+            TransactionPayload::Coinbase(payload)
+        }
+        x if x == TransactionPayloadID::CoinbaseToAltRecipient as u8 => {
+            let payload = CoinbasePayload(get_prop_fixed_bytes(obj, "payload_buffer")?);
+            let recipient = principal_data_from_json(obj, "recipient")?;
+            TransactionPayload::CoinbaseToAltRecipient(payload, recipient)
+        }
+        x if x == TransactionPayloadID::VersionedSmartContract as u8 => {
+            let clarity_version_u8 = get_prop_u8(obj, "clarity_version")?;
+            let clarity_version = ClarityVersion::from_u8(clarity_version_u8)
+                .ok_or_else(|| format!("Unknown Clarity version {}", clarity_version_u8))?;
+            let smart_contract = smart_contract_from_json(obj)?;
+            TransactionPayload::VersionedSmartContract(smart_contract, clarity_version)
+        }
+        x if x == TransactionPayloadID::TenureChange as u8 => {
+            let cause_u8 = get_prop_u8(obj, "cause")?;
+            let cause = TenureChangeCause::from_u8(cause_u8)
+                .ok_or_else(|| format!("Invalid tenure change cause {}", cause_u8))?;
+            TransactionPayload::TenureChange(TransactionTenureChange {
+                tenure_consensus_hash: get_prop_fixed_bytes(obj, "tenure_consensus_hash")?,
+                prev_tenure_consensus_hash: get_prop_fixed_bytes(
+                    obj,
+                    "prev_tenure_consensus_hash",
+                )?,
+                burn_view_consensus_hash: get_prop_fixed_bytes(obj, "burn_view_consensus_hash")?,
+                previous_tenure_end: get_prop_fixed_bytes(obj, "previous_tenure_end")?,
+                previous_tenure_blocks: get_prop_u32(
+                    obj,
+                    "previous_tenure_blocks",
+                    "previous_tenure_blocks",
+                )?,
+                cause,
+                pubkey_hash: get_prop_fixed_bytes(obj, "pubkey_hash")?,
+            })
+        }
+        x if x == TransactionPayloadID::NakamotoCoinbase as u8 => {
+            let payload = CoinbasePayload(get_prop_fixed_bytes(obj, "payload_buffer")?);
+            let recipient = match get_prop(obj, "recipient") {
+                Value::Null => None,
+                _ => Some(principal_data_from_json(obj, "recipient")?),
+            };
+            let vrf_proof: [u8; 80] = get_prop_fixed_bytes(obj, "vrf_proof")?;
+            TransactionPayload::NakamotoCoinbase(payload, recipient, VRFProof(vrf_proof.to_vec()))
+        }
+        _ => return Err(format!("Unknown payload type_id {}", type_id)),
+    };
+    Ok(payload)
+}
+
+fn smart_contract_from_json(obj: &JsonObject) -> Result<TransactionSmartContract, String> {
+    Ok(TransactionSmartContract {
+        name: clarity_name_from_json(obj, "contract_name")?,
+        code_body: StacksString(get_prop_string(obj, "code_body")?.as_bytes().to_vec()),
+    })
+}
+
+fn microblock_header_from_json(obj: &JsonObject) -> Result<StacksMicroblockHeader, String> {
+    let version = get_prop_u8(obj, "version")?;
+    let sequence = get_prop_number(obj, "sequence")?;
+    if sequence.fract() != 0.0 || !(0.0..=u16::MAX as f64).contains(&sequence) {
+        return Err(format!("Invalid microblock sequence {}", sequence));
+    }
+    Ok(StacksMicroblockHeader {
+        version,
+        sequence: sequence as u16,
+        prev_block: BlockHeaderHash(get_prop_fixed_bytes(obj, "prev_block")?),
+        tx_merkle_root: Sha512Trunc256Sum(get_prop_fixed_bytes(obj, "tx_merkle_root")?),
+        signature: MessageSignature(get_prop_fixed_bytes(obj, "signature")?),
+        serialized_bytes: vec![],
+    })
+}
+
+fn principal_data_from_json(obj: &JsonObject, key: &str) -> Result<PrincipalData, String> {
+    let principal_obj = get_prop_object(obj, key)?;
+    let type_id = get_prop_u8(principal_obj, "type_id")?;
+    let (version, hash160) = c32_address_decode(get_prop_string(principal_obj, "address")?)
+        .map_err(|e| format!("Error parsing Stacks address {}", e))?;
+    let issuer = StandardPrincipalData(version, hash160);
+    match TypePrefix::from_u8(type_id) {
+        Some(TypePrefix::PrincipalStandard) => Ok(PrincipalData::Standard(issuer)),
+        Some(TypePrefix::PrincipalContract) => {
+            Ok(PrincipalData::Contract(QualifiedContractIdentifier {
+                issuer,
+                name: clarity_name_from_json(principal_obj, "contract_name")?,
+            }))
+        }
+        _ => Err(format!("Bad principal type_id {}", type_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::{decode_clarity_value, transaction_to_json};
+    use crate::stacks_tx::decode_tx_bytes;
+    use serde_json::json;
+
+    #[test]
+    fn test_tx_round_trip() {
+        // contract call with principal, uint and optional function args
+        let tx_bytes = decode_hex("0x00000000010400b1907a5f15e25dfbb7487a032d37f61205d6127600000000000001ba0000000000002710000004fbc868d0ca4a9145f43a396c796bcbb2888a5519afe08ab80afc3e1d4f86607e66f795e59efa824c12782870f8fa29aaf03eb0380fde6e70b8bbf2e2a5f8c8030200000002010216b1907a5f15e25dfbb7487a032d37f61205d6127616e685b016b3b6cd9ebf35f38e5ae29392e2acd51d176167653030302d676f7665726e616e63652d746f6b656e04616c65780100000012f2fd9506000316e685b016b3b6cd9ebf35f38e5ae29392e2acd51d0a616c65782d7661756c7403000000002c9ad3150216e685b016b3b6cd9ebf35f38e5ae29392e2acd51d1766697865642d7765696768742d706f6f6c2d76312d30310b737761702d68656c706572000000060616e685b016b3b6cd9ebf35f38e5ae29392e2acd51d176167653030302d676f7665726e616e63652d746f6b656e0616e685b016b3b6cd9ebf35f38e5ae29392e2acd51d0a746f6b656e2d777374780100000000000000000000000002faf0800100000000000000000000000002faf08001000000000000000000000012f2fd95060a010000000000000000000000116c7a7446").unwrap();
+        let (tx, _) = decode_tx_bytes(&tx_bytes).unwrap();
+        let tx_json = transaction_to_json(&tx, OutputOptions::default()).unwrap();
+        let decoded = tx_from_json(tx_json.as_object().unwrap()).unwrap();
+        assert_eq!(decoded.serialize_to_vec().unwrap(), tx_bytes.to_vec());
+    }
+
+    #[test]
+    fn test_clarity_value_from_json() {
+        // (tuple (a u1) (b (some "x")))
+        let hex = "0x0c000000020161010000000000000000000000000000000101620a0d0000000178";
+        let decoded = decode_clarity_value(
+            &decode_hex(hex).unwrap(),
+            DecodeClarityValueOptions::default(),
+        )
+        .unwrap();
+        let value = clarity_value_from_json(decoded.as_object().unwrap()).unwrap();
+        assert_eq!(value.repr_string(), "(tuple (a u1) (b (some \"x\")))");
+
+        let err = clarity_value_from_json(json!({"type_id": 99}).as_object().unwrap());
+        assert_eq!(err.unwrap_err(), "Bad Clarity type_id: 99");
+    }
+
+    #[test]
+    fn test_type_signature_from_json() {
+        let abi = json!({"list": {"type": {"optional": "uint128"}, "length": 5}});
+        let from_abi = type_signature_from_json(&abi).unwrap();
+        let from_str = type_signature_from_json(&json!("(list 5 (optional uint))")).unwrap();
+        assert_eq!(from_abi, from_str);
+        assert_eq!(
+            type_signature_from_json(&json!({"foo": 1})).unwrap_err(),
+            "Unknown ABI type `foo`"
+        );
+    }
+
+    #[test]
+    fn test_path_from_json() {
+        assert_eq!(
+            path_from_json(&json!(["data", 2])).unwrap(),
+            vec![
+                PathSegment::Field("data".to_string()),
+                PathSegment::Index(2)
+            ]
+        );
+        assert_eq!(
+            path_from_json(&json!(["data", -1])).unwrap_err(),
+            "Invalid list index in path: -1"
+        );
+    }
+}
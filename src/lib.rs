@@ -1,38 +1,77 @@
 use git_version::git_version;
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
 #[cfg(feature = "profiling")]
 use neon::types::buffer::TypedArray;
 
+#[cfg(feature = "nodejs")]
 use crate::address::{
     bitcoin_to_stacks_address, decode_clarity_value_to_principal, decode_stacks_address,
-    is_valid_stacks_address, stacks_address_from_parts, stacks_to_bitcoin_address,
+    is_valid_stacks_address, multisig_to_stacks_address, public_key_to_stacks_address,
+    stacks_address_from_parts, stacks_to_bitcoin_address,
 };
+#[cfg(feature = "nodejs")]
 use crate::clarity_value::{
-    decode_clarity_value, decode_clarity_value_array, decode_clarity_value_to_repr,
-    decode_clarity_value_type_name,
+    decode_clarity_value, decode_clarity_value_array, decode_clarity_value_at_path,
+    decode_clarity_value_to_native, decode_clarity_value_to_repr, decode_clarity_value_type_name,
+    decode_clarity_value_with_type, encode_clarity_value, parse_clarity_repr,
 };
+#[cfg(feature = "nodejs")]
 use crate::memo::memo_to_string;
-use crate::pox_events::decode_pox_event;
+#[cfg(feature = "nodejs")]
+use crate::neon_util::deserialize_error_class;
+#[cfg(feature = "nodejs")]
+use crate::new_block::decode_new_block_message;
+#[cfg(feature = "nodejs")]
 use crate::post_condition::decode_tx_post_conditions;
-use crate::stacks_block::{decode_nakamoto_block, decode_stacks_block};
-use crate::stacks_tx::decode_transaction;
+#[cfg(feature = "nodejs")]
+use crate::pox_events::decode_pox_event;
+#[cfg(feature = "nodejs")]
+use crate::stacks_block::{
+    decode_microblock, decode_microblock_stream, decode_nakamoto_block,
+    decode_nakamoto_block_async, decode_stacks_block, decode_stacks_block_async,
+    get_tx_merkle_proof, verify_nakamoto_block_miner_signature,
+    verify_nakamoto_block_signer_signatures, verify_tx_merkle_proof,
+};
+#[cfg(feature = "nodejs")]
+use crate::stacks_tx::{
+    decode_transaction, decode_transaction_async, decode_transaction_list, decode_transactions,
+    encode_transaction, verify_transaction,
+};
+#[cfg(feature = "nodejs")]
+use crate::token_events::{decode_sip010_memo, decode_sip010_transfer, decode_token_event_object};
 
 pub mod address;
 pub mod clarity_value;
+pub mod crypto;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod hex;
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub mod json;
+#[cfg(all(feature = "serde", feature = "serde_json"))]
+pub mod json_decoder;
 pub mod memo;
+#[cfg(feature = "nodejs")]
 pub mod neon_util;
-pub mod pox_events;
+#[cfg(feature = "nodejs")]
+pub mod new_block;
+pub mod output_options;
 pub mod post_condition;
+pub mod pox_events;
 pub mod serialize_util;
 pub mod stacks_block;
 pub mod stacks_tx;
+pub mod token_events;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-const GIT_VERSION: &str = git_version!(
+pub const GIT_VERSION: &str = git_version!(
     args = ["--all", "--long", "--always"],
     fallback = "unavailable"
 );
 
+#[cfg(feature = "nodejs")]
 fn get_version(mut cx: FunctionContext) -> JsResult<JsString> {
     let version = cx.string(GIT_VERSION);
     Ok(version)
@@ -109,20 +148,47 @@ fn stop_profiler(mut cx: FunctionContext) -> JsResult<JsBuffer> {
     Ok(result)
 }
 
+#[cfg(feature = "nodejs")]
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("getVersion", get_version)?;
+    let deserialize_error = deserialize_error_class(&mut cx)?;
+    cx.export_value("DeserializeError", deserialize_error)?;
     cx.export_function("decodeClarityValueToRepr", decode_clarity_value_to_repr)?;
     cx.export_function(
         "decodeClarityValueToTypeName",
         decode_clarity_value_type_name,
     )?;
     cx.export_function("decodeClarityValue", decode_clarity_value)?;
+    cx.export_function("decodeClarityValueWithType", decode_clarity_value_with_type)?;
     cx.export_function("decodeClarityValueList", decode_clarity_value_array)?;
+    cx.export_function("decodeClarityValueAtPath", decode_clarity_value_at_path)?;
+    cx.export_function("decodeClarityValueToNative", decode_clarity_value_to_native)?;
+    cx.export_function("encodeClarityValue", encode_clarity_value)?;
+    cx.export_function("parseClarityRepr", parse_clarity_repr)?;
     cx.export_function("decodePostConditions", decode_tx_post_conditions)?;
     cx.export_function("decodeTransaction", decode_transaction)?;
+    cx.export_function("decodeTransactionAsync", decode_transaction_async)?;
+    cx.export_function("decodeTransactions", decode_transactions)?;
+    cx.export_function("decodeTransactionList", decode_transaction_list)?;
+    cx.export_function("encodeTransaction", encode_transaction)?;
+    cx.export_function("verifyTransaction", verify_transaction)?;
     cx.export_function("decodeNakamotoBlock", decode_nakamoto_block)?;
+    cx.export_function("decodeNakamotoBlockAsync", decode_nakamoto_block_async)?;
     cx.export_function("decodeStacksBlock", decode_stacks_block)?;
+    cx.export_function("decodeStacksBlockAsync", decode_stacks_block_async)?;
+    cx.export_function("decodeMicroblock", decode_microblock)?;
+    cx.export_function("decodeMicroblockStream", decode_microblock_stream)?;
+    cx.export_function("getTxMerkleProof", get_tx_merkle_proof)?;
+    cx.export_function("verifyTxMerkleProof", verify_tx_merkle_proof)?;
+    cx.export_function(
+        "verifyNakamotoBlockSignerSignatures",
+        verify_nakamoto_block_signer_signatures,
+    )?;
+    cx.export_function(
+        "verifyNakamotoBlockMinerSignature",
+        verify_nakamoto_block_miner_signature,
+    )?;
     cx.export_function("stacksToBitcoinAddress", stacks_to_bitcoin_address)?;
     cx.export_function("bitcoinToStacksAddress", bitcoin_to_stacks_address)?;
     cx.export_function("isValidStacksAddress", is_valid_stacks_address)?;
@@ -132,8 +198,14 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
         decode_clarity_value_to_principal,
     )?;
     cx.export_function("stacksAddressFromParts", stacks_address_from_parts)?;
+    cx.export_function("publicKeyToStacksAddress", public_key_to_stacks_address)?;
+    cx.export_function("multisigToStacksAddress", multisig_to_stacks_address)?;
     cx.export_function("memoToString", memo_to_string)?;
     cx.export_function("decodePoxSyntheticEvent", decode_pox_event)?;
+    cx.export_function("decodeNewBlockMessage", decode_new_block_message)?;
+    cx.export_function("decodeTokenEvent", decode_token_event_object)?;
+    cx.export_function("decodeSip010TransferArgs", decode_sip010_transfer)?;
+    cx.export_function("decodeSip010PrintMemo", decode_sip010_memo)?;
 
     #[cfg(feature = "profiling")]
     {
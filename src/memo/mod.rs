@@ -1,13 +1,15 @@
 use lazy_static::lazy_static;
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
 use regex::Regex;
 use unicode_segmentation::UnicodeSegmentation;
 
+#[cfg(feature = "nodejs")]
 use crate::neon_util::arg_as_bytes;
 
 mod unicode_printable;
 
-fn memo_normalize<T: AsRef<[u8]>>(input: T) -> String {
+pub fn memo_normalize<T: AsRef<[u8]>>(input: T) -> String {
     let memo_str = String::from_utf8_lossy(input.as_ref());
     let mut result_str: String = String::with_capacity(memo_str.len());
     for g in memo_str.graphemes(true) {
@@ -32,9 +34,10 @@ fn memo_normalize<T: AsRef<[u8]>>(input: T) -> String {
     memo_no_invalid.to_string()
 }
 
+#[cfg(feature = "nodejs")]
 pub fn memo_to_string(mut cx: FunctionContext) -> JsResult<JsString> {
     let normalized = arg_as_bytes(&mut cx, 0, |input_bytes| Ok(memo_normalize(input_bytes)))
-        .or_else(|e| cx.throw_error(e))?;
+        .or_else(|e: String| cx.throw_error(e))?;
     let str_result = cx.string(normalized);
     Ok(str_result)
 }
@@ -1,11 +1,19 @@
-use neon::{prelude::*, types::buffer::TypedArray};
+use std::convert::TryInto;
+
+use neon::{
+    prelude::*,
+    thread::LocalKey,
+    types::{buffer::TypedArray, JsBigInt},
+};
 
 use crate::hex::decode_hex;
+pub use crate::output_options::{NumberFormat, OutputOptions, OutputProfile};
+use crate::serialize_util::DeserializeError;
 
 pub trait NeonJsSerialize<ExtraCtx = (), TResult = ()> {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &ExtraCtx,
     ) -> NeonResult<TResult>;
@@ -53,8 +61,170 @@ pub fn json_parse<'a, C: Context<'a>, S: AsRef<str>>(
     Ok(result)
 }
 
+static DESERIALIZE_ERROR_CLASS: LocalKey<Root<JsFunction>> = LocalKey::new();
+
+/// The `DeserializeError` JS class, an `Error` subclass carrying the `code`, `offset` and `path`
+/// of a `serialize_util::DeserializeError`. Created once per JS environment.
+pub fn deserialize_error_class<'a, C: Context<'a>>(cx: &mut C) -> JsResult<'a, JsFunction> {
+    let class = DESERIALIZE_ERROR_CLASS.get_or_try_init(cx, |cx| {
+        let script = cx.string(
+            "(class DeserializeError extends Error {
+                constructor(message, code, offset, path) {
+                    super(message);
+                    this.name = 'DeserializeError';
+                    this.code = code;
+                    this.offset = offset;
+                    this.path = path;
+                }
+            })",
+        );
+        let class = neon::reflect::eval(cx, script)?.downcast_or_throw::<JsFunction, _>(cx)?;
+        NeonResult::Ok(class.root(cx))
+    })?;
+    Ok(class.to_inner(cx))
+}
+
+/// Construct a `DeserializeError` JS object with the message prefixed by `context`, e.g.
+/// "Failed to decode transaction". Used for per-item errors in batch results.
+pub fn deserialize_error_to_js<'a, C: Context<'a>>(
+    cx: &mut C,
+    context: &str,
+    err: &DeserializeError,
+) -> JsResult<'a, JsObject> {
+    let class = deserialize_error_class(cx)?;
+    let message = cx.string(format!("{}: {}", context, err));
+    let code = cx.string(err.code());
+    let offset: Handle<JsValue> = match err.offset {
+        Some(offset) => cx.number(offset as f64).upcast(),
+        None => cx.null().upcast(),
+    };
+    let path = cx.string(err.path_string());
+    class
+        .construct_with(cx)
+        .arg(message)
+        .arg(code)
+        .arg(offset)
+        .arg(path)
+        .apply(cx)
+}
+
+/// Throw a `DeserializeError` JS exception, see `deserialize_error_to_js`.
+pub fn throw_deserialize_error<'a, C: Context<'a>, T>(
+    cx: &mut C,
+    context: &str,
+    err: &DeserializeError,
+) -> NeonResult<T> {
+    let error = deserialize_error_to_js(cx, context, err)?;
+    cx.throw(error)
+}
+
+impl NumberFormat {
+    /// Read the optional `numbers` property of an options object.
+    pub fn from_options(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<Self> {
+        NumberFormat::from_options_or(cx, obj, NumberFormat::String)
+    }
+
+    /// Like `from_options`, for a function with a different default.
+    pub fn from_options_or(
+        cx: &mut FunctionContext,
+        obj: &Handle<JsObject>,
+        default: NumberFormat,
+    ) -> NeonResult<Self> {
+        match get_obj_prop_opt::<JsString>(cx, obj, "numbers")? {
+            None => Ok(default),
+            Some(numbers) => match numbers.value(cx).as_str() {
+                "string" => Ok(NumberFormat::String),
+                "bigint" => Ok(NumberFormat::BigInt),
+                other => cx.throw_error(format!("Unknown numbers format `{}`", other)),
+            },
+        }
+    }
+
+    /// Read the `numbers` property of an optional options argument.
+    pub fn from_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<Self> {
+        match arg_options_obj(cx, arg_index)? {
+            Some(obj) => NumberFormat::from_options(cx, &obj),
+            None => Ok(NumberFormat::String),
+        }
+    }
+
+    pub fn int<'cx, C: Context<'cx>>(self, cx: &mut C, val: i128) -> Handle<'cx, JsValue> {
+        match self {
+            NumberFormat::String => cx.string(val.to_string()).upcast(),
+            NumberFormat::BigInt => JsBigInt::from_i128(cx, val).upcast(),
+        }
+    }
+
+    pub fn uint<'cx, C: Context<'cx>>(self, cx: &mut C, val: u128) -> Handle<'cx, JsValue> {
+        match self {
+            NumberFormat::String => cx.string(val.to_string()).upcast(),
+            NumberFormat::BigInt => JsBigInt::from_u128(cx, val).upcast(),
+        }
+    }
+}
+
+impl OutputOptions {
+    /// Read the optional `numbers` and `profile` properties of an options object.
+    pub fn from_options(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<Self> {
+        let numbers = NumberFormat::from_options(cx, obj)?;
+        let profile = match get_obj_prop_opt::<JsString>(cx, obj, "profile")? {
+            None => OutputProfile::Full,
+            Some(profile) => match profile.value(cx).as_str() {
+                "full" => OutputProfile::Full,
+                "minimal" => OutputProfile::Minimal,
+                "json" => OutputProfile::Json,
+                other => return cx.throw_error(format!("Unknown profile `{}`", other)),
+            },
+        };
+        Ok(OutputOptions { numbers, profile })
+    }
+
+    /// Read the output options of an optional options argument.
+    pub fn from_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<Self> {
+        match arg_options_obj(cx, arg_index)? {
+            Some(obj) => OutputOptions::from_options(cx, &obj),
+            None => Ok(OutputOptions::default()),
+        }
+    }
+}
+
+/// Get an optional options object argument, returning `None` if it is not given or undefined.
+pub fn arg_options_obj<'a>(
+    cx: &mut FunctionContext<'a>,
+    arg_index: usize,
+) -> NeonResult<Option<Handle<'a, JsObject>>> {
+    match cx.argument_opt(arg_index) {
+        Some(arg) if !arg.is_a::<JsUndefined, _>(cx) => {
+            Ok(Some(arg.downcast_or_throw::<JsObject, _>(cx)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Read a number argument that must be an integer 0-`max`, e.g. a hash mode.
+pub fn arg_as_uint(
+    cx: &mut FunctionContext,
+    arg_index: usize,
+    name: &str,
+    max: u32,
+) -> NeonResult<u32> {
+    let num = cx.argument::<JsNumber>(arg_index)?.value(cx);
+    if num.fract() != 0.0 || !(0.0..=max as f64).contains(&num) {
+        return cx.throw_type_error(format!("`{}` must be an integer 0-{}", name, max));
+    }
+    Ok(num as u32)
+}
+
 pub fn arg_as_bytes_copied(cx: &mut FunctionContext, arg_index: i32) -> NeonResult<Box<[u8]>> {
     let input_arg: Handle<JsValue> = cx.argument(arg_index as usize)?;
+    value_as_bytes_copied(cx, input_arg)
+}
+
+/// Copy the bytes of a hex string or Buffer value, e.g. an element of an array argument.
+pub fn value_as_bytes_copied(
+    cx: &mut FunctionContext,
+    input_arg: Handle<JsValue>,
+) -> NeonResult<Box<[u8]>> {
     if let Ok(handle) = input_arg.downcast::<JsString, _>(cx) {
         let val_bytes = decode_hex(handle.value(cx))
             .or_else(|e| cx.throw_error(format!("Hex parsing error: {}", e)))?;
@@ -68,9 +238,10 @@ pub fn arg_as_bytes_copied(cx: &mut FunctionContext, arg_index: i32) -> NeonResu
     }
 }
 
-pub fn arg_as_bytes<F, T>(cx: &mut FunctionContext, arg_index: i32, cb: F) -> Result<T, String>
+pub fn arg_as_bytes<F, T, E>(cx: &mut FunctionContext, arg_index: i32, cb: F) -> Result<T, E>
 where
-    F: Fn(&[u8]) -> Result<T, String>,
+    F: Fn(&[u8]) -> Result<T, E>,
+    E: From<String>,
 {
     let input_arg: Handle<JsValue> = cx
         .argument(arg_index as usize)
@@ -83,6 +254,142 @@ where
         let slice = handle.as_slice(cx);
         cb(slice)
     } else {
-        Err("Argument must be a hex string or a Buffer".to_string())
+        Err(String::from("Argument must be a hex string or a Buffer").into())
+    }
+}
+
+pub fn get_obj_prop<'a, V: Value>(
+    cx: &mut FunctionContext<'a>,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Handle<'a, V>> {
+    let prop: Handle<JsValue> = obj.get_value(cx, key)?;
+    prop.downcast::<V, _>(cx)
+        .or_else(|_| cx.throw_type_error(format!("Unexpected type for property `{}`", key)))
+}
+
+/// Read an optional property, returning `None` if it is undefined or null.
+pub fn get_obj_prop_opt<'a, V: Value>(
+    cx: &mut FunctionContext<'a>,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Option<Handle<'a, V>>> {
+    let prop: Handle<JsValue> = obj.get_value(cx, key)?;
+    if prop.is_a::<JsUndefined, _>(cx) || prop.is_a::<JsNull, _>(cx) {
+        return Ok(None);
     }
+    prop.downcast::<V, _>(cx)
+        .map(Some)
+        .or_else(|_| cx.throw_type_error(format!("Unexpected type for property `{}`", key)))
+}
+
+pub fn get_obj_prop_string(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<String> {
+    let prop = get_obj_prop::<JsString>(cx, obj, key)?;
+    Ok(prop.value(cx))
+}
+
+pub fn get_obj_prop_number(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<f64> {
+    let prop = get_obj_prop::<JsNumber>(cx, obj, key)?;
+    Ok(prop.value(cx))
+}
+
+/// Read an integer property that may be given as a string-quoted integer, a number or a bigint.
+pub fn get_obj_prop_integer_string(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<String> {
+    let prop: Handle<JsValue> = obj.get_value(cx, key)?;
+    if let Ok(handle) = prop.downcast::<JsString, _>(cx) {
+        Ok(handle.value(cx))
+    } else if let Ok(handle) = prop.downcast::<JsNumber, _>(cx) {
+        let num = handle.value(cx);
+        if num.fract() != 0.0 || !num.is_finite() {
+            return cx.throw_type_error(format!("Property `{}` must be an integer", key));
+        }
+        Ok(format!("{:.0}", num))
+    } else if let Ok(handle) = prop.downcast::<JsBigInt, _>(cx) {
+        // Values decoded with `numbers: 'bigint'` can be passed back to the encoders
+        match handle.to_u128(cx) {
+            Ok(num) => Ok(num.to_string()),
+            Err(_) => match handle.to_i128(cx) {
+                Ok(num) => Ok(num.to_string()),
+                Err(_) => cx.throw_range_error(format!("Property `{}` is out of range", key)),
+            },
+        }
+    } else {
+        cx.throw_type_error(format!(
+            "Property `{}` must be a string-quoted integer, a number or a bigint",
+            key
+        ))
+    }
+}
+
+/// Read a property that may be given as a hex string or as a Buffer.
+pub fn get_obj_prop_bytes(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Box<[u8]>> {
+    let prop: Handle<JsValue> = obj.get_value(cx, key)?;
+    if let Ok(handle) = prop.downcast::<JsString, _>(cx) {
+        decode_hex(handle.value(cx))
+            .or_else(|e| cx.throw_error(format!("Hex parsing error for property `{}`: {}", key, e)))
+    } else if let Ok(handle) = prop.downcast::<JsBuffer, _>(cx) {
+        Ok(handle.as_slice(cx).into())
+    } else {
+        cx.throw_type_error(format!(
+            "Property `{}` must be a hex string or a Buffer",
+            key
+        ))
+    }
+}
+
+/// Read a hex string or Buffer property that must be exactly `N` bytes long.
+pub fn get_obj_prop_fixed_bytes<const N: usize>(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<[u8; N]> {
+    let bytes = get_obj_prop_bytes(cx, obj, key)?;
+    let len = bytes.len();
+    bytes.as_ref().try_into().or_else(|_| {
+        cx.throw_error(format!(
+            "Property `{}` must be {} bytes, received {} bytes",
+            key, N, len
+        ))
+    })
+}
+
+/// Read a number property that must be an integer in the range of `u8`, e.g. a type ID.
+pub fn get_obj_prop_u8(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<u8> {
+    let num = get_obj_prop_number(cx, obj, key)?;
+    if num.fract() != 0.0 || !(0.0..=255.0).contains(&num) {
+        return cx.throw_type_error(format!("Property `{}` must be an integer 0-255", key));
+    }
+    Ok(num as u8)
+}
+
+/// Read a u64 property given as a string-quoted integer, a number or a bigint, e.g. a nonce.
+pub fn get_obj_prop_u64(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<u64> {
+    let val_str = get_obj_prop_integer_string(cx, obj, key)?;
+    val_str
+        .parse::<u64>()
+        .or_else(|e| cx.throw_error(format!("Invalid `{}` value `{}`: {}", key, val_str, e)))
 }
@@ -0,0 +1,207 @@
+use std::io::Cursor;
+
+use neon::prelude::*;
+use rayon::prelude::*;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::clarity_value::neon_encoder::decode_clarity_val;
+use crate::clarity_value::value_ref::ClarityValueRef;
+use crate::hex::decode_hex;
+use crate::neon_util::{throw_deserialize_error, OutputOptions};
+use crate::serialize_util::DeserializeError;
+use crate::stacks_tx::{decode_tx_bytes, tx_to_js, DecodedTx};
+
+const ERROR_CONTEXT: &str = "Failed to decode new block message";
+
+/// Decode a stacks-node event observer `/new_block` message, given as a JSON string.
+///
+/// The message is returned as-is, with the decoded value added next to each serialized one:
+/// `decoded_tx` for `transactions[].raw_tx` (null for the `0x00` of burnchain operations),
+/// `decoded_result` for `transactions[].raw_result`, and `decoded_value` for the `raw_value` of
+/// each `events[].*_event` object (contract events and NFT events).
+pub fn decode_new_block_message(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let message_json = cx.argument::<JsString>(0)?.value(&mut cx);
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let message: Map<String, JsonValue> = serde_json::from_str(&message_json)
+        .or_else(|e| cx.throw_error(format!("Error parsing new block message JSON: {}", e)))?;
+
+    let transactions = match message.get("transactions") {
+        Some(JsonValue::Array(transactions)) => transactions.as_slice(),
+        _ => &[],
+    };
+    // The transactions are the bulk of the work, deserialize them in parallel
+    let decoded_txs: Vec<Result<Option<DecodedTx>, DeserializeError>> = transactions
+        .par_iter()
+        .enumerate()
+        .map(|(i, tx)| {
+            decode_raw_tx(tx.get("raw_tx"))
+                .map_err(|e| e.in_field("raw_tx").in_element("transactions", i))
+        })
+        .collect();
+
+    let result = cx.empty_object();
+    for (key, value) in &message {
+        let js_value = match (key.as_str(), value) {
+            ("transactions", JsonValue::Array(transactions)) => {
+                let array = JsArray::new(&mut cx, transactions.len());
+                for (i, (tx, decoded_tx)) in transactions.iter().zip(&decoded_txs).enumerate() {
+                    let tx_obj = json_to_js(&mut cx, tx)?;
+                    let tx_obj = tx_obj.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                    let decoded_tx: Handle<JsValue> = match decoded_tx {
+                        Ok(Some((tx, tx_id))) => tx_to_js(&mut cx, tx, tx_id, options)?.upcast(),
+                        Ok(None) => cx.null().upcast(),
+                        Err(e) => return throw_deserialize_error(&mut cx, ERROR_CONTEXT, e),
+                    };
+                    tx_obj.set(&mut cx, "decoded_tx", decoded_tx)?;
+                    if let Some(raw_result) = tx.get("raw_result") {
+                        let decoded_result =
+                            raw_clarity_value_to_js(&mut cx, raw_result, options, |e| {
+                                e.in_field("raw_result").in_element("transactions", i)
+                            })?;
+                        tx_obj.set(&mut cx, "decoded_result", decoded_result)?;
+                    }
+                    array.set(&mut cx, i as u32, tx_obj)?;
+                }
+                array.upcast()
+            }
+            ("events", JsonValue::Array(events)) => {
+                let array = JsArray::new(&mut cx, events.len());
+                for (i, event) in events.iter().enumerate() {
+                    let event_obj = event_to_js(&mut cx, i, event, options)?;
+                    array.set(&mut cx, i as u32, event_obj)?;
+                }
+                array.upcast()
+            }
+            _ => json_to_js(&mut cx, value)?,
+        };
+        set_json_property(&mut cx, result, key, js_value)?;
+    }
+    Ok(result)
+}
+
+fn raw_hex_bytes(raw: Option<&JsonValue>) -> Result<Box<[u8]>, DeserializeError> {
+    match raw {
+        Some(JsonValue::String(hex)) => {
+            decode_hex(hex).map_err(|e| format!("Hex parsing error: {}", e).into())
+        }
+        _ => Err("Expected a hex string".to_string().into()),
+    }
+}
+
+/// Burnchain operations (e.g. STX transfers sent via Bitcoin) have a `raw_tx` of `0x00`.
+fn decode_raw_tx(raw_tx: Option<&JsonValue>) -> Result<Option<DecodedTx>, DeserializeError> {
+    let tx_bytes = raw_hex_bytes(raw_tx)?;
+    if tx_bytes.as_ref() == [0] {
+        return Ok(None);
+    }
+    decode_tx_bytes(&tx_bytes).map(Some)
+}
+
+/// Events are returned as-is, with a `decoded_value` added to each `*_event` object with a
+/// `raw_value`, e.g. `contract_event` and `nft_transfer_event`.
+fn event_to_js<'cx>(
+    cx: &mut FunctionContext<'cx>,
+    index: usize,
+    event: &JsonValue,
+    options: OutputOptions,
+) -> JsResult<'cx, JsValue> {
+    let event_js = json_to_js(cx, event)?;
+    let event = match event.as_object() {
+        Some(event) => event,
+        None => return Ok(event_js),
+    };
+    for (key, value) in event {
+        let raw_value = match value.get("raw_value") {
+            Some(raw_value) if key.ends_with("_event") => raw_value,
+            _ => continue,
+        };
+        let decoded_value = raw_clarity_value_to_js(cx, raw_value, options, |e| {
+            e.in_field("raw_value")
+                .in_field(key)
+                .in_element("events", index)
+        })?;
+        let event_obj = event_js.downcast_or_throw::<JsObject, _>(cx)?;
+        let inner_obj: Handle<JsObject> = event_obj.get(cx, key.as_str())?;
+        inner_obj.set(cx, "decoded_value", decoded_value)?;
+    }
+    Ok(event_js)
+}
+
+/// Decode a hex Clarity value, throwing a `DeserializeError` with the path given by `in_path`.
+fn raw_clarity_value_to_js<'cx, F>(
+    cx: &mut FunctionContext<'cx>,
+    raw_value: &JsonValue,
+    options: OutputOptions,
+    in_path: F,
+) -> JsResult<'cx, JsValue>
+where
+    F: Fn(DeserializeError) -> DeserializeError,
+{
+    let val_bytes = raw_hex_bytes(Some(raw_value))
+        .or_else(|e| throw_deserialize_error(cx, ERROR_CONTEXT, &in_path(e)))?;
+    let mut cursor = Cursor::new(val_bytes.as_ref());
+    let clarity_value = ClarityValueRef::deserialize(&mut cursor)
+        .or_else(|e| throw_deserialize_error(cx, ERROR_CONTEXT, &in_path(e)))?;
+    let value_obj = cx.empty_object();
+    decode_clarity_val(cx, &value_obj, &clarity_value, true, options)?;
+    Ok(value_obj.upcast())
+}
+
+/// Convert a parsed JSON value to the JS value `JSON.parse` would return. serde_json is built
+/// with `preserve_order` so object keys keep their input order, and with `float_roundtrip` so that
+/// numbers round to the same doubles, e.g. integers above 2^53 lose precision exactly as they do
+/// in `JSON.parse`.
+fn json_to_js<'cx, C: Context<'cx>>(cx: &mut C, value: &JsonValue) -> JsResult<'cx, JsValue> {
+    let js_value = match value {
+        JsonValue::Null => cx.null().upcast(),
+        JsonValue::Bool(b) => cx.boolean(*b).upcast(),
+        JsonValue::Number(n) => cx.number(n.as_f64().unwrap_or(f64::NAN)).upcast(),
+        JsonValue::String(s) => cx.string(s).upcast(),
+        JsonValue::Array(items) => {
+            let array = JsArray::new(cx, items.len());
+            for (i, item) in items.iter().enumerate() {
+                let item = json_to_js(cx, item)?;
+                array.set(cx, i as u32, item)?;
+            }
+            array.upcast()
+        }
+        JsonValue::Object(map) => {
+            let obj = cx.empty_object();
+            for (key, item) in map {
+                let item = json_to_js(cx, item)?;
+                set_json_property(cx, obj, key, item)?;
+            }
+            obj.upcast()
+        }
+    };
+    Ok(js_value)
+}
+
+/// Set a property the way `JSON.parse` does. Assigning `__proto__` would set the object's
+/// prototype, so that key is defined as an own property instead.
+fn set_json_property<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    obj: Handle<'cx, JsObject>,
+    key: &str,
+    value: Handle<'cx, JsValue>,
+) -> NeonResult<()> {
+    if key != "__proto__" {
+        obj.set(cx, key, value)?;
+        return Ok(());
+    }
+    let object_ctor: Handle<JsFunction> = cx.global("Object")?;
+    let define_property: Handle<JsFunction> = object_ctor.get(cx, "defineProperty")?;
+    let descriptor = cx.empty_object();
+    descriptor.set(cx, "value", value)?;
+    for attribute in ["writable", "enumerable", "configurable"] {
+        let enabled = cx.boolean(true);
+        descriptor.set(cx, attribute, enabled)?;
+    }
+    let key = cx.string(key);
+    define_property.call(
+        cx,
+        object_ctor,
+        [obj.upcast(), key.upcast(), descriptor.upcast()],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Decode and output options shared by the Node addon, the JSON outputs of `json.rs` and the
+//! wasm bindings, see `DecodeOutputOptions` in index.ts.
+
+/// How the decode functions output 64 and 128-bit integers, e.g. Clarity `int`/`uint` values and
+/// transaction amounts, fees and nonces. Set with the `numbers` property of the options argument.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NumberFormat {
+    /// Decimal strings, e.g. `"1000"`
+    #[default]
+    String,
+    /// Native JS `bigint` values
+    BigInt,
+}
+
+/// The shape of decoded Clarity values. Set with the `profile` property of the options argument.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputProfile {
+    /// `repr`, `hex` and `type_id` on every value
+    #[default]
+    Full,
+    /// Only `type_id` and the decoded contents, skipping the per-value `repr` and `hex` strings
+    Minimal,
+    /// `{type, value}` objects in the Stacks API JSON convention
+    Json,
+}
+
+/// How the decode functions output values, read from the options argument.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OutputOptions {
+    pub numbers: NumberFormat,
+    pub profile: OutputProfile,
+}
+
+impl From<NumberFormat> for OutputOptions {
+    fn from(numbers: NumberFormat) -> Self {
+        OutputOptions {
+            numbers,
+            profile: OutputProfile::Full,
+        }
+    }
+}
+
+/// Options of `decodeClarityValue`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DecodeClarityValueOptions {
+    /// Reject non-canonical encodings, see `ClarityValue::deserialize_strict`.
+    pub strict: bool,
+    pub output: OutputOptions,
+}
+
+/// The `format` option of `decodeClarityValueAtPath`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectedValueFormat {
+    Object(NumberFormat),
+    Repr,
+    Hex,
+}
+
+impl Default for SelectedValueFormat {
+    fn default() -> Self {
+        SelectedValueFormat::Object(NumberFormat::String)
+    }
+}
+
+/// The `buffers` option of `decodeClarityValueToNative`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BufferFormat {
+    #[default]
+    Hex,
+    Uint8Array,
+}
+
+/// Options of `decodeClarityValueToNative`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NativeValueOptions {
+    /// Integers are `bigint` unless set to `string`
+    pub numbers: NumberFormat,
+    pub buffers: BufferFormat,
+    /// Return the inner value of responses rather than `{ok}` / `{err}` objects
+    pub unwrap_responses: bool,
+}
+
+impl Default for NativeValueOptions {
+    fn default() -> Self {
+        NativeValueOptions {
+            numbers: NumberFormat::BigInt,
+            buffers: BufferFormat::Hex,
+            unwrap_responses: false,
+        }
+    }
+}
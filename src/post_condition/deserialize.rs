@@ -7,6 +7,8 @@ use std::{
 use crate::clarity_value::types::{ClarityName, ClarityValue};
 use crate::{address::stacks_address::StacksAddress, serialize_util::DeserializeError};
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionPostCondition {
     STX(PostConditionPrincipal, FungibleConditionCode, u64),
     Fungible(
@@ -23,12 +25,16 @@ pub enum TransactionPostCondition {
     ),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PostConditionPrincipal {
     Origin,
     Standard(StacksAddress),
     Contract(StacksAddress, ClarityName),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum PostConditionPrincipalID {
     Origin = 0x01,
@@ -37,7 +43,8 @@ pub enum PostConditionPrincipalID {
 }
 
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FungibleConditionCode {
     SentEq = 0x01,
     SentGt = 0x02,
@@ -61,7 +68,8 @@ impl TryFrom<u8> for FungibleConditionCode {
 }
 
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NonfungibleConditionCode {
     Sent = 0x10,
     NotSent = 0x11,
@@ -78,12 +86,16 @@ impl TryFrom<u8> for NonfungibleConditionCode {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AssetInfo {
     pub contract_address: StacksAddress,
     pub contract_name: ClarityName,
     pub asset_name: ClarityName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[repr(u8)]
 pub enum AssetInfoID {
     STX = 0,
@@ -93,6 +105,10 @@ pub enum AssetInfoID {
 
 impl TransactionPostCondition {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
         let asset_info_id: u8 = fd.read_u8()?;
         let postcond = match asset_info_id {
             x if x == AssetInfoID::STX as u8 => {
@@ -126,7 +142,7 @@ impl TransactionPostCondition {
                 let asset_value = {
                     let cursor_pos = fd.position();
                     let mut val = ClarityValue::deserialize(fd, false)
-                        .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+                        .map_err(|e| e.in_field("asset_value"))?;
                     let decoded_bytes = &fd.get_ref()[cursor_pos as usize..fd.position() as usize];
                     val.serialized_bytes = Some(decoded_bytes.to_vec());
                     val
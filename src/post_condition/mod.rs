@@ -1,16 +1,27 @@
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
+#[cfg(feature = "nodejs")]
 use std::{convert::TryInto, io::Cursor};
 
+#[cfg(feature = "nodejs")]
 use crate::hex::encode_hex;
-use crate::neon_util::arg_as_bytes_copied;
+#[cfg(feature = "nodejs")]
+use crate::neon_util::{arg_as_bytes_copied, throw_deserialize_error, NumberFormat};
 
+#[cfg(feature = "nodejs")]
 use self::deserialize::TransactionPostCondition;
 
 pub mod deserialize;
+#[cfg(feature = "nodejs")]
+pub mod neon_decoder;
+#[cfg(feature = "nodejs")]
 pub mod neon_encoder;
+pub mod serialize;
 
+#[cfg(feature = "nodejs")]
 pub fn decode_tx_post_conditions(mut cx: FunctionContext) -> JsResult<JsObject> {
     let input_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let numbers = NumberFormat::from_arg(&mut cx, 1)?;
     let resp_obj = cx.empty_object();
 
     // first byte is post condition mode
@@ -26,19 +37,22 @@ pub fn decode_tx_post_conditions(mut cx: FunctionContext) -> JsResult<JsObject>
                 e
             ))
         })?);
-        let array_result = JsArray::new(&mut cx, result_length as usize);
         // next bytes are serialized post condition items
         let post_condition_bytes = &input_bytes[5..];
+        // `result_length` is untrusted, each item takes more than a byte of the remaining input
+        let array_len = (result_length as usize).min(post_condition_bytes.len());
+        let array_result = JsArray::new(&mut cx, array_len);
         let post_condition_bytes_len = post_condition_bytes.len() as u64;
         let mut cursor = Cursor::new(post_condition_bytes);
         let mut i: u32 = 0;
         while cursor.position() < post_condition_bytes_len {
             let post_condition =
                 TransactionPostCondition::deserialize(&mut cursor).or_else(|e| {
-                    cx.throw_error(format!("Error deserializing post condition: {}", e.error))
+                    let e = e.in_element("post_conditions", i as usize).shift_offset(5);
+                    throw_deserialize_error(&mut cx, "Error deserializing post condition", &e)
                 })?;
             let value_obj = cx.empty_object();
-            post_condition.neon_js_serialize(&mut cx, &value_obj)?;
+            post_condition.neon_js_serialize(&mut cx, &value_obj, numbers.into())?;
             array_result.set(&mut cx, i, value_obj)?;
             i = i + 1;
         }
@@ -52,10 +66,10 @@ pub fn decode_tx_post_conditions(mut cx: FunctionContext) -> JsResult<JsObject>
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use super::deserialize::TransactionPostCondition;
     use crate::hex::decode_hex;
     use flate2::read::GzDecoder;
-    use std::io::{prelude::*, BufReader};
+    use std::io::{prelude::*, BufReader, Cursor};
 
     const SAMPLED_POST_CONDITIONS: &'static [u8] =
         include_bytes!("../../perf-tests/decode-post-conditions/sampled-post-conditions.txt.gz");
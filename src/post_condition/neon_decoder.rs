@@ -0,0 +1,117 @@
+use std::convert::{TryFrom, TryInto};
+
+use neon::prelude::*;
+
+use crate::address::stacks_address::StacksAddress;
+use crate::clarity_value::neon_decoder::clarity_val_from_js_hex_or_deep;
+use crate::clarity_value::types::ClarityName;
+use crate::neon_util::{get_obj_prop, get_obj_prop_string, get_obj_prop_u64, get_obj_prop_u8};
+
+use super::deserialize::{
+    AssetInfo, AssetInfoID, FungibleConditionCode, NonfungibleConditionCode,
+    PostConditionPrincipal, PostConditionPrincipalID, TransactionPostCondition,
+};
+
+/// Build a post-condition from an object in the shape produced by `decodePostConditions`.
+/// The `condition_name` property is ignored.
+pub fn post_condition_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionPostCondition> {
+    let asset_info_id = get_obj_prop_u8(cx, obj, "asset_info_id")?;
+    let principal_obj = get_obj_prop::<JsObject>(cx, obj, "principal")?;
+    let principal = post_condition_principal_from_js(cx, &principal_obj)?;
+    let condition_code = get_obj_prop_u8(cx, obj, "condition_code")?;
+
+    let post_condition = match asset_info_id {
+        x if x == AssetInfoID::STX as u8 => {
+            let condition_code = fungible_condition_code(cx, condition_code)?;
+            let amount = get_obj_prop_u64(cx, obj, "amount")?;
+            TransactionPostCondition::STX(principal, condition_code, amount)
+        }
+        x if x == AssetInfoID::FungibleAsset as u8 => {
+            let asset = asset_info_from_js(cx, obj)?;
+            let condition_code = fungible_condition_code(cx, condition_code)?;
+            let amount = get_obj_prop_u64(cx, obj, "amount")?;
+            TransactionPostCondition::Fungible(principal, asset, condition_code, amount)
+        }
+        x if x == AssetInfoID::NonfungibleAsset as u8 => {
+            let asset = asset_info_from_js(cx, obj)?;
+            let asset_value_obj = get_obj_prop::<JsObject>(cx, obj, "asset_value")?;
+            let asset_value = clarity_val_from_js_hex_or_deep(cx, &asset_value_obj)?;
+            let condition_code: NonfungibleConditionCode =
+                condition_code.try_into().or_else(|_| {
+                    cx.throw_error(format!(
+                        "Invalid NonfungibleConditionCode: {}",
+                        condition_code
+                    ))
+                })?;
+            TransactionPostCondition::Nonfungible(principal, asset, asset_value, condition_code)
+        }
+        _ => cx.throw_error(format!(
+            "Unknown post condition asset_info_id {}",
+            asset_info_id
+        ))?,
+    };
+    Ok(post_condition)
+}
+
+fn post_condition_principal_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<PostConditionPrincipal> {
+    let type_id = get_obj_prop_u8(cx, obj, "type_id")?;
+    let principal = match type_id {
+        x if x == PostConditionPrincipalID::Origin as u8 => PostConditionPrincipal::Origin,
+        x if x == PostConditionPrincipalID::Standard as u8 => {
+            let address = stacks_address_from_js(cx, obj, "address")?;
+            PostConditionPrincipal::Standard(address)
+        }
+        x if x == PostConditionPrincipalID::Contract as u8 => {
+            let address = stacks_address_from_js(cx, obj, "address")?;
+            let contract_name = clarity_name_from_js(cx, obj, "contract_name")?;
+            PostConditionPrincipal::Contract(address, contract_name)
+        }
+        _ => cx.throw_error(format!(
+            "Unknown post condition principal type_id {}",
+            type_id
+        ))?,
+    };
+    Ok(principal)
+}
+
+fn asset_info_from_js(cx: &mut FunctionContext, obj: &Handle<JsObject>) -> NeonResult<AssetInfo> {
+    let asset_obj = get_obj_prop::<JsObject>(cx, obj, "asset")?;
+    Ok(AssetInfo {
+        contract_address: stacks_address_from_js(cx, &asset_obj, "contract_address")?,
+        contract_name: clarity_name_from_js(cx, &asset_obj, "contract_name")?,
+        asset_name: clarity_name_from_js(cx, &asset_obj, "asset_name")?,
+    })
+}
+
+fn fungible_condition_code(
+    cx: &mut FunctionContext,
+    condition_code: u8,
+) -> NeonResult<FungibleConditionCode> {
+    FungibleConditionCode::try_from(condition_code)
+        .or_else(|_| cx.throw_error(format!("Invalid FungibleConditionCode: {}", condition_code)))
+}
+
+/// Read a c32check-encoded Stacks address property.
+pub fn stacks_address_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<StacksAddress> {
+    let address = get_obj_prop_string(cx, obj, key)?;
+    StacksAddress::from_string(&address).or_else(|e| cx.throw_error(e))
+}
+
+pub fn clarity_name_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<ClarityName> {
+    let name = get_obj_prop_string(cx, obj, key)?;
+    ClarityName::try_from(name).or_else(|e| cx.throw_error(e))
+}
@@ -2,6 +2,7 @@ use neon::prelude::*;
 
 use crate::address::stacks_address::StacksAddress;
 use crate::clarity_value::neon_encoder::decode_clarity_val;
+use crate::neon_util::{OutputOptions, OutputProfile};
 use crate::{address::c32::c32_address, hex::encode_hex};
 
 use super::deserialize::{
@@ -10,10 +11,11 @@ use super::deserialize::{
 };
 
 impl TransactionPostCondition {
-    pub fn neon_js_serialize(
+    pub fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
+        options: OutputOptions,
     ) -> NeonResult<()> {
         match *self {
             TransactionPostCondition::STX(ref principal, ref fungible_condition, ref amount) => {
@@ -26,8 +28,8 @@ impl TransactionPostCondition {
 
                 fungible_condition.neon_js_serialize(cx, obj)?;
 
-                let amount_str = cx.string(amount.to_string());
-                obj.set(cx, "amount", amount_str)?;
+                let amount = options.numbers.uint(cx, (*amount).into());
+                obj.set(cx, "amount", amount)?;
             }
             TransactionPostCondition::Fungible(
                 ref principal,
@@ -48,8 +50,8 @@ impl TransactionPostCondition {
 
                 fungible_condition.neon_js_serialize(cx, obj)?;
 
-                let amount_str = cx.string(amount.to_string());
-                obj.set(cx, "amount", amount_str)?;
+                let amount = options.numbers.uint(cx, (*amount).into());
+                obj.set(cx, "amount", amount)?;
             }
             TransactionPostCondition::Nonfungible(
                 ref principal,
@@ -72,9 +74,9 @@ impl TransactionPostCondition {
                 decode_clarity_val(
                     cx,
                     &asset_value_obj,
-                    &asset_value,
-                    false,
-                    asset_value.serialized_bytes.as_ref().unwrap(),
+                    &asset_value.as_value_ref().unwrap(),
+                    options.profile == OutputProfile::Minimal,
+                    options,
                 )?;
                 obj.set(cx, "asset_value", asset_value_obj)?;
 
@@ -86,9 +88,9 @@ impl TransactionPostCondition {
 }
 
 impl PostConditionPrincipal {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
     ) -> NeonResult<()> {
         match *self {
@@ -117,9 +119,9 @@ impl PostConditionPrincipal {
 }
 
 impl StacksAddress {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
     ) -> NeonResult<()> {
         let address_version = cx.number(self.version);
@@ -138,9 +140,9 @@ impl StacksAddress {
 }
 
 impl FungibleConditionCode {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
     ) -> NeonResult<()> {
         let condition_name = match *self {
@@ -159,9 +161,9 @@ impl FungibleConditionCode {
 }
 
 impl AssetInfo {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
     ) -> NeonResult<()> {
         let contract_address_str = c32_address(
@@ -182,9 +184,9 @@ impl AssetInfo {
 }
 
 impl NonfungibleConditionCode {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
     ) -> NeonResult<()> {
         let condition_name = match *self {
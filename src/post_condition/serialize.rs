@@ -0,0 +1,112 @@
+use std::io::Write;
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+use crate::address::stacks_address::StacksAddress;
+use crate::serialize_util::SerializeError;
+
+use super::deserialize::{
+    AssetInfo, AssetInfoID, PostConditionPrincipal, PostConditionPrincipalID,
+    TransactionPostCondition,
+};
+
+impl TransactionPostCondition {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionPostCondition::STX(principal, condition_code, amount) => {
+                fd.write_u8(AssetInfoID::STX as u8)?;
+                principal.serialize(fd)?;
+                fd.write_u8(*condition_code as u8)?;
+                fd.write_u64::<BigEndian>(*amount)?;
+            }
+            TransactionPostCondition::Fungible(principal, asset, condition_code, amount) => {
+                fd.write_u8(AssetInfoID::FungibleAsset as u8)?;
+                principal.serialize(fd)?;
+                asset.serialize(fd)?;
+                fd.write_u8(*condition_code as u8)?;
+                fd.write_u64::<BigEndian>(*amount)?;
+            }
+            TransactionPostCondition::Nonfungible(
+                principal,
+                asset,
+                asset_value,
+                condition_code,
+            ) => {
+                fd.write_u8(AssetInfoID::NonfungibleAsset as u8)?;
+                principal.serialize(fd)?;
+                asset.serialize(fd)?;
+                asset_value.serialize(fd)?;
+                fd.write_u8(*condition_code as u8)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl PostConditionPrincipal {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            PostConditionPrincipal::Origin => {
+                fd.write_u8(PostConditionPrincipalID::Origin as u8)?;
+            }
+            PostConditionPrincipal::Standard(address) => {
+                fd.write_u8(PostConditionPrincipalID::Standard as u8)?;
+                address.serialize(fd)?;
+            }
+            PostConditionPrincipal::Contract(address, contract_name) => {
+                fd.write_u8(PostConditionPrincipalID::Contract as u8)?;
+                address.serialize(fd)?;
+                contract_name.serialize(fd)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl StacksAddress {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u8(self.version)?;
+        fd.write_all(&self.hash160_bytes)?;
+        Ok(())
+    }
+}
+
+impl AssetInfo {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        self.contract_address.serialize(fd)?;
+        self.contract_name.serialize(fd)?;
+        self.asset_name.serialize(fd)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{prelude::*, BufReader, Cursor};
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+    use crate::hex::decode_hex;
+
+    const SAMPLED_POST_CONDITIONS: &[u8] =
+        include_bytes!("../../perf-tests/decode-post-conditions/sampled-post-conditions.txt.gz");
+
+    #[test]
+    fn test_serialize_samples_roundtrip() {
+        let decoder = GzDecoder::new(SAMPLED_POST_CONDITIONS);
+        let reader = BufReader::new(decoder);
+        for line in reader.lines().map_while(Result::ok) {
+            let input_bytes = decode_hex(line).unwrap();
+            let post_condition_bytes = &input_bytes[5..];
+            let post_condition_bytes_len = post_condition_bytes.len() as u64;
+            let mut cursor = Cursor::new(post_condition_bytes);
+            let mut serialized = vec![];
+            while cursor.position() < post_condition_bytes_len {
+                let post_condition = TransactionPostCondition::deserialize(&mut cursor).unwrap();
+                post_condition.serialize(&mut serialized).unwrap();
+            }
+            assert_eq!(serialized, post_condition_bytes);
+        }
+    }
+}
@@ -1,7 +1,5 @@
-use std::collections::BTreeMap;
-
 use crate::address::c32::c32_address;
-use crate::clarity_value::types::{ClarityName, ClarityValue, Value};
+use crate::clarity_value::value_ref::{ClarityValueRef, TupleRef, ValueRef};
 use crate::hex::encode_hex;
 
 use super::btc_address::pox_address_to_btc_address;
@@ -11,13 +9,13 @@ use super::types::*;
 /// Returns `Ok(None)` if the value is a `ResponseErr` (non-event).
 /// Returns `Err` if the structure is unexpected.
 pub fn decode_pox_synthetic_event(
-    clarity_value: &ClarityValue,
+    clarity_value: &ClarityValueRef,
     network: StacksNetwork,
 ) -> Result<Option<PoxSyntheticEvent>, String> {
     // 1. Root must be ResponseOk; ResponseErr means no event.
-    let inner = match &clarity_value.value {
-        Value::ResponseOk(inner) => inner,
-        Value::ResponseErr(_) => return Ok(None),
+    let inner = match clarity_value.value() {
+        ValueRef::ResponseOk(inner) => inner,
+        ValueRef::ResponseErr(_) => return Ok(None),
         other => {
             return Err(format!(
                 "Unexpected PoX synthetic event Clarity type, expected ResponseOk, got {:?}",
@@ -27,8 +25,8 @@ pub fn decode_pox_synthetic_event(
     };
 
     // 2. Inner must be a Tuple
-    let op_data = match &inner.value {
-        Value::Tuple(map) => map,
+    let op_data = match inner.value() {
+        ValueRef::Tuple(map) => map,
         other => {
             return Err(format!(
                 "Unexpected PoX synthetic event Clarity type, expected Tuple, got {:?}",
@@ -45,9 +43,9 @@ pub fn decode_pox_synthetic_event(
         extract_uint(get_tuple_field(op_data, "burnchain-unlock-height")?)?;
 
     // 4. Extract event name
-    let name_str = match &get_tuple_field(op_data, "name")?.value {
-        Value::StringASCII(bytes) => {
-            String::from_utf8(bytes.clone()).map_err(|e| format!("Invalid event name: {}", e))?
+    let name_str = match get_tuple_field(op_data, "name")?.value() {
+        ValueRef::StringASCII(bytes) => {
+            String::from_utf8(bytes.to_vec()).map_err(|e| format!("Invalid event name: {}", e))?
         }
         other => {
             return Err(format!(
@@ -61,8 +59,8 @@ pub fn decode_pox_synthetic_event(
         .ok_or_else(|| format!("Unexpected PoX synthetic event data name: {}", name_str))?;
 
     // 5. Extract inner data tuple
-    let event_data_tuple = match &get_tuple_field(op_data, "data")?.value {
-        Value::Tuple(map) => map,
+    let event_data_tuple = match get_tuple_field(op_data, "data")?.value() {
+        ValueRef::Tuple(map) => map,
         other => {
             return Err(format!(
                 "Unexpected PoX synthetic event data payload type, expected Tuple, got {:?}",
@@ -375,18 +373,15 @@ pub fn decode_pox_synthetic_event(
 
 // ─── Helper functions ───────────────────────────────────────────────────────
 
-fn get_tuple_field<'a>(
-    tuple: &'a BTreeMap<ClarityName, ClarityValue>,
-    key: &str,
-) -> Result<&'a ClarityValue, String> {
+fn get_tuple_field<'a>(tuple: TupleRef<'a>, key: &str) -> Result<ClarityValueRef<'a>, String> {
     tuple
         .get(key)
         .ok_or_else(|| format!("Missing expected tuple field: {}", key))
 }
 
-fn extract_uint(val: &ClarityValue) -> Result<u128, String> {
-    match &val.value {
-        Value::UInt(v) => Ok(*v),
+pub(crate) fn extract_uint(val: ClarityValueRef) -> Result<u128, String> {
+    match val.value() {
+        ValueRef::UInt(v) => Ok(v),
         other => Err(format!(
             "Expected UInt, got {:?}",
             other.type_prefix()
@@ -399,19 +394,19 @@ fn extract_uint(val: &ClarityValue) -> Result<u128, String> {
 /// - `OptionalNone` → `Ok(None)`
 /// - `OptionalSome(UInt(v))` → `Ok(Some(v))`
 /// - `UInt(v)` → `Ok(Some(v))` (for fields that are sometimes bare uints)
-fn extract_optional_uint(val: Option<&ClarityValue>) -> Result<Option<u128>, String> {
+fn extract_optional_uint(val: Option<ClarityValueRef>) -> Result<Option<u128>, String> {
     match val {
         None => Ok(None),
-        Some(cv) => match &cv.value {
-            Value::OptionalNone => Ok(None),
-            Value::OptionalSome(inner) => match &inner.value {
-                Value::UInt(v) => Ok(Some(*v)),
+        Some(cv) => match cv.value() {
+            ValueRef::OptionalNone => Ok(None),
+            ValueRef::OptionalSome(inner) => match inner.value() {
+                ValueRef::UInt(v) => Ok(Some(v)),
                 other => Err(format!(
                     "Expected UInt inside OptionalSome, got {:?}",
                     other.type_prefix()
                 )),
             },
-            Value::UInt(v) => Ok(Some(*v)),
+            ValueRef::UInt(v) => Ok(Some(v)),
             other => Err(format!(
                 "Expected OptionalSome/OptionalNone/UInt, got {:?}",
                 other.type_prefix()
@@ -425,16 +420,14 @@ fn extract_optional_uint(val: Option<&ClarityValue>) -> Result<Option<u128>, Str
 /// - `OptionalNone` → `Ok(None)`
 /// - `Buffer(bytes)` → `Ok(Some("0x..."))`
 /// - `OptionalSome(Buffer(bytes))` → `Ok(Some("0x..."))`
-fn extract_optional_buffer_hex(
-    val: Option<&ClarityValue>,
-) -> Result<Option<String>, String> {
+fn extract_optional_buffer_hex(val: Option<ClarityValueRef>) -> Result<Option<String>, String> {
     match val {
         None => Ok(None),
-        Some(cv) => match &cv.value {
-            Value::OptionalNone => Ok(None),
-            Value::Buffer(bytes) => Ok(Some(encode_hex(bytes).to_string())),
-            Value::OptionalSome(inner) => match &inner.value {
-                Value::Buffer(bytes) => Ok(Some(encode_hex(bytes).to_string())),
+        Some(cv) => match cv.value() {
+            ValueRef::OptionalNone => Ok(None),
+            ValueRef::Buffer(bytes) => Ok(Some(encode_hex(bytes).to_string())),
+            ValueRef::OptionalSome(inner) => match inner.value() {
+                ValueRef::Buffer(bytes) => Ok(Some(encode_hex(bytes).to_string())),
                 other => Err(format!(
                     "Expected Buffer inside OptionalSome, got {:?}",
                     other.type_prefix()
@@ -449,12 +442,12 @@ fn extract_optional_buffer_hex(
 }
 
 /// Convert a Clarity principal value to a string address.
-fn clarity_principal_to_string(val: &ClarityValue) -> Result<String, String> {
-    match &val.value {
-        Value::PrincipalStandard(data) => c32_address(data.0, &data.1),
-        Value::PrincipalContract(data) => {
-            let addr = c32_address(data.issuer.0, &data.issuer.1)?;
-            Ok(format!("{}.{}", addr, data.name))
+pub(crate) fn clarity_principal_to_string(val: ClarityValueRef) -> Result<String, String> {
+    match val.value() {
+        ValueRef::PrincipalStandard(version, hash_bytes) => c32_address(version, hash_bytes),
+        ValueRef::PrincipalContract(version, hash_bytes, name) => {
+            let addr = c32_address(version, hash_bytes)?;
+            Ok(format!("{}.{}", addr, name))
         }
         other => Err(format!(
             "Unexpected Clarity value type for principal: {:?}",
@@ -466,18 +459,18 @@ fn clarity_principal_to_string(val: &ClarityValue) -> Result<String, String> {
 /// Extract pox-addr tuple (version + hashbytes) and convert to BTC address.
 /// Returns (btc_addr, raw_hex). Gracefully returns (None, None) on encoding errors.
 fn extract_pox_addr(
-    val: &ClarityValue,
+    val: ClarityValueRef,
     network: StacksNetwork,
 ) -> Result<(Option<String>, Option<String>), String> {
     // Handle OptionalNone
-    if let Value::OptionalNone = &val.value {
+    if let ValueRef::OptionalNone = val.value() {
         return Ok((None, None));
     }
 
     // Handle OptionalSome wrapping
-    let addr_tuple = match &val.value {
-        Value::OptionalSome(inner) => match &inner.value {
-            Value::Tuple(map) => map,
+    let addr_tuple = match val.value() {
+        ValueRef::OptionalSome(inner) => match inner.value() {
+            ValueRef::Tuple(map) => map,
             other => {
                 return Err(format!(
                     "Expected Tuple inside OptionalSome for pox-addr, got {:?}",
@@ -485,7 +478,7 @@ fn extract_pox_addr(
                 ))
             }
         },
-        Value::Tuple(map) => map,
+        ValueRef::Tuple(map) => map,
         other => {
             return Err(format!(
                 "Expected Tuple/OptionalSome/OptionalNone for pox-addr, got {:?}",
@@ -494,8 +487,8 @@ fn extract_pox_addr(
         }
     };
 
-    let version_bytes = match &get_tuple_field(addr_tuple, "version")?.value {
-        Value::Buffer(bytes) => bytes.clone(),
+    let version_bytes = match get_tuple_field(addr_tuple, "version")?.value() {
+        ValueRef::Buffer(bytes) => bytes,
         other => {
             return Err(format!(
                 "Expected Buffer for pox-addr version, got {:?}",
@@ -504,8 +497,8 @@ fn extract_pox_addr(
         }
     };
 
-    let hashbytes = match &get_tuple_field(addr_tuple, "hashbytes")?.value {
-        Value::Buffer(bytes) => bytes.clone(),
+    let hashbytes = match get_tuple_field(addr_tuple, "hashbytes")?.value() {
+        ValueRef::Buffer(bytes) => bytes,
         other => {
             return Err(format!(
                 "Expected Buffer for pox-addr hashbytes, got {:?}",
@@ -516,8 +509,8 @@ fn extract_pox_addr(
 
     // Build raw hex: version_bytes ++ hashbytes
     let mut raw = Vec::with_capacity(version_bytes.len() + hashbytes.len());
-    raw.extend_from_slice(&version_bytes);
-    raw.extend_from_slice(&hashbytes);
+    raw.extend_from_slice(version_bytes);
+    raw.extend_from_slice(hashbytes);
     let raw_hex = encode_hex(&raw).to_string();
 
     // Try to encode BTC address; on error, return None for btc_addr (matches TS try/catch)
@@ -527,66 +520,84 @@ fn extract_pox_addr(
         version_bytes[0]
     };
 
-    let btc_addr = match pox_address_to_btc_address(version, &hashbytes, network) {
-        Ok(addr) => Some(addr),
-        Err(_) => None,
-    };
+    let btc_addr = pox_address_to_btc_address(version, hashbytes, network).ok();
 
     Ok((btc_addr, Some(raw_hex)))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io::Cursor;
+
     use super::*;
+    use crate::clarity_value::types::{ClarityValue, Value};
+
+    fn serialize(value: Value) -> Vec<u8> {
+        value.serialize_to_vec().unwrap()
+    }
+
+    fn value_ref(bytes: &[u8]) -> ClarityValueRef<'_> {
+        ClarityValueRef::deserialize(&mut Cursor::new(bytes)).unwrap()
+    }
 
     #[test]
     fn test_response_err_returns_none() {
         // (err u1) = 0x08 0x01 0x00...01
-        let cv = ClarityValue::new(Value::ResponseErr(Box::new(ClarityValue::new(
+        let bytes = serialize(Value::ResponseErr(Box::new(ClarityValue::new(
             Value::UInt(1),
         ))));
-        let result = decode_pox_synthetic_event(&cv, StacksNetwork::Mainnet).unwrap();
+        let result =
+            decode_pox_synthetic_event(&value_ref(&bytes), StacksNetwork::Mainnet).unwrap();
         assert!(result.is_none());
     }
 
     #[test]
     fn test_non_response_errors() {
-        let cv = ClarityValue::new(Value::UInt(42));
-        let result = decode_pox_synthetic_event(&cv, StacksNetwork::Mainnet);
+        let bytes = serialize(Value::UInt(42));
+        let result = decode_pox_synthetic_event(&value_ref(&bytes), StacksNetwork::Mainnet);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_extract_uint_works() {
-        let cv = ClarityValue::new(Value::UInt(12345));
-        assert_eq!(extract_uint(&cv).unwrap(), 12345);
+        let bytes = serialize(Value::UInt(12345));
+        assert_eq!(extract_uint(value_ref(&bytes)).unwrap(), 12345);
     }
 
     #[test]
     fn test_extract_optional_uint_none() {
         assert_eq!(extract_optional_uint(None).unwrap(), None);
-        let cv = ClarityValue::new(Value::OptionalNone);
-        assert_eq!(extract_optional_uint(Some(&cv)).unwrap(), None);
+        let bytes = serialize(Value::OptionalNone);
+        assert_eq!(
+            extract_optional_uint(Some(value_ref(&bytes))).unwrap(),
+            None
+        );
     }
 
     #[test]
     fn test_extract_optional_uint_some() {
-        let cv = ClarityValue::new(Value::OptionalSome(Box::new(ClarityValue::new(
+        let bytes = serialize(Value::OptionalSome(Box::new(ClarityValue::new(
             Value::UInt(999),
         ))));
-        assert_eq!(extract_optional_uint(Some(&cv)).unwrap(), Some(999));
+        assert_eq!(
+            extract_optional_uint(Some(value_ref(&bytes))).unwrap(),
+            Some(999)
+        );
     }
 
     #[test]
     fn test_extract_optional_buffer_hex() {
-        let cv = ClarityValue::new(Value::Buffer(vec![0xab, 0xcd]));
+        let bytes = serialize(Value::Buffer(vec![0xab, 0xcd]));
         assert_eq!(
-            extract_optional_buffer_hex(Some(&cv)).unwrap(),
+            extract_optional_buffer_hex(Some(value_ref(&bytes))).unwrap(),
             Some("0xabcd".to_string())
         );
 
-        let cv_none = ClarityValue::new(Value::OptionalNone);
-        assert_eq!(extract_optional_buffer_hex(Some(&cv_none)).unwrap(), None);
+        let bytes_none = serialize(Value::OptionalNone);
+        assert_eq!(
+            extract_optional_buffer_hex(Some(value_ref(&bytes_none))).unwrap(),
+            None
+        );
 
         assert_eq!(extract_optional_buffer_hex(None).unwrap(), None);
     }
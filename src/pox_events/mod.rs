@@ -1,21 +1,30 @@
+#[cfg(feature = "nodejs")]
 use std::io::Cursor;
 
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
 
-use crate::clarity_value::types::ClarityValue;
+#[cfg(feature = "nodejs")]
+use crate::clarity_value::value_ref::ClarityValueRef;
+#[cfg(feature = "nodejs")]
 use crate::neon_util::arg_as_bytes_copied;
 
+#[cfg(feature = "nodejs")]
 use self::decode::decode_pox_synthetic_event;
+#[cfg(feature = "nodejs")]
 use self::neon_encoder::encode_pox_event;
+#[cfg(feature = "nodejs")]
 use self::types::StacksNetwork;
 
 pub mod btc_address;
 pub mod decode;
+#[cfg(feature = "nodejs")]
 pub mod neon_encoder;
 pub mod types;
 
 /// Neon-exported function: decodePoxSyntheticEvent(arg: string | Buffer, network: string)
 /// Returns a JS object or null.
+#[cfg(feature = "nodejs")]
 pub fn decode_pox_event(mut cx: FunctionContext) -> JsResult<JsValue> {
     // Arg 0: hex string or Buffer (raw Clarity value bytes)
     let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
@@ -25,9 +34,9 @@ pub fn decode_pox_event(mut cx: FunctionContext) -> JsResult<JsValue> {
     let network = StacksNetwork::from_str(&network_str)
         .or_else(|e| cx.throw_error(e))?;
 
-    // Deserialize Clarity value (no need for serialized_bytes since we don't output hex/repr)
+    // Validate the Clarity value; fields are read from the input bytes as they are looked up
     let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
-    let clarity_value = ClarityValue::deserialize(&mut cursor, false)
+    let clarity_value = ClarityValueRef::deserialize(&mut cursor)
         .or_else(|e| cx.throw_error(format!("Error deserializing Clarity value: {}", e)))?;
 
     // Decode to PoX synthetic event
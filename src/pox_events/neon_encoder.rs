@@ -198,7 +198,7 @@ fn encode_event_data<'a>(
 
 // ─── Neon helper functions ──────────────────────────────────────────────────
 
-fn set_string<'a>(
+pub(crate) fn set_string<'a>(
     cx: &mut FunctionContext<'a>,
     obj: &Handle<'a, JsObject>,
     key: &str,
@@ -209,7 +209,7 @@ fn set_string<'a>(
     Ok(())
 }
 
-fn set_u128_string<'a>(
+pub(crate) fn set_u128_string<'a>(
     cx: &mut FunctionContext<'a>,
     obj: &Handle<'a, JsObject>,
     key: &str,
@@ -220,7 +220,7 @@ fn set_u128_string<'a>(
     Ok(())
 }
 
-fn set_optional_string<'a>(
+pub(crate) fn set_optional_string<'a>(
     cx: &mut FunctionContext<'a>,
     obj: &Handle<'a, JsObject>,
     key: &str,
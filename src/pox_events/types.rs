@@ -1,5 +1,6 @@
 /// Network type for BTC address encoding.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StacksNetwork {
     Mainnet,
     Testnet,
@@ -25,6 +26,7 @@ impl StacksNetwork {
 
 /// All PoX synthetic event names emitted by the Clarity contracts.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PoxEventName {
     HandleUnlock,
     StackStx,
@@ -80,7 +82,8 @@ impl PoxEventName {
 }
 
 /// Base fields common to all PoX synthetic events.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PoxEventBase {
     pub stacker: String,
     pub locked: u128,
@@ -91,7 +94,8 @@ pub struct PoxEventBase {
 }
 
 /// A fully decoded PoX synthetic event.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PoxSyntheticEvent {
     pub base: PoxEventBase,
     pub name: PoxEventName,
@@ -99,7 +103,8 @@ pub struct PoxSyntheticEvent {
 }
 
 /// Event-specific data payload.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PoxEventData {
     HandleUnlock {
         first_cycle_locked: u128,
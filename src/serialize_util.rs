@@ -1,35 +1,222 @@
 use std::fmt::Display;
 
-#[derive(Debug)]
+/// The kind of failure encountered while deserializing. The variant name is exposed to JS as the
+/// `code` property of the thrown `DeserializeError`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeserializeErrorKind {
+    /// The input ended before the value was complete.
+    UnexpectedEof,
+    /// Unknown Clarity value type prefix byte.
+    BadTypePrefix(u8),
+    /// Clarity value nested deeper than the consensus limit.
+    TooDeep(u8),
+    /// A length prefix larger than the maximum allowed for its type.
+    IllegalLength {
+        type_name: &'static str,
+        length: u32,
+    },
+    /// A Clarity name or contract name that is too long or not valid UTF-8.
+    InvalidName(String),
+    /// Unknown transaction payload type ID.
+    UnknownPayloadId(u8),
+    /// A valid but non-canonical encoding, rejected by strict decoding.
+    NonCanonical(String),
+    /// Any other malformed input, e.g. an unknown hash mode or post-condition code.
+    InvalidValue(String),
+}
+
+impl DeserializeErrorKind {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DeserializeErrorKind::UnexpectedEof => "UnexpectedEof",
+            DeserializeErrorKind::BadTypePrefix(_) => "BadTypePrefix",
+            DeserializeErrorKind::TooDeep(_) => "TooDeep",
+            DeserializeErrorKind::IllegalLength { .. } => "IllegalLength",
+            DeserializeErrorKind::InvalidName(_) => "InvalidName",
+            DeserializeErrorKind::UnknownPayloadId(_) => "UnknownPayloadId",
+            DeserializeErrorKind::NonCanonical(_) => "NonCanonical",
+            DeserializeErrorKind::InvalidValue(_) => "InvalidValue",
+        }
+    }
+}
+
+impl Display for DeserializeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeserializeErrorKind::UnexpectedEof => f.write_str("Unexpected end of input"),
+            DeserializeErrorKind::BadTypePrefix(prefix) => {
+                write!(f, "Bad type prefix 0x{:02x}", prefix)
+            }
+            DeserializeErrorKind::TooDeep(depth) => write!(f, "TypeSignatureTooDeep: {}", depth),
+            DeserializeErrorKind::IllegalLength { type_name, length } => {
+                write!(f, "Illegal {} type size: {}", type_name, length)
+            }
+            DeserializeErrorKind::InvalidName(message) => f.write_str(message),
+            DeserializeErrorKind::UnknownPayloadId(id) => write!(
+                f,
+                "Failed to parse transaction -- unknown payload ID {}",
+                id
+            ),
+            DeserializeErrorKind::NonCanonical(message) => {
+                write!(f, "Non-canonical Clarity value: {}", message)
+            }
+            DeserializeErrorKind::InvalidValue(message) => f.write_str(message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+/// A deserialization failure, with the byte offset of the cursor where it happened and the
+/// logical path to the failing value, e.g. `payload.function_args[2].tuple.amount`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct DeserializeError {
-    pub error: String,
+    pub kind: DeserializeErrorKind,
+    pub offset: Option<u64>,
+    pub path: Vec<PathSegment>,
 }
 
 impl DeserializeError {
+    pub fn new(kind: DeserializeErrorKind) -> Self {
+        DeserializeError {
+            kind,
+            offset: None,
+            path: vec![],
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+
+    /// Sets the byte offset, unless an inner deserializer already reported a more precise one.
+    pub fn at_offset(mut self, offset: u64) -> Self {
+        if self.offset.is_none() {
+            self.offset = Some(offset);
+        }
+        self
+    }
+
+    /// Makes the offset relative to an enclosing buffer that starts `base` bytes earlier.
+    pub fn shift_offset(mut self, base: u64) -> Self {
+        self.offset = self.offset.map(|offset| offset + base);
+        self
+    }
+
+    /// Prepends a field name to the path, called while the error propagates outwards.
+    pub fn in_field(mut self, name: &str) -> Self {
+        self.path.insert(0, PathSegment::Field(name.to_string()));
+        self
+    }
+
+    /// Prepends an array index to the path.
+    pub fn in_index(mut self, index: usize) -> Self {
+        self.path.insert(0, PathSegment::Index(index));
+        self
+    }
+
+    /// Prepends an indexed field (e.g. `post_conditions[1]`) to the path.
+    pub fn in_element(self, name: &str, index: usize) -> Self {
+        self.in_index(index).in_field(name)
+    }
+
+    pub fn path_string(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Field(name) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                }
+                PathSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+
     pub fn as_string(self) -> String {
-        self.error
+        self.to_string()
+    }
+}
+
+impl From<DeserializeErrorKind> for DeserializeError {
+    fn from(kind: DeserializeErrorKind) -> Self {
+        DeserializeError::new(kind)
     }
 }
 
 impl From<String> for DeserializeError {
     fn from(error: String) -> Self {
-        DeserializeError { error }
+        DeserializeErrorKind::InvalidValue(error).into()
     }
 }
 
 impl From<std::io::Error> for DeserializeError {
     fn from(err: std::io::Error) -> Self {
-        format!("Serialization error: {:?}", err).into()
+        if err.kind() == std::io::ErrorKind::UnexpectedEof {
+            DeserializeErrorKind::UnexpectedEof.into()
+        } else {
+            format!("Serialization error: {:?}", err).into()
+        }
     }
 }
 
 impl From<&str> for DeserializeError {
     fn from(err: &str) -> Self {
-        err.into()
+        err.to_string().into()
     }
 }
 
 impl Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        match (self.path.is_empty(), self.offset) {
+            (true, None) => Ok(()),
+            (true, Some(offset)) => write!(f, " (at byte offset {})", offset),
+            (false, None) => write!(f, " (at `{}`)", self.path_string()),
+            (false, Some(offset)) => {
+                write!(f, " (at `{}`, byte offset {})", self.path_string(), offset)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SerializeError {
+    pub error: String,
+}
+
+impl SerializeError {
+    pub fn as_string(self) -> String {
+        self.error
+    }
+}
+
+impl From<String> for SerializeError {
+    fn from(error: String) -> Self {
+        SerializeError { error }
+    }
+}
+
+impl From<std::io::Error> for SerializeError {
+    fn from(err: std::io::Error) -> Self {
+        format!("Serialization error: {:?}", err).into()
+    }
+}
+
+impl From<&str> for SerializeError {
+    fn from(err: &str) -> Self {
+        err.to_string().into()
+    }
+}
+
+impl Display for SerializeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.error)
     }
@@ -3,20 +3,35 @@ use std::io::{Cursor, Read};
 
 use crate::serialize_util::DeserializeError;
 use crate::stacks_tx::deserialize::{
-    BlockHeaderHash, MessageSignature, Sha512Trunc256Sum, StacksTransaction,
+    BlockHeaderHash, MessageSignature, Sha512Trunc256Sum, StacksMicroblockHeader, StacksTransaction,
 };
 
 /// Consensus hash - 20 bytes
-pub struct ConsensusHash(pub [u8; 20]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConsensusHash(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 20],
+);
 
 /// Stacks block ID - 32 bytes (hash of consensus hash + block header hash)
-pub struct StacksBlockId(pub [u8; 32]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StacksBlockId(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 32],
+);
 
 /// Trie hash for MARF - 32 bytes
-pub struct TrieHash(pub [u8; 32]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TrieHash(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 32],
+);
 
 /// A bitvector with a maximum size
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BitVec {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub data: Vec<u8>,
     pub len: u16,
 }
@@ -37,7 +52,7 @@ impl BitVec {
 
         let expected_data_len = Self::data_len(len);
         let data_len = fd.read_u32::<BigEndian>()?;
-        if data_len as u16 != expected_data_len {
+        if data_len != expected_data_len as u32 {
             return Err(format!(
                 "BitVec data length mismatch: expected {}, got {}",
                 expected_data_len, data_len
@@ -67,6 +82,8 @@ impl BitVec {
 }
 
 /// Header for a Nakamoto block (Stacks 3.x+)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NakamotoBlockHeader {
     /// Version byte
     pub version: u8,
@@ -94,6 +111,10 @@ pub struct NakamotoBlockHeader {
 
 impl NakamotoBlockHeader {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
         let version = fd.read_u8()?;
         let chain_length = fd.read_u64::<BigEndian>()?;
         let burn_spent = fd.read_u64::<BigEndian>()?;
@@ -122,7 +143,10 @@ impl NakamotoBlockHeader {
 
         // Read signer signatures (length-prefixed array)
         let signer_sig_count = fd.read_u32::<BigEndian>()?;
-        let mut signer_signature = Vec::with_capacity(signer_sig_count as usize);
+        // `signer_sig_count` is untrusted, each signature takes 65 bytes of the remaining input
+        let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+        let mut signer_signature =
+            Vec::with_capacity((signer_sig_count as usize).min(remaining / 65));
         for _ in 0..signer_sig_count {
             let mut sig_bytes = [0u8; 65];
             fd.read_exact(&mut sig_bytes)?;
@@ -190,6 +214,8 @@ impl NakamotoBlockHeader {
 }
 
 /// A Nakamoto block (Stacks 3.x+)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NakamotoBlock {
     pub header: NakamotoBlockHeader,
     pub txs: Vec<StacksTransaction>,
@@ -197,13 +223,21 @@ pub struct NakamotoBlock {
 
 impl NakamotoBlock {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
-        let header = NakamotoBlockHeader::deserialize(fd)?;
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        let header = NakamotoBlockHeader::deserialize(fd).map_err(|e| e.in_field("header"))?;
 
         // Read transactions (length-prefixed array)
         let tx_count = fd.read_u32::<BigEndian>()?;
-        let mut txs = Vec::with_capacity(tx_count as usize);
-        for _ in 0..tx_count {
-            txs.push(StacksTransaction::deserialize(fd)?);
+        // `tx_count` is untrusted, each transaction takes more than a byte of the remaining input
+        let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+        let mut txs = Vec::with_capacity((tx_count as usize).min(remaining));
+        for i in 0..tx_count {
+            let tx =
+                StacksTransaction::deserialize(fd).map_err(|e| e.in_element("txs", i as usize))?;
+            txs.push(tx);
         }
 
         Ok(NakamotoBlock { header, txs })
@@ -211,6 +245,8 @@ impl NakamotoBlock {
 }
 
 /// Header for Stacks 2.x blocks
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksBlockHeader {
     pub version: u8,
     /// Total work done on the chain tip this block builds on
@@ -228,17 +264,24 @@ pub struct StacksBlockHeader {
     /// State index root (MARF trie)
     pub state_index_root: TrieHash,
     /// Hash160 of the microblock public key
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub microblock_pubkey_hash: [u8; 20],
 }
 
 /// Work score for Stacks 2.x consensus
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksWorkScore {
     pub burn: u64,
     pub work: u64,
 }
 
 /// VRF proof - 80 bytes
-pub struct VRFProof(pub [u8; 80]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VRFProof(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 80],
+);
 
 impl StacksBlockHeader {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
@@ -313,6 +356,8 @@ impl StacksBlockHeader {
 }
 
 /// A Stacks 2.x block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksBlock {
     pub header: StacksBlockHeader,
     pub txs: Vec<StacksTransaction>,
@@ -320,19 +365,58 @@ pub struct StacksBlock {
 
 impl StacksBlock {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
-        let header = StacksBlockHeader::deserialize(fd)?;
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        let header = StacksBlockHeader::deserialize(fd).map_err(|e| e.in_field("header"))?;
 
         // Read transactions (length-prefixed array)
         let tx_count = fd.read_u32::<BigEndian>()?;
-        let mut txs = Vec::with_capacity(tx_count as usize);
-        for _ in 0..tx_count {
-            txs.push(StacksTransaction::deserialize(fd)?);
+        // `tx_count` is untrusted, each transaction takes more than a byte of the remaining input
+        let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+        let mut txs = Vec::with_capacity((tx_count as usize).min(remaining));
+        for i in 0..tx_count {
+            let tx =
+                StacksTransaction::deserialize(fd).map_err(|e| e.in_element("txs", i as usize))?;
+            txs.push(tx);
         }
 
         Ok(StacksBlock { header, txs })
     }
 }
 
+/// A Stacks 2.x microblock
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StacksMicroblock {
+    pub header: StacksMicroblockHeader,
+    pub txs: Vec<StacksTransaction>,
+}
+
+impl StacksMicroblock {
+    pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        let header = StacksMicroblockHeader::deserialize(fd).map_err(|e| e.in_field("header"))?;
+
+        // Read transactions (length-prefixed array)
+        let tx_count = fd.read_u32::<BigEndian>()?;
+        // `tx_count` is untrusted, each transaction takes more than a byte of the remaining input
+        let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+        let mut txs = Vec::with_capacity((tx_count as usize).min(remaining));
+        for i in 0..tx_count {
+            let tx =
+                StacksTransaction::deserialize(fd).map_err(|e| e.in_element("txs", i as usize))?;
+            txs.push(tx);
+        }
+
+        Ok(StacksMicroblock { header, txs })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,6 +438,10 @@ mod tests {
         assert_eq!(bitvec.get(1), Some(false));
         assert_eq!(bitvec.get(7), Some(false));
         assert_eq!(bitvec.get(8), None);
+
+        // data_len = 0x10001 must not pass as 1
+        let data: Vec<u8> = vec![0x00, 0x08, 0x00, 0x01, 0x00, 0x01, 0b10101010];
+        assert!(BitVec::deserialize(&mut Cursor::new(data.as_ref()), 4000).is_err());
     }
 
     #[test]
@@ -380,4 +468,22 @@ mod tests {
         assert_eq!(block.txs.len(), 1);
         assert_eq!(cursor.position() as usize, data.len());
     }
+
+    #[test]
+    fn test_block_counts_exceed_input() {
+        let data = include_bytes!("../../tests/fixtures/nakamoto-block.bin");
+        // signer signature count, after the 206 bytes of fixed size header fields
+        let mut header = data[..206].to_vec();
+        header.extend_from_slice(&[0xff; 4]);
+        assert!(NakamotoBlockHeader::deserialize(&mut Cursor::new(header.as_ref())).is_err());
+
+        let mut cursor = Cursor::new(data.as_ref());
+        NakamotoBlockHeader::deserialize(&mut cursor).unwrap();
+        let mut data = data[..cursor.position() as usize].to_vec();
+        data.extend_from_slice(&[0xff; 4]);
+        let err = NakamotoBlock::deserialize(&mut Cursor::new(data.as_ref()))
+            .err()
+            .unwrap();
+        assert_eq!(err.offset, Some(data.len() as u64));
+    }
 }
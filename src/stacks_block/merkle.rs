@@ -0,0 +1,252 @@
+use sha2::{Digest, Sha512_256};
+
+use crate::hex::encode_hex;
+use crate::serialize_util::SerializeError;
+use crate::stacks_tx::deserialize::StacksTransaction;
+
+use super::deserialize::{NakamotoBlock, StacksBlock, StacksMicroblock};
+
+// Domain-separation tags, as in stacks-core
+const MERKLE_PATH_LEAF_TAG: u8 = 0x00;
+const MERKLE_PATH_NODE_TAG: u8 = 0x01;
+
+/// SHA512/256 merkle tree with the stacks-core leaf and inner-node tagging. Rows with an odd
+/// number of hashes have their last hash duplicated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleTree {
+    nodes: Vec<Vec<[u8; 32]>>,
+}
+
+/// Which side of the running hash a proof sibling goes on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MerklePathOrder {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MerklePathPoint {
+    pub order: MerklePathOrder,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
+    pub hash: [u8; 32],
+}
+
+impl MerkleTree {
+    pub fn new<T: AsRef<[u8]>>(data: &[T]) -> MerkleTree {
+        if data.is_empty() {
+            return MerkleTree { nodes: vec![] };
+        }
+        let mut row: Vec<[u8; 32]> = data
+            .iter()
+            .map(|item| MerkleTree::leaf_hash(item.as_ref()))
+            .collect();
+        if !row.len().is_multiple_of(2) {
+            row.push(row[row.len() - 1]);
+        }
+        let mut nodes = vec![row];
+        loop {
+            let prev_row = &nodes[nodes.len() - 1];
+            let mut row: Vec<[u8; 32]> = prev_row
+                .chunks(2)
+                .map(|pair| MerkleTree::node_hash(&pair[0], &pair[1]))
+                .collect();
+            if row.len() > 1 && !row.len().is_multiple_of(2) {
+                row.push(row[row.len() - 1]);
+            }
+            let done = row.len() == 1;
+            nodes.push(row);
+            if done {
+                break;
+            }
+        }
+        MerkleTree { nodes }
+    }
+
+    pub fn leaf_hash(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha512_256::new();
+        hasher.update([MERKLE_PATH_LEAF_TAG]);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    pub fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut hasher = Sha512_256::new();
+        hasher.update([MERKLE_PATH_NODE_TAG]);
+        hasher.update(left);
+        hasher.update(right);
+        hasher.finalize().into()
+    }
+
+    /// The root hash, or all zeros for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        match self.nodes.last() {
+            Some(row) => row[0],
+            None => [0u8; 32],
+        }
+    }
+
+    /// The sibling hashes from the leaf at `index` up to (but excluding) the root.
+    pub fn path(&self, index: usize) -> Option<Vec<MerklePathPoint>> {
+        let leaves = self.nodes.first()?;
+        if index >= leaves.len() {
+            return None;
+        }
+        let mut idx = index;
+        let mut path = Vec::with_capacity(self.nodes.len() - 1);
+        for row in &self.nodes[..self.nodes.len() - 1] {
+            let point = if idx.is_multiple_of(2) {
+                MerklePathPoint {
+                    order: MerklePathOrder::Right,
+                    hash: row[idx + 1],
+                }
+            } else {
+                MerklePathPoint {
+                    order: MerklePathOrder::Left,
+                    hash: row[idx - 1],
+                }
+            };
+            path.push(point);
+            idx /= 2;
+        }
+        Some(path)
+    }
+
+    /// Check that `data` is a leaf of the tree with the given `root`.
+    pub fn verify_path(data: &[u8], path: &[MerklePathPoint], root: &[u8; 32]) -> bool {
+        let mut hash = MerkleTree::leaf_hash(data);
+        for point in path {
+            hash = match point.order {
+                MerklePathOrder::Left => MerkleTree::node_hash(&point.hash, &hash),
+                MerklePathOrder::Right => MerkleTree::node_hash(&hash, &point.hash),
+            };
+        }
+        hash == *root
+    }
+}
+
+/// A merkle inclusion proof of a transaction in a block, as returned by `getTxMerkleProof`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxMerkleProof {
+    pub tx_index: usize,
+    pub merkle_root: [u8; 32],
+    pub path: Vec<MerklePathPoint>,
+}
+
+impl TxMerkleProof {
+    /// Build the proof of `txid` from the txids of a block, checking that they hash to the
+    /// block header's `merkle_root`.
+    pub fn new(txids: &[[u8; 32]], merkle_root: [u8; 32], txid: &[u8]) -> Result<Self, String> {
+        let tx_index = txids
+            .iter()
+            .position(|id| id[..] == txid[..])
+            .ok_or_else(|| format!("Transaction {} not found in block", encode_hex(txid)))?;
+        let tree = MerkleTree::new(txids);
+        if tree.root() != merkle_root {
+            return Err(format!(
+                "Computed merkle root {} does not match block header tx_merkle_root {}",
+                encode_hex(&tree.root()),
+                encode_hex(&merkle_root)
+            ));
+        }
+        let path = tree.path(tx_index).unwrap();
+        Ok(TxMerkleProof {
+            tx_index,
+            merkle_root,
+            path,
+        })
+    }
+}
+
+fn txids(txs: &[StacksTransaction]) -> Result<Vec<[u8; 32]>, SerializeError> {
+    txs.iter().map(|tx| tx.txid()).collect()
+}
+
+impl NakamotoBlock {
+    pub fn txids(&self) -> Result<Vec<[u8; 32]>, SerializeError> {
+        txids(&self.txs)
+    }
+
+    pub fn tx_merkle_tree(&self) -> Result<MerkleTree, SerializeError> {
+        Ok(MerkleTree::new(&self.txids()?))
+    }
+    /// Whether the header's `tx_merkle_root` matches the merkle root of the transactions
+    pub fn merkle_root_valid(&self) -> Result<bool, SerializeError> {
+        Ok(self.tx_merkle_tree()?.root() == self.header.tx_merkle_root.0)
+    }
+}
+
+impl StacksBlock {
+    pub fn txids(&self) -> Result<Vec<[u8; 32]>, SerializeError> {
+        txids(&self.txs)
+    }
+
+    pub fn tx_merkle_tree(&self) -> Result<MerkleTree, SerializeError> {
+        Ok(MerkleTree::new(&self.txids()?))
+    }
+    /// Whether the header's `tx_merkle_root` matches the merkle root of the transactions
+    pub fn merkle_root_valid(&self) -> Result<bool, SerializeError> {
+        Ok(self.tx_merkle_tree()?.root() == self.header.tx_merkle_root.0)
+    }
+}
+
+impl StacksMicroblock {
+    pub fn txids(&self) -> Result<Vec<[u8; 32]>, SerializeError> {
+        txids(&self.txs)
+    }
+
+    pub fn tx_merkle_tree(&self) -> Result<MerkleTree, SerializeError> {
+        Ok(MerkleTree::new(&self.txids()?))
+    }
+    /// Whether the header's `tx_merkle_root` matches the merkle root of the transactions
+    pub fn merkle_root_valid(&self) -> Result<bool, SerializeError> {
+        Ok(self.tx_merkle_tree()?.root() == self.header.tx_merkle_root.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn test_nakamoto_block_merkle_root() {
+        let data = include_bytes!("../../tests/fixtures/nakamoto-block.bin");
+        let mut cursor = Cursor::new(data.as_ref());
+        let block = NakamotoBlock::deserialize(&mut cursor).unwrap();
+        let tree = block.tx_merkle_tree().unwrap();
+        assert_eq!(tree.root(), block.header.tx_merkle_root.0);
+
+        let txid = block.txids().unwrap()[0];
+        let path = tree.path(0).unwrap();
+        assert!(MerkleTree::verify_path(&txid, &path, &tree.root()));
+        assert!(!MerkleTree::verify_path(&[0u8; 32], &path, &tree.root()));
+
+        let txids = block.txids().unwrap();
+        let proof = TxMerkleProof::new(&txids, tree.root(), &txid).unwrap();
+        assert_eq!(proof.tx_index, 0);
+        assert_eq!(proof.path, path);
+        assert!(TxMerkleProof::new(&txids, tree.root(), &[0u8; 32]).is_err());
+        assert!(TxMerkleProof::new(&txids, [0u8; 32], &txid).is_err());
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        assert_eq!(MerkleTree::new::<[u8; 32]>(&[]).root(), [0u8; 32]);
+
+        let leaves: Vec<[u8; 32]> = (1u8..=5).map(|i| [i; 32]).collect();
+        let tree = MerkleTree::new(&leaves);
+        assert_eq!(
+            encode_hex(&tree.root()).as_ref(),
+            "0x60d0995611723e315fbba0a8c641328cf87caac58daa9c7bb43d5af87d2e1a68"
+        );
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = tree.path(i).unwrap();
+            assert_eq!(path.len(), 3);
+            assert!(MerkleTree::verify_path(leaf, &path, &tree.root()));
+        }
+        assert!(tree.path(6).is_none());
+    }
+}
@@ -0,0 +1,247 @@
+use std::io::Cursor;
+
+use byteorder::{BigEndian, ReadBytesExt};
+use sha2::{Digest, Sha512_256};
+
+use crate::crypto::{hash160, recover_public_key, serialize_public_key};
+use crate::hex::encode_hex;
+use crate::serialize_util::{DeserializeError, SerializeError};
+use crate::stacks_tx::deserialize::StacksMicroblockHeader;
+
+use super::deserialize::StacksMicroblock;
+
+impl StacksMicroblockHeader {
+    fn hash_with_signature(&self, signature: &[u8; 65]) -> [u8; 32] {
+        let mut hasher = Sha512_256::new();
+        hasher.update([self.version]);
+        hasher.update(self.sequence.to_be_bytes());
+        hasher.update(self.prev_block.0);
+        hasher.update(self.tx_merkle_root.0);
+        hasher.update(signature);
+        hasher.finalize().into()
+    }
+
+    /// The microblock hash: SHA512/256 of the serialized header.
+    pub fn block_hash(&self) -> [u8; 32] {
+        self.hash_with_signature(&self.signature.0)
+    }
+
+    /// The hash signed by the anchor block's miner: the header hash with an empty signature.
+    pub fn signature_hash(&self) -> [u8; 32] {
+        self.hash_with_signature(&[0u8; 65])
+    }
+
+    /// Recover the hash160 of the signer's compressed public key, which is committed to by the
+    /// anchor block's `microblock_pubkey_hash`.
+    pub fn recover_signer_pubkey_hash(&self) -> Result<[u8; 20], String> {
+        let public_key = recover_public_key(&self.signature_hash(), &self.signature.0)?;
+        Ok(hash160(&serialize_public_key(&public_key, true)))
+    }
+}
+
+/// Read a microblock stream, serialized as a u32 count followed by the microblocks.
+pub fn deserialize_microblock_stream(
+    val_bytes: &[u8],
+) -> Result<Vec<StacksMicroblock>, DeserializeError> {
+    let mut cursor = Cursor::new(val_bytes);
+    let count = cursor
+        .read_u32::<BigEndian>()
+        .map_err(|e| DeserializeError::from(e).at_offset(cursor.position()))?;
+    // `count` is untrusted, each microblock takes more than a byte of the remaining input
+    let remaining = val_bytes.len() - cursor.position() as usize;
+    let mut microblocks = Vec::with_capacity((count as usize).min(remaining));
+    for i in 0..count {
+        let microblock = StacksMicroblock::deserialize(&mut cursor)
+            .map_err(|e| e.in_element("microblocks", i as usize))?;
+        microblocks.push(microblock);
+    }
+    Ok(microblocks)
+}
+
+/// Check that a microblock stream starts at sequence 0, that each microblock builds on the
+/// previous one with the next sequence number, that each microblock is signed by the same key,
+/// and that each `tx_merkle_root` matches the microblock's transactions. The first microblock's
+/// `prev_block` and the signer are also checked against the anchor block, if given.
+/// Returns a description of each problem found.
+pub fn verify_microblock_stream(
+    microblocks: &[StacksMicroblock],
+    parent_block_hash: Option<&[u8; 32]>,
+    microblock_pubkey_hash: Option<&[u8; 20]>,
+) -> Result<Vec<String>, SerializeError> {
+    let mut errors = vec![];
+    let mut expected_pubkey_hash = microblock_pubkey_hash.copied();
+    for (i, microblock) in microblocks.iter().enumerate() {
+        let header = &microblock.header;
+        if i == 0 {
+            if header.sequence != 0 {
+                errors.push(format!(
+                    "Microblock 0 has sequence {}, expected 0",
+                    header.sequence
+                ));
+            }
+            if let Some(parent_block_hash) = parent_block_hash {
+                if header.prev_block.0 != *parent_block_hash {
+                    errors.push(format!(
+                        "Microblock 0 prev_block {} does not match the parent block {}",
+                        encode_hex(&header.prev_block.0),
+                        encode_hex(parent_block_hash)
+                    ));
+                }
+            }
+        } else {
+            let prev_header = &microblocks[i - 1].header;
+            if Some(header.sequence) != prev_header.sequence.checked_add(1) {
+                errors.push(format!(
+                    "Microblock {} has sequence {}, expected {}",
+                    i,
+                    header.sequence,
+                    prev_header.sequence as u32 + 1
+                ));
+            }
+            if header.prev_block.0 != prev_header.block_hash() {
+                errors.push(format!(
+                    "Microblock {} prev_block {} does not match the hash of microblock {}",
+                    i,
+                    encode_hex(&header.prev_block.0),
+                    i - 1
+                ));
+            }
+        }
+
+        match header.recover_signer_pubkey_hash() {
+            Ok(pubkey_hash) => match expected_pubkey_hash {
+                Some(expected) if expected != pubkey_hash => {
+                    errors.push(format!(
+                        "Microblock {} is signed by {}, expected {}",
+                        i,
+                        encode_hex(&pubkey_hash),
+                        encode_hex(&expected)
+                    ));
+                }
+                Some(_) => {}
+                None => expected_pubkey_hash = Some(pubkey_hash),
+            },
+            Err(e) => errors.push(format!("Microblock {}: {}", i, e)),
+        }
+
+        if microblock.tx_merkle_tree()?.root() != header.tx_merkle_root.0 {
+            errors.push(format!(
+                "Microblock {} tx_merkle_root does not match its transactions",
+                i
+            ));
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::hex::decode_hex;
+    use crate::stacks_block::merkle::MerkleTree;
+    use crate::stacks_tx::deserialize::{
+        BlockHeaderHash, MessageSignature, Sha512Trunc256Sum, StacksTransaction,
+    };
+
+    const TX: &str = "00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000";
+
+    fn signed_microblock(
+        key: &SigningKey,
+        sequence: u16,
+        prev_block: [u8; 32],
+    ) -> StacksMicroblock {
+        let bytes = decode_hex(TX).unwrap();
+        let tx = StacksTransaction::deserialize(&mut Cursor::new(bytes.as_ref())).unwrap();
+        let mut header = StacksMicroblockHeader {
+            version: 0,
+            sequence,
+            prev_block: BlockHeaderHash(prev_block),
+            tx_merkle_root: Sha512Trunc256Sum(MerkleTree::new(&[tx.txid().unwrap()]).root()),
+            signature: MessageSignature([0u8; 65]),
+            serialized_bytes: vec![],
+        };
+        let (sig, recovery_id) = key
+            .sign_prehash_recoverable(&header.signature_hash())
+            .unwrap();
+        header.signature.0[0] = recovery_id.to_byte();
+        header.signature.0[1..].copy_from_slice(&sig.to_bytes());
+
+        // round trip through the wire format
+        let mut bytes = vec![];
+        header.serialize(&mut bytes).unwrap();
+        bytes.extend_from_slice(&1u32.to_be_bytes());
+        tx.serialize(&mut bytes).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let microblock = StacksMicroblock::deserialize(&mut cursor).unwrap();
+        assert_eq!(cursor.position() as usize, bytes.len());
+        microblock
+    }
+
+    fn signed_stream(key: &SigningKey, parent_block_hash: [u8; 32]) -> Vec<StacksMicroblock> {
+        let mut microblocks = vec![signed_microblock(key, 0, parent_block_hash)];
+        for sequence in 1..3 {
+            let prev_block = microblocks.last().unwrap().header.block_hash();
+            microblocks.push(signed_microblock(key, sequence, prev_block));
+        }
+        microblocks
+    }
+
+    #[test]
+    fn test_recover_signer_pubkey_hash() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let pubkey_hash = hash160(key.verifying_key().to_encoded_point(true).as_bytes());
+        let microblock = signed_microblock(&key, 0, [0u8; 32]);
+        assert_eq!(
+            microblock.header.recover_signer_pubkey_hash().unwrap(),
+            pubkey_hash
+        );
+        assert_ne!(
+            microblock.header.block_hash(),
+            microblock.header.signature_hash()
+        );
+    }
+
+    #[test]
+    fn test_verify_microblock_stream() {
+        let key = SigningKey::from_slice(&[1u8; 32]).unwrap();
+        let pubkey_hash = hash160(key.verifying_key().to_encoded_point(true).as_bytes());
+        let parent_block_hash = [7u8; 32];
+        let microblocks = signed_stream(&key, parent_block_hash);
+        let errors =
+            verify_microblock_stream(&microblocks, Some(&parent_block_hash), Some(&pubkey_hash))
+                .unwrap();
+        assert!(errors.is_empty());
+
+        let errors =
+            verify_microblock_stream(&microblocks, Some(&[0u8; 32]), Some(&[0u8; 20])).unwrap();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].starts_with("Microblock 0 prev_block"));
+        assert!(errors[1].starts_with("Microblock 0 is signed by"));
+
+        // drop the middle microblock
+        let mut microblocks = microblocks;
+        microblocks.remove(1);
+        let errors = verify_microblock_stream(&microblocks, None, None).unwrap();
+        assert_eq!(
+            errors,
+            vec![
+                "Microblock 1 has sequence 2, expected 1".to_string(),
+                "Microblock 1 prev_block ".to_string()
+                    + &encode_hex(&microblocks[1].header.prev_block.0)
+                    + " does not match the hash of microblock 0",
+            ]
+        );
+
+        // a microblock signed by a different key
+        let other_key = SigningKey::from_slice(&[2u8; 32]).unwrap();
+        let prev_block = microblocks[0].header.block_hash();
+        microblocks[1] = signed_microblock(&other_key, 1, prev_block);
+        let errors = verify_microblock_stream(&microblocks, None, None).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Microblock 1 is signed by"));
+    }
+}
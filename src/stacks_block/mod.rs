@@ -1,50 +1,416 @@
+#[cfg(feature = "nodejs")]
 use neon::prelude::*;
+#[cfg(feature = "nodejs")]
+use std::convert::TryInto;
+#[cfg(feature = "nodejs")]
 use std::io::Cursor;
 
+#[cfg(feature = "nodejs")]
+use crate::crypto::hash160;
+#[cfg(feature = "nodejs")]
 use crate::hex::encode_hex;
+#[cfg(feature = "nodejs")]
 use crate::neon_util::*;
+#[cfg(feature = "nodejs")]
+use crate::serialize_util::DeserializeError;
 
-use self::deserialize::{NakamotoBlock, StacksBlock};
+#[cfg(feature = "nodejs")]
+use self::deserialize::{NakamotoBlock, NakamotoBlockHeader, StacksBlock, StacksMicroblock};
+#[cfg(feature = "nodejs")]
+use self::merkle::{MerklePathOrder, MerklePathPoint, MerkleTree, TxMerkleProof};
+#[cfg(feature = "nodejs")]
+use self::microblock::{deserialize_microblock_stream, verify_microblock_stream};
+#[cfg(feature = "nodejs")]
+use self::signers::RewardSetSigner;
 
 pub mod deserialize;
+pub mod merkle;
+pub mod microblock;
+#[cfg(feature = "nodejs")]
 mod neon_encoder;
+pub mod signers;
 
 /// Decode a Nakamoto block (Stacks 3.x+)
+#[cfg(feature = "nodejs")]
 pub fn decode_nakamoto_block(mut cx: FunctionContext) -> JsResult<JsObject> {
     let block = arg_as_bytes(&mut cx, 0, |val_bytes| {
         let mut cursor = Cursor::new(val_bytes);
-        let block = NakamotoBlock::deserialize(&mut cursor)
-            .or_else(|e| Err(format!("Failed to decode Nakamoto block: {:?}\n", &e)))?;
-        Ok(block)
+        NakamotoBlock::deserialize(&mut cursor)
     })
-    .or_else(|e| cx.throw_error(e))?;
+    .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode Nakamoto block", &e))?;
 
+    let merkle_root_valid = if verify_merkle_root_arg(&mut cx, 1)? {
+        let valid = block
+            .merkle_root_valid()
+            .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+        Some(valid)
+    } else {
+        None
+    };
+    let options = OutputOptions::from_arg(&mut cx, 2)?;
+    nakamoto_block_to_js(&mut cx, &block, merkle_root_valid, options)
+}
+
+/// Like `decode_nakamoto_block`, but deserializes on the libuv threadpool and returns a Promise
+#[cfg(feature = "nodejs")]
+pub fn decode_nakamoto_block_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let verify_merkle_root = verify_merkle_root_arg(&mut cx, 1)?;
+    let options = OutputOptions::from_arg(&mut cx, 2)?;
+    let promise = cx
+        .task(move || {
+            let mut cursor = Cursor::new(val_bytes.as_ref());
+            let block = NakamotoBlock::deserialize(&mut cursor)?;
+            let merkle_root_valid = verify_merkle_root.then(|| block.merkle_root_valid());
+            Ok((block, merkle_root_valid))
+        })
+        .promise(move |mut cx, result: Result<_, DeserializeError>| {
+            let (block, merkle_root_valid) = result.or_else(|e| {
+                throw_deserialize_error(&mut cx, "Failed to decode Nakamoto block", &e)
+            })?;
+            let merkle_root_valid = merkle_root_valid
+                .transpose()
+                .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+            nakamoto_block_to_js(&mut cx, &block, merkle_root_valid, options)
+        });
+    Ok(promise)
+}
+
+#[cfg(feature = "nodejs")]
+fn nakamoto_block_to_js<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    block: &NakamotoBlock,
+    merkle_root_valid: Option<bool>,
+    options: OutputOptions,
+) -> JsResult<'cx, JsObject> {
     let block_obj = cx.empty_object();
 
     // Add computed block_id at top level for convenience
     let block_id = cx.string(encode_hex(&block.header.block_id()));
-    block_obj.set(&mut cx, "block_id", block_id)?;
+    block_obj.set(cx, "block_id", block_id)?;
+
+    if let Some(merkle_root_valid) = merkle_root_valid {
+        let merkle_root_valid = cx.boolean(merkle_root_valid);
+        block_obj.set(cx, "merkle_root_valid", merkle_root_valid)?;
+    }
 
-    block.neon_js_serialize(&mut cx, &block_obj, &())?;
+    block.neon_js_serialize(cx, &block_obj, &options)?;
     Ok(block_obj)
 }
 
 /// Decode a Stacks 2.x block
+#[cfg(feature = "nodejs")]
 pub fn decode_stacks_block(mut cx: FunctionContext) -> JsResult<JsObject> {
     let block = arg_as_bytes(&mut cx, 0, |val_bytes| {
         let mut cursor = Cursor::new(val_bytes);
-        let block = StacksBlock::deserialize(&mut cursor)
-            .or_else(|e| Err(format!("Failed to decode Stacks block: {:?}\n", &e)))?;
-        Ok(block)
+        StacksBlock::deserialize(&mut cursor)
     })
-    .or_else(|e| cx.throw_error(e))?;
+    .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode Stacks block", &e))?;
 
+    let merkle_root_valid = if verify_merkle_root_arg(&mut cx, 1)? {
+        let valid = block
+            .merkle_root_valid()
+            .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+        Some(valid)
+    } else {
+        None
+    };
+    let options = OutputOptions::from_arg(&mut cx, 2)?;
+    stacks_block_to_js(&mut cx, &block, merkle_root_valid, options)
+}
+
+/// Like `decode_stacks_block`, but deserializes on the libuv threadpool and returns a Promise
+#[cfg(feature = "nodejs")]
+pub fn decode_stacks_block_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let verify_merkle_root = verify_merkle_root_arg(&mut cx, 1)?;
+    let options = OutputOptions::from_arg(&mut cx, 2)?;
+    let promise = cx
+        .task(move || {
+            let mut cursor = Cursor::new(val_bytes.as_ref());
+            let block = StacksBlock::deserialize(&mut cursor)?;
+            let merkle_root_valid = verify_merkle_root.then(|| block.merkle_root_valid());
+            Ok((block, merkle_root_valid))
+        })
+        .promise(move |mut cx, result: Result<_, DeserializeError>| {
+            let (block, merkle_root_valid) = result.or_else(|e| {
+                throw_deserialize_error(&mut cx, "Failed to decode Stacks block", &e)
+            })?;
+            let merkle_root_valid = merkle_root_valid
+                .transpose()
+                .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+            stacks_block_to_js(&mut cx, &block, merkle_root_valid, options)
+        });
+    Ok(promise)
+}
+
+#[cfg(feature = "nodejs")]
+fn stacks_block_to_js<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    block: &StacksBlock,
+    merkle_root_valid: Option<bool>,
+    options: OutputOptions,
+) -> JsResult<'cx, JsObject> {
     let block_obj = cx.empty_object();
 
     // Add computed block_hash at top level for convenience
     let block_hash = cx.string(encode_hex(&block.header.block_hash()));
-    block_obj.set(&mut cx, "block_hash", block_hash)?;
+    block_obj.set(cx, "block_hash", block_hash)?;
 
-    block.neon_js_serialize(&mut cx, &block_obj, &())?;
+    if let Some(merkle_root_valid) = merkle_root_valid {
+        let merkle_root_valid = cx.boolean(merkle_root_valid);
+        block_obj.set(cx, "merkle_root_valid", merkle_root_valid)?;
+    }
+
+    block.neon_js_serialize(cx, &block_obj, &options)?;
     Ok(block_obj)
 }
+
+/// Decode a Stacks 2.x microblock
+#[cfg(feature = "nodejs")]
+pub fn decode_microblock(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let microblock = arg_as_bytes(&mut cx, 0, |val_bytes| {
+        let mut cursor = Cursor::new(val_bytes);
+        StacksMicroblock::deserialize(&mut cursor)
+    })
+    .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode microblock", &e))?;
+
+    let microblock_obj = cx.empty_object();
+    if verify_merkle_root_arg(&mut cx, 1)? {
+        let merkle_root_valid = microblock
+            .merkle_root_valid()
+            .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+        let merkle_root_valid = cx.boolean(merkle_root_valid);
+        microblock_obj.set(&mut cx, "merkle_root_valid", merkle_root_valid)?;
+    }
+    let options = OutputOptions::from_arg(&mut cx, 2)?;
+    microblock.neon_js_serialize(&mut cx, &microblock_obj, &options)?;
+    Ok(microblock_obj)
+}
+
+/// Decode a length-prefixed stream of Stacks 2.x microblocks and verify that it is well-formed
+#[cfg(feature = "nodejs")]
+pub fn decode_microblock_stream(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let microblocks = arg_as_bytes(&mut cx, 0, deserialize_microblock_stream)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode microblock stream", &e))?;
+    let parent_block_hash = optional_fixed_bytes_arg::<32>(&mut cx, 1, "Parent block hash")?;
+    let microblock_pubkey_hash =
+        optional_fixed_bytes_arg::<20>(&mut cx, 2, "Microblock public key hash")?;
+    let options = OutputOptions::from_arg(&mut cx, 3)?;
+
+    let errors = verify_microblock_stream(
+        &microblocks,
+        parent_block_hash.as_ref(),
+        microblock_pubkey_hash.as_ref(),
+    )
+    .or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+
+    let resp_obj = cx.empty_object();
+    let valid = cx.boolean(errors.is_empty());
+    resp_obj.set(&mut cx, "valid", valid)?;
+    let errors_array = JsArray::new(&mut cx, errors.len());
+    for (i, error) in errors.iter().enumerate() {
+        let error = cx.string(error);
+        errors_array.set(&mut cx, i as u32, error)?;
+    }
+    resp_obj.set(&mut cx, "errors", errors_array)?;
+    let microblocks_array = JsArray::new(&mut cx, microblocks.len());
+    for (i, microblock) in microblocks.iter().enumerate() {
+        let microblock_obj = cx.empty_object();
+        microblock.neon_js_serialize(&mut cx, &microblock_obj, &options)?;
+        microblocks_array.set(&mut cx, i as u32, microblock_obj)?;
+    }
+    resp_obj.set(&mut cx, "microblocks", microblocks_array)?;
+    Ok(resp_obj)
+}
+
+#[cfg(feature = "nodejs")]
+fn optional_fixed_bytes_arg<const N: usize>(
+    cx: &mut FunctionContext,
+    arg_index: i32,
+    name: &str,
+) -> NeonResult<Option<[u8; N]>> {
+    match cx.argument_opt(arg_index as usize) {
+        Some(arg) if !arg.is_a::<JsUndefined, _>(cx) && !arg.is_a::<JsNull, _>(cx) => {
+            let bytes = arg_as_bytes_copied(cx, arg_index)?;
+            let len = bytes.len();
+            let bytes = bytes.as_ref().try_into().or_else(|_| {
+                cx.throw_error(format!(
+                    "{} must be {} bytes, received {} bytes",
+                    name, N, len
+                ))
+            })?;
+            Ok(Some(bytes))
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(feature = "nodejs")]
+fn verify_merkle_root_arg(cx: &mut FunctionContext, arg_index: usize) -> NeonResult<bool> {
+    match cx.argument_opt(arg_index) {
+        Some(arg) if !arg.is_a::<JsUndefined, _>(cx) => {
+            Ok(arg.downcast_or_throw::<JsBoolean, _>(cx)?.value(cx))
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Build a merkle inclusion proof for a transaction in a Nakamoto or Stacks 2.x block
+#[cfg(feature = "nodejs")]
+pub fn get_tx_merkle_proof(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let block_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let txid = arg_as_bytes_copied(&mut cx, 1)?;
+    let block_type = cx.argument::<JsString>(2)?.value(&mut cx);
+
+    let mut cursor = Cursor::new(block_bytes.as_ref());
+    let (txids, merkle_root) = match block_type.as_str() {
+        "nakamoto" => {
+            let block = NakamotoBlock::deserialize(&mut cursor).or_else(|e| {
+                throw_deserialize_error(&mut cx, "Failed to decode Nakamoto block", &e)
+            })?;
+            (block.txids(), block.header.tx_merkle_root.0)
+        }
+        "stacks" => {
+            let block = StacksBlock::deserialize(&mut cursor).or_else(|e| {
+                throw_deserialize_error(&mut cx, "Failed to decode Stacks block", &e)
+            })?;
+            (block.txids(), block.header.tx_merkle_root.0)
+        }
+        _ => cx.throw_error(format!("Unknown block type: {}", block_type))?,
+    };
+    let txids = txids.or_else(|e| cx.throw_error(format!("Error computing txids: {}", e)))?;
+    let proof = TxMerkleProof::new(&txids, merkle_root, &txid).or_else(|e| cx.throw_error(e))?;
+
+    let resp_obj = cx.empty_object();
+    let tx_index = cx.number(proof.tx_index as u32);
+    resp_obj.set(&mut cx, "tx_index", tx_index)?;
+    let merkle_root = cx.string(encode_hex(&proof.merkle_root));
+    resp_obj.set(&mut cx, "merkle_root", merkle_root)?;
+    let path_array = JsArray::new(&mut cx, proof.path.len());
+    for (i, point) in proof.path.iter().enumerate() {
+        let point_obj = cx.empty_object();
+        let order = cx.string(match point.order {
+            MerklePathOrder::Left => "left",
+            MerklePathOrder::Right => "right",
+        });
+        point_obj.set(&mut cx, "order", order)?;
+        let hash = cx.string(encode_hex(&point.hash));
+        point_obj.set(&mut cx, "hash", hash)?;
+        path_array.set(&mut cx, i as u32, point_obj)?;
+    }
+    resp_obj.set(&mut cx, "path", path_array)?;
+    Ok(resp_obj)
+}
+
+/// Check a merkle inclusion proof produced by `get_tx_merkle_proof`
+#[cfg(feature = "nodejs")]
+pub fn verify_tx_merkle_proof(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let txid = arg_as_bytes_copied(&mut cx, 0)?;
+    let proof_obj = cx.argument::<JsObject>(1)?;
+    let merkle_root = get_obj_prop_fixed_bytes::<32>(&mut cx, &proof_obj, "merkle_root")?;
+    let path_values = get_obj_prop::<JsArray>(&mut cx, &proof_obj, "path")?.to_vec(&mut cx)?;
+    let mut path = Vec::with_capacity(path_values.len());
+    for point in path_values {
+        let point_obj = point.downcast_or_throw::<JsObject, _>(&mut cx)?;
+        let order = match get_obj_prop_string(&mut cx, &point_obj, "order")?.as_str() {
+            "left" => MerklePathOrder::Left,
+            "right" => MerklePathOrder::Right,
+            order => cx.throw_error(format!("Invalid merkle path order: {}", order))?,
+        };
+        let hash = get_obj_prop_fixed_bytes::<32>(&mut cx, &point_obj, "hash")?;
+        path.push(MerklePathPoint { order, hash });
+    }
+    let valid = MerkleTree::verify_path(&txid, &path, &merkle_root);
+    Ok(cx.boolean(valid))
+}
+
+#[cfg(feature = "nodejs")]
+fn nakamoto_block_header_arg(
+    cx: &mut FunctionContext,
+    arg_index: i32,
+) -> NeonResult<NakamotoBlockHeader> {
+    arg_as_bytes(cx, arg_index, |val_bytes| {
+        let mut cursor = Cursor::new(val_bytes);
+        NakamotoBlockHeader::deserialize(&mut cursor)
+    })
+    .or_else(|e| throw_deserialize_error(cx, "Failed to decode Nakamoto block header", &e))
+}
+
+/// Verify the signer signatures of a Nakamoto block against a reward set
+#[cfg(feature = "nodejs")]
+pub fn verify_nakamoto_block_signer_signatures(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let header = nakamoto_block_header_arg(&mut cx, 0)?;
+    let reward_set_values = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+    let mut reward_set = Vec::with_capacity(reward_set_values.len());
+    for value in reward_set_values {
+        let signer_obj = value.downcast_or_throw::<JsObject, _>(&mut cx)?;
+        let signing_key = get_obj_prop_fixed_bytes::<33>(&mut cx, &signer_obj, "signing_key")?;
+        let weight = get_obj_prop_number(&mut cx, &signer_obj, "weight")?;
+        if weight.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&weight) {
+            return cx.throw_type_error("Property `weight` must be a u32 integer");
+        }
+        reward_set.push(RewardSetSigner {
+            signing_key,
+            weight: weight as u32,
+        });
+    }
+
+    let verification = header.verify_signer_signatures(&reward_set);
+
+    let resp_obj = cx.empty_object();
+    let threshold_met = cx.boolean(verification.threshold_met());
+    resp_obj.set(&mut cx, "threshold_met", threshold_met)?;
+    let total_weight = cx.number(verification.total_weight as f64);
+    resp_obj.set(&mut cx, "total_weight", total_weight)?;
+    let signed_weight = cx.number(verification.signed_weight as f64);
+    resp_obj.set(&mut cx, "signed_weight", signed_weight)?;
+    let threshold = cx.number(verification.threshold as f64);
+    resp_obj.set(&mut cx, "threshold", threshold)?;
+
+    let signers_array = JsArray::new(&mut cx, verification.signer_indices.len());
+    for (i, signer_index) in verification.signer_indices.iter().enumerate() {
+        let signer = &reward_set[*signer_index];
+        let signer_obj = cx.empty_object();
+        let index = cx.number(*signer_index as u32);
+        signer_obj.set(&mut cx, "index", index)?;
+        let signing_key = cx.string(encode_hex(&signer.signing_key));
+        signer_obj.set(&mut cx, "signing_key", signing_key)?;
+        let weight = cx.number(signer.weight);
+        signer_obj.set(&mut cx, "weight", weight)?;
+        signers_array.set(&mut cx, i as u32, signer_obj)?;
+    }
+    resp_obj.set(&mut cx, "signers", signers_array)?;
+
+    if let Some(error) = verification.error {
+        let error = cx.string(error);
+        resp_obj.set(&mut cx, "error", error)?;
+    } else {
+        let error = cx.null();
+        resp_obj.set(&mut cx, "error", error)?;
+    }
+    Ok(resp_obj)
+}
+
+/// Verify that a Nakamoto block's miner signature recovers to the given miner public key hash
+#[cfg(feature = "nodejs")]
+pub fn verify_nakamoto_block_miner_signature(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let header = nakamoto_block_header_arg(&mut cx, 0)?;
+    let miner_pubkey_hash = arg_as_bytes_copied(&mut cx, 1)?;
+    if miner_pubkey_hash.len() != 20 {
+        return cx.throw_error(format!(
+            "Miner public key hash must be 20 bytes, received {} bytes",
+            miner_pubkey_hash.len()
+        ));
+    }
+    let miner_public_key = header
+        .recover_miner_public_key()
+        .or_else(|e| cx.throw_error(format!("Error recovering miner public key: {}", e)))?;
+
+    let resp_obj = cx.empty_object();
+    let valid = cx.boolean(hash160(&miner_public_key)[..] == miner_pubkey_hash[..]);
+    resp_obj.set(&mut cx, "valid", valid)?;
+    let miner_public_key = cx.string(encode_hex(&miner_public_key));
+    resp_obj.set(&mut cx, "miner_public_key", miner_public_key)?;
+    Ok(resp_obj)
+}
@@ -1,19 +1,19 @@
 use neon::prelude::*;
 
 use crate::hex::encode_hex;
-use crate::neon_util::NeonJsSerialize;
+use crate::neon_util::{NeonJsSerialize, OutputOptions};
 
 use super::deserialize::{
-    BitVec, NakamotoBlock, NakamotoBlockHeader, StacksBlock, StacksBlockHeader,
+    BitVec, NakamotoBlock, NakamotoBlockHeader, StacksBlock, StacksBlockHeader, StacksMicroblock,
     StacksWorkScore,
 };
 
-impl NeonJsSerialize for NakamotoBlock {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for NakamotoBlock {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        _extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
         // Serialize header
         let header_obj = cx.empty_object();
@@ -24,7 +24,7 @@ impl NeonJsSerialize for NakamotoBlock {
         let txs_array = JsArray::new(cx, self.txs.len());
         for (i, tx) in self.txs.iter().enumerate() {
             let tx_obj = cx.empty_object();
-            tx.neon_js_serialize(cx, &tx_obj, &())?;
+            tx.neon_js_serialize(cx, &tx_obj, options)?;
             txs_array.set(cx, i as u32, tx_obj)?;
         }
         obj.set(cx, "txs", txs_array)?;
@@ -34,9 +34,9 @@ impl NeonJsSerialize for NakamotoBlock {
 }
 
 impl NeonJsSerialize for NakamotoBlockHeader {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -92,9 +92,9 @@ impl NeonJsSerialize for NakamotoBlockHeader {
 }
 
 impl NeonJsSerialize for BitVec {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -116,12 +116,12 @@ impl NeonJsSerialize for BitVec {
     }
 }
 
-impl NeonJsSerialize for StacksBlock {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for StacksBlock {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        _extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
         // Serialize header
         let header_obj = cx.empty_object();
@@ -132,7 +132,42 @@ impl NeonJsSerialize for StacksBlock {
         let txs_array = JsArray::new(cx, self.txs.len());
         for (i, tx) in self.txs.iter().enumerate() {
             let tx_obj = cx.empty_object();
-            tx.neon_js_serialize(cx, &tx_obj, &())?;
+            tx.neon_js_serialize(cx, &tx_obj, options)?;
+            txs_array.set(cx, i as u32, tx_obj)?;
+        }
+        obj.set(cx, "txs", txs_array)?;
+
+        Ok(())
+    }
+}
+
+impl NeonJsSerialize<OutputOptions> for StacksMicroblock {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
+        &self,
+        cx: &mut C,
+        obj: &Handle<JsObject>,
+        options: &OutputOptions,
+    ) -> NeonResult<()> {
+        let block_hash = cx.string(encode_hex(&self.header.block_hash()));
+        obj.set(cx, "block_hash", block_hash)?;
+
+        // Null if the signature is malformed
+        let signer_pubkey_hash: Handle<JsValue> = match self.header.recover_signer_pubkey_hash() {
+            Ok(pubkey_hash) => cx.string(encode_hex(&pubkey_hash)).upcast(),
+            Err(_) => cx.null().upcast(),
+        };
+        obj.set(cx, "signer_pubkey_hash", signer_pubkey_hash)?;
+
+        // Serialize header
+        let header_obj = cx.empty_object();
+        self.header.neon_js_serialize(cx, &header_obj, &())?;
+        obj.set(cx, "header", header_obj)?;
+
+        // Serialize transactions
+        let txs_array = JsArray::new(cx, self.txs.len());
+        for (i, tx) in self.txs.iter().enumerate() {
+            let tx_obj = cx.empty_object();
+            tx.neon_js_serialize(cx, &tx_obj, options)?;
             txs_array.set(cx, i as u32, tx_obj)?;
         }
         obj.set(cx, "txs", txs_array)?;
@@ -142,9 +177,9 @@ impl NeonJsSerialize for StacksBlock {
 }
 
 impl NeonJsSerialize for StacksBlockHeader {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -187,9 +222,9 @@ impl NeonJsSerialize for StacksBlockHeader {
 }
 
 impl NeonJsSerialize for StacksWorkScore {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
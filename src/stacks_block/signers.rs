@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha512_256};
+
+use crate::crypto::{hash160, recover_public_key, serialize_public_key};
+
+use super::deserialize::NakamotoBlockHeader;
+
+/// A signer entry of a reward set, as returned by the `/v3/stacker_set` endpoint.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RewardSetSigner {
+    /// Compressed secp256k1 public key
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
+    pub signing_key: [u8; 33],
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SignerSignatureVerification {
+    /// Sum of the weights of every signer in the reward set
+    pub total_weight: u64,
+    /// Sum of the weights of the signers with a recovered signature
+    pub signed_weight: u64,
+    /// 70% of `total_weight`, rounded up
+    pub threshold: u64,
+    /// Reward set indices of the signers with a recovered signature, in signature order
+    pub signer_indices: Vec<usize>,
+    /// The first unrecoverable, unknown, duplicate, or out-of-order signature, if any
+    pub error: Option<String>,
+}
+
+impl SignerSignatureVerification {
+    pub fn threshold_met(&self) -> bool {
+        self.error.is_none() && self.signed_weight >= self.threshold
+    }
+}
+
+/// The minimum signed weight for a block to be accepted, as in stacks-core.
+pub fn compute_voting_weight_threshold(total_weight: u64) -> u64 {
+    (total_weight * 7).div_ceil(10)
+}
+
+impl NakamotoBlockHeader {
+    /// The hash signed by the miner: the block hash without the miner and signer signatures.
+    pub fn miner_signature_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha512_256::new();
+        hasher.update([self.version]);
+        hasher.update(self.chain_length.to_be_bytes());
+        hasher.update(self.burn_spent.to_be_bytes());
+        hasher.update(self.consensus_hash.0);
+        hasher.update(self.parent_block_id.0);
+        hasher.update(self.tx_merkle_root.0);
+        hasher.update(self.state_index_root.0);
+        hasher.update(self.timestamp.to_be_bytes());
+        hasher.update(self.pox_treatment.len.to_be_bytes());
+        hasher.update((self.pox_treatment.data.len() as u32).to_be_bytes());
+        hasher.update(&self.pox_treatment.data);
+        hasher.finalize().into()
+    }
+
+    /// Recover the miner's compressed public key from `miner_signature`.
+    pub fn recover_miner_public_key(&self) -> Result<Vec<u8>, String> {
+        let public_key = recover_public_key(&self.miner_signature_hash(), &self.miner_signature.0)?;
+        Ok(serialize_public_key(&public_key, true))
+    }
+
+    /// Check that `miner_signature` was produced by the key with the given hash160.
+    pub fn verify_miner_signature(&self, miner_pubkey_hash: &[u8; 20]) -> Result<bool, String> {
+        let public_key = self.recover_miner_public_key()?;
+        Ok(hash160(&public_key) == *miner_pubkey_hash)
+    }
+
+    /// Recover each signer key from the signer signature hash (`block_hash()`) and sum the
+    /// weights of the matching reward set signers. As in stacks-core, signatures must be in
+    /// reward set order.
+    pub fn verify_signer_signatures(
+        &self,
+        reward_set: &[RewardSetSigner],
+    ) -> SignerSignatureVerification {
+        let total_weight: u64 = reward_set.iter().map(|signer| signer.weight as u64).sum();
+        let signers_by_key: HashMap<&[u8], usize> = reward_set
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| (&signer.signing_key[..], i))
+            .collect();
+        let message = self.block_hash();
+
+        let mut verification = SignerSignatureVerification {
+            total_weight,
+            signed_weight: 0,
+            threshold: compute_voting_weight_threshold(total_weight),
+            signer_indices: vec![],
+            error: None,
+        };
+        for (i, signature) in self.signer_signature.iter().enumerate() {
+            let signer_index = recover_public_key(&message, &signature.0).and_then(|key| {
+                let key = serialize_public_key(&key, true);
+                signers_by_key.get(&key[..]).copied().ok_or_else(|| {
+                    format!("Signature {} is from a signer not in the reward set", i)
+                })
+            });
+            let signer_index = match signer_index {
+                Ok(signer_index) => signer_index,
+                Err(e) => {
+                    verification.error.get_or_insert(e);
+                    continue;
+                }
+            };
+            if verification.signer_indices.contains(&signer_index) {
+                verification
+                    .error
+                    .get_or_insert(format!("Signature {} is a duplicate", i));
+                continue;
+            }
+            if matches!(verification.signer_indices.last(), Some(last_index) if *last_index > signer_index)
+            {
+                verification
+                    .error
+                    .get_or_insert(format!("Signature {} is out of order", i));
+            }
+            verification.signed_weight += reward_set[signer_index].weight as u64;
+            verification.signer_indices.push(signer_index);
+        }
+        verification
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::hex::decode_hex;
+    use crate::stacks_block::deserialize::NakamotoBlock;
+
+    fn fixture_block() -> NakamotoBlock {
+        let data = include_bytes!("../../tests/fixtures/nakamoto-block.bin");
+        let mut cursor = Cursor::new(data.as_ref());
+        NakamotoBlock::deserialize(&mut cursor).unwrap()
+    }
+
+    /// The fixture's signers, recovered from its signatures, in reward set (sorted key) order
+    fn fixture_signers(header: &NakamotoBlockHeader) -> Vec<RewardSetSigner> {
+        let message = header.block_hash();
+        header
+            .signer_signature
+            .iter()
+            .map(|sig| {
+                let key = recover_public_key(&message, &sig.0).unwrap();
+                RewardSetSigner {
+                    signing_key: serialize_public_key(&key, true).try_into().unwrap(),
+                    weight: 1,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_signer_signatures() {
+        let header = fixture_block().header;
+        let mut reward_set = fixture_signers(&header);
+        assert_eq!(
+            reward_set[0].signing_key[..],
+            decode_hex("0x02254a34747123978819f2a90506f76cb057fe3fbff6d8721a0d9cf8e9412d0e60")
+                .unwrap()[..]
+        );
+        assert!(reward_set
+            .windows(2)
+            .all(|pair| pair[0].signing_key < pair[1].signing_key));
+
+        // 24 of 30 signed
+        for i in 0..6u8 {
+            let mut signing_key = [i; 33];
+            signing_key[0] = 0x02;
+            reward_set.push(RewardSetSigner {
+                signing_key,
+                weight: 1,
+            });
+        }
+        let verification = header.verify_signer_signatures(&reward_set);
+        assert!(verification.threshold_met());
+        assert_eq!(verification.total_weight, 30);
+        assert_eq!(verification.threshold, 21);
+        assert_eq!(verification.signed_weight, 24);
+        assert_eq!(verification.signer_indices, (0..24).collect::<Vec<_>>());
+
+        // a non-signer holds most of the weight
+        reward_set[29].weight = 100;
+        let verification = header.verify_signer_signatures(&reward_set);
+        assert!(verification.error.is_none());
+        assert!(!verification.threshold_met());
+        assert_eq!(verification.threshold, 91);
+
+        reward_set.remove(3);
+        let verification = header.verify_signer_signatures(&reward_set);
+        assert!(!verification.threshold_met());
+        assert_eq!(
+            verification.error.unwrap(),
+            "Signature 3 is from a signer not in the reward set"
+        );
+        assert_eq!(verification.signer_indices.len(), 23);
+
+        let mut reward_set = fixture_signers(&header);
+        reward_set.swap(0, 1);
+        let verification = header.verify_signer_signatures(&reward_set);
+        assert_eq!(verification.error.unwrap(), "Signature 1 is out of order");
+    }
+
+    #[test]
+    fn test_verify_miner_signature() {
+        let header = fixture_block().header;
+        // hash160 of the key that signed the block's tenure change tx
+        let miner_pubkey_hash: [u8; 20] = decode_hex("0x37e79a837b4071a1fc6c1b49208e7d2141a25905")
+            .unwrap()[..]
+            .try_into()
+            .unwrap();
+        assert!(header.verify_miner_signature(&miner_pubkey_hash).unwrap());
+        assert!(!header.verify_miner_signature(&[0u8; 20]).unwrap());
+    }
+
+    #[test]
+    fn test_voting_weight_threshold() {
+        assert_eq!(compute_voting_weight_threshold(10), 7);
+        assert_eq!(compute_voting_weight_threshold(11), 8);
+        assert_eq!(compute_voting_weight_threshold(0), 0);
+    }
+}
@@ -3,15 +3,18 @@ use std::io::{Cursor, Read};
 
 use crate::address::stacks_address::StacksAddress;
 use crate::clarity_value::deserialize::TypePrefix;
-use crate::clarity_value::types::{ClarityName, ClarityValue};
+use crate::clarity_value::types::{ClarityName, SerializedClarityValue};
 use crate::post_condition::deserialize::TransactionPostCondition;
-use crate::serialize_util::DeserializeError;
+use crate::serialize_util::{DeserializeError, DeserializeErrorKind};
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksTransaction {
     pub version: TransactionVersion,
     pub chain_id: u32,
     pub auth: TransactionAuth,
     pub anchor_mode: TransactionAnchorMode,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub post_conditions_serialized: Vec<u8>,
     pub post_condition_mode: TransactionPostConditionMode,
     pub post_conditions: Vec<TransactionPostCondition>,
@@ -20,24 +23,33 @@ pub struct StacksTransaction {
 
 impl StacksTransaction {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
+        Self::deserialize_fields(fd).map_err(|e| e.at_offset(fd.position()))
+    }
+
+    fn deserialize_fields(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
         let version_u8 = fd.read_u8()?;
         let chain_id: u32 = fd.read_u32::<BigEndian>()?;
-        let auth = TransactionAuth::deserialize(fd)?;
+        let auth = TransactionAuth::deserialize(fd).map_err(|e| e.in_field("auth"))?;
         let anchor_mode_u8 = fd.read_u8()?;
 
         let cursor_pos = fd.position() as usize;
         let post_condition_mode_u8 = fd.read_u8()?;
         let post_conditions: Vec<TransactionPostCondition> = {
             let len = fd.read_u32::<BigEndian>()?;
-            let mut results: Vec<TransactionPostCondition> = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                results.push(TransactionPostCondition::deserialize(fd)?);
+            // `len` is untrusted, each post condition takes at least a byte of the remaining input
+            let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+            let mut results: Vec<TransactionPostCondition> =
+                Vec::with_capacity((len as usize).min(remaining));
+            for i in 0..len {
+                let post_condition = TransactionPostCondition::deserialize(fd)
+                    .map_err(|e| e.in_element("post_conditions", i as usize))?;
+                results.push(post_condition);
             }
             results
         };
         let post_conditions_serialized = fd.get_ref()[cursor_pos..fd.position() as usize].to_vec();
 
-        let payload = TransactionPayload::deserialize(fd)?;
+        let payload = TransactionPayload::deserialize(fd).map_err(|e| e.in_field("payload"))?;
 
         let version = if (version_u8 & 0x80) == 0 {
             TransactionVersion::Mainnet
@@ -94,12 +106,15 @@ impl TransactionAuth {
         let type_id = fd.read_u8()?;
         let auth = match type_id {
             x if x == TransactionAuthFlags::AuthStandard as u8 => {
-                let origin_auth = TransactionSpendingCondition::deserialize(fd)?;
+                let origin_auth = TransactionSpendingCondition::deserialize(fd)
+                    .map_err(|e| e.in_field("origin_condition"))?;
                 TransactionAuth::Standard(origin_auth)
             }
             x if x == TransactionAuthFlags::AuthSponsored as u8 => {
-                let origin_auth = TransactionSpendingCondition::deserialize(fd)?;
-                let sponsor_auth = TransactionSpendingCondition::deserialize(fd)?;
+                let origin_auth = TransactionSpendingCondition::deserialize(fd)
+                    .map_err(|e| e.in_field("origin_condition"))?;
+                let sponsor_auth = TransactionSpendingCondition::deserialize(fd)
+                    .map_err(|e| e.in_field("sponsor_condition"))?;
                 TransactionAuth::Sponsored(origin_auth, sponsor_auth)
             }
             _ => {
@@ -193,9 +208,14 @@ impl MultisigSpendingCondition {
         let tx_fee = fd.read_u64::<BigEndian>()?;
         let fields: Vec<TransactionAuthField> = {
             let len = fd.read_u32::<BigEndian>()?;
-            let mut results: Vec<TransactionAuthField> = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                results.push(TransactionAuthField::deserialize(fd)?);
+            // `len` is untrusted, each field takes at least a byte of the remaining input
+            let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+            let mut results: Vec<TransactionAuthField> =
+                Vec::with_capacity((len as usize).min(remaining));
+            for i in 0..len {
+                let field = TransactionAuthField::deserialize(fd)
+                    .map_err(|e| e.in_element("fields", i as usize))?;
+                results.push(field);
             }
             results
         };
@@ -344,7 +364,8 @@ impl TransactionPayload {
         let type_id = fd.read_u8()?;
         let payload = match type_id {
             x if x == TransactionPayloadID::TokenTransfer as u8 => {
-                let principal = PrincipalData::deserialize(fd)?;
+                let principal =
+                    PrincipalData::deserialize(fd).map_err(|e| e.in_field("recipient"))?;
                 let amount = fd.read_u64::<BigEndian>()?;
                 let mut memo_bytes = [0u8; 34];
                 fd.read_exact(&mut memo_bytes)?;
@@ -360,8 +381,10 @@ impl TransactionPayload {
                 TransactionPayload::SmartContract(payload)
             }
             x if x == TransactionPayloadID::PoisonMicroblock as u8 => {
-                let h1 = StacksMicroblockHeader::deserialize(fd)?;
-                let h2 = StacksMicroblockHeader::deserialize(fd)?;
+                let h1 = StacksMicroblockHeader::deserialize(fd)
+                    .map_err(|e| e.in_field("microblock_header_1"))?;
+                let h2 = StacksMicroblockHeader::deserialize(fd)
+                    .map_err(|e| e.in_field("microblock_header_2"))?;
                 TransactionPayload::PoisonMicroblock(h1, h2)
             }
             x if x == TransactionPayloadID::Coinbase as u8 => {
@@ -374,7 +397,8 @@ impl TransactionPayload {
                 let mut payload_bytes = [0u8; 32];
                 fd.read_exact(&mut payload_bytes)?;
                 let payload = CoinbasePayload(payload_bytes);
-                let principal = PrincipalData::deserialize(fd)?;
+                let principal =
+                    PrincipalData::deserialize(fd).map_err(|e| e.in_field("recipient"))?;
                 TransactionPayload::CoinbaseToAltRecipient(payload, principal)
             }
             x if x == TransactionPayloadID::VersionedSmartContract as u8 => {
@@ -396,7 +420,8 @@ impl TransactionPayload {
                 fd.read_exact(&mut payload_bytes)?;
                 let payload = CoinbasePayload(payload_bytes);
 
-                let principal = PrincipalData::deserialize_optional(fd)?;
+                let principal =
+                    PrincipalData::deserialize_optional(fd).map_err(|e| e.in_field("recipient"))?;
 
                 let mut vrf_proof: Vec<u8> = vec![0u8; 80];
                 fd.read_exact(&mut vrf_proof)?;
@@ -404,10 +429,10 @@ impl TransactionPayload {
                 TransactionPayload::NakamotoCoinbase(payload, principal, VRFProof(vrf_proof))
             }
             _ => {
-                return Err(format!(
-                    "Failed to parse transaction -- unknown payload ID {}",
-                    type_id
-                ))?;
+                return Err(
+                    DeserializeError::new(DeserializeErrorKind::UnknownPayloadId(type_id))
+                        .at_offset(fd.position() - 1),
+                );
             }
         };
 
@@ -420,11 +445,16 @@ impl TransactionContractCall {
         let address = StacksAddress::deserialize(fd)?;
         let contract_name = ClarityName::deserialize(fd)?;
         let function_name = ClarityName::deserialize(fd)?;
-        let function_args: Vec<ClarityValue> = {
+        let function_args: Vec<SerializedClarityValue> = {
             let len = fd.read_u32::<BigEndian>()?;
-            let mut results: Vec<ClarityValue> = Vec::with_capacity(len as usize);
-            for _ in 0..len {
-                results.push(ClarityValue::deserialize(fd, true)?);
+            // `len` is untrusted, each value takes at least a byte of the remaining input
+            let remaining = fd.get_ref().len().saturating_sub(fd.position() as usize);
+            let mut results = Vec::with_capacity((len as usize).min(remaining));
+            for i in 0..len {
+                // Only validated, nested values are decoded lazily by `ClarityValueRef`.
+                let arg = SerializedClarityValue::deserialize(fd)
+                    .map_err(|e| e.in_element("function_args", i as usize))?;
+                results.push(arg);
             }
             results
         };
@@ -563,6 +593,15 @@ impl PrincipalData {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for StandardPrincipalData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let address =
+            crate::address::c32::c32_address(self.0, &self.1).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&address)
+    }
+}
+
 impl StandardPrincipalData {
     pub fn deserialize(fd: &mut Cursor<&[u8]>) -> Result<Self, DeserializeError> {
         let mut version = [0; 1];
@@ -574,14 +613,16 @@ impl StandardPrincipalData {
 }
 
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionVersion {
     Mainnet = 0x00,
     Testnet = 0x80,
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionAnchorMode {
     OnChainOnly = 1,  // must be included in a StacksBlock
     OffChainOnly = 2, // must be included in a StacksMicroBlock
@@ -589,30 +630,40 @@ pub enum TransactionAnchorMode {
 }
 
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionPostConditionMode {
     Allow = 0x01, // allow any other changes not specified
     Deny = 0x02,  // deny any other changes not specified
 }
 
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionAuthFlags {
     AuthStandard = 0x04,
     AuthSponsored = 0x05,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionAuth {
     Standard(TransactionSpendingCondition),
     Sponsored(TransactionSpendingCondition, TransactionSpendingCondition), // the second account pays on behalf of the first account
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionSpendingCondition {
     Singlesig(SinglesigSpendingCondition),
     Multisig(MultisigSpendingCondition),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MultisigSpendingCondition {
     pub hash_mode: MultisigHashMode,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub signer: [u8; 20],
     pub nonce: u64,  // nth authorization from this account
     pub tx_fee: u64, // microSTX/compute rate offered by this account
@@ -620,8 +671,11 @@ pub struct MultisigSpendingCondition {
     pub signatures_required: u16,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SinglesigSpendingCondition {
     pub hash_mode: SinglesigHashMode,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub signer: [u8; 20],
     pub nonce: u64,  // nth authorization from this account
     pub tx_fee: u64, // microSTX/compute rate offerred by this account
@@ -630,7 +684,8 @@ pub struct SinglesigSpendingCondition {
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum MultisigHashMode {
     P2SH = 0x01,
     P2SHNonSequential = 0x05,
@@ -639,28 +694,42 @@ pub enum MultisigHashMode {
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SinglesigHashMode {
     P2PKH = 0x00,
     P2WPKH = 0x02,
 }
 
-pub struct StacksPublicKeyBuffer(pub [u8; 33]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StacksPublicKeyBuffer(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 33],
+);
 
-pub struct MessageSignature(pub [u8; 65]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MessageSignature(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 65],
+);
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Secp256k1PublicKey {
     pub key: StacksPublicKeyBuffer,
     pub compressed: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionAuthField {
     PublicKey(Secp256k1PublicKey),
     Signature(TransactionPublicKeyEncoding, MessageSignature),
 }
 
 #[repr(u8)]
-#[derive(PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionAuthFieldID {
     // types of auth fields
     PublicKeyCompressed = 0x00,
@@ -670,7 +739,8 @@ pub enum TransactionAuthFieldID {
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionPublicKeyEncoding {
     // ways we can encode a public key
     Compressed = 0x00,
@@ -678,7 +748,8 @@ pub enum TransactionPublicKeyEncoding {
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ClarityVersion {
     Clarity1 = 1,
     Clarity2 = 2,
@@ -688,7 +759,8 @@ pub enum ClarityVersion {
 }
 
 #[repr(u8)]
-#[derive(PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionPayloadID {
     TokenTransfer = 0,
     SmartContract = 1,
@@ -701,6 +773,8 @@ pub enum TransactionPayloadID {
     NakamotoCoinbase = 8,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TransactionPayload {
     TokenTransfer(PrincipalData, u64, TokenTransferMemo),
     ContractCall(TransactionContractCall),
@@ -713,22 +787,38 @@ pub enum TransactionPayload {
     NakamotoCoinbase(CoinbasePayload, Option<PrincipalData>, VRFProof),
 }
 
-pub struct CoinbasePayload(pub [u8; 32]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CoinbasePayload(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 32],
+);
 
-pub struct VRFProof(pub Vec<u8>);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VRFProof(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub Vec<u8>,
+);
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TransactionTenureChange {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub tenure_consensus_hash: [u8; 20],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub prev_tenure_consensus_hash: [u8; 20],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub burn_view_consensus_hash: [u8; 20],
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub previous_tenure_end: [u8; 32],
     pub previous_tenure_blocks: u32,
     pub cause: TenureChangeCause,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub pubkey_hash: [u8; 20],
 }
 
 #[repr(u8)]
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TenureChangeCause {
     /// A valid winning block-commit
     BlockFound = 0,
@@ -757,45 +847,73 @@ impl TenureChangeCause {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TransactionSmartContract {
     pub name: ClarityName,
     pub code_body: StacksString,
 }
 
-pub struct StacksString(pub Vec<u8>);
-
-pub struct BlockHeaderHash(pub [u8; 32]);
-
-pub struct Sha512Trunc256Sum(pub [u8; 32]);
-
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StacksString(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub Vec<u8>,
+);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BlockHeaderHash(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 32],
+);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sha512Trunc256Sum(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 32],
+);
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StacksMicroblockHeader {
     pub version: u8,
     pub sequence: u16,
     pub prev_block: BlockHeaderHash,
     pub tx_merkle_root: Sha512Trunc256Sum,
     pub signature: MessageSignature,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))]
     pub serialized_bytes: Vec<u8>,
 }
 
-pub struct TokenTransferMemo(pub [u8; 34]);
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TokenTransferMemo(
+    #[cfg_attr(feature = "serde", serde(serialize_with = "crate::hex::serialize_hex"))] pub [u8; 34],
+);
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct StandardPrincipalData(pub u8, pub [u8; 20]);
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QualifiedContractIdentifier {
     pub issuer: StandardPrincipalData,
     pub name: ClarityName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PrincipalData {
     Standard(StandardPrincipalData),
     Contract(QualifiedContractIdentifier),
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TransactionContractCall {
     pub address: StacksAddress,
     pub contract_name: ClarityName,
     pub function_name: ClarityName,
-    pub function_args: Vec<ClarityValue>,
+    pub function_args: Vec<SerializedClarityValue>,
 }
 
 #[cfg(test)]
@@ -821,4 +939,90 @@ mod tests {
         assert!(tx.is_ok());
         assert_eq!(cursor.position() as usize, bytes_len);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_json() {
+        // STX token transfer of 2000000 uSTX, nonce 47, fee 10000
+        let input = "0x00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000";
+        let bytes = decode_hex(input).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let tx = StacksTransaction::deserialize(&mut cursor).unwrap();
+        let json = serde_json::to_value(&tx).unwrap();
+        let origin = &json["auth"]["Standard"]["Singlesig"];
+        assert_eq!(origin["nonce"], 47);
+        assert_eq!(origin["tx_fee"], 10000);
+        assert_eq!(
+            origin["signer"],
+            "0xd27548439b50c8d8ca86756151bd6e07059510a0"
+        );
+        assert_eq!(
+            json["payload"]["TokenTransfer"][0]["Standard"],
+            "SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92"
+        );
+        assert_eq!(json["payload"]["TokenTransfer"][1], 2000000);
+        assert_eq!(json["post_condition_mode"], "Deny");
+        assert_eq!(tx.clone(), tx);
+    }
+
+    #[test]
+    fn test_deserialize_error_path() {
+        let mut input = String::from("0x0000000001");
+        // standard auth, singlesig p2pkh, zeroed signer, nonce, fee, and signature
+        input.push_str(&format!("0400{}00{}", "00".repeat(36), "00".repeat(65)));
+        // anchor mode any, post-condition mode deny, no post-conditions
+        input.push_str("030200000000");
+        // contract call to SP000000000000000000002Q6VF78.pox-4 `f`
+        input.push_str(&format!("0216{}05706f782d340166", "00".repeat(20)));
+        // (u1 u2 (tuple (amount <bad type prefix>)))
+        input.push_str("00000003");
+        input.push_str(&format!("01{}01", "00".repeat(15)));
+        input.push_str(&format!("01{}02", "00".repeat(15)));
+        input.push_str("0c0000000106616d6f756e7463");
+
+        let bytes = decode_hex(input).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        let err = StacksTransaction::deserialize(&mut cursor).err().unwrap();
+        assert_eq!(err.code(), "BadTypePrefix");
+        assert_eq!(err.offset, Some(bytes.len() as u64 - 1));
+        assert_eq!(err.path_string(), "payload.function_args[2].tuple.amount");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Bad type prefix 0x63 (at `payload.function_args[2].tuple.amount`, byte offset {})",
+                bytes.len() - 1
+            )
+        );
+
+        // truncated inside the auth spending condition
+        let mut cursor = Cursor::new(&bytes[..20]);
+        let err = StacksTransaction::deserialize(&mut cursor).err().unwrap();
+        assert_eq!(err.code(), "UnexpectedEof");
+        assert_eq!(err.offset, Some(20));
+        assert_eq!(err.path_string(), "auth.origin_condition");
+    }
+
+    #[test]
+    fn test_deserialize_counts_exceed_input() {
+        let mut input = String::from("0x0000000001");
+        // standard auth, singlesig p2pkh, zeroed signer, nonce, fee, and signature
+        input.push_str(&format!("0400{}00{}", "00".repeat(36), "00".repeat(65)));
+        // anchor mode any, post-condition mode deny, 0xffffffff post-conditions
+        input.push_str("0302ffffffff");
+        let bytes = decode_hex(input).unwrap();
+        let err = StacksTransaction::deserialize(&mut Cursor::new(bytes.as_ref()))
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), "UnexpectedEof");
+        assert_eq!(err.path_string(), "post_conditions[0]");
+
+        // standard auth, multisig p2sh, zeroed signer, nonce and fee, 0xffffffff fields
+        let input = format!("0x00000000010401{}ffffffff", "00".repeat(36));
+        let bytes = decode_hex(input).unwrap();
+        let err = StacksTransaction::deserialize(&mut Cursor::new(bytes.as_ref()))
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), "UnexpectedEof");
+        assert_eq!(err.path_string(), "auth.origin_condition.fields[0]");
+    }
 }
@@ -1,30 +1,218 @@
-use neon::prelude::*;
+use byteorder::{BigEndian, ReadBytesExt};
+#[cfg(feature = "nodejs")]
+use neon::{prelude::*, types::buffer::TypedArray};
+#[cfg(feature = "nodejs")]
+use rayon::prelude::*;
 use sha2::{Digest, Sha512_256};
 use std::io::Cursor;
 
-use crate::hex::encode_hex;
+#[cfg(feature = "nodejs")]
+use crate::hex::{decode_hex, encode_hex};
+#[cfg(feature = "nodejs")]
 use crate::neon_util::*;
+use crate::serialize_util::{DeserializeError, DeserializeErrorKind};
 
 use self::deserialize::StacksTransaction;
+#[cfg(feature = "nodejs")]
+use self::neon_decoder::tx_from_js;
 pub mod deserialize;
+#[cfg(feature = "nodejs")]
+pub mod neon_decoder;
+#[cfg(feature = "nodejs")]
 pub mod neon_encoder;
+pub mod serialize;
+pub mod verify;
+#[cfg(feature = "nodejs")]
 pub use neon_encoder::neon_encoder_internal;
 
+#[cfg(feature = "nodejs")]
 pub fn decode_transaction(mut cx: FunctionContext) -> JsResult<JsObject> {
-    let (tx, tx_id_bytes) = arg_as_bytes(&mut cx, 0, |val_bytes| {
-        let mut cursor = Cursor::new(val_bytes);
-        let tx = StacksTransaction::deserialize(&mut cursor)
-            .or_else(|e| Err(format!("Failed to decode transaction: {:?}\n", &e)))?;
-        let tx_id_bytes = Sha512_256::digest(val_bytes);
-        Ok((tx, tx_id_bytes))
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let (tx, tx_id_bytes) = arg_as_bytes(&mut cx, 0, decode_tx_bytes)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode transaction", &e))?;
+    tx_to_js(&mut cx, &tx, &tx_id_bytes, options)
+}
+
+/// Like `decode_transaction`, but deserializes on the libuv threadpool and returns a Promise
+#[cfg(feature = "nodejs")]
+pub fn decode_transaction_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let promise = cx
+        .task(move || decode_tx_bytes(&val_bytes))
+        .promise(move |mut cx, result| {
+            let (tx, tx_id_bytes) = result.or_else(|e| {
+                throw_deserialize_error(&mut cx, "Failed to decode transaction", &e)
+            })?;
+            tx_to_js(&mut cx, &tx, &tx_id_bytes, options)
+        });
+    Ok(promise)
+}
+
+/// Transaction bytes borrowed from a Buffer, or a hex string decoded on the thread pool
+#[cfg(feature = "nodejs")]
+enum TxInput<'a> {
+    Bytes(&'a [u8]),
+    Hex(String),
+}
+
+/// A deserialized transaction and its txid.
+pub type DecodedTx = (StacksTransaction, [u8; 32]);
+
+/// Deserialize a transaction and compute its txid.
+pub fn decode_tx_bytes(val_bytes: &[u8]) -> Result<DecodedTx, DeserializeError> {
+    let mut cursor = Cursor::new(val_bytes);
+    let tx = StacksTransaction::deserialize(&mut cursor)?;
+    let tx_id_bytes = Sha512_256::digest(val_bytes).into();
+    Ok((tx, tx_id_bytes))
+}
+
+/// Split a transaction list, serialized as a u32 count followed by each transaction prefixed with
+/// its u32 byte length, into the serialized transactions.
+pub fn split_tx_list(val_bytes: &[u8]) -> Result<Vec<&[u8]>, DeserializeError> {
+    let mut cursor = Cursor::new(val_bytes);
+    let count = cursor.read_u32::<BigEndian>()?;
+    // `count` is untrusted, each transaction takes at least its 4 byte length prefix
+    let max_count = (val_bytes.len() - cursor.position() as usize) / 4;
+    let mut tx_slices = Vec::with_capacity((count as usize).min(max_count));
+    for i in 0..count {
+        let len = cursor
+            .read_u32::<BigEndian>()
+            .map_err(|e| DeserializeError::from(e).in_index(i as usize))?;
+        let start = cursor.position() as usize;
+        let end = start + len as usize;
+        if end > val_bytes.len() {
+            return Err(DeserializeError::new(DeserializeErrorKind::UnexpectedEof)
+                .in_index(i as usize)
+                .at_offset(val_bytes.len() as u64));
+        }
+        tx_slices.push(&val_bytes[start..end]);
+        cursor.set_position(end as u64);
+    }
+    if cursor.position() as usize != val_bytes.len() {
+        let trailing = val_bytes.len() - cursor.position() as usize;
+        return Err(DeserializeError::from(format!(
+            "{} trailing bytes after the last transaction",
+            trailing
+        ))
+        .at_offset(cursor.position()));
+    }
+    Ok(tx_slices)
+}
+
+/// Decode an array of transactions (hex strings or Buffers) in parallel. Each entry in the
+/// returned array is either the decoded transaction or a `DeserializeError` object, so one
+/// malformed transaction doesn't fail the whole batch.
+#[cfg(feature = "nodejs")]
+pub fn decode_transactions(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let input_values = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let mut buffers = Vec::with_capacity(input_values.len());
+    for value in input_values {
+        if let Ok(handle) = value.downcast::<JsString, _>(&mut cx) {
+            buffers.push(Err(handle.value(&mut cx)));
+        } else if let Ok(handle) = value.downcast::<JsBuffer, _>(&mut cx) {
+            buffers.push(Ok(handle));
+        } else {
+            return cx.throw_type_error("Array items must be hex strings or Buffers");
+        }
+    }
+
+    let inputs: Vec<TxInput> = buffers
+        .into_iter()
+        .map(|buffer| match buffer {
+            Ok(handle) => TxInput::Bytes(handle.as_slice(&cx)),
+            Err(hex) => TxInput::Hex(hex),
+        })
+        .collect();
+    let results: Vec<_> = inputs
+        .par_iter()
+        .map(|input| match input {
+            TxInput::Bytes(val_bytes) => decode_tx_bytes(val_bytes),
+            TxInput::Hex(hex) => {
+                let val_bytes = decode_hex(hex).map_err(|e| format!("Hex parsing error: {}", e))?;
+                decode_tx_bytes(&val_bytes)
+            }
+        })
+        .collect();
+    drop(inputs);
+
+    txs_to_js(&mut cx, &results, options)
+}
+
+/// Like `decode_transactions`, but the input is a single buffer containing a u32 count followed
+/// by each transaction prefixed with its u32 byte length.
+#[cfg(feature = "nodejs")]
+pub fn decode_transaction_list(mut cx: FunctionContext) -> JsResult<JsArray> {
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let results = arg_as_bytes(&mut cx, 0, |val_bytes| {
+        let tx_slices = split_tx_list(val_bytes)?;
+        let results: Vec<_> = tx_slices
+            .par_iter()
+            .map(|tx_bytes| decode_tx_bytes(tx_bytes))
+            .collect();
+        Ok(results)
     })
-    .or_else(|e| cx.throw_error(e))?;
+    .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode transaction list", &e))?;
+
+    txs_to_js(&mut cx, &results, options)
+}
 
+#[cfg(feature = "nodejs")]
+fn txs_to_js<'cx>(
+    cx: &mut FunctionContext<'cx>,
+    results: &[Result<DecodedTx, DeserializeError>],
+    options: OutputOptions,
+) -> JsResult<'cx, JsArray> {
+    let array = JsArray::new(cx, results.len());
+    for (i, result) in results.iter().enumerate() {
+        let item = match result {
+            Ok((tx, tx_id_bytes)) => tx_to_js(cx, tx, tx_id_bytes, options)?,
+            Err(e) => deserialize_error_to_js(cx, "Failed to decode transaction", e)?,
+        };
+        array.set(cx, i as u32, item)?;
+    }
+    Ok(array)
+}
+
+#[cfg(feature = "nodejs")]
+pub fn tx_to_js<'cx, C: Context<'cx>>(
+    cx: &mut C,
+    tx: &StacksTransaction,
+    tx_id_bytes: &[u8],
+    options: OutputOptions,
+) -> JsResult<'cx, JsObject> {
     let tx_json_obj = cx.empty_object();
 
-    let tx_id = cx.string(encode_hex(&tx_id_bytes));
-    tx_json_obj.set(&mut cx, "tx_id", tx_id)?;
+    let tx_id = cx.string(encode_hex(tx_id_bytes));
+    tx_json_obj.set(cx, "tx_id", tx_id)?;
 
-    tx.neon_js_serialize(&mut cx, &tx_json_obj, &())?;
+    tx.neon_js_serialize(cx, &tx_json_obj, &options)?;
     Ok(tx_json_obj)
 }
+
+#[cfg(feature = "nodejs")]
+pub fn encode_transaction(mut cx: FunctionContext) -> JsResult<JsString> {
+    let input_obj = cx.argument::<JsObject>(0)?;
+    let tx = tx_from_js(&mut cx, &input_obj)?;
+    let serialized = tx
+        .serialize_to_vec()
+        .or_else(|e| cx.throw_error(format!("Error serializing transaction: {}", e)))?;
+    Ok(cx.string(encode_hex(&serialized)))
+}
+
+#[cfg(feature = "nodejs")]
+pub fn verify_transaction(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let tx = arg_as_bytes(&mut cx, 0, |val_bytes| {
+        let mut cursor = Cursor::new(val_bytes);
+        StacksTransaction::deserialize(&mut cursor)
+    })
+    .or_else(|e| throw_deserialize_error(&mut cx, "Failed to decode transaction", &e))?;
+    let verification = tx
+        .verify_signatures()
+        .or_else(|e| cx.throw_error(format!("Error computing transaction sighash: {}", e)))?;
+
+    let resp_obj = cx.empty_object();
+    verification.neon_js_serialize(&mut cx, &resp_obj, &())?;
+    Ok(resp_obj)
+}
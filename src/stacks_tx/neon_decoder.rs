@@ -0,0 +1,360 @@
+use neon::prelude::*;
+
+use crate::address::c32::c32_address_decode;
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::clarity_value::neon_decoder::clarity_val_from_js_hex_or_deep;
+use crate::clarity_value::types::SerializedClarityValue;
+use crate::neon_util::{
+    get_obj_prop, get_obj_prop_bytes, get_obj_prop_fixed_bytes, get_obj_prop_number,
+    get_obj_prop_string, get_obj_prop_u64, get_obj_prop_u8,
+};
+use crate::post_condition::neon_decoder::{
+    clarity_name_from_js, post_condition_from_js, stacks_address_from_js,
+};
+
+use super::deserialize::*;
+
+/// Build a transaction from an object in the shape produced by `decodeTransaction`.
+/// Properties derived from other fields (`tx_id`, `post_conditions_buffer`,
+/// `function_args_buffer`, microblock header `buffer`, and Clarity `repr`) are ignored.
+pub fn tx_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<StacksTransaction> {
+    let version = match get_obj_prop_u8(cx, obj, "version")? {
+        x if x == TransactionVersion::Mainnet as u8 => TransactionVersion::Mainnet,
+        x if x == TransactionVersion::Testnet as u8 => TransactionVersion::Testnet,
+        x => cx.throw_error(format!("Invalid transaction version {}", x))?,
+    };
+
+    let chain_id = get_obj_prop_number(cx, obj, "chain_id")?;
+    if chain_id.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&chain_id) {
+        cx.throw_error(format!("Invalid chain_id {}", chain_id))?;
+    }
+
+    let auth_obj = get_obj_prop::<JsObject>(cx, obj, "auth")?;
+    let auth = tx_auth_from_js(cx, &auth_obj)?;
+
+    let anchor_mode = match get_obj_prop_u8(cx, obj, "anchor_mode")? {
+        x if x == TransactionAnchorMode::OnChainOnly as u8 => TransactionAnchorMode::OnChainOnly,
+        x if x == TransactionAnchorMode::OffChainOnly as u8 => TransactionAnchorMode::OffChainOnly,
+        x if x == TransactionAnchorMode::Any as u8 => TransactionAnchorMode::Any,
+        x => cx.throw_error(format!("Invalid anchor mode {}", x))?,
+    };
+
+    let post_condition_mode = match get_obj_prop_u8(cx, obj, "post_condition_mode")? {
+        x if x == TransactionPostConditionMode::Allow as u8 => TransactionPostConditionMode::Allow,
+        x if x == TransactionPostConditionMode::Deny as u8 => TransactionPostConditionMode::Deny,
+        x => cx.throw_error(format!("Invalid post-condition mode {}", x))?,
+    };
+
+    let post_conditions_arr = get_obj_prop::<JsArray>(cx, obj, "post_conditions")?.to_vec(cx)?;
+    let mut post_conditions = Vec::with_capacity(post_conditions_arr.len());
+    for item in post_conditions_arr {
+        let item_obj = item.downcast_or_throw::<JsObject, _>(cx)?;
+        post_conditions.push(post_condition_from_js(cx, &item_obj)?);
+    }
+
+    let payload_obj = get_obj_prop::<JsObject>(cx, obj, "payload")?;
+    let payload = tx_payload_from_js(cx, &payload_obj)?;
+
+    Ok(StacksTransaction {
+        version,
+        chain_id: chain_id as u32,
+        auth,
+        anchor_mode,
+        post_conditions_serialized: vec![],
+        post_condition_mode,
+        post_conditions,
+        payload,
+    })
+}
+
+fn tx_auth_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionAuth> {
+    let type_id = get_obj_prop_u8(cx, obj, "type_id")?;
+    let origin_obj = get_obj_prop::<JsObject>(cx, obj, "origin_condition")?;
+    let origin_condition = spending_condition_from_js(cx, &origin_obj)?;
+    let auth = match type_id {
+        x if x == TransactionAuthFlags::AuthStandard as u8 => {
+            TransactionAuth::Standard(origin_condition)
+        }
+        x if x == TransactionAuthFlags::AuthSponsored as u8 => {
+            let sponsor_obj = get_obj_prop::<JsObject>(cx, obj, "sponsor_condition")?;
+            let sponsor_condition = spending_condition_from_js(cx, &sponsor_obj)?;
+            TransactionAuth::Sponsored(origin_condition, sponsor_condition)
+        }
+        _ => cx.throw_error(format!("Unrecognized auth type_id {}", type_id))?,
+    };
+    Ok(auth)
+}
+
+fn spending_condition_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionSpendingCondition> {
+    let hash_mode_u8 = get_obj_prop_u8(cx, obj, "hash_mode")?;
+    let signer_obj = get_obj_prop::<JsObject>(cx, obj, "signer")?;
+    let signer = stacks_address_from_js(cx, &signer_obj, "address")?.hash160_bytes;
+    let nonce = get_obj_prop_u64(cx, obj, "nonce")?;
+    let tx_fee = get_obj_prop_u64(cx, obj, "tx_fee")?;
+
+    if let Some(hash_mode) = SinglesigHashMode::from_u8(hash_mode_u8) {
+        let key_encoding_u8 = get_obj_prop_u8(cx, obj, "key_encoding")?;
+        let key_encoding = match TransactionPublicKeyEncoding::from_u8(key_encoding_u8) {
+            Some(key_encoding) => key_encoding,
+            None => cx.throw_error(format!("Unknown key encoding {}", key_encoding_u8))?,
+        };
+        let signature = MessageSignature(get_obj_prop_fixed_bytes(cx, obj, "signature")?);
+        Ok(TransactionSpendingCondition::Singlesig(
+            SinglesigSpendingCondition {
+                hash_mode,
+                signer,
+                nonce,
+                tx_fee,
+                key_encoding,
+                signature,
+            },
+        ))
+    } else if let Some(hash_mode) = MultisigHashMode::from_u8(hash_mode_u8) {
+        let fields_arr = get_obj_prop::<JsArray>(cx, obj, "fields")?.to_vec(cx)?;
+        let mut fields = Vec::with_capacity(fields_arr.len());
+        for item in fields_arr {
+            let item_obj = item.downcast_or_throw::<JsObject, _>(cx)?;
+            fields.push(auth_field_from_js(cx, &item_obj)?);
+        }
+        let signatures_required = get_obj_prop_number(cx, obj, "signatures_required")?;
+        if signatures_required.fract() != 0.0
+            || !(0.0..=u16::MAX as f64).contains(&signatures_required)
+        {
+            cx.throw_error(format!(
+                "Invalid signatures_required {}",
+                signatures_required
+            ))?;
+        }
+        Ok(TransactionSpendingCondition::Multisig(
+            MultisigSpendingCondition {
+                hash_mode,
+                signer,
+                nonce,
+                tx_fee,
+                fields,
+                signatures_required: signatures_required as u16,
+            },
+        ))
+    } else {
+        cx.throw_error(format!(
+            "Invalid spending condition hash mode {}",
+            hash_mode_u8
+        ))
+    }
+}
+
+fn auth_field_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionAuthField> {
+    let type_id = get_obj_prop_u8(cx, obj, "type_id")?;
+    let field = match type_id {
+        x if x == TransactionAuthFieldID::PublicKeyCompressed as u8
+            || x == TransactionAuthFieldID::PublicKeyUncompressed as u8 =>
+        {
+            let key = StacksPublicKeyBuffer(get_obj_prop_fixed_bytes(cx, obj, "public_key")?);
+            TransactionAuthField::PublicKey(Secp256k1PublicKey {
+                key,
+                compressed: x == TransactionAuthFieldID::PublicKeyCompressed as u8,
+            })
+        }
+        x if x == TransactionAuthFieldID::SignatureCompressed as u8 => {
+            let sig = MessageSignature(get_obj_prop_fixed_bytes(cx, obj, "signature")?);
+            TransactionAuthField::Signature(TransactionPublicKeyEncoding::Compressed, sig)
+        }
+        x if x == TransactionAuthFieldID::SignatureUncompressed as u8 => {
+            let sig = MessageSignature(get_obj_prop_fixed_bytes(cx, obj, "signature")?);
+            TransactionAuthField::Signature(TransactionPublicKeyEncoding::Uncompressed, sig)
+        }
+        _ => cx.throw_error(format!("Unknown auth field type_id {}", type_id))?,
+    };
+    Ok(field)
+}
+
+fn tx_payload_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionPayload> {
+    let type_id = get_obj_prop_u8(cx, obj, "type_id")?;
+    let payload = match type_id {
+        x if x == TransactionPayloadID::TokenTransfer as u8 => {
+            let recipient = principal_data_from_js(cx, obj, "recipient")?;
+            let amount = get_obj_prop_u64(cx, obj, "amount")?;
+            // shorter memos are right-padded with zero bytes
+            let memo_bytes = get_obj_prop_bytes(cx, obj, "memo_hex")?;
+            if memo_bytes.len() > 34 {
+                cx.throw_error(format!(
+                    "Token transfer memo must be at most 34 bytes, received {} bytes",
+                    memo_bytes.len()
+                ))?;
+            }
+            let mut memo = [0u8; 34];
+            memo[..memo_bytes.len()].copy_from_slice(&memo_bytes);
+            TransactionPayload::TokenTransfer(recipient, amount, TokenTransferMemo(memo))
+        }
+        x if x == TransactionPayloadID::ContractCall as u8 => {
+            let address = stacks_address_from_js(cx, obj, "address")?;
+            let contract_name = clarity_name_from_js(cx, obj, "contract_name")?;
+            let function_name = clarity_name_from_js(cx, obj, "function_name")?;
+            let args_arr = get_obj_prop::<JsArray>(cx, obj, "function_args")?.to_vec(cx)?;
+            let mut function_args = Vec::with_capacity(args_arr.len());
+            for (i, item) in args_arr.into_iter().enumerate() {
+                let item_obj = item.downcast_or_throw::<JsObject, _>(cx)?;
+                let arg = clarity_val_from_js_hex_or_deep(cx, &item_obj)?;
+                function_args.push(
+                    SerializedClarityValue::from_value(&arg.value).or_else(|e| {
+                        cx.throw_error(format!("Invalid function arg {}: {}", i, e))
+                    })?,
+                );
+            }
+            TransactionPayload::ContractCall(TransactionContractCall {
+                address,
+                contract_name,
+                function_name,
+                function_args,
+            })
+        }
+        x if x == TransactionPayloadID::SmartContract as u8 => {
+            TransactionPayload::SmartContract(smart_contract_from_js(cx, obj)?)
+        }
+        x if x == TransactionPayloadID::PoisonMicroblock as u8 => {
+            let h1_obj = get_obj_prop::<JsObject>(cx, obj, "microblock_header_1")?;
+            let h1 = microblock_header_from_js(cx, &h1_obj)?;
+            let h2_obj = get_obj_prop::<JsObject>(cx, obj, "microblock_header_2")?;
+            let h2 = microblock_header_from_js(cx, &h2_obj)?;
+            TransactionPayload::PoisonMicroblock(h1, h2)
+        }
+        x if x == TransactionPayloadID::Coinbase as u8 => {
+            let payload = CoinbasePayload(get_obj_prop_fixed_bytes(cx, obj, "payload_buffer")?);
+            TransactionPayload::Coinbase(payload)
+        }
+        x if x == TransactionPayloadID::CoinbaseToAltRecipient as u8 => {
+            let payload = CoinbasePayload(get_obj_prop_fixed_bytes(cx, obj, "payload_buffer")?);
+            let recipient = principal_data_from_js(cx, obj, "recipient")?;
+            TransactionPayload::CoinbaseToAltRecipient(payload, recipient)
+        }
+        x if x == TransactionPayloadID::VersionedSmartContract as u8 => {
+            let clarity_version_u8 = get_obj_prop_u8(cx, obj, "clarity_version")?;
+            let clarity_version = match ClarityVersion::from_u8(clarity_version_u8) {
+                Some(clarity_version) => clarity_version,
+                None => {
+                    cx.throw_error(format!("Unknown Clarity version {}", clarity_version_u8))?
+                }
+            };
+            let smart_contract = smart_contract_from_js(cx, obj)?;
+            TransactionPayload::VersionedSmartContract(smart_contract, clarity_version)
+        }
+        x if x == TransactionPayloadID::TenureChange as u8 => {
+            let cause_u8 = get_obj_prop_u8(cx, obj, "cause")?;
+            let cause = match TenureChangeCause::from_u8(cause_u8) {
+                Some(cause) => cause,
+                None => cx.throw_error(format!("Invalid tenure change cause {}", cause_u8))?,
+            };
+            let previous_tenure_blocks = get_obj_prop_number(cx, obj, "previous_tenure_blocks")?;
+            if previous_tenure_blocks.fract() != 0.0
+                || !(0.0..=u32::MAX as f64).contains(&previous_tenure_blocks)
+            {
+                cx.throw_error(format!(
+                    "Invalid previous_tenure_blocks {}",
+                    previous_tenure_blocks
+                ))?;
+            }
+            TransactionPayload::TenureChange(TransactionTenureChange {
+                tenure_consensus_hash: get_obj_prop_fixed_bytes(cx, obj, "tenure_consensus_hash")?,
+                prev_tenure_consensus_hash: get_obj_prop_fixed_bytes(
+                    cx,
+                    obj,
+                    "prev_tenure_consensus_hash",
+                )?,
+                burn_view_consensus_hash: get_obj_prop_fixed_bytes(
+                    cx,
+                    obj,
+                    "burn_view_consensus_hash",
+                )?,
+                previous_tenure_end: get_obj_prop_fixed_bytes(cx, obj, "previous_tenure_end")?,
+                previous_tenure_blocks: previous_tenure_blocks as u32,
+                cause,
+                pubkey_hash: get_obj_prop_fixed_bytes(cx, obj, "pubkey_hash")?,
+            })
+        }
+        x if x == TransactionPayloadID::NakamotoCoinbase as u8 => {
+            let payload = CoinbasePayload(get_obj_prop_fixed_bytes(cx, obj, "payload_buffer")?);
+            let recipient_val: Handle<JsValue> = obj.get_value(cx, "recipient")?;
+            let recipient = if recipient_val.is_a::<JsNull, _>(cx)
+                || recipient_val.is_a::<JsUndefined, _>(cx)
+            {
+                None
+            } else {
+                Some(principal_data_from_js(cx, obj, "recipient")?)
+            };
+            let vrf_proof: [u8; 80] = get_obj_prop_fixed_bytes(cx, obj, "vrf_proof")?;
+            TransactionPayload::NakamotoCoinbase(payload, recipient, VRFProof(vrf_proof.to_vec()))
+        }
+        _ => cx.throw_error(format!("Unknown payload type_id {}", type_id))?,
+    };
+    Ok(payload)
+}
+
+fn smart_contract_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TransactionSmartContract> {
+    let name = clarity_name_from_js(cx, obj, "contract_name")?;
+    let code_body = get_obj_prop_string(cx, obj, "code_body")?;
+    Ok(TransactionSmartContract {
+        name,
+        code_body: StacksString(code_body.into_bytes()),
+    })
+}
+
+fn microblock_header_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<StacksMicroblockHeader> {
+    let version = get_obj_prop_u8(cx, obj, "version")?;
+    let sequence = get_obj_prop_number(cx, obj, "sequence")?;
+    if sequence.fract() != 0.0 || !(0.0..=u16::MAX as f64).contains(&sequence) {
+        cx.throw_error(format!("Invalid microblock sequence {}", sequence))?;
+    }
+    Ok(StacksMicroblockHeader {
+        version,
+        sequence: sequence as u16,
+        prev_block: BlockHeaderHash(get_obj_prop_fixed_bytes(cx, obj, "prev_block")?),
+        tx_merkle_root: Sha512Trunc256Sum(get_obj_prop_fixed_bytes(cx, obj, "tx_merkle_root")?),
+        signature: MessageSignature(get_obj_prop_fixed_bytes(cx, obj, "signature")?),
+        serialized_bytes: vec![],
+    })
+}
+
+fn principal_data_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+    key: &str,
+) -> NeonResult<PrincipalData> {
+    let principal_obj = get_obj_prop::<JsObject>(cx, obj, key)?;
+    let type_id = get_obj_prop_u8(cx, &principal_obj, "type_id")?;
+    let address = get_obj_prop_string(cx, &principal_obj, "address")?;
+    let (version, hash160) = c32_address_decode(&address)
+        .or_else(|e| cx.throw_error(format!("Error parsing Stacks address {}", e)))?;
+    let issuer = StandardPrincipalData(version, hash160);
+    match TypePrefix::from_u8(type_id) {
+        Some(TypePrefix::PrincipalStandard) => Ok(PrincipalData::Standard(issuer)),
+        Some(TypePrefix::PrincipalContract) => {
+            let name = clarity_name_from_js(cx, &principal_obj, "contract_name")?;
+            Ok(PrincipalData::Contract(QualifiedContractIdentifier {
+                issuer,
+                name,
+            }))
+        }
+        _ => cx.throw_error(format!("Bad principal type_id {}", type_id)),
+    }
+}
@@ -7,7 +7,7 @@ use crate::address::stacks_address::{AddressHashMode, StacksAddress};
 use crate::clarity_value::deserialize::TypePrefix;
 use crate::clarity_value::neon_encoder::decode_clarity_val;
 use crate::hex::encode_hex;
-use crate::neon_util::NeonJsSerialize;
+use crate::neon_util::{NeonJsSerialize, NumberFormat, OutputOptions, OutputProfile};
 
 use crate::post_condition::deserialize::{
     AssetInfo, AssetInfoID, FungibleConditionCode, NonfungibleConditionCode,
@@ -21,6 +21,7 @@ use super::deserialize::{
     TransactionPayloadID, TransactionPublicKeyEncoding, TransactionSmartContract,
     TransactionSpendingCondition, TransactionTenureChange, TransactionVersion,
 };
+use super::verify::{SpendingConditionVerification, TransactionVerification};
 
 /// Re-export for use in stacks_block module
 pub mod neon_encoder_internal {
@@ -28,12 +29,14 @@ pub mod neon_encoder_internal {
     
     pub struct TxSerializationContext {
         pub transaction_version: TransactionVersion,
+        pub numbers: NumberFormat,
     }
     
     impl TxSerializationContext {
-        pub fn new(version: TransactionVersion) -> Self {
+        pub fn new(version: TransactionVersion, numbers: NumberFormat) -> Self {
             TxSerializationContext {
                 transaction_version: version,
+                numbers,
             }
         }
     }
@@ -41,12 +44,12 @@ pub mod neon_encoder_internal {
 
 use neon_encoder_internal::TxSerializationContext;
 
-impl NeonJsSerialize for StacksTransaction {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for StacksTransaction {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        _extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
         let version_number = cx.number(self.version as u8);
         obj.set(cx, "version", version_number)?;
@@ -60,6 +63,7 @@ impl NeonJsSerialize for StacksTransaction {
             &auth_obj,
             &TxSerializationContext {
                 transaction_version: self.version,
+                numbers: options.numbers,
             },
         )?;
         obj.set(cx, "auth", auth_obj)?;
@@ -73,7 +77,7 @@ impl NeonJsSerialize for StacksTransaction {
         let post_conditions = JsArray::new(cx, self.post_conditions.len());
         for (i, x) in self.post_conditions.iter().enumerate() {
             let post_condition_obj = cx.empty_object();
-            x.neon_js_serialize(cx, &post_condition_obj)?;
+            x.neon_js_serialize(cx, &post_condition_obj, *options)?;
             post_conditions.set(cx, i as u32, post_condition_obj)?;
         }
         obj.set(cx, "post_conditions", post_conditions)?;
@@ -82,7 +86,7 @@ impl NeonJsSerialize for StacksTransaction {
         obj.set(cx, "post_conditions_buffer", post_conditions_buff)?;
 
         let payload_obj = cx.empty_object();
-        self.payload.neon_js_serialize(cx, &payload_obj, &())?;
+        self.payload.neon_js_serialize(cx, &payload_obj, options)?;
         obj.set(cx, "payload", payload_obj)?;
 
         Ok(())
@@ -90,9 +94,9 @@ impl NeonJsSerialize for StacksTransaction {
 }
 
 impl NeonJsSerialize<TxSerializationContext> for TransactionAuth {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &TxSerializationContext,
     ) -> NeonResult<()> {
@@ -123,9 +127,9 @@ impl NeonJsSerialize<TxSerializationContext> for TransactionAuth {
 }
 
 impl NeonJsSerialize<TxSerializationContext> for TransactionSpendingCondition {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &TxSerializationContext,
     ) -> NeonResult<()> {
@@ -142,9 +146,9 @@ impl NeonJsSerialize<TxSerializationContext> for TransactionSpendingCondition {
 }
 
 impl NeonJsSerialize<TxSerializationContext> for SinglesigSpendingCondition {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &TxSerializationContext,
     ) -> NeonResult<()> {
@@ -163,12 +167,10 @@ impl NeonJsSerialize<TxSerializationContext> for SinglesigSpendingCondition {
         stacks_address.neon_js_serialize(cx, &stacks_address_obj, &())?;
         obj.set(cx, "signer", stacks_address_obj)?;
 
-        // TODO: bigint
-        let nonce = cx.string(self.nonce.to_string());
+        let nonce = extra_ctx.numbers.uint(cx, self.nonce.into());
         obj.set(cx, "nonce", nonce)?;
 
-        // TODO: bigint
-        let tx_fee = cx.string(self.tx_fee.to_string());
+        let tx_fee = extra_ctx.numbers.uint(cx, self.tx_fee.into());
         obj.set(cx, "tx_fee", tx_fee)?;
 
         let key_encoding = cx.number(self.key_encoding as u8);
@@ -182,9 +184,9 @@ impl NeonJsSerialize<TxSerializationContext> for SinglesigSpendingCondition {
 }
 
 impl NeonJsSerialize<TxSerializationContext> for MultisigSpendingCondition {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &TxSerializationContext,
     ) -> NeonResult<()> {
@@ -202,12 +204,10 @@ impl NeonJsSerialize<TxSerializationContext> for MultisigSpendingCondition {
         stacks_address.neon_js_serialize(cx, &stacks_address_obj, &())?;
         obj.set(cx, "signer", stacks_address_obj)?;
 
-        // TODO: bigint
-        let nonce = cx.string(self.nonce.to_string());
+        let nonce = extra_ctx.numbers.uint(cx, self.nonce.into());
         obj.set(cx, "nonce", nonce)?;
 
-        // TODO: bigint
-        let tx_fee = cx.string(self.tx_fee.to_string());
+        let tx_fee = extra_ctx.numbers.uint(cx, self.tx_fee.into());
         obj.set(cx, "tx_fee", tx_fee)?;
 
         let fields = JsArray::new(cx, self.fields.len().try_into().unwrap());
@@ -226,9 +226,9 @@ impl NeonJsSerialize<TxSerializationContext> for MultisigSpendingCondition {
 }
 
 impl NeonJsSerialize for StacksAddress {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -248,9 +248,9 @@ impl NeonJsSerialize for StacksAddress {
 }
 
 impl NeonJsSerialize for TransactionAuthField {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -284,12 +284,12 @@ impl NeonJsSerialize for TransactionAuthField {
     }
 }
 
-impl NeonJsSerialize for TransactionPostCondition {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for TransactionPostCondition {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
         match *self {
             TransactionPostCondition::STX(ref principal, ref fungible_condition, ref amount) => {
@@ -297,13 +297,13 @@ impl NeonJsSerialize for TransactionPostCondition {
                 obj.set(cx, "asset_info_id", asset_info_id)?;
 
                 let pricipal_obj = cx.empty_object();
-                principal.neon_js_serialize(cx, &pricipal_obj, extra_ctx)?;
+                principal.neon_js_serialize(cx, &pricipal_obj, &())?;
                 obj.set(cx, "principal", pricipal_obj)?;
 
-                fungible_condition.neon_js_serialize(cx, obj, extra_ctx)?;
+                fungible_condition.neon_js_serialize(cx, obj, &())?;
 
-                let amount_str = cx.string(amount.to_string());
-                obj.set(cx, "amount", amount_str)?;
+                let amount = options.numbers.uint(cx, (*amount).into());
+                obj.set(cx, "amount", amount)?;
             }
             TransactionPostCondition::Fungible(
                 ref principal,
@@ -315,17 +315,17 @@ impl NeonJsSerialize for TransactionPostCondition {
                 obj.set(cx, "asset_info_id", asset_info_id)?;
 
                 let pricipal_obj = cx.empty_object();
-                principal.neon_js_serialize(cx, &pricipal_obj, extra_ctx)?;
+                principal.neon_js_serialize(cx, &pricipal_obj, &())?;
                 obj.set(cx, "principal", pricipal_obj)?;
 
                 let asset_info_obj = cx.empty_object();
-                asset_info.neon_js_serialize(cx, &asset_info_obj, extra_ctx)?;
+                asset_info.neon_js_serialize(cx, &asset_info_obj, &())?;
                 obj.set(cx, "asset", asset_info_obj)?;
 
-                fungible_condition.neon_js_serialize(cx, obj, extra_ctx)?;
+                fungible_condition.neon_js_serialize(cx, obj, &())?;
 
-                let amount_str = cx.string(amount.to_string());
-                obj.set(cx, "amount", amount_str)?;
+                let amount = options.numbers.uint(cx, (*amount).into());
+                obj.set(cx, "amount", amount)?;
             }
             TransactionPostCondition::Nonfungible(
                 ref principal,
@@ -337,24 +337,24 @@ impl NeonJsSerialize for TransactionPostCondition {
                 obj.set(cx, "asset_info_id", asset_info_id)?;
 
                 let pricipal_obj = cx.empty_object();
-                principal.neon_js_serialize(cx, &pricipal_obj, extra_ctx)?;
+                principal.neon_js_serialize(cx, &pricipal_obj, &())?;
                 obj.set(cx, "principal", pricipal_obj)?;
 
                 let asset_info_obj = cx.empty_object();
-                asset_info.neon_js_serialize(cx, &asset_info_obj, extra_ctx)?;
+                asset_info.neon_js_serialize(cx, &asset_info_obj, &())?;
                 obj.set(cx, "asset", asset_info_obj)?;
 
                 let asset_value_obj = cx.empty_object();
                 decode_clarity_val(
                     cx,
                     &asset_value_obj,
-                    &asset_value,
-                    false,
-                    asset_value.serialized_bytes.as_ref().unwrap(),
+                    &asset_value.as_value_ref().unwrap(),
+                    options.profile == OutputProfile::Minimal,
+                    *options,
                 )?;
                 obj.set(cx, "asset_value", asset_value_obj)?;
 
-                nonfungible_condition.neon_js_serialize(cx, obj, extra_ctx)?;
+                nonfungible_condition.neon_js_serialize(cx, obj, &())?;
             }
         };
         Ok(())
@@ -362,9 +362,9 @@ impl NeonJsSerialize for TransactionPostCondition {
 }
 
 impl NeonJsSerialize for FungibleConditionCode {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -384,9 +384,9 @@ impl NeonJsSerialize for FungibleConditionCode {
 }
 
 impl NeonJsSerialize for NonfungibleConditionCode {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -403,9 +403,9 @@ impl NeonJsSerialize for NonfungibleConditionCode {
 }
 
 impl NeonJsSerialize for PostConditionPrincipal {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -435,9 +435,9 @@ impl NeonJsSerialize for PostConditionPrincipal {
 }
 
 impl NeonJsSerialize for AssetInfo {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -458,12 +458,12 @@ impl NeonJsSerialize for AssetInfo {
     }
 }
 
-impl NeonJsSerialize for TransactionPayload {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for TransactionPayload {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
         match *self {
             TransactionPayload::TokenTransfer(ref address, ref amount, ref memo) => {
@@ -471,11 +471,11 @@ impl NeonJsSerialize for TransactionPayload {
                 obj.set(cx, "type_id", type_id)?;
 
                 let recipient_obj = cx.empty_object();
-                address.neon_js_serialize(cx, &recipient_obj, extra_ctx)?;
+                address.neon_js_serialize(cx, &recipient_obj, &())?;
                 obj.set(cx, "recipient", recipient_obj)?;
 
-                let amount_str = cx.string(amount.to_string());
-                obj.set(cx, "amount", amount_str)?;
+                let amount = options.numbers.uint(cx, (*amount).into());
+                obj.set(cx, "amount", amount)?;
 
                 let memo_hex = cx.string(encode_hex(&memo.0));
                 obj.set(cx, "memo_hex", memo_hex)?;
@@ -484,24 +484,24 @@ impl NeonJsSerialize for TransactionPayload {
                 let type_id = cx.number(TransactionPayloadID::ContractCall as u8);
                 obj.set(cx, "type_id", type_id)?;
 
-                contract_call.neon_js_serialize(cx, obj, extra_ctx)?;
+                contract_call.neon_js_serialize(cx, obj, options)?;
             }
             TransactionPayload::SmartContract(ref smart_contract) => {
                 let type_id = cx.number(TransactionPayloadID::SmartContract as u8);
                 obj.set(cx, "type_id", type_id)?;
 
-                smart_contract.neon_js_serialize(cx, obj, extra_ctx)?;
+                smart_contract.neon_js_serialize(cx, obj, &())?;
             }
             TransactionPayload::PoisonMicroblock(ref h1, ref h2) => {
                 let type_id = cx.number(TransactionPayloadID::PoisonMicroblock as u8);
                 obj.set(cx, "type_id", type_id)?;
 
                 let microblock_header_1_obj = cx.empty_object();
-                h1.neon_js_serialize(cx, &microblock_header_1_obj, extra_ctx)?;
+                h1.neon_js_serialize(cx, &microblock_header_1_obj, &())?;
                 obj.set(cx, "microblock_header_1", microblock_header_1_obj)?;
 
                 let microblock_header_2_obj = cx.empty_object();
-                h2.neon_js_serialize(cx, &microblock_header_2_obj, extra_ctx)?;
+                h2.neon_js_serialize(cx, &microblock_header_2_obj, &())?;
                 obj.set(cx, "microblock_header_2", microblock_header_2_obj)?;
             }
             TransactionPayload::Coinbase(ref buf) => {
@@ -519,7 +519,7 @@ impl NeonJsSerialize for TransactionPayload {
                 obj.set(cx, "payload_buffer", payload_buffer)?;
 
                 let recipient_obj = cx.empty_object();
-                address.neon_js_serialize(cx, &recipient_obj, extra_ctx)?;
+                address.neon_js_serialize(cx, &recipient_obj, &())?;
                 obj.set(cx, "recipient", recipient_obj)?;
             }
             TransactionPayload::VersionedSmartContract(ref smart_contract, ref version) => {
@@ -529,13 +529,13 @@ impl NeonJsSerialize for TransactionPayload {
                 let type_id = cx.number(*version as u8);
                 obj.set(cx, "clarity_version", type_id)?;
 
-                smart_contract.neon_js_serialize(cx, obj, extra_ctx)?;
+                smart_contract.neon_js_serialize(cx, obj, &())?;
             }
             TransactionPayload::TenureChange(ref tenure_change) => {
                 let type_id = cx.number(TransactionPayloadID::TenureChange as u8);
                 obj.set(cx, "type_id", type_id)?;
 
-                tenure_change.neon_js_serialize(cx, obj, extra_ctx)?;
+                tenure_change.neon_js_serialize(cx, obj, &())?;
             }
             TransactionPayload::NakamotoCoinbase(ref buf, ref principal, ref vrf_proof) => {
                 let type_id = cx.number(TransactionPayloadID::NakamotoCoinbase as u8);
@@ -546,7 +546,7 @@ impl NeonJsSerialize for TransactionPayload {
 
                 if let Some(principal) = principal {
                     let recipient_obj = cx.empty_object();
-                    principal.neon_js_serialize(cx, &recipient_obj, extra_ctx)?;
+                    principal.neon_js_serialize(cx, &recipient_obj, &())?;
                     obj.set(cx, "recipient", recipient_obj)?;
                 } else {
                     let recipient_obj = cx.null();
@@ -562,9 +562,9 @@ impl NeonJsSerialize for TransactionPayload {
 }
 
 impl NeonJsSerialize for PrincipalData {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -593,9 +593,9 @@ impl NeonJsSerialize for PrincipalData {
 }
 
 impl NeonJsSerialize for StandardPrincipalData {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -615,14 +615,14 @@ impl NeonJsSerialize for StandardPrincipalData {
     }
 }
 
-impl NeonJsSerialize for TransactionContractCall {
-    fn neon_js_serialize(
+impl NeonJsSerialize<OutputOptions> for TransactionContractCall {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
-        extra_ctx: &(),
+        options: &OutputOptions,
     ) -> NeonResult<()> {
-        self.address.neon_js_serialize(cx, obj, extra_ctx)?;
+        self.address.neon_js_serialize(cx, obj, &())?;
 
         let contract_name = cx.string(self.contract_name.as_str());
         obj.set(cx, "contract_name", contract_name)?;
@@ -634,13 +634,13 @@ impl NeonJsSerialize for TransactionContractCall {
         let function_args = JsArray::new(cx, self.function_args.len());
         for (i, clarity_val) in self.function_args.iter().enumerate() {
             let val_obj = cx.empty_object();
-            function_args_raw.extend_from_slice(&clarity_val.serialized_bytes.as_ref().unwrap());
+            function_args_raw.extend_from_slice(clarity_val.as_bytes());
             decode_clarity_val(
                 cx,
                 &val_obj,
-                &clarity_val,
-                false,
-                &clarity_val.serialized_bytes.as_ref().unwrap(),
+                &clarity_val.as_value_ref(),
+                options.profile == OutputProfile::Minimal,
+                *options,
             )?;
             function_args.set(cx, i as u32, val_obj)?;
         }
@@ -654,9 +654,9 @@ impl NeonJsSerialize for TransactionContractCall {
 }
 
 impl NeonJsSerialize for TransactionSmartContract {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -670,9 +670,9 @@ impl NeonJsSerialize for TransactionSmartContract {
 }
 
 impl NeonJsSerialize for TransactionTenureChange {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -702,9 +702,9 @@ impl NeonJsSerialize for TransactionTenureChange {
 }
 
 impl NeonJsSerialize for StacksMicroblockHeader {
-    fn neon_js_serialize(
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
         &self,
-        cx: &mut FunctionContext,
+        cx: &mut C,
         obj: &Handle<JsObject>,
         _extra_ctx: &(),
     ) -> NeonResult<()> {
@@ -729,3 +729,57 @@ impl NeonJsSerialize for StacksMicroblockHeader {
         Ok(())
     }
 }
+
+impl NeonJsSerialize for TransactionVerification {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
+        &self,
+        cx: &mut C,
+        obj: &Handle<JsObject>,
+        _extra_ctx: &(),
+    ) -> NeonResult<()> {
+        let valid = cx.boolean(self.is_valid());
+        obj.set(cx, "valid", valid)?;
+
+        let origin_obj = cx.empty_object();
+        self.origin.neon_js_serialize(cx, &origin_obj, &())?;
+        obj.set(cx, "origin", origin_obj)?;
+
+        if let Some(ref sponsor) = self.sponsor {
+            let sponsor_obj = cx.empty_object();
+            sponsor.neon_js_serialize(cx, &sponsor_obj, &())?;
+            obj.set(cx, "sponsor", sponsor_obj)?;
+        } else {
+            let sponsor_obj = cx.null();
+            obj.set(cx, "sponsor", sponsor_obj)?;
+        }
+        Ok(())
+    }
+}
+
+impl NeonJsSerialize for SpendingConditionVerification {
+    fn neon_js_serialize<'cx, C: Context<'cx>>(
+        &self,
+        cx: &mut C,
+        obj: &Handle<JsObject>,
+        _extra_ctx: &(),
+    ) -> NeonResult<()> {
+        let valid = cx.boolean(self.error.is_none());
+        obj.set(cx, "valid", valid)?;
+
+        let public_keys = JsArray::new(cx, self.public_keys.len());
+        for (i, public_key) in self.public_keys.iter().enumerate() {
+            let public_key = cx.string(encode_hex(public_key));
+            public_keys.set(cx, i as u32, public_key)?;
+        }
+        obj.set(cx, "public_keys", public_keys)?;
+
+        if let Some(ref error) = self.error {
+            let error = cx.string(error);
+            obj.set(cx, "error", error)?;
+        } else {
+            let error = cx.null();
+            obj.set(cx, "error", error)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,430 @@
+use std::io::Write;
+
+use byteorder::{BigEndian, WriteBytesExt};
+use sha2::{Digest, Sha512_256};
+
+use crate::clarity_value::deserialize::TypePrefix;
+use crate::serialize_util::SerializeError;
+
+use super::deserialize::*;
+
+impl StacksTransaction {
+    /// Serialize to the consensus wire format. The cached `post_conditions_serialized` bytes are
+    /// not used; post-conditions are written from `post_condition_mode` and `post_conditions`.
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u8(self.version as u8)?;
+        fd.write_u32::<BigEndian>(self.chain_id)?;
+        self.auth.serialize(fd)?;
+        fd.write_u8(self.anchor_mode as u8)?;
+        fd.write_u8(self.post_condition_mode as u8)?;
+        fd.write_u32::<BigEndian>(self.post_conditions.len() as u32)?;
+        for post_condition in self.post_conditions.iter() {
+            post_condition.serialize(fd)?;
+        }
+        self.payload.serialize(fd)?;
+        Ok(())
+    }
+
+    pub fn serialize_to_vec(&self) -> Result<Vec<u8>, SerializeError> {
+        let mut bytes = vec![];
+        self.serialize(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// SHA512/256 of the serialized transaction.
+    pub fn txid(&self) -> Result<[u8; 32], SerializeError> {
+        Ok(Sha512_256::digest(self.serialize_to_vec()?).into())
+    }
+}
+
+impl TransactionAuth {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionAuth::Standard(origin_condition) => {
+                fd.write_u8(TransactionAuthFlags::AuthStandard as u8)?;
+                origin_condition.serialize(fd)?;
+            }
+            TransactionAuth::Sponsored(origin_condition, sponsor_condition) => {
+                fd.write_u8(TransactionAuthFlags::AuthSponsored as u8)?;
+                origin_condition.serialize(fd)?;
+                sponsor_condition.serialize(fd)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl TransactionSpendingCondition {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionSpendingCondition::Singlesig(cond) => cond.serialize(fd),
+            TransactionSpendingCondition::Multisig(cond) => cond.serialize(fd),
+        }
+    }
+}
+
+impl SinglesigSpendingCondition {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        // sanity check -- must be compressed if we're using p2wpkh
+        if self.hash_mode == SinglesigHashMode::P2WPKH
+            && self.key_encoding != TransactionPublicKeyEncoding::Compressed
+        {
+            return Err("Failed to serialize singlesig spending condition: incompatible hash mode and key encoding")?;
+        }
+        fd.write_u8(self.hash_mode as u8)?;
+        fd.write_all(&self.signer)?;
+        fd.write_u64::<BigEndian>(self.nonce)?;
+        fd.write_u64::<BigEndian>(self.tx_fee)?;
+        fd.write_u8(self.key_encoding as u8)?;
+        self.signature.serialize(fd)?;
+        Ok(())
+    }
+}
+
+impl MultisigSpendingCondition {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        // must all be compressed if we're using P2WSH
+        let have_uncompressed = self.fields.iter().any(|field| match field {
+            TransactionAuthField::Signature(key_encoding, _) => {
+                *key_encoding == TransactionPublicKeyEncoding::Uncompressed
+            }
+            TransactionAuthField::PublicKey(pubkey) => !pubkey.compressed,
+        });
+        if have_uncompressed && self.hash_mode == MultisigHashMode::P2WSH {
+            return Err(
+                "Failed to serialize multisig spending condition: expected compressed keys only",
+            )?;
+        }
+        fd.write_u8(self.hash_mode as u8)?;
+        fd.write_all(&self.signer)?;
+        fd.write_u64::<BigEndian>(self.nonce)?;
+        fd.write_u64::<BigEndian>(self.tx_fee)?;
+        fd.write_u32::<BigEndian>(self.fields.len() as u32)?;
+        for field in self.fields.iter() {
+            field.serialize(fd)?;
+        }
+        fd.write_u16::<BigEndian>(self.signatures_required)?;
+        Ok(())
+    }
+}
+
+impl TransactionAuthField {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionAuthField::PublicKey(pubkey) => {
+                let field_id = if pubkey.compressed {
+                    TransactionAuthFieldID::PublicKeyCompressed
+                } else {
+                    TransactionAuthFieldID::PublicKeyUncompressed
+                };
+                fd.write_u8(field_id as u8)?;
+                fd.write_all(&pubkey.key.0)?;
+            }
+            TransactionAuthField::Signature(key_encoding, sig) => {
+                let field_id = if *key_encoding == TransactionPublicKeyEncoding::Compressed {
+                    TransactionAuthFieldID::SignatureCompressed
+                } else {
+                    TransactionAuthFieldID::SignatureUncompressed
+                };
+                fd.write_u8(field_id as u8)?;
+                sig.serialize(fd)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl MessageSignature {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_all(&self.0)?;
+        Ok(())
+    }
+}
+
+impl TransactionPayload {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionPayload::TokenTransfer(principal, amount, memo) => {
+                fd.write_u8(TransactionPayloadID::TokenTransfer as u8)?;
+                principal.serialize(fd)?;
+                fd.write_u64::<BigEndian>(*amount)?;
+                fd.write_all(&memo.0)?;
+            }
+            TransactionPayload::ContractCall(contract_call) => {
+                fd.write_u8(TransactionPayloadID::ContractCall as u8)?;
+                contract_call.serialize(fd)?;
+            }
+            TransactionPayload::SmartContract(smart_contract) => {
+                fd.write_u8(TransactionPayloadID::SmartContract as u8)?;
+                smart_contract.serialize(fd)?;
+            }
+            TransactionPayload::PoisonMicroblock(h1, h2) => {
+                fd.write_u8(TransactionPayloadID::PoisonMicroblock as u8)?;
+                h1.serialize(fd)?;
+                h2.serialize(fd)?;
+            }
+            TransactionPayload::Coinbase(payload) => {
+                fd.write_u8(TransactionPayloadID::Coinbase as u8)?;
+                fd.write_all(&payload.0)?;
+            }
+            TransactionPayload::CoinbaseToAltRecipient(payload, principal) => {
+                fd.write_u8(TransactionPayloadID::CoinbaseToAltRecipient as u8)?;
+                fd.write_all(&payload.0)?;
+                principal.serialize(fd)?;
+            }
+            TransactionPayload::VersionedSmartContract(smart_contract, clarity_version) => {
+                fd.write_u8(TransactionPayloadID::VersionedSmartContract as u8)?;
+                fd.write_u8(*clarity_version as u8)?;
+                smart_contract.serialize(fd)?;
+            }
+            TransactionPayload::TenureChange(tenure_change) => {
+                fd.write_u8(TransactionPayloadID::TenureChange as u8)?;
+                tenure_change.serialize(fd)?;
+            }
+            TransactionPayload::NakamotoCoinbase(payload, principal, vrf_proof) => {
+                fd.write_u8(TransactionPayloadID::NakamotoCoinbase as u8)?;
+                fd.write_all(&payload.0)?;
+                PrincipalData::serialize_optional(principal.as_ref(), fd)?;
+                if vrf_proof.0.len() != 80 {
+                    return Err(format!(
+                        "Failed to serialize VRF proof: expected 80 bytes, got {}",
+                        vrf_proof.0.len()
+                    ))?;
+                }
+                fd.write_all(&vrf_proof.0)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl TransactionContractCall {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        self.address.serialize(fd)?;
+        self.contract_name.serialize(fd)?;
+        self.function_name.serialize(fd)?;
+        fd.write_u32::<BigEndian>(self.function_args.len() as u32)?;
+        for arg in self.function_args.iter() {
+            fd.write_all(arg.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl TransactionSmartContract {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        self.name.serialize(fd)?;
+        self.code_body.serialize(fd)?;
+        Ok(())
+    }
+}
+
+impl TransactionTenureChange {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_all(&self.tenure_consensus_hash)?;
+        fd.write_all(&self.prev_tenure_consensus_hash)?;
+        fd.write_all(&self.burn_view_consensus_hash)?;
+        fd.write_all(&self.previous_tenure_end)?;
+        fd.write_u32::<BigEndian>(self.previous_tenure_blocks)?;
+        fd.write_u8(self.cause as u8)?;
+        fd.write_all(&self.pubkey_hash)?;
+        Ok(())
+    }
+}
+
+impl StacksMicroblockHeader {
+    /// Serialize the header fields. The cached `serialized_bytes` are not used.
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u8(self.version)?;
+        fd.write_u16::<BigEndian>(self.sequence)?;
+        fd.write_all(&self.prev_block.0)?;
+        fd.write_all(&self.tx_merkle_root.0)?;
+        self.signature.serialize(fd)?;
+        Ok(())
+    }
+}
+
+impl StacksString {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u32::<BigEndian>(self.0.len() as u32)?;
+        fd.write_all(&self.0)?;
+        Ok(())
+    }
+}
+
+impl PrincipalData {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            PrincipalData::Standard(principal) => {
+                fd.write_u8(TypePrefix::PrincipalStandard.to_u8())?;
+                principal.serialize(fd)?;
+            }
+            PrincipalData::Contract(contract_identifier) => {
+                fd.write_u8(TypePrefix::PrincipalContract.to_u8())?;
+                contract_identifier.issuer.serialize(fd)?;
+                contract_identifier.name.serialize(fd)?;
+            }
+        };
+        Ok(())
+    }
+
+    pub fn serialize_optional<W: Write>(
+        principal: Option<&Self>,
+        fd: &mut W,
+    ) -> Result<(), SerializeError> {
+        match principal {
+            None => fd.write_u8(TypePrefix::OptionalNone.to_u8())?,
+            Some(principal) => {
+                fd.write_u8(TypePrefix::OptionalSome.to_u8())?;
+                principal.serialize(fd)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl StandardPrincipalData {
+    pub fn serialize<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        fd.write_u8(self.0)?;
+        fd.write_all(&self.1)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::clarity_value::types::ClarityName;
+    use crate::hex::decode_hex;
+    use crate::stacks_block::deserialize::NakamotoBlock;
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let inputs = [
+            "00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000",
+            "00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000",
+            "000000000105001f6000287c9ed40ba39b27fc4257ff30cda4e91d000000000000005c0000000000000000000188f35fb5444ebdfcfced4b267aa76e60b90fc38ff7f7c8d770fc21407a26650a78aded3af77a5f0d7ca2ba7dabeff0579ec1d891e715c55237cfd5737e42378000661506d48705f932af21abcff23046b216886e840000000000000a1a000000000007a12000003fe006c2b46c5c6a16bc0af6f065c63d6e6c5a40e51fec26a9768caab0acd7ca42401d9c8030f120d2005388c1d04c602a3a9177eb26534a4fd6a3897686b1490302000000010002161f6000287c9ed40ba39b27fc4257ff30cda4e91d0100000000bda999040216debc095099629badb11b9d5335e874d12f1f1d450e73656e642d6d616e792d6d656d6f0973656e642d6d616e79000000010b0000001e0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162c34acd88561d6905f635e8eb59a36395b8c42a1047573747801000000000000000000000000002e640b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f1818ba832287fc71375c3e78bae2c8c64f6bb01047573747801000000000000000000000000008d51b10c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516267248c930110d411248d9ff16e63a8ddfa0c641047573747801000000000000000000000000121d39d80c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e73e15214dab160f2b78c935279e99a4a8ce83fc047573747801000000000000000000000000003beba90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166176366f88666f63ee0280db409fc8bcdc5eccbd047573747801000000000000000000000000056fb9430c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cb8de306c17412fc869c8337fa7b6b6f3a17cc160475737478010000000000000000000000000014018b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516477b4de6bfcf52f8cfec6854720b7a6982193eb704757374780100000000000000000000000005f518e20c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051668210b7f12b4ee43f374da9a59a51ecc8d0d89230475737478010000000000000000000000000029a8780c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05163b8e6ef49d23e432100aeca022c89555b7209aad04757374780100000000000000000000000010152ebb0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051694401aeb5474061ac390f723cb1826feb1b90448047573747801000000000000000000000000482a34380c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051686e0feea7bd59ae51dd0d2e47c7220406348e73804757374780100000000000000000000000000c938550c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516a52961a0d54cb3b9ce9a7092440dff4573cff58c04757374780100000000000000000000000000f19d340c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516130b7aee04f3eee627e103d3c33aea1d5339087d047573747801000000000000000000000000032aadd60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165db3d733033684784ebfa931b8e5f228bb247c4d04757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051600da0575e398c7b3d64f3df9d32ec57b9c0af3f704757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051607b7982ea3aaff67270d86e62436b0d08ea8d17804757374780100000000000000000000000010bc307b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fd048b0a4e906580e4a5281ebef59f2d60585ac4047573747801000000000000000000000000076efb580c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05164ecd67a6d4ca15c0cb9cf7cc0bc0594ba10ce4ee047573747801000000000000000000000000016a6c4c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f6641fafd4cdb742214558a43a5effeeb6061d76047573747801000000000000000000000000002a5b460c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166af9a2a4e295c9b23b7304f4bd14082b7101e7c304757374780100000000000000000000000002b299d90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166c317c39e65133aa7a414eb75e516dc59536f55004757374780100000000000000000000000018b0beba0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e13a94997d076788fa000588d7bfb053b2cfa3c3047573747801000000000000000000000000003b2c660c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165b7ee1d697120fbe6a761df99cddb46774cf1ca50475737478010000000000000000000000000005da110c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051688e4220b60f2222e115d8a8b1e46b09aed368854047573747801000000000000000000000000056d4c270c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051609973469a88ffcbf70651d8078442b9eedd47f590475737478010000000000000000000000000002e6410c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162bbf6ea8291431ec60fcb86c066ff375071ce7d8047573747801000000000000000000000000001e57d60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fbd72ce502f8a46db5a6e473a4e2c8830bc1a43904757374780100000000000000000000000001a593880c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051667a3c0f1b06075e9f7b9fa4d3e5d6492041c295a0475737478010000000000000000000000000005cb390c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cba8da4a454ae7ad02cb71fb523ddd720d73ad3a0475737478010000000000000000000000000176f41c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05169bb88a9c3db00f126f08f380d28b348458ebb6f7047573747801000000000000000000000000025c3d73",
+            "80800000000400fd3cd910d78fe7c4cd697d5228e51a912ff2ba740000000000000004000000000000000001008d36064b250dba5d3221ac235a9320adb072cfc23cd63511e6d814f97f0302e66c2ece80d7512df1b3e90ca6dce18179cb67b447973c739825ce6c6756bc247d010200000000050000000000000000000000000000000000000000000000000000000000000000051aba27f99e007c7f605a8305e318c1abde3cd220ac",
+            "80800000000400e6c05355e0c990ffad19a5e9bda394a9c50034290000000000000000000000000000000000009172c9841e763c32e827c177491f5228956e6ef1071043be898bfdd694bf3e680309b0666e8fec013a8a453573a8bd707152c9f21aa6f2d5e57c407af672b6f00302000000000602086b762d73746f72650000015628646566696e652d6d61702073746f72652028286b657920286275666620333229292920282876616c7565202862756666203332292929290a0a28646566696e652d7075626c696320286765742d76616c756520286b65792028627566662033322929290a20202020286d6174636820286d61702d6765743f2073746f72652028286b6579206b65792929290a2020202020202020656e74727920286f6b20286765742076616c756520656e74727929290a20202020202020202865727220302929290a0a28646566696e652d7075626c696320287365742d76616c756520286b65792028627566662033322929202876616c75652028627566662033322929290a2020202028626567696e0a2020202020202020286d61702d7365742073746f72652028286b6579206b6579292920282876616c75652076616c75652929290a2020202020202020286f6b2027747275652929290a",
+        ];
+        for input in inputs {
+            let bytes = decode_hex(input).unwrap();
+            let mut cursor = Cursor::new(bytes.as_ref());
+            let tx = StacksTransaction::deserialize(&mut cursor).unwrap();
+            assert_eq!(tx.serialize_to_vec().unwrap(), bytes.as_ref());
+        }
+    }
+
+    #[test]
+    fn test_serialize_nakamoto_block_txs() {
+        let data = include_bytes!("../../tests/fixtures/nakamoto-block.bin");
+        let mut cursor = Cursor::new(data.as_ref());
+        let block = NakamotoBlock::deserialize(&mut cursor).unwrap();
+        let mut serialized = vec![];
+        for tx in block.txs.iter() {
+            tx.serialize(&mut serialized).unwrap();
+        }
+        assert_eq!(serialized, &data[data.len() - serialized.len()..]);
+    }
+
+    fn tx_with_payload(payload: TransactionPayload) -> StacksTransaction {
+        let origin = MultisigSpendingCondition {
+            hash_mode: MultisigHashMode::P2SH,
+            signer: [0x11; 20],
+            nonce: 7,
+            tx_fee: 1000,
+            fields: vec![
+                TransactionAuthField::Signature(
+                    TransactionPublicKeyEncoding::Compressed,
+                    MessageSignature([0x22; 65]),
+                ),
+                TransactionAuthField::PublicKey(Secp256k1PublicKey {
+                    key: StacksPublicKeyBuffer([0x02; 33]),
+                    compressed: true,
+                }),
+            ],
+            signatures_required: 1,
+        };
+        StacksTransaction {
+            version: TransactionVersion::Testnet,
+            chain_id: 0x80000000,
+            auth: TransactionAuth::Standard(TransactionSpendingCondition::Multisig(origin)),
+            anchor_mode: TransactionAnchorMode::Any,
+            post_conditions_serialized: vec![],
+            post_condition_mode: TransactionPostConditionMode::Deny,
+            post_conditions: vec![],
+            payload,
+        }
+    }
+
+    fn microblock_header(sequence: u16) -> StacksMicroblockHeader {
+        StacksMicroblockHeader {
+            version: 0,
+            sequence,
+            prev_block: BlockHeaderHash([0x33; 32]),
+            tx_merkle_root: Sha512Trunc256Sum([0x44; 32]),
+            signature: MessageSignature([0x55; 65]),
+            serialized_bytes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_serialize_synthesized_payloads() {
+        let recipient = || {
+            PrincipalData::Contract(QualifiedContractIdentifier {
+                issuer: StandardPrincipalData(26, [0x66; 20]),
+                name: ClarityName("pool".to_string()),
+            })
+        };
+        let payloads = vec![
+            TransactionPayload::PoisonMicroblock(microblock_header(1), microblock_header(2)),
+            TransactionPayload::Coinbase(CoinbasePayload([0x77; 32])),
+            TransactionPayload::CoinbaseToAltRecipient(CoinbasePayload([0x77; 32]), recipient()),
+            TransactionPayload::VersionedSmartContract(
+                TransactionSmartContract {
+                    name: ClarityName("hello-world".to_string()),
+                    code_body: StacksString(b"(define-read-only (hello) \"hi\")".to_vec()),
+                },
+                ClarityVersion::Clarity3,
+            ),
+            TransactionPayload::TenureChange(TransactionTenureChange {
+                tenure_consensus_hash: [0x01; 20],
+                prev_tenure_consensus_hash: [0x02; 20],
+                burn_view_consensus_hash: [0x03; 20],
+                previous_tenure_end: [0x04; 32],
+                previous_tenure_blocks: 42,
+                cause: TenureChangeCause::Extended,
+                pubkey_hash: [0x05; 20],
+            }),
+            TransactionPayload::NakamotoCoinbase(
+                CoinbasePayload([0x77; 32]),
+                Some(recipient()),
+                VRFProof(vec![0x88; 80]),
+            ),
+            TransactionPayload::NakamotoCoinbase(
+                CoinbasePayload([0x77; 32]),
+                None,
+                VRFProof(vec![0x88; 80]),
+            ),
+        ];
+        for payload in payloads {
+            let bytes = tx_with_payload(payload).serialize_to_vec().unwrap();
+            let mut cursor = Cursor::new(bytes.as_ref());
+            let tx = StacksTransaction::deserialize(&mut cursor).unwrap();
+            assert_eq!(cursor.position() as usize, bytes.len());
+            assert_eq!(tx.serialize_to_vec().unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_serialize_bad_vrf_proof() {
+        let tx = tx_with_payload(TransactionPayload::NakamotoCoinbase(
+            CoinbasePayload([0; 32]),
+            None,
+            VRFProof(vec![0; 79]),
+        ));
+        assert!(tx.serialize_to_vec().is_err());
+    }
+}
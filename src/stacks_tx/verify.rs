@@ -0,0 +1,496 @@
+use std::convert::TryFrom;
+use std::io::Write;
+
+use byteorder::{BigEndian, WriteBytesExt};
+use sha2::{Digest, Sha512_256};
+
+use crate::address::stacks_address::AddressHashMode;
+use crate::crypto::{parse_public_key, recover_public_key, serialize_public_key};
+use crate::serialize_util::SerializeError;
+
+use super::deserialize::*;
+
+/// Outcome of verifying the signatures of one spending condition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpendingConditionVerification {
+    /// Public keys in field order, serialized with their declared key encoding. For singlesig
+    /// conditions this is the single key recovered from the signature.
+    pub public_keys: Vec<Vec<u8>>,
+    /// Why verification failed, or `None` if the public keys hash to the condition's `signer`.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TransactionVerification {
+    pub origin: SpendingConditionVerification,
+    pub sponsor: Option<SpendingConditionVerification>,
+}
+
+impl TransactionVerification {
+    pub fn is_valid(&self) -> bool {
+        self.origin.error.is_none()
+            && self
+                .sponsor
+                .as_ref()
+                .is_none_or(|sponsor| sponsor.error.is_none())
+    }
+}
+
+impl StacksTransaction {
+    /// The sighash signed over by the first origin signer: the txid of this transaction with
+    /// the origin condition cleared and the sponsor condition (if any) reset.
+    pub fn initial_sighash(&self) -> Result<[u8; 32], SerializeError> {
+        let mut auth_bytes = vec![];
+        self.auth.serialize(&mut auth_bytes)?;
+        let mut sighash_auth_bytes = vec![];
+        self.auth
+            .serialize_initial_sighash(&mut sighash_auth_bytes)?;
+
+        // the auth is preceded by the version byte and 4-byte chain ID
+        let mut tx_bytes = self.serialize_to_vec()?;
+        tx_bytes.splice(5..5 + auth_bytes.len(), sighash_auth_bytes);
+        Ok(Sha512_256::digest(&tx_bytes).into())
+    }
+
+    /// Walk the sighash chain of the origin (and sponsor) spending conditions, recovering the
+    /// public key of each signature and checking that the keys hash to the declared signer.
+    pub fn verify_signatures(&self) -> Result<TransactionVerification, SerializeError> {
+        let initial_sighash = self.initial_sighash()?;
+        let verification = match &self.auth {
+            TransactionAuth::Standard(origin_condition) => {
+                let (origin, _) =
+                    origin_condition.verify(&initial_sighash, TransactionAuthFlags::AuthStandard);
+                TransactionVerification {
+                    origin,
+                    sponsor: None,
+                }
+            }
+            TransactionAuth::Sponsored(origin_condition, sponsor_condition) => {
+                let (origin, origin_sighash) =
+                    origin_condition.verify(&initial_sighash, TransactionAuthFlags::AuthStandard);
+                let sponsor = match origin_sighash {
+                    Some(origin_sighash) => {
+                        sponsor_condition
+                            .verify(&origin_sighash, TransactionAuthFlags::AuthSponsored)
+                            .0
+                    }
+                    None => SpendingConditionVerification {
+                        public_keys: vec![],
+                        error: Some("Origin signatures could not be recovered".to_string()),
+                    },
+                };
+                TransactionVerification {
+                    origin,
+                    sponsor: Some(sponsor),
+                }
+            }
+        };
+        Ok(verification)
+    }
+}
+
+impl TransactionAuth {
+    fn serialize_initial_sighash<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionAuth::Standard(origin_condition) => {
+                fd.write_u8(TransactionAuthFlags::AuthStandard as u8)?;
+                origin_condition.serialize_cleared(fd)?;
+            }
+            TransactionAuth::Sponsored(origin_condition, _) => {
+                fd.write_u8(TransactionAuthFlags::AuthSponsored as u8)?;
+                origin_condition.serialize_cleared(fd)?;
+                // initial sighash sponsor: P2PKH singlesig with empty signer, nonce, fee, and signature
+                fd.write_u8(SinglesigHashMode::P2PKH as u8)?;
+                fd.write_all(&[0u8; 20])?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u8(TransactionPublicKeyEncoding::Compressed as u8)?;
+                fd.write_all(&[0u8; 65])?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl TransactionSpendingCondition {
+    /// Serialize with the nonce, fee, and signatures cleared.
+    fn serialize_cleared<W: Write>(&self, fd: &mut W) -> Result<(), SerializeError> {
+        match self {
+            TransactionSpendingCondition::Singlesig(cond) => {
+                fd.write_u8(cond.hash_mode as u8)?;
+                fd.write_all(&cond.signer)?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u8(cond.key_encoding as u8)?;
+                fd.write_all(&[0u8; 65])?;
+            }
+            TransactionSpendingCondition::Multisig(cond) => {
+                fd.write_u8(cond.hash_mode as u8)?;
+                fd.write_all(&cond.signer)?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u64::<BigEndian>(0)?;
+                fd.write_u32::<BigEndian>(0)?;
+                fd.write_u16::<BigEndian>(cond.signatures_required)?;
+            }
+        };
+        Ok(())
+    }
+
+    /// Verify against `cur_sighash`, returning the result along with the sighash that the
+    /// next signer signs over (`None` if a signature could not be recovered).
+    pub fn verify(
+        &self,
+        cur_sighash: &[u8; 32],
+        cond_code: TransactionAuthFlags,
+    ) -> (SpendingConditionVerification, Option<[u8; 32]>) {
+        let result = match self {
+            TransactionSpendingCondition::Singlesig(cond) => cond.verify(cur_sighash, cond_code),
+            TransactionSpendingCondition::Multisig(cond) => cond.verify(cur_sighash, cond_code),
+        };
+        match result {
+            Ok((verification, next_sighash)) => (verification, Some(next_sighash)),
+            Err(verification) => (verification, None),
+        }
+    }
+}
+
+type VerifyResult =
+    Result<(SpendingConditionVerification, [u8; 32]), SpendingConditionVerification>;
+
+impl SinglesigSpendingCondition {
+    fn verify(&self, cur_sighash: &[u8; 32], cond_code: TransactionAuthFlags) -> VerifyResult {
+        let (public_key, next_sighash) = next_verification(
+            cur_sighash,
+            cond_code,
+            self.tx_fee,
+            self.nonce,
+            self.key_encoding,
+            &self.signature,
+        )
+        .map_err(|e| SpendingConditionVerification {
+            public_keys: vec![],
+            error: Some(e),
+        })?;
+        let public_keys = vec![public_key];
+        let hash_mode = AddressHashMode::try_from(self.hash_mode as u8).unwrap();
+        let error = check_signer_hash(hash_mode, 1, &public_keys, &self.signer);
+        Ok((
+            SpendingConditionVerification { public_keys, error },
+            next_sighash,
+        ))
+    }
+}
+
+impl MultisigSpendingCondition {
+    fn verify(&self, cur_sighash: &[u8; 32], cond_code: TransactionAuthFlags) -> VerifyResult {
+        let sequential = matches!(
+            self.hash_mode,
+            MultisigHashMode::P2SH | MultisigHashMode::P2WSH
+        );
+        let mut public_keys = vec![];
+        let mut num_sigs: u16 = 0;
+        let mut have_uncompressed = false;
+        let mut next_sighash = *cur_sighash;
+
+        for field in self.fields.iter() {
+            let public_key = match field {
+                TransactionAuthField::PublicKey(public_key) => {
+                    have_uncompressed |= !public_key.compressed;
+                    parse_public_key(&public_key.key.0)
+                        .map(|key| serialize_public_key(&key, public_key.compressed))
+                }
+                TransactionAuthField::Signature(key_encoding, signature) => {
+                    have_uncompressed |=
+                        *key_encoding == TransactionPublicKeyEncoding::Uncompressed;
+                    num_sigs = num_sigs.saturating_add(1);
+                    if sequential {
+                        // each signer signs over the previous signer's postsign sighash
+                        next_verification(
+                            &next_sighash,
+                            cond_code,
+                            self.tx_fee,
+                            self.nonce,
+                            *key_encoding,
+                            signature,
+                        )
+                        .map(|(public_key, sighash)| {
+                            next_sighash = sighash;
+                            public_key
+                        })
+                    } else {
+                        // all signers sign over the same presign sighash
+                        let presign =
+                            make_sighash_presign(cur_sighash, cond_code, self.tx_fee, self.nonce);
+                        recover_public_key(&presign, &signature.0).map(|key| {
+                            serialize_public_key(
+                                &key,
+                                *key_encoding == TransactionPublicKeyEncoding::Compressed,
+                            )
+                        })
+                    }
+                }
+            };
+            match public_key {
+                Ok(public_key) => public_keys.push(public_key),
+                Err(e) => {
+                    return Err(SpendingConditionVerification {
+                        public_keys,
+                        error: Some(e),
+                    })
+                }
+            }
+        }
+
+        let error = if sequential && num_sigs != self.signatures_required {
+            Some("Incorrect number of signatures".to_string())
+        } else if !sequential && num_sigs < self.signatures_required {
+            Some("Not enough signatures".to_string())
+        } else if have_uncompressed
+            && matches!(
+                self.hash_mode,
+                MultisigHashMode::P2WSH | MultisigHashMode::P2WSHNonSequential
+            )
+        {
+            Some("Uncompressed keys are not allowed in this hash mode".to_string())
+        } else {
+            let hash_mode = AddressHashMode::try_from(self.hash_mode as u8).unwrap();
+            check_signer_hash(
+                hash_mode,
+                self.signatures_required as usize,
+                &public_keys,
+                &self.signer,
+            )
+        };
+        if !sequential {
+            next_sighash = *cur_sighash;
+        }
+        Ok((
+            SpendingConditionVerification { public_keys, error },
+            next_sighash,
+        ))
+    }
+}
+
+fn check_signer_hash(
+    hash_mode: AddressHashMode,
+    num_sigs: usize,
+    public_keys: &[Vec<u8>],
+    signer: &[u8; 20],
+) -> Option<String> {
+    match hash_mode.public_keys_to_hash160(num_sigs, public_keys) {
+        Ok(hash) if hash == *signer => None,
+        Ok(_) => Some("Signer hash does not equal hash of public key(s)".to_string()),
+        Err(e) => Some(e),
+    }
+}
+
+/// Recover the public key for one signature and compute the sighash for the next signer.
+fn next_verification(
+    cur_sighash: &[u8; 32],
+    cond_code: TransactionAuthFlags,
+    tx_fee: u64,
+    nonce: u64,
+    key_encoding: TransactionPublicKeyEncoding,
+    signature: &MessageSignature,
+) -> Result<(Vec<u8>, [u8; 32]), String> {
+    let presign = make_sighash_presign(cur_sighash, cond_code, tx_fee, nonce);
+    let public_key = recover_public_key(&presign, &signature.0)?;
+    let public_key = serialize_public_key(
+        &public_key,
+        key_encoding == TransactionPublicKeyEncoding::Compressed,
+    );
+    let postsign = make_sighash_postsign(&presign, key_encoding, signature);
+    Ok((public_key, postsign))
+}
+
+fn make_sighash_presign(
+    cur_sighash: &[u8; 32],
+    cond_code: TransactionAuthFlags,
+    tx_fee: u64,
+    nonce: u64,
+) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(cur_sighash);
+    hasher.update([cond_code as u8]);
+    hasher.update(tx_fee.to_be_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn make_sighash_postsign(
+    presign: &[u8; 32],
+    key_encoding: TransactionPublicKeyEncoding,
+    signature: &MessageSignature,
+) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(presign);
+    hasher.update([key_encoding as u8]);
+    hasher.update(signature.0);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+    use crate::hex::{decode_hex, encode_hex};
+
+    const SINGLESIG_TX: &str = "00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000";
+    const UNCOMPRESSED_SINGLESIG_TX: &str = "80800000000400fd3cd910d78fe7c4cd697d5228e51a912ff2ba740000000000000004000000000000000001008d36064b250dba5d3221ac235a9320adb072cfc23cd63511e6d814f97f0302e66c2ece80d7512df1b3e90ca6dce18179cb67b447973c739825ce6c6756bc247d010200000000050000000000000000000000000000000000000000000000000000000000000000051aba27f99e007c7f605a8305e318c1abde3cd220ac";
+    const SPONSORED_TX: &str = "000000000105001f6000287c9ed40ba39b27fc4257ff30cda4e91d000000000000005c0000000000000000000188f35fb5444ebdfcfced4b267aa76e60b90fc38ff7f7c8d770fc21407a26650a78aded3af77a5f0d7ca2ba7dabeff0579ec1d891e715c55237cfd5737e42378000661506d48705f932af21abcff23046b216886e840000000000000a1a000000000007a12000003fe006c2b46c5c6a16bc0af6f065c63d6e6c5a40e51fec26a9768caab0acd7ca42401d9c8030f120d2005388c1d04c602a3a9177eb26534a4fd6a3897686b1490302000000010002161f6000287c9ed40ba39b27fc4257ff30cda4e91d0100000000bda999040216debc095099629badb11b9d5335e874d12f1f1d450e73656e642d6d616e792d6d656d6f0973656e642d6d616e79000000010b0000001e0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162c34acd88561d6905f635e8eb59a36395b8c42a1047573747801000000000000000000000000002e640b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f1818ba832287fc71375c3e78bae2c8c64f6bb01047573747801000000000000000000000000008d51b10c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516267248c930110d411248d9ff16e63a8ddfa0c641047573747801000000000000000000000000121d39d80c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e73e15214dab160f2b78c935279e99a4a8ce83fc047573747801000000000000000000000000003beba90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166176366f88666f63ee0280db409fc8bcdc5eccbd047573747801000000000000000000000000056fb9430c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cb8de306c17412fc869c8337fa7b6b6f3a17cc160475737478010000000000000000000000000014018b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516477b4de6bfcf52f8cfec6854720b7a6982193eb704757374780100000000000000000000000005f518e20c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051668210b7f12b4ee43f374da9a59a51ecc8d0d89230475737478010000000000000000000000000029a8780c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05163b8e6ef49d23e432100aeca022c89555b7209aad04757374780100000000000000000000000010152ebb0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051694401aeb5474061ac390f723cb1826feb1b90448047573747801000000000000000000000000482a34380c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051686e0feea7bd59ae51dd0d2e47c7220406348e73804757374780100000000000000000000000000c938550c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516a52961a0d54cb3b9ce9a7092440dff4573cff58c04757374780100000000000000000000000000f19d340c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516130b7aee04f3eee627e103d3c33aea1d5339087d047573747801000000000000000000000000032aadd60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165db3d733033684784ebfa931b8e5f228bb247c4d04757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051600da0575e398c7b3d64f3df9d32ec57b9c0af3f704757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051607b7982ea3aaff67270d86e62436b0d08ea8d17804757374780100000000000000000000000010bc307b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fd048b0a4e906580e4a5281ebef59f2d60585ac4047573747801000000000000000000000000076efb580c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05164ecd67a6d4ca15c0cb9cf7cc0bc0594ba10ce4ee047573747801000000000000000000000000016a6c4c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f6641fafd4cdb742214558a43a5effeeb6061d76047573747801000000000000000000000000002a5b460c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166af9a2a4e295c9b23b7304f4bd14082b7101e7c304757374780100000000000000000000000002b299d90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166c317c39e65133aa7a414eb75e516dc59536f55004757374780100000000000000000000000018b0beba0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e13a94997d076788fa000588d7bfb053b2cfa3c3047573747801000000000000000000000000003b2c660c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165b7ee1d697120fbe6a761df99cddb46774cf1ca50475737478010000000000000000000000000005da110c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051688e4220b60f2222e115d8a8b1e46b09aed368854047573747801000000000000000000000000056d4c270c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051609973469a88ffcbf70651d8078442b9eedd47f590475737478010000000000000000000000000002e6410c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162bbf6ea8291431ec60fcb86c066ff375071ce7d8047573747801000000000000000000000000001e57d60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fbd72ce502f8a46db5a6e473a4e2c8830bc1a43904757374780100000000000000000000000001a593880c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051667a3c0f1b06075e9f7b9fa4d3e5d6492041c295a0475737478010000000000000000000000000005cb390c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cba8da4a454ae7ad02cb71fb523ddd720d73ad3a0475737478010000000000000000000000000176f41c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05169bb88a9c3db00f126f08f380d28b348458ebb6f7047573747801000000000000000000000000025c3d73";
+
+    fn deserialize_tx(input: &str) -> StacksTransaction {
+        let bytes = decode_hex(input).unwrap();
+        let mut cursor = Cursor::new(bytes.as_ref());
+        StacksTransaction::deserialize(&mut cursor).unwrap()
+    }
+
+    fn sign(presign: &[u8; 32], key: &SigningKey) -> MessageSignature {
+        let (sig, recovery_id) = key.sign_prehash_recoverable(presign).unwrap();
+        let mut bytes = [0u8; 65];
+        bytes[0] = recovery_id.to_byte();
+        bytes[1..].copy_from_slice(&sig.to_bytes());
+        MessageSignature(bytes)
+    }
+
+    /// Replace the auth of `tx` with a 2-of-3 multisig origin signed by the first two keys.
+    fn multisig_tx(hash_mode: MultisigHashMode) -> StacksTransaction {
+        let keys: Vec<SigningKey> = (1u8..=3)
+            .map(|i| SigningKey::from_slice(&[i; 32]).unwrap())
+            .collect();
+        let public_keys: Vec<Vec<u8>> = keys
+            .iter()
+            .map(|key| {
+                key.verifying_key()
+                    .to_encoded_point(true)
+                    .as_bytes()
+                    .to_vec()
+            })
+            .collect();
+        let signer = AddressHashMode::try_from(hash_mode as u8)
+            .unwrap()
+            .public_keys_to_hash160(2, &public_keys)
+            .unwrap();
+
+        let mut tx = deserialize_tx(SINGLESIG_TX);
+        let (nonce, tx_fee) = (7, 180);
+        tx.auth = TransactionAuth::Standard(TransactionSpendingCondition::Multisig(
+            MultisigSpendingCondition {
+                hash_mode,
+                signer,
+                nonce,
+                tx_fee,
+                fields: vec![],
+                signatures_required: 2,
+            },
+        ));
+
+        let sequential = hash_mode == MultisigHashMode::P2SH;
+        let mut sighash = tx.initial_sighash().unwrap();
+        let mut fields = vec![];
+        for key in &keys[..2] {
+            let presign =
+                make_sighash_presign(&sighash, TransactionAuthFlags::AuthStandard, tx_fee, nonce);
+            let signature = sign(&presign, key);
+            if sequential {
+                sighash = make_sighash_postsign(
+                    &presign,
+                    TransactionPublicKeyEncoding::Compressed,
+                    &signature,
+                );
+            }
+            fields.push(TransactionAuthField::Signature(
+                TransactionPublicKeyEncoding::Compressed,
+                signature,
+            ));
+        }
+        let mut third_key = [0u8; 33];
+        third_key.copy_from_slice(&public_keys[2]);
+        fields.push(TransactionAuthField::PublicKey(Secp256k1PublicKey {
+            key: StacksPublicKeyBuffer(third_key),
+            compressed: true,
+        }));
+
+        if let TransactionAuth::Standard(TransactionSpendingCondition::Multisig(ref mut cond)) =
+            tx.auth
+        {
+            cond.fields = fields;
+        }
+        tx
+    }
+
+    #[test]
+    fn test_verify_singlesig() {
+        let verification = deserialize_tx(SINGLESIG_TX).verify_signatures().unwrap();
+        assert!(verification.is_valid());
+        assert!(verification.sponsor.is_none());
+        assert_eq!(
+            encode_hex(&verification.origin.public_keys[0]).as_ref(),
+            "0x03e356007964fc225a44c38352899c41e6293a97f8d8115998ae7e97184704c092"
+        );
+
+        let verification = deserialize_tx(UNCOMPRESSED_SINGLESIG_TX)
+            .verify_signatures()
+            .unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.origin.public_keys[0].len(), 65);
+    }
+
+    #[test]
+    fn test_verify_sponsored() {
+        let verification = deserialize_tx(SPONSORED_TX).verify_signatures().unwrap();
+        assert!(verification.origin.error.is_none());
+        assert!(verification.sponsor.unwrap().error.is_none());
+    }
+
+    #[test]
+    fn test_verify_tampered() {
+        let mut tx = deserialize_tx(SINGLESIG_TX);
+        if let TransactionAuth::Standard(TransactionSpendingCondition::Singlesig(ref mut cond)) =
+            tx.auth
+        {
+            cond.tx_fee += 1;
+        }
+        let verification = tx.verify_signatures().unwrap();
+        assert!(!verification.is_valid());
+        assert_eq!(
+            verification.origin.error.unwrap(),
+            "Signer hash does not equal hash of public key(s)"
+        );
+    }
+
+    #[test]
+    fn test_verify_multisig() {
+        for hash_mode in [MultisigHashMode::P2SH, MultisigHashMode::P2SHNonSequential] {
+            let tx = multisig_tx(hash_mode);
+            let verification = tx.verify_signatures().unwrap();
+            assert!(verification.is_valid());
+            assert_eq!(verification.origin.public_keys.len(), 3);
+        }
+
+        // signatures made for the sequential chain do not verify as non-sequential
+        let mut tx = multisig_tx(MultisigHashMode::P2SH);
+        if let TransactionAuth::Standard(TransactionSpendingCondition::Multisig(ref mut cond)) =
+            tx.auth
+        {
+            cond.hash_mode = MultisigHashMode::P2SHNonSequential;
+        }
+        assert!(!tx.verify_signatures().unwrap().is_valid());
+
+        let mut tx = multisig_tx(MultisigHashMode::P2SH);
+        if let TransactionAuth::Standard(TransactionSpendingCondition::Multisig(ref mut cond)) =
+            tx.auth
+        {
+            cond.signatures_required = 3;
+        }
+        assert_eq!(
+            tx.verify_signatures().unwrap().origin.error.unwrap(),
+            "Incorrect number of signatures"
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::io::Cursor;
+
+use crate::address::c32::c32_address_decode;
+use crate::clarity_value::value_ref::{ClarityValueRef, ValueRef};
+use crate::hex::encode_hex;
+use crate::memo::memo_normalize;
+use crate::pox_events::decode::{clarity_principal_to_string, extract_uint};
+
+use super::types::*;
+
+/// Decode the fields of a fungible or non-fungible token event.
+/// Returns `Ok(None)` if `event_type` is not a token event type (e.g. `stx_transfer_event`).
+/// Returns `Err` if a field required by the event type is missing or malformed.
+pub fn decode_token_event<'a>(
+    event_type: &str,
+    payload: &'a TokenEventPayload,
+) -> Result<Option<TokenEvent<'a>>, String> {
+    let name = match TokenEventName::from_str(event_type) {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let asset = parse_asset_identifier(&payload.asset_identifier)?;
+    let sender = match &payload.sender {
+        Some(sender) => Some(sender.clone()),
+        None if name.has_sender() => return Err("Missing expected event field: sender".into()),
+        None => None,
+    };
+    let recipient = match &payload.recipient {
+        Some(recipient) => Some(recipient.clone()),
+        None if name.has_recipient() => {
+            return Err("Missing expected event field: recipient".into())
+        }
+        None => None,
+    };
+
+    let data = if name.is_nft() {
+        let raw_value = payload
+            .raw_value
+            .as_ref()
+            .ok_or("Missing expected event field: raw_value")?;
+        let value = ClarityValueRef::deserialize(&mut Cursor::new(raw_value.as_slice()))
+            .map_err(|e| format!("Error deserializing Clarity value: {}", e))?;
+        TokenEventData::Nft { value }
+    } else {
+        let amount = payload
+            .amount
+            .as_ref()
+            .ok_or("Missing expected event field: amount")?;
+        let amount = amount
+            .parse::<u128>()
+            .map_err(|e| format!("Invalid event amount `{}`: {}", amount, e))?;
+        TokenEventData::Ft { amount }
+    };
+
+    Ok(Some(TokenEvent {
+        name,
+        asset,
+        sender,
+        recipient,
+        data,
+    }))
+}
+
+/// Split an asset identifier of the form `<address>.<contract-name>::<asset-name>`.
+pub fn parse_asset_identifier(asset_identifier: &str) -> Result<AssetIdentifier, String> {
+    let invalid = || format!("Invalid asset identifier: {}", asset_identifier);
+    let (contract_id, asset_name) = asset_identifier.split_once("::").ok_or_else(invalid)?;
+    let (contract_address, contract_name) = contract_id.split_once('.').ok_or_else(invalid)?;
+    if contract_name.is_empty() || asset_name.is_empty() {
+        return Err(invalid());
+    }
+    c32_address_decode(contract_address).map_err(|e| format!("{}: {}", invalid(), e))?;
+    Ok(AssetIdentifier {
+        contract_id: contract_id.to_string(),
+        contract_address: contract_address.to_string(),
+        contract_name: contract_name.to_string(),
+        asset_name: asset_name.to_string(),
+    })
+}
+
+/// Decode the arguments of a SIP-010 `transfer` contract call.
+/// Returns `Err` if the arguments do not match the SIP-010 `transfer` signature.
+pub fn decode_sip010_transfer_args(args: &[ClarityValueRef]) -> Result<Sip010Transfer, String> {
+    let (amount, sender, recipient, memo) = match args {
+        [amount, sender, recipient, memo] => (amount, sender, recipient, memo),
+        _ => {
+            return Err(format!(
+                "Expected 4 SIP-010 transfer arguments, got {}",
+                args.len()
+            ))
+        }
+    };
+    let amount = extract_uint(*amount)?;
+    let sender = clarity_principal_to_string(*sender)?;
+    let recipient = clarity_principal_to_string(*recipient)?;
+    let memo = match memo.value() {
+        ValueRef::OptionalNone => None,
+        ValueRef::OptionalSome(inner) => match inner.value() {
+            ValueRef::Buffer(bytes) => Some(bytes),
+            other => {
+                return Err(format!(
+                    "Expected Buffer inside OptionalSome for memo, got {:?}",
+                    other.type_prefix()
+                ))
+            }
+        },
+        other => {
+            return Err(format!(
+                "Expected OptionalSome/OptionalNone for memo, got {:?}",
+                other.type_prefix()
+            ))
+        }
+    };
+    Ok(Sip010Transfer {
+        amount,
+        sender,
+        recipient,
+        memo: memo.map(memo_normalize),
+        memo_hex: memo.map(|bytes| encode_hex(bytes).to_string()),
+    })
+}
+
+/// Decode the value of a `print` event emitted by a SIP-010 `transfer` with a memo.
+/// Returns `None` if the value is not a `Buffer` or `OptionalSome(Buffer)`.
+pub fn decode_sip010_print_memo(value: &ClarityValueRef) -> Option<Sip010Memo> {
+    let bytes = match value.value() {
+        ValueRef::Buffer(bytes) => bytes,
+        ValueRef::OptionalSome(inner) => match inner.value() {
+            ValueRef::Buffer(bytes) => bytes,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(Sip010Memo {
+        memo: memo_normalize(bytes),
+        memo_hex: encode_hex(bytes).to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clarity_value::types::{ClarityValue, StandardPrincipalData, Value};
+
+    const SENDER: &str = "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B";
+    const RECIPIENT: &str = "SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92";
+
+    fn serialize(value: Value) -> Vec<u8> {
+        value.serialize_to_vec().unwrap()
+    }
+
+    fn value_ref(bytes: &[u8]) -> ClarityValueRef<'_> {
+        ClarityValueRef::deserialize(&mut Cursor::new(bytes)).unwrap()
+    }
+
+    fn principal(address: &str) -> Value {
+        let (version, hash160) = c32_address_decode(address).unwrap();
+        Value::PrincipalStandard(StandardPrincipalData(version, hash160))
+    }
+
+    fn function_args(args: Vec<Value>) -> Vec<u8> {
+        let mut bytes = (args.len() as u32).to_be_bytes().to_vec();
+        for arg in args {
+            bytes.extend(serialize(arg));
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_parse_asset_identifier() {
+        let asset =
+            parse_asset_identifier("SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex")
+                .unwrap();
+        assert_eq!(
+            asset.contract_id,
+            "SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex"
+        );
+        assert_eq!(
+            asset.contract_address,
+            "SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9"
+        );
+        assert_eq!(asset.contract_name, "token-alex");
+        assert_eq!(asset.asset_name, "alex");
+        assert!(parse_asset_identifier("SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token").is_err());
+        assert!(parse_asset_identifier("not-an-address.token::alex").is_err());
+    }
+
+    #[test]
+    fn test_decode_ft_transfer_event() {
+        let payload = TokenEventPayload {
+            asset_identifier: "SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex".into(),
+            sender: Some(SENDER.into()),
+            recipient: Some(RECIPIENT.into()),
+            amount: Some("1000".into()),
+            raw_value: None,
+        };
+        let event = decode_token_event("ft_transfer_event", &payload)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.name, TokenEventName::FtTransfer);
+        assert_eq!(event.sender.as_deref(), Some(SENDER));
+        assert_eq!(event.data, TokenEventData::Ft { amount: 1000 });
+
+        let err = decode_token_event(
+            "ft_burn_event",
+            &TokenEventPayload {
+                sender: None,
+                ..payload.clone()
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, "Missing expected event field: sender");
+        assert!(decode_token_event("stx_transfer_event", &payload)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_decode_nft_mint_event() {
+        let raw_value = serialize(Value::UInt(1));
+        let payload = TokenEventPayload {
+            asset_identifier: "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B.nft::token".into(),
+            recipient: Some(RECIPIENT.into()),
+            raw_value: Some(raw_value.clone()),
+            ..Default::default()
+        };
+        let event = decode_token_event("nft_mint_event", &payload)
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.name, TokenEventName::NftMint);
+        assert_eq!(event.sender, None);
+        match event.data {
+            TokenEventData::Nft { value } => {
+                assert_eq!(value.serialized_bytes(), raw_value.as_slice());
+                assert_eq!(value.repr_string(), "u1");
+            }
+            data => panic!("Expected NFT data, got {:?}", data),
+        }
+    }
+
+    #[test]
+    fn test_decode_sip010_transfer_args() {
+        let bytes = function_args(vec![
+            Value::UInt(500),
+            principal(SENDER),
+            principal(RECIPIENT),
+            Value::OptionalSome(Box::new(ClarityValue::new(Value::Buffer(
+                b"\x00\x00exchange-id 42".to_vec(),
+            )))),
+        ]);
+        let args = ClarityValueRef::deserialize_list(&bytes).unwrap();
+        let transfer = decode_sip010_transfer_args(&args).unwrap();
+        assert_eq!(transfer.amount, 500);
+        assert_eq!(transfer.sender, SENDER);
+        assert_eq!(transfer.recipient, RECIPIENT);
+        assert_eq!(transfer.memo.as_deref(), Some("exchange-id 42"));
+        assert_eq!(
+            transfer.memo_hex.as_deref(),
+            Some("0x000065786368616e67652d6964203432")
+        );
+
+        let bytes = function_args(vec![
+            Value::UInt(500),
+            principal(SENDER),
+            principal(RECIPIENT),
+            Value::OptionalNone,
+        ]);
+        let transfer =
+            decode_sip010_transfer_args(&ClarityValueRef::deserialize_list(&bytes).unwrap())
+                .unwrap();
+        assert_eq!(transfer.memo, None);
+        assert_eq!(transfer.memo_hex, None);
+
+        let bytes = function_args(vec![Value::UInt(500), principal(SENDER)]);
+        let err = decode_sip010_transfer_args(&ClarityValueRef::deserialize_list(&bytes).unwrap())
+            .unwrap_err();
+        assert_eq!(err, "Expected 4 SIP-010 transfer arguments, got 2");
+    }
+
+    #[test]
+    fn test_decode_sip010_print_memo() {
+        let bytes = serialize(Value::Buffer(b"hello\n".to_vec()));
+        let memo = decode_sip010_print_memo(&value_ref(&bytes)).unwrap();
+        assert_eq!(memo.memo, "hello");
+        assert_eq!(memo.memo_hex, "0x68656c6c6f0a");
+
+        let bytes = serialize(Value::OptionalSome(Box::new(ClarityValue::new(
+            Value::Buffer(b"hello".to_vec()),
+        ))));
+        assert_eq!(
+            decode_sip010_print_memo(&value_ref(&bytes)).unwrap().memo,
+            "hello"
+        );
+
+        let bytes = serialize(Value::UInt(1));
+        assert!(decode_sip010_print_memo(&value_ref(&bytes)).is_none());
+    }
+}
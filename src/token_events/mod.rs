@@ -0,0 +1,108 @@
+#[cfg(feature = "nodejs")]
+use std::io::Cursor;
+
+#[cfg(feature = "nodejs")]
+use neon::prelude::*;
+
+#[cfg(feature = "nodejs")]
+use crate::clarity_value::value_ref::ClarityValueRef;
+#[cfg(feature = "nodejs")]
+use crate::hex::decode_hex;
+#[cfg(feature = "nodejs")]
+use crate::neon_util::{
+    arg_as_bytes_copied, get_obj_prop, get_obj_prop_opt, get_obj_prop_string,
+    throw_deserialize_error, OutputOptions,
+};
+
+#[cfg(feature = "nodejs")]
+use self::decode::{decode_sip010_print_memo, decode_sip010_transfer_args, decode_token_event};
+#[cfg(feature = "nodejs")]
+use self::neon_encoder::{encode_sip010_memo, encode_sip010_transfer, encode_token_event};
+#[cfg(feature = "nodejs")]
+use self::types::{TokenEventName, TokenEventPayload};
+
+pub mod decode;
+#[cfg(feature = "nodejs")]
+pub mod neon_encoder;
+pub mod types;
+
+/// Neon-exported function: decodeTokenEvent(event: object, options?: object)
+/// Takes an event of a `/new_block` message, e.g. `{ type: 'ft_transfer_event', ft_transfer_event: {..} }`.
+/// Returns a JS object, or null if the event is not a fungible or non-fungible token event.
+#[cfg(feature = "nodejs")]
+pub fn decode_token_event_object(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let event_obj = cx.argument::<JsObject>(0)?;
+    let options = OutputOptions::from_arg(&mut cx, 1)?;
+    let event_type = get_obj_prop_string(&mut cx, &event_obj, "type")?;
+    if TokenEventName::from_str(&event_type).is_none() {
+        return Ok(cx.null().upcast());
+    }
+    let payload_obj = get_obj_prop::<JsObject>(&mut cx, &event_obj, &event_type)?;
+    let payload = token_event_payload_from_js(&mut cx, &payload_obj)?;
+
+    let event = decode_token_event(&event_type, &payload)
+        .or_else(|e| cx.throw_error(format!("Error decoding token event: {}", e)))?;
+
+    match event {
+        Some(evt) => {
+            let obj = encode_token_event(&mut cx, &evt, options)?;
+            Ok(obj.upcast())
+        }
+        None => Ok(cx.null().upcast()),
+    }
+}
+
+#[cfg(feature = "nodejs")]
+fn token_event_payload_from_js(
+    cx: &mut FunctionContext,
+    obj: &Handle<JsObject>,
+) -> NeonResult<TokenEventPayload> {
+    let mut opt_string = |key: &str| -> NeonResult<Option<String>> {
+        Ok(get_obj_prop_opt::<JsString>(cx, obj, key)?.map(|s| s.value(cx)))
+    };
+    let sender = opt_string("sender")?;
+    let recipient = opt_string("recipient")?;
+    let amount = opt_string("amount")?;
+    let raw_value = match opt_string("raw_value")? {
+        Some(hex) => Some(decode_hex(hex).or_else(|e| {
+            cx.throw_error(format!("Hex parsing error for property `raw_value`: {}", e))
+        })?),
+        None => None,
+    };
+    Ok(TokenEventPayload {
+        asset_identifier: get_obj_prop_string(cx, obj, "asset_identifier")?,
+        sender,
+        recipient,
+        amount,
+        raw_value: raw_value.map(Vec::from),
+    })
+}
+
+/// Neon-exported function: decodeSip010TransferArgs(arg: string | Buffer)
+/// Takes the `function_args_buffer` of a SIP-010 `transfer` contract call.
+#[cfg(feature = "nodejs")]
+pub fn decode_sip010_transfer(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let args_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let args = ClarityValueRef::deserialize_list(&args_bytes)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    let transfer = decode_sip010_transfer_args(&args)
+        .or_else(|e| cx.throw_error(format!("Error decoding SIP-010 transfer arguments: {}", e)))?;
+    encode_sip010_transfer(&mut cx, &transfer)
+}
+
+/// Neon-exported function: decodeSip010PrintMemo(arg: string | Buffer)
+/// Returns a JS object, or null if the printed value is not a memo buffer.
+#[cfg(feature = "nodejs")]
+pub fn decode_sip010_memo(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let val_bytes = arg_as_bytes_copied(&mut cx, 0)?;
+    let mut cursor: Cursor<&[u8]> = Cursor::new(&val_bytes);
+    let clarity_value = ClarityValueRef::deserialize(&mut cursor)
+        .or_else(|e| throw_deserialize_error(&mut cx, "Error deserializing Clarity value", &e))?;
+    match decode_sip010_print_memo(&clarity_value) {
+        Some(memo) => {
+            let obj = encode_sip010_memo(&mut cx, &memo)?;
+            Ok(obj.upcast())
+        }
+        None => Ok(cx.null().upcast()),
+    }
+}
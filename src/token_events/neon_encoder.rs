@@ -0,0 +1,76 @@
+use neon::prelude::*;
+
+use crate::clarity_value::neon_encoder::decode_clarity_val;
+use crate::neon_util::OutputOptions;
+use crate::pox_events::neon_encoder::{set_optional_string, set_string, set_u128_string};
+
+use super::types::*;
+
+/// Serialize a `TokenEvent` into a Neon JS object.
+/// Amounts and the NFT value are output as for `decodeClarityValue` with the given options.
+pub fn encode_token_event<'a>(
+    cx: &mut FunctionContext<'a>,
+    event: &TokenEvent,
+    options: OutputOptions,
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    set_string(cx, &obj, "name", event.name.as_str())?;
+
+    let asset_obj = cx.empty_object();
+    set_string(cx, &asset_obj, "contract_id", &event.asset.contract_id)?;
+    set_string(
+        cx,
+        &asset_obj,
+        "contract_address",
+        &event.asset.contract_address,
+    )?;
+    set_string(cx, &asset_obj, "contract_name", &event.asset.contract_name)?;
+    set_string(cx, &asset_obj, "asset_name", &event.asset.asset_name)?;
+    obj.set(cx, "asset", asset_obj)?;
+
+    if event.name.has_sender() {
+        set_optional_string(cx, &obj, "sender", event.sender.as_deref())?;
+    }
+    if event.name.has_recipient() {
+        set_optional_string(cx, &obj, "recipient", event.recipient.as_deref())?;
+    }
+
+    match &event.data {
+        TokenEventData::Ft { amount } => {
+            let amount = options.numbers.uint(cx, *amount);
+            obj.set(cx, "amount", amount)?;
+        }
+        TokenEventData::Nft { value } => {
+            let value_obj = cx.empty_object();
+            decode_clarity_val(cx, &value_obj, value, true, options)?;
+            obj.set(cx, "value", value_obj)?;
+        }
+    }
+
+    Ok(obj)
+}
+
+/// Serialize a `Sip010Transfer` into a Neon JS object.
+pub fn encode_sip010_transfer<'a>(
+    cx: &mut FunctionContext<'a>,
+    transfer: &Sip010Transfer,
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    set_u128_string(cx, &obj, "amount", transfer.amount)?;
+    set_string(cx, &obj, "sender", &transfer.sender)?;
+    set_string(cx, &obj, "recipient", &transfer.recipient)?;
+    set_optional_string(cx, &obj, "memo", transfer.memo.as_deref())?;
+    set_optional_string(cx, &obj, "memo_hex", transfer.memo_hex.as_deref())?;
+    Ok(obj)
+}
+
+/// Serialize a `Sip010Memo` into a Neon JS object.
+pub fn encode_sip010_memo<'a>(
+    cx: &mut FunctionContext<'a>,
+    memo: &Sip010Memo,
+) -> JsResult<'a, JsObject> {
+    let obj = cx.empty_object();
+    set_string(cx, &obj, "memo", &memo.memo)?;
+    set_string(cx, &obj, "memo_hex", &memo.memo_hex)?;
+    Ok(obj)
+}
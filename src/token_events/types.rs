@@ -0,0 +1,138 @@
+use crate::clarity_value::value_ref::ClarityValueRef;
+
+/// The fungible and non-fungible token event types of a stacks-node event observer message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TokenEventName {
+    FtTransfer,
+    FtMint,
+    FtBurn,
+    NftTransfer,
+    NftMint,
+    NftBurn,
+}
+
+impl TokenEventName {
+    /// Parse the `type` of an event, e.g. `ft_transfer_event`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "ft_transfer_event" => Some(TokenEventName::FtTransfer),
+            "ft_mint_event" => Some(TokenEventName::FtMint),
+            "ft_burn_event" => Some(TokenEventName::FtBurn),
+            "nft_transfer_event" => Some(TokenEventName::NftTransfer),
+            "nft_mint_event" => Some(TokenEventName::NftMint),
+            "nft_burn_event" => Some(TokenEventName::NftBurn),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenEventName::FtTransfer => "ft_transfer",
+            TokenEventName::FtMint => "ft_mint",
+            TokenEventName::FtBurn => "ft_burn",
+            TokenEventName::NftTransfer => "nft_transfer",
+            TokenEventName::NftMint => "nft_mint",
+            TokenEventName::NftBurn => "nft_burn",
+        }
+    }
+
+    pub fn is_nft(&self) -> bool {
+        matches!(
+            self,
+            TokenEventName::NftTransfer | TokenEventName::NftMint | TokenEventName::NftBurn
+        )
+    }
+
+    /// Transfers and burns have a sender.
+    pub fn has_sender(&self) -> bool {
+        !matches!(self, TokenEventName::FtMint | TokenEventName::NftMint)
+    }
+
+    /// Transfers and mints have a recipient.
+    pub fn has_recipient(&self) -> bool {
+        !matches!(self, TokenEventName::FtBurn | TokenEventName::NftBurn)
+    }
+}
+
+/// A token asset identifier, e.g. `SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssetIdentifier {
+    /// The contract principal defining the token, e.g. `SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex`
+    pub contract_id: String,
+    pub contract_address: String,
+    pub contract_name: String,
+    pub asset_name: String,
+}
+
+/// The raw fields of an event observer `*_event` object, before they are validated.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenEventPayload {
+    pub asset_identifier: String,
+    pub sender: Option<String>,
+    pub recipient: Option<String>,
+    /// String-quoted amount of fungible token events
+    pub amount: Option<String>,
+    /// Serialized Clarity value identifying the asset of NFT events
+    pub raw_value: Option<Vec<u8>>,
+}
+
+/// A decoded fungible or non-fungible token event, borrowing the NFT value from its payload.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TokenEvent<'a> {
+    pub name: TokenEventName,
+    pub asset: AssetIdentifier,
+    pub sender: Option<String>,
+    pub recipient: Option<String>,
+    pub data: TokenEventData<'a>,
+}
+
+/// Event-specific data payload.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TokenEventData<'a> {
+    Ft {
+        amount: u128,
+    },
+    Nft {
+        /// The Clarity value identifying the token, serialized as its `raw_value` hex
+        #[cfg_attr(
+            feature = "serde",
+            serde(rename = "raw_value", serialize_with = "serialize_value_hex")
+        )]
+        value: ClarityValueRef<'a>,
+    },
+}
+
+#[cfg(feature = "serde")]
+fn serialize_value_hex<S: serde::Serializer>(
+    value: &ClarityValueRef,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    crate::hex::serialize_hex(&value.serialized_bytes(), serializer)
+}
+
+/// The arguments of a SIP-010 `transfer` contract call:
+/// `(transfer (amount uint) (sender principal) (recipient principal) (memo (optional (buff 34))))`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sip010Transfer {
+    pub amount: u128,
+    pub sender: String,
+    pub recipient: String,
+    /// The memo normalized to a printable string, as for `memoToString`
+    pub memo: Option<String>,
+    pub memo_hex: Option<String>,
+}
+
+/// A SIP-010 memo, printed by token contracts as a `(buff 34)` or `(some (buff 34))`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sip010Memo {
+    /// The memo normalized to a printable string, as for `memoToString`
+    pub memo: String,
+    pub memo_hex: String,
+}
@@ -0,0 +1,1340 @@
+use std::convert::{TryFrom, TryInto};
+use std::io::Cursor;
+
+use js_sys::{Array, BigInt, Object, Reflect, Uint8Array};
+use serde_json::Map;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::address::bitcoin_address;
+use crate::address::c32::{c32_address, c32_address_decode};
+use crate::address::stacks_address::AddressHashMode;
+use crate::address::{
+    btc_addr_to_stx_addr, decode_clarity_value_to_principal_inner, public_keys_to_stacks_address,
+    stacks_to_bitcoin_address_internal,
+};
+use crate::clarity_value::types::{ClarityValue, Value};
+use crate::clarity_value::value_ref::ClarityValueRef;
+use crate::crypto::hash160;
+use crate::hex::{decode_hex, encode_hex};
+use crate::json::{
+    self, microblock_stream_to_json, microblock_to_json, nakamoto_block_to_json, pox_event_to_json,
+    stacks_block_to_json, DecodedPostConditions, DecodedTransaction,
+};
+use crate::json_decoder::{
+    clarity_value_from_json, decode_clarity_value_options_from_json,
+    native_value_options_from_json, numbers_from_json, output_options_from_json, path_from_json,
+    reward_set_from_json, selected_value_format_from_json, tx_from_json, tx_merkle_proof_from_json,
+    type_signature_from_json,
+};
+use crate::memo::memo_normalize;
+use crate::output_options::{BufferFormat, NativeValueOptions, NumberFormat, SelectedValueFormat};
+use crate::pox_events::decode::decode_pox_synthetic_event;
+use crate::pox_events::types::StacksNetwork;
+use crate::serialize_util::{DeserializeError, SerializeError};
+use crate::stacks_block::deserialize::{
+    NakamotoBlock, NakamotoBlockHeader, StacksBlock, StacksMicroblock,
+};
+use crate::stacks_block::merkle::{MerkleTree, TxMerkleProof};
+use crate::stacks_block::microblock::{deserialize_microblock_stream, verify_microblock_stream};
+use crate::stacks_tx::deserialize::StacksTransaction;
+use crate::stacks_tx::split_tx_list;
+use crate::GIT_VERSION;
+
+/// Read a hex string or Uint8Array (including a Node.js Buffer) argument.
+fn arg_as_bytes(arg: &JsValue) -> Result<Vec<u8>, JsError> {
+    if let Some(hex) = arg.as_string() {
+        let bytes =
+            decode_hex(hex).map_err(|e| JsError::new(&format!("Hex parsing error: {}", e)))?;
+        Ok(bytes.into_vec())
+    } else if let Some(array) = arg.dyn_ref::<Uint8Array>() {
+        Ok(array.to_vec())
+    } else {
+        Err(JsError::new("Argument must be a hex string or a Buffer"))
+    }
+}
+
+/// Read an optional hex string or Uint8Array argument that must be exactly `N` bytes long.
+fn optional_fixed_bytes_arg<const N: usize>(
+    arg: &JsValue,
+    name: &str,
+) -> Result<Option<[u8; N]>, JsError> {
+    if arg.is_undefined() || arg.is_null() {
+        return Ok(None);
+    }
+    let bytes = arg_as_bytes(arg)?;
+    let bytes = bytes.as_slice().try_into().map_err(|_| {
+        JsError::new(&format!(
+            "{} must be {} bytes, received {} bytes",
+            name,
+            N,
+            bytes.len()
+        ))
+    })?;
+    Ok(Some(bytes))
+}
+
+/// Convert a JS argument to JSON for the `json_decoder` readers. Buffers become `0x`-prefixed
+/// hex strings and bigints decimal strings, which the readers accept wherever the Node addon
+/// accepts a Buffer or a bigint. `undefined` becomes null.
+fn js_to_json(value: &JsValue) -> Result<serde_json::Value, JsError> {
+    if value.is_undefined() || value.is_null() {
+        Ok(serde_json::Value::Null)
+    } else if let Some(val) = value.as_bool() {
+        Ok(val.into())
+    } else if let Some(val) = value.as_f64() {
+        serde_json::Number::from_f64(val)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| JsError::new(&format!("Unsupported number value {}", val)))
+    } else if let Some(val) = value.as_string() {
+        Ok(val.into())
+    } else if let Some(val) = value.dyn_ref::<BigInt>() {
+        let decimal: String = val
+            .to_string(10)
+            .map_err(|_| JsError::new("Error converting bigint to a string"))?
+            .into();
+        Ok(decimal.into())
+    } else if let Some(array) = value.dyn_ref::<Uint8Array>() {
+        let hex: String = encode_hex(&array.to_vec()).into();
+        Ok(hex.into())
+    } else if let Some(array) = value.dyn_ref::<Array>() {
+        array
+            .iter()
+            .map(|item| js_to_json(&item))
+            .collect::<Result<Vec<_>, _>>()
+            .map(serde_json::Value::Array)
+    } else if let Some(obj) = value.dyn_ref::<Object>() {
+        let mut map = Map::new();
+        for entry in Object::entries(obj).iter() {
+            let entry: Array = entry.unchecked_into();
+            let key = entry.get(0).as_string().unwrap_or_default();
+            map.insert(key, js_to_json(&entry.get(1))?);
+        }
+        Ok(map.into())
+    } else {
+        Err(JsError::new("Unsupported argument type"))
+    }
+}
+
+/// Convert a JSON value to a plain JS value, with objects rather than `Map`s and the
+/// `{"$bigint": "<decimal>"}` objects of `numbers: 'bigint'` as bigints.
+fn json_value_to_js(value: &serde_json::Value) -> JsValue {
+    match value {
+        serde_json::Value::Null => JsValue::NULL,
+        serde_json::Value::Bool(val) => JsValue::from_bool(*val),
+        serde_json::Value::Number(num) => JsValue::from_f64(num.as_f64().unwrap_or(f64::NAN)),
+        serde_json::Value::String(val) => JsValue::from_str(val),
+        serde_json::Value::Array(items) => {
+            items.iter().map(json_value_to_js).collect::<Array>().into()
+        }
+        serde_json::Value::Object(obj) => {
+            if let (1, Some(serde_json::Value::String(decimal))) = (obj.len(), obj.get("$bigint")) {
+                // Always a decimal integer, the string is a fallback that can't happen
+                return BigInt::new(&JsValue::from_str(decimal))
+                    .map(JsValue::from)
+                    .unwrap_or_else(|_| JsValue::from_str(decimal));
+            }
+            let js_obj = Object::new();
+            for (key, val) in obj {
+                Reflect::set(&js_obj, &JsValue::from_str(key), &json_value_to_js(val))
+                    .unwrap_throw();
+            }
+            js_obj.into()
+        }
+    }
+}
+
+/// Convert a decoded JSON value to a plain JS value, see `json_value_to_js`.
+fn json_to_js(value: Result<serde_json::Value, String>) -> Result<JsValue, JsError> {
+    Ok(json_value_to_js(&value.map_err(|e| JsError::new(&e))?))
+}
+
+/// Construct an `Error` named `DeserializeError` with the `code`, `offset` and `path` properties
+/// of the Node addon's `DeserializeError`, for per-item errors in batch results.
+fn deserialize_error_to_js(context: &str, err: &DeserializeError) -> JsValue {
+    let error = js_sys::Error::new(&format!("{}: {}", context, err));
+    error.set_name("DeserializeError");
+    let offset = match err.offset {
+        Some(offset) => JsValue::from_f64(offset as f64),
+        None => JsValue::NULL,
+    };
+    let props = [
+        ("code", JsValue::from_str(err.code())),
+        ("offset", offset),
+        ("path", JsValue::from(err.path_string())),
+    ];
+    for (key, val) in props.iter() {
+        Reflect::set(&error, &JsValue::from_str(key), val).unwrap_throw();
+    }
+    error.into()
+}
+
+fn address_version(network: &str, hash_mode: &AddressHashMode) -> Result<u8, JsError> {
+    let network = StacksNetwork::from_str(network).map_err(|e| JsError::new(&e))?;
+    if network.is_mainnet() {
+        Ok(hash_mode.to_version_mainnet())
+    } else {
+        Ok(hash_mode.to_version_testnet())
+    }
+}
+
+fn address_hash_mode(hash_mode: u8) -> Result<AddressHashMode, JsError> {
+    AddressHashMode::try_from(hash_mode)
+        .map_err(|e| JsError::new(&format!("Invalid hash mode: {}", e)))
+}
+
+#[wasm_bindgen(js_name = getVersion)]
+pub fn get_version() -> String {
+    GIT_VERSION.to_string()
+}
+
+/// Takes an optional `{strict, numbers, profile}` options object, as in the Node.js addon.
+#[wasm_bindgen(js_name = decodeClarityValue)]
+pub fn decode_clarity_value(arg: JsValue, options: JsValue) -> Result<JsValue, JsError> {
+    let options = decode_clarity_value_options_from_json(&js_to_json(&options)?)
+        .map_err(|e| JsError::new(&e))?;
+    json_to_js(json::decode_clarity_value(&arg_as_bytes(&arg)?, options))
+}
+
+/// Decode a Clarity value against a type given in Clarity syntax, e.g. `(list 10 uint)`, or as
+/// an ABI type object or JSON string. Each decoded value also gets its declared `type`.
+#[wasm_bindgen(js_name = decodeClarityValueWithType)]
+pub fn decode_clarity_value_with_type(
+    arg: JsValue,
+    type_sig: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let type_sig =
+        type_signature_from_json(&js_to_json(&type_sig)?).map_err(|e| JsError::new(&e))?;
+    let numbers = numbers_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let clarity_value = ClarityValueRef::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    type_sig
+        .admits(&clarity_value)
+        .map_err(|e| JsError::new(&e))?;
+    json_to_js(json::clarity_value_typed_to_json(
+        &clarity_value,
+        &type_sig,
+        numbers,
+    ))
+}
+
+/// Decode a u32 count followed by that many serialized Clarity values. Unless `deep` is set,
+/// only the top-level `repr`, `hex` and `type_id` of each value are decoded.
+#[wasm_bindgen(js_name = decodeClarityValueList)]
+pub fn decode_clarity_value_list(
+    arg: JsValue,
+    deep: Option<bool>,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let numbers = numbers_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    json_to_js(json::decode_clarity_value_list(
+        &arg_as_bytes(&arg)?,
+        deep.unwrap_or(false),
+        numbers,
+    ))
+}
+
+/// Decode only the nested value at a path such as `data.items[2]` or `["data", "items", 2]`.
+/// Returns null if there is no value at the path.
+#[wasm_bindgen(js_name = decodeClarityValueAtPath)]
+pub fn decode_clarity_value_at_path(
+    arg: JsValue,
+    path: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let path = path_from_json(&js_to_json(&path)?).map_err(|e| JsError::new(&e))?;
+    let format =
+        selected_value_format_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let selected =
+        ClarityValueRef::deserialize_at_path(&mut Cursor::new(val_bytes.as_slice()), &path)
+            .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    let value = match selected {
+        Some(value) => value,
+        None => return Ok(JsValue::NULL),
+    };
+    match format {
+        SelectedValueFormat::Object(numbers) => {
+            json_to_js(json::clarity_value_to_json(&value, true, numbers.into()))
+        }
+        SelectedValueFormat::Repr => Ok(JsValue::from(value.repr_string())),
+        SelectedValueFormat::Hex => Ok(JsValue::from_str(&encode_hex(value.serialized_bytes()))),
+    }
+}
+
+/// Decode a Clarity value to plain JS values, e.g. `(tuple (a u1) (b (some "x")))` to
+/// `{a: 1n, b: "x"}`. Takes an optional `{numbers, buffers, unwrapResponses}` options object.
+#[wasm_bindgen(js_name = decodeClarityValueToNative)]
+pub fn decode_clarity_value_to_native(arg: JsValue, options: JsValue) -> Result<JsValue, JsError> {
+    let options =
+        native_value_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let clarity_value = ClarityValue::deserialize(&mut Cursor::new(val_bytes.as_slice()), false)
+        .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    clarity_val_to_native(&clarity_value.value, &options)
+}
+
+/// Convert a Clarity value to a plain JS value, as the Node.js addon's `clarity_val_to_native`.
+fn clarity_val_to_native(val: &Value, options: &NativeValueOptions) -> Result<JsValue, JsError> {
+    let native = match val {
+        Value::Int(val) => match options.numbers {
+            NumberFormat::String => JsValue::from(val.to_string()),
+            NumberFormat::BigInt => JsValue::from(*val),
+        },
+        Value::UInt(val) => match options.numbers {
+            NumberFormat::String => JsValue::from(val.to_string()),
+            NumberFormat::BigInt => JsValue::from(*val),
+        },
+        Value::Bool(val) => JsValue::from_bool(*val),
+        Value::Buffer(buff) => match options.buffers {
+            BufferFormat::Hex => JsValue::from_str(&encode_hex(buff)),
+            BufferFormat::Uint8Array => Uint8Array::from(buff.as_slice()).into(),
+        },
+        Value::StringASCII(str_data) => JsValue::from(String::from_utf8_lossy(str_data).as_ref()),
+        Value::StringUTF8(str_data) => {
+            let str_bytes = str_data.concat();
+            JsValue::from(String::from_utf8_lossy(&str_bytes).as_ref())
+        }
+        Value::PrincipalStandard(principal) => JsValue::from(
+            c32_address(principal.0, &principal.1)
+                .map_err(|e| JsError::new(&format!("Error converting to C32 address: {}", e)))?,
+        ),
+        Value::PrincipalContract(contract) => {
+            let address = c32_address(contract.issuer.0, &contract.issuer.1)
+                .map_err(|e| JsError::new(&format!("Error converting to C32 address: {}", e)))?;
+            JsValue::from(format!("{}.{}", address, contract.name.as_str()))
+        }
+        Value::List(data) => data
+            .iter()
+            .map(|item| clarity_val_to_native(&item.value, options))
+            .collect::<Result<Array, _>>()?
+            .into(),
+        Value::Tuple(data) => {
+            let tuple_obj = Object::new();
+            for (key, value) in data.iter() {
+                let field = clarity_val_to_native(&value.value, options)?;
+                Reflect::set(&tuple_obj, &JsValue::from_str(key.as_str()), &field).unwrap_throw();
+            }
+            tuple_obj.into()
+        }
+        Value::OptionalSome(data) => clarity_val_to_native(&data.value, options)?,
+        Value::OptionalNone => JsValue::NULL,
+        Value::ResponseOk(data) | Value::ResponseErr(data) => {
+            let inner = clarity_val_to_native(&data.value, options)?;
+            if options.unwrap_responses {
+                inner
+            } else {
+                let key = if matches!(val, Value::ResponseOk(_)) {
+                    "ok"
+                } else {
+                    "err"
+                };
+                let response_obj = Object::new();
+                Reflect::set(&response_obj, &JsValue::from_str(key), &inner).unwrap_throw();
+                response_obj.into()
+            }
+        }
+    };
+    Ok(native)
+}
+
+/// Serialize a Clarity value given in the shape of the deep `decodeClarityValue` output.
+#[wasm_bindgen(js_name = encodeClarityValue)]
+pub fn encode_clarity_value(value: JsValue) -> Result<String, JsError> {
+    let value = js_to_json(&value)?;
+    let obj = value
+        .as_object()
+        .ok_or_else(|| JsError::new("Argument must be an object"))?;
+    let clarity_value = clarity_value_from_json(obj).map_err(|e| JsError::new(&e))?;
+    let serialized = clarity_value
+        .serialize_to_vec()
+        .map_err(|e| JsError::new(&format!("Error serializing Clarity value: {}", e)))?;
+    Ok(encode_hex(&serialized).into())
+}
+
+/// Takes an optional `{numbers, profile}` options object, as in the Node.js addon.
+#[wasm_bindgen(js_name = decodeTransaction)]
+pub fn decode_transaction(arg: JsValue, options: JsValue) -> Result<JsValue, JsError> {
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let tx = DecodedTransaction::decode(&arg_as_bytes(&arg)?)
+        .map_err(|e| JsError::new(&format!("Failed to decode transaction: {}", e)))?;
+    json_to_js(tx.to_json(options))
+}
+
+/// Convert decoded transactions to JS, with a `DeserializeError` in place of each transaction
+/// that failed to decode.
+fn txs_to_js(
+    results: &[Result<DecodedTransaction, DeserializeError>],
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let array = Array::new_with_length(results.len() as u32);
+    for (i, result) in results.iter().enumerate() {
+        let item = match result {
+            Ok(tx) => json_to_js(tx.to_json(options))?,
+            Err(e) => deserialize_error_to_js("Failed to decode transaction", e),
+        };
+        array.set(i as u32, item);
+    }
+    Ok(array.into())
+}
+
+/// Decode an array of transactions (hex strings or Buffers). Each entry in the returned array is
+/// either the decoded transaction or a `DeserializeError`, so one malformed transaction doesn't
+/// fail the whole batch.
+#[wasm_bindgen(js_name = decodeTransactions)]
+pub fn decode_transactions(txs: Array, options: JsValue) -> Result<JsValue, JsError> {
+    let mut results = Vec::with_capacity(txs.length() as usize);
+    for item in txs.iter() {
+        let result = if let Some(hex) = item.as_string() {
+            decode_hex(hex)
+                .map_err(|e| DeserializeError::from(format!("Hex parsing error: {}", e)))
+                .and_then(|val_bytes| DecodedTransaction::decode(&val_bytes))
+        } else if let Some(array) = item.dyn_ref::<Uint8Array>() {
+            DecodedTransaction::decode(&array.to_vec())
+        } else {
+            return Err(JsError::new("Array items must be hex strings or Buffers"));
+        };
+        results.push(result);
+    }
+    txs_to_js(&results, options)
+}
+
+/// Like `decodeTransactions`, but the input is a single buffer containing a u32 count followed
+/// by each transaction prefixed with its u32 byte length.
+#[wasm_bindgen(js_name = decodeTransactionList)]
+pub fn decode_transaction_list(arg: JsValue, options: JsValue) -> Result<JsValue, JsError> {
+    let val_bytes = arg_as_bytes(&arg)?;
+    let tx_slices = split_tx_list(&val_bytes)
+        .map_err(|e| JsError::new(&format!("Failed to decode transaction list: {}", e)))?;
+    let results: Vec<_> = tx_slices
+        .iter()
+        .map(|tx_bytes| DecodedTransaction::decode(tx_bytes))
+        .collect();
+    txs_to_js(&results, options)
+}
+
+/// Serialize a transaction given in the shape of the `decodeTransaction` output.
+#[wasm_bindgen(js_name = encodeTransaction)]
+pub fn encode_transaction(tx: JsValue) -> Result<String, JsError> {
+    let tx = js_to_json(&tx)?;
+    let obj = tx
+        .as_object()
+        .ok_or_else(|| JsError::new("Argument must be an object"))?;
+    let tx = tx_from_json(obj).map_err(|e| JsError::new(&e))?;
+    let serialized = tx
+        .serialize_to_vec()
+        .map_err(|e| JsError::new(&format!("Error serializing transaction: {}", e)))?;
+    Ok(encode_hex(&serialized).into())
+}
+
+#[wasm_bindgen(js_name = verifyTransaction)]
+pub fn verify_transaction(arg: JsValue) -> Result<JsValue, JsError> {
+    let val_bytes = arg_as_bytes(&arg)?;
+    let tx = StacksTransaction::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Failed to decode transaction: {}", e)))?;
+    let verification = tx
+        .verify_signatures()
+        .map_err(|e| JsError::new(&format!("Error computing transaction sighash: {}", e)))?;
+    json_to_js(Ok(json::transaction_verification_to_json(&verification)))
+}
+
+/// Takes an optional `{numbers}` options object, as in the Node.js addon.
+#[wasm_bindgen(js_name = decodePostConditions)]
+pub fn decode_post_conditions(arg: JsValue, options: JsValue) -> Result<JsValue, JsError> {
+    let numbers = numbers_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let post_conditions = DecodedPostConditions::decode(&arg_as_bytes(&arg)?)
+        .map_err(|e| JsError::new(&format!("Error deserializing post condition: {}", e)))?;
+    json_to_js(post_conditions.to_json(numbers))
+}
+
+/// Verify the merkle root of a block if `verify_merkle_root` is set.
+fn merkle_root_valid(
+    verify_merkle_root: Option<bool>,
+    verify: impl FnOnce() -> Result<bool, SerializeError>,
+) -> Result<Option<bool>, JsError> {
+    if verify_merkle_root.unwrap_or(false) {
+        let valid = verify().map_err(|e| JsError::new(&format!("Error computing txids: {}", e)))?;
+        Ok(Some(valid))
+    } else {
+        Ok(None)
+    }
+}
+
+#[wasm_bindgen(js_name = decodeNakamotoBlock)]
+pub fn decode_nakamoto_block(
+    arg: JsValue,
+    verify_merkle_root: Option<bool>,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let block = NakamotoBlock::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Failed to decode Nakamoto block: {}", e)))?;
+    let merkle_root_valid = merkle_root_valid(verify_merkle_root, || block.merkle_root_valid())?;
+    json_to_js(nakamoto_block_to_json(&block, merkle_root_valid, options))
+}
+
+#[wasm_bindgen(js_name = decodeStacksBlock)]
+pub fn decode_stacks_block(
+    arg: JsValue,
+    verify_merkle_root: Option<bool>,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let block = StacksBlock::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Failed to decode Stacks block: {}", e)))?;
+    let merkle_root_valid = merkle_root_valid(verify_merkle_root, || block.merkle_root_valid())?;
+    json_to_js(stacks_block_to_json(&block, merkle_root_valid, options))
+}
+
+#[wasm_bindgen(js_name = decodeMicroblock)]
+pub fn decode_microblock(
+    arg: JsValue,
+    verify_merkle_root: Option<bool>,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let microblock = StacksMicroblock::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Failed to decode microblock: {}", e)))?;
+    let merkle_root_valid =
+        merkle_root_valid(verify_merkle_root, || microblock.merkle_root_valid())?;
+    json_to_js(microblock_to_json(&microblock, merkle_root_valid, options))
+}
+
+/// Decode a length-prefixed stream of Stacks 2.x microblocks and verify that it is well-formed,
+/// optionally against the parent block hash and the microblock public key hash.
+#[wasm_bindgen(js_name = decodeMicroblockStream)]
+pub fn decode_microblock_stream(
+    arg: JsValue,
+    parent_block_hash: JsValue,
+    microblock_pubkey_hash: JsValue,
+    options: JsValue,
+) -> Result<JsValue, JsError> {
+    let microblocks = deserialize_microblock_stream(&arg_as_bytes(&arg)?)
+        .map_err(|e| JsError::new(&format!("Failed to decode microblock stream: {}", e)))?;
+    let parent_block_hash =
+        optional_fixed_bytes_arg::<32>(&parent_block_hash, "Parent block hash")?;
+    let microblock_pubkey_hash =
+        optional_fixed_bytes_arg::<20>(&microblock_pubkey_hash, "Microblock public key hash")?;
+    let options = output_options_from_json(&js_to_json(&options)?).map_err(|e| JsError::new(&e))?;
+    let errors = verify_microblock_stream(
+        &microblocks,
+        parent_block_hash.as_ref(),
+        microblock_pubkey_hash.as_ref(),
+    )
+    .map_err(|e| JsError::new(&format!("Error computing txids: {}", e)))?;
+    json_to_js(microblock_stream_to_json(&microblocks, &errors, options))
+}
+
+/// Build a merkle inclusion proof for a transaction in a `nakamoto` or `stacks` (2.x) block.
+#[wasm_bindgen(js_name = getTxMerkleProof)]
+pub fn get_tx_merkle_proof(
+    block: JsValue,
+    txid: JsValue,
+    block_type: &str,
+) -> Result<JsValue, JsError> {
+    let block_bytes = arg_as_bytes(&block)?;
+    let txid = arg_as_bytes(&txid)?;
+    let mut cursor = Cursor::new(block_bytes.as_slice());
+    let (txids, merkle_root) = match block_type {
+        "nakamoto" => {
+            let block = NakamotoBlock::deserialize(&mut cursor)
+                .map_err(|e| JsError::new(&format!("Failed to decode Nakamoto block: {}", e)))?;
+            (block.txids(), block.header.tx_merkle_root.0)
+        }
+        "stacks" => {
+            let block = StacksBlock::deserialize(&mut cursor)
+                .map_err(|e| JsError::new(&format!("Failed to decode Stacks block: {}", e)))?;
+            (block.txids(), block.header.tx_merkle_root.0)
+        }
+        _ => return Err(JsError::new(&format!("Unknown block type: {}", block_type))),
+    };
+    let txids = txids.map_err(|e| JsError::new(&format!("Error computing txids: {}", e)))?;
+    let proof = TxMerkleProof::new(&txids, merkle_root, &txid).map_err(|e| JsError::new(&e))?;
+    json_to_js(Ok(json::tx_merkle_proof_to_json(&proof)))
+}
+
+/// Check a merkle inclusion proof produced by `getTxMerkleProof`.
+#[wasm_bindgen(js_name = verifyTxMerkleProof)]
+pub fn verify_tx_merkle_proof(txid: JsValue, proof: JsValue) -> Result<bool, JsError> {
+    let txid = arg_as_bytes(&txid)?;
+    let (merkle_root, path) =
+        tx_merkle_proof_from_json(&js_to_json(&proof)?).map_err(|e| JsError::new(&e))?;
+    Ok(MerkleTree::verify_path(&txid, &path, &merkle_root))
+}
+
+fn nakamoto_block_header_arg(arg: &JsValue) -> Result<NakamotoBlockHeader, JsError> {
+    let val_bytes = arg_as_bytes(arg)?;
+    NakamotoBlockHeader::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Failed to decode Nakamoto block header: {}", e)))
+}
+
+/// Verify the signer signatures of a Nakamoto block header against a reward set of
+/// `{signing_key, weight}` signers.
+#[wasm_bindgen(js_name = verifyNakamotoBlockSignerSignatures)]
+pub fn verify_nakamoto_block_signer_signatures(
+    header: JsValue,
+    reward_set: JsValue,
+) -> Result<JsValue, JsError> {
+    let header = nakamoto_block_header_arg(&header)?;
+    let reward_set =
+        reward_set_from_json(&js_to_json(&reward_set)?).map_err(|e| JsError::new(&e))?;
+    let verification = header.verify_signer_signatures(&reward_set);
+    json_to_js(Ok(json::signer_signature_verification_to_json(
+        &verification,
+        &reward_set,
+    )))
+}
+
+/// Verify that a Nakamoto block header's miner signature recovers to the given miner public key
+/// hash.
+#[wasm_bindgen(js_name = verifyNakamotoBlockMinerSignature)]
+pub fn verify_nakamoto_block_miner_signature(
+    header: JsValue,
+    miner_pubkey_hash: JsValue,
+) -> Result<JsValue, JsError> {
+    let header = nakamoto_block_header_arg(&header)?;
+    let miner_pubkey_hash = arg_as_bytes(&miner_pubkey_hash)?;
+    if miner_pubkey_hash.len() != 20 {
+        return Err(JsError::new(&format!(
+            "Miner public key hash must be 20 bytes, received {} bytes",
+            miner_pubkey_hash.len()
+        )));
+    }
+    let miner_public_key = header
+        .recover_miner_public_key()
+        .map_err(|e| JsError::new(&format!("Error recovering miner public key: {}", e)))?;
+    let valid = hash160(&miner_public_key)[..] == miner_pubkey_hash[..];
+    json_to_js(Ok(json::miner_signature_verification_to_json(
+        valid,
+        &miner_public_key,
+    )))
+}
+
+/// Returns null if the value is not a PoX synthetic event, as in the Node.js addon.
+#[wasm_bindgen(js_name = decodePoxSyntheticEvent)]
+pub fn decode_pox_event(arg: JsValue, network: &str) -> Result<JsValue, JsError> {
+    let network = StacksNetwork::from_str(network).map_err(|e| JsError::new(&e))?;
+    let val_bytes = arg_as_bytes(&arg)?;
+    let clarity_value = ClarityValueRef::deserialize(&mut Cursor::new(val_bytes.as_slice()))
+        .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    let event = decode_pox_synthetic_event(&clarity_value, network)
+        .map_err(|e| JsError::new(&format!("Error decoding PoX synthetic event: {}", e)))?;
+    match event {
+        Some(event) => json_to_js(Ok(pox_event_to_json(&event))),
+        None => Ok(JsValue::NULL),
+    }
+}
+
+#[wasm_bindgen(js_name = decodeClarityValueToRepr)]
+pub fn decode_clarity_value_to_repr(arg: JsValue) -> Result<String, JsError> {
+    let val_bytes = arg_as_bytes(&arg)?;
+    let mut cursor = Cursor::new(val_bytes.as_slice());
+    let value = ClarityValueRef::deserialize(&mut cursor)
+        .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    Ok(value.repr_string())
+}
+
+#[wasm_bindgen(js_name = decodeClarityValueToTypeName)]
+pub fn decode_clarity_value_type_name(arg: JsValue) -> Result<String, JsError> {
+    let val_bytes = arg_as_bytes(&arg)?;
+    let mut cursor = Cursor::new(val_bytes.as_slice());
+    let clarity_value = ClarityValue::deserialize(&mut cursor, false)
+        .map_err(|e| JsError::new(&format!("Error deserializing Clarity value: {}", e)))?;
+    clarity_value
+        .value
+        .type_signature()
+        .map_err(|e| JsError::new(&format!("Error inferring Clarity type: {}", e)))
+}
+
+#[wasm_bindgen(js_name = parseClarityRepr)]
+pub fn parse_clarity_repr(repr: &str) -> Result<String, JsError> {
+    let value = Value::parse_repr(repr)
+        .map_err(|e| JsError::new(&format!("Error parsing Clarity repr: {}", e)))?;
+    let serialized = value
+        .serialize_to_vec()
+        .map_err(|e| JsError::new(&format!("Error serializing Clarity value: {}", e)))?;
+    Ok(encode_hex(&serialized).into())
+}
+
+#[wasm_bindgen(js_name = memoToString)]
+pub fn memo_to_string(arg: JsValue) -> Result<String, JsError> {
+    Ok(memo_normalize(arg_as_bytes(&arg)?))
+}
+
+#[wasm_bindgen(js_name = isValidStacksAddress)]
+pub fn is_valid_stacks_address(address: &str) -> bool {
+    c32_address_decode(address).is_ok()
+}
+
+/// Returns `[version, hash160]`, as in the Node.js addon.
+#[wasm_bindgen(js_name = decodeStacksAddress)]
+pub fn decode_stacks_address(address: &str) -> Result<Array, JsError> {
+    let (version, hash160) = c32_address_decode(address)
+        .map_err(|e| JsError::new(&format!("Error parsing Stacks address {}", e)))?;
+    let hash160: String = encode_hex(&hash160).into();
+    Ok(Array::of2(&JsValue::from(version), &JsValue::from(hash160)))
+}
+
+#[wasm_bindgen(js_name = decodeClarityValueToPrincipal)]
+pub fn decode_clarity_value_to_principal(arg: JsValue) -> Result<String, JsError> {
+    decode_clarity_value_to_principal_inner(&arg_as_bytes(&arg)?).map_err(|e| {
+        JsError::new(&format!(
+            "Error decoding clarity value to principal string: {}",
+            e
+        ))
+    })
+}
+
+#[wasm_bindgen(js_name = stacksAddressFromParts)]
+pub fn stacks_address_from_parts(version: u8, hash160: JsValue) -> Result<String, JsError> {
+    c32_address(version, &arg_as_bytes(&hash160)?)
+        .map_err(|e| JsError::new(&format!("Error converting to C32 address: {}", e)))
+}
+
+#[wasm_bindgen(js_name = publicKeyToStacksAddress)]
+pub fn public_key_to_stacks_address(
+    public_key: JsValue,
+    hash_mode: u8,
+    network: &str,
+) -> Result<String, JsError> {
+    let public_key = arg_as_bytes(&public_key)?;
+    let hash_mode = address_hash_mode(hash_mode)?;
+    let version = address_version(network, &hash_mode)?;
+    if !matches!(
+        hash_mode,
+        AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH
+    ) {
+        return Err(JsError::new(&format!(
+            "Hash mode {} is not a single-sig hash mode",
+            hash_mode as u8
+        )));
+    }
+    public_keys_to_stacks_address(version, &hash_mode, 1, &[public_key])
+        .map_err(|e| JsError::new(&e))
+}
+
+#[wasm_bindgen(js_name = multisigToStacksAddress)]
+pub fn multisig_to_stacks_address(
+    public_keys: Array,
+    num_sigs: u32,
+    hash_mode: u8,
+    network: &str,
+) -> Result<String, JsError> {
+    let public_keys = public_keys
+        .iter()
+        .map(|public_key| arg_as_bytes(&public_key))
+        .collect::<Result<Vec<_>, _>>()?;
+    let hash_mode = address_hash_mode(hash_mode)?;
+    let version = address_version(network, &hash_mode)?;
+    if matches!(
+        hash_mode,
+        AddressHashMode::SerializeP2PKH | AddressHashMode::SerializeP2WPKH
+    ) {
+        return Err(JsError::new(&format!(
+            "Hash mode {} is not a multisig hash mode",
+            hash_mode as u8
+        )));
+    }
+    public_keys_to_stacks_address(version, &hash_mode, num_sigs as usize, &public_keys)
+        .map_err(|e| JsError::new(&e))
+}
+
+#[wasm_bindgen(js_name = stacksToBitcoinAddress)]
+pub fn stacks_to_bitcoin_address(address: &str) -> Result<String, JsError> {
+    stacks_to_bitcoin_address_internal(address.to_string()).map_err(|e| JsError::new(&e))
+}
+
+#[wasm_bindgen(js_name = bitcoinToStacksAddress)]
+pub fn bitcoin_to_stacks_address(address: &str) -> Result<String, JsError> {
+    let bitcoin_address = bitcoin_address::from_b58(address)
+        .map_err(|e| JsError::new(&format!("Error parsing Bitcoin address: {}", e)))?;
+    let stacks_address = btc_addr_to_stx_addr(&bitcoin_address).map_err(|e| {
+        JsError::new(&format!(
+            "Error getting Stacks address version from Bitcoin address: {}",
+            e
+        ))
+    })?;
+    c32_address(stacks_address.version, &stacks_address.hash160_bytes)
+        .map_err(|e| JsError::new(&format!("Error converting to C32 address: {}", e)))
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wasm_bindgen_test::*;
+
+    const TOKEN_TRANSFER_TX: &str = "00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000";
+
+    fn to_json(value: JsValue) -> serde_json::Value {
+        js_to_json(&value).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_get_version() {
+        assert!(!get_version().is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value() {
+        // (tuple (a u1) (b (some "x")))
+        let hex = "0x0c000000020161010000000000000000000000000000000101620a0d0000000178";
+        let decoded =
+            to_json(decode_clarity_value(JsValue::from(hex), JsValue::UNDEFINED).unwrap());
+        assert_eq!(decoded["repr"], "(tuple (a u1) (b (some \"x\")))");
+        assert_eq!(decoded["hex"], hex);
+        assert_eq!(decoded["type_id"], 12);
+        assert_eq!(
+            decoded["data"]["a"],
+            json!({
+                "repr": "u1",
+                "hex": "0x0100000000000000000000000000000001",
+                "type_id": 1,
+                "value": "1",
+            })
+        );
+        assert_eq!(decoded["data"]["b"]["value"]["data"], "x");
+
+        let trailing = to_json(
+            decode_clarity_value(
+                JsValue::from("0x0100000000000000000000000000000001ff"),
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(trailing["hex"], "0x0100000000000000000000000000000001ff");
+        assert!(decode_clarity_value(JsValue::from("0x01"), JsValue::UNDEFINED).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_transaction() {
+        let tx = to_json(
+            decode_transaction(JsValue::from(TOKEN_TRANSFER_TX), JsValue::UNDEFINED).unwrap(),
+        );
+        assert_eq!(
+            tx["tx_id"],
+            "0xd59d655128d67e33c6e9c20c7cca309b91da352358639092e13ce74fc0a6a5d4"
+        );
+        assert_eq!(
+            tx["auth"]["origin_condition"]["signer"]["address"],
+            "SP2R0VR6CFDF533VX3S1XDSFCQN9E1K8C5WS5Q9JC"
+        );
+        assert_eq!(tx["auth"]["origin_condition"]["nonce"], "7644");
+        assert_eq!(tx["payload"]["type_id"], 4);
+        assert!(decode_transaction(JsValue::from("0x00"), JsValue::UNDEFINED).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_post_conditions() {
+        // mode deny, one `sent_equal_to` 2800000 uSTX condition
+        let hex = "020000000100021642779fa5c48120aa60c18eb05a164bad77bf2cdd0100000000002ab980";
+        let decoded =
+            to_json(decode_post_conditions(JsValue::from(hex), JsValue::UNDEFINED).unwrap());
+        assert_eq!(
+            decoded,
+            json!({
+                "post_condition_mode": 2,
+                "post_conditions": [{
+                    "asset_info_id": 0,
+                    "principal": {
+                        "type_id": 2,
+                        "address_version": 22,
+                        "address_hash_bytes": "0x42779fa5c48120aa60c18eb05a164bad77bf2cdd",
+                        "address": "SP117F7X5RJ0J1AK0R67B0PGP9EPQFFSCVQNASZBC",
+                    },
+                    "condition_code": 1,
+                    "condition_name": "sent_equal_to",
+                    "amount": "2800000",
+                }],
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_nakamoto_block() {
+        let bytes = Uint8Array::from(&include_bytes!("../tests/fixtures/nakamoto-block.bin")[..]);
+        let block = to_json(
+            decode_nakamoto_block(bytes.clone().into(), Some(true), JsValue::UNDEFINED).unwrap(),
+        );
+        assert_eq!(
+            block["block_id"],
+            "0x05b7fbc03e541271a29baf21ad43e68e48070df018ebe5baa13892f3828be9bd"
+        );
+        assert_eq!(block["header"]["chain_length"], "557923");
+        assert_eq!(block["merkle_root_valid"], true);
+        assert_eq!(block["txs"].as_array().unwrap().len(), 1);
+        let block = to_json(decode_nakamoto_block(bytes.into(), None, JsValue::UNDEFINED).unwrap());
+        assert!(block.get("merkle_root_valid").is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_stacks_block() {
+        let hex = format!(
+            "00{}{}{}{}0000{}{}{}00000000",
+            "0000000000000001".repeat(2),
+            "00".repeat(80),
+            "11".repeat(32),
+            "22".repeat(32),
+            "33".repeat(32),
+            "44".repeat(32),
+            "55".repeat(20),
+        );
+        let block = to_json(
+            decode_stacks_block(JsValue::from(hex), Some(true), JsValue::UNDEFINED).unwrap(),
+        );
+        assert_eq!(
+            block["block_hash"],
+            "0x111af9d5d2635747a2220c08af6ae943d9f53f08bb9221b72f34f48361177dd5"
+        );
+        assert_eq!(
+            block["header"]["total_work"],
+            json!({ "burn": "1", "work": "1" })
+        );
+        assert_eq!(block["merkle_root_valid"], false);
+        assert_eq!(block["txs"], json!([]));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_microblock() {
+        // The first of a stream of 3 microblocks, after the u32 stream length
+        let stream = include_bytes!("../tests/fixtures/microblock-stream.bin");
+        let microblock = &stream[4..4 + (stream.len() - 4) / 3];
+        let decoded = to_json(
+            decode_microblock(
+                Uint8Array::from(microblock).into(),
+                Some(true),
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            decoded["block_hash"],
+            "0xccc76d181a505e55e4ca25289209093568f7a3de1ed1c20b24c323503734916c"
+        );
+        assert_eq!(decoded["header"]["sequence"], 0);
+        assert_eq!(decoded["merkle_root_valid"], true);
+        assert_eq!(decoded["txs"].as_array().unwrap().len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_pox_synthetic_event() {
+        // (ok (tuple (name "revoke-delegate-stx") (stacker 'SP2JX..) (locked u100) (balance u1000)
+        //   (burnchain-unlock-height u5) (data (tuple (delegate-to 'SPTDV..)))))
+        let hex = "0x070c000000060762616c616e636501000000000000000000000000000003e8176275726e636861696e2d756e6c6f636b2d686569676874010000000000000000000000000000000504646174610c000000010b64656c65676174652d746f051634ddc468605d7e5b65f2970002ae55b554f5dda4066c6f636b65640100000000000000000000000000000064046e616d650d000000137265766f6b652d64656c65676174652d73747807737461636b65720516a5d9d331000f5b79578ce56bd157f29a9056f0d6";
+        let event = to_json(decode_pox_event(JsValue::from(hex), "mainnet").unwrap());
+        assert_eq!(
+            event,
+            json!({
+                "stacker": "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B",
+                "locked": "100",
+                "balance": "1000",
+                "burnchain_unlock_height": "5",
+                "pox_addr": null,
+                "pox_addr_raw": null,
+                "name": "revoke-delegate-stx",
+                "data": {
+                    "delegate_to": "SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92",
+                    "end_cycle_id": null,
+                    "start_cycle_id": null,
+                },
+            })
+        );
+        // (err u1)
+        let err = "0x080100000000000000000000000000000001";
+        assert!(decode_pox_event(JsValue::from(err), "mainnet")
+            .unwrap()
+            .is_null());
+        assert!(decode_pox_event(JsValue::from(hex), "regtest").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_to_repr() {
+        // (tuple (a u1) (b (some "x")))
+        let hex = "0x0c000000020161010000000000000000000000000000000101620a0d0000000178";
+        let repr = decode_clarity_value_to_repr(JsValue::from(hex)).unwrap();
+        assert_eq!(repr, "(tuple (a u1) (b (some \"x\")))");
+        let bytes = Uint8Array::from(decode_hex(hex).unwrap().as_ref());
+        assert_eq!(decode_clarity_value_to_repr(bytes.into()).unwrap(), repr);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_type_name() {
+        let hex = "0x0c000000020161010000000000000000000000000000000101620a0d0000000178";
+        assert_eq!(
+            decode_clarity_value_type_name(JsValue::from(hex)).unwrap(),
+            "(tuple (a uint) (b (optional (string-ascii 1))))"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_parse_clarity_repr() {
+        assert_eq!(
+            parse_clarity_repr("(tuple (a u1) (b (some \"x\")))").unwrap(),
+            "0x0c000000020161010000000000000000000000000000000101620a0d0000000178"
+        );
+        assert!(parse_clarity_repr("(tuple").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_memo_to_string() {
+        assert_eq!(
+            memo_to_string(JsValue::from("0x68656c6c6f00")).unwrap(),
+            "hello"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_is_valid_stacks_address() {
+        assert!(is_valid_stacks_address(
+            "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B"
+        ));
+        assert!(!is_valid_stacks_address(
+            "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02C"
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_stacks_address() {
+        let parts = decode_stacks_address("ST36HYPY9N94YEGBWXRZ5VEGTJ9B7V90TYTM9HGTJ").unwrap();
+        assert_eq!(parts.get(0), JsValue::from(26));
+        assert_eq!(
+            parts.get(1),
+            JsValue::from("0xcd1f5bc9aa49e7417cee3e5dba1a92567da41af6")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_to_principal() {
+        assert_eq!(
+            decode_clarity_value_to_principal(JsValue::from(
+                "0x0516a13dce8114be0f707f94470a2e5e86eb402f2923"
+            ))
+            .unwrap(),
+            "SP2GKVKM12JZ0YW3ZJH3GMBJYGVNM0BS94ERA45AM"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_stacks_address_from_parts() {
+        assert_eq!(
+            stacks_address_from_parts(
+                26,
+                JsValue::from("0xcd1f5bc9aa49e7417cee3e5dba1a92567da41af6")
+            )
+            .unwrap(),
+            "ST36HYPY9N94YEGBWXRZ5VEGTJ9B7V90TYTM9HGTJ"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_public_key_to_stacks_address() {
+        let public_key =
+            JsValue::from("0x03e356007964fc225a44c38352899c41e6293a97f8d8115998ae7e97184704c092");
+        assert_eq!(
+            public_key_to_stacks_address(public_key.clone(), 0, "mainnet").unwrap(),
+            "SP2R0VR6CFDF533VX3S1XDSFCQN9E1K8C5WS5Q9JC"
+        );
+        assert!(public_key_to_stacks_address(public_key, 1, "mainnet").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_multisig_to_stacks_address() {
+        let public_keys = Array::of3(
+            &JsValue::from("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            &JsValue::from("02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+            &JsValue::from("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"),
+        );
+        assert_eq!(
+            multisig_to_stacks_address(public_keys.clone(), 2, 1, "mainnet").unwrap(),
+            "SMAZR1TMWWZBGQ8WQKG8F1QTVDSJ1V5RVGMRGMFW"
+        );
+        assert!(multisig_to_stacks_address(public_keys, 4, 1, "mainnet").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_stacks_to_bitcoin_address() {
+        assert_eq!(
+            stacks_to_bitcoin_address("SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B").unwrap(),
+            "1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_bitcoin_to_stacks_address() {
+        assert_eq!(
+            bitcoin_to_stacks_address("1G7wSPyCfXgxWHSpQuCkeAisiZkw5QunAB").unwrap(),
+            "SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_options() {
+        // (tuple (a u1) (b (some "x")))
+        let hex = "0x0c000000020161010000000000000000000000000000000101620a0d0000000178";
+        let options = js_sys::JSON::parse(r#"{"numbers": "bigint", "profile": "json"}"#).unwrap();
+        let decoded = decode_clarity_value(JsValue::from(hex), options).unwrap();
+        let a = Reflect::get(&decoded, &"value".into()).unwrap();
+        let a = Reflect::get(&a, &"a".into()).unwrap();
+        assert_eq!(
+            Reflect::get(&a, &"value".into()).unwrap(),
+            JsValue::from(1u128)
+        );
+        let strict = js_sys::JSON::parse(r#"{"strict": true}"#).unwrap();
+        assert!(decode_clarity_value(
+            JsValue::from("0x0100000000000000000000000000000001ff"),
+            strict
+        )
+        .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_with_type() {
+        // (list none (some u1))
+        let hex = "0x0b00000002090a0100000000000000000000000000000001";
+        let decoded = to_json(
+            decode_clarity_value_with_type(
+                JsValue::from(hex),
+                JsValue::from("(list 2 (optional uint))"),
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(decoded["type"], "(list 2 (optional uint))");
+        assert_eq!(decoded["list"][1]["value"]["type"], "uint");
+        assert!(decode_clarity_value_with_type(
+            JsValue::from(hex),
+            JsValue::from("(list 1 (optional uint))"),
+            JsValue::UNDEFINED,
+        )
+        .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_list() {
+        // [u10000000, none]
+        let hex = "0x00000002010000000000000000000000000098968009";
+        let decoded = to_json(
+            decode_clarity_value_list(JsValue::from(hex), None, JsValue::UNDEFINED).unwrap(),
+        );
+        assert_eq!(
+            decoded,
+            json!([
+                { "hex": "0x0100000000000000000000000000989680", "repr": "u10000000", "type_id": 1 },
+                { "hex": "0x09", "repr": "none", "type_id": 9 },
+            ])
+        );
+        let deep = to_json(
+            decode_clarity_value_list(JsValue::from(hex), Some(true), JsValue::UNDEFINED).unwrap(),
+        );
+        assert_eq!(deep[0]["value"], "10000000");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_at_path() {
+        // (ok (tuple (items (list u1 u2 u3)) (name "a")))
+        let hex = "0x070c00000002056974656d730b00000003010000000000000000000000000000000101000000000000000000000000000000020100000000000000000000000000000003046e616d650d0000000161";
+        let item = to_json(
+            decode_clarity_value_at_path(
+                JsValue::from(hex),
+                JsValue::from("ok.items[1]"),
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(item["value"], "2");
+        let path = Array::of3(&"ok".into(), &"items".into(), &JsValue::from(1));
+        let options = js_sys::JSON::parse(r#"{"format": "repr"}"#).unwrap();
+        assert_eq!(
+            decode_clarity_value_at_path(JsValue::from(hex), path.into(), options).unwrap(),
+            JsValue::from("u2")
+        );
+        assert!(decode_clarity_value_at_path(
+            JsValue::from(hex),
+            JsValue::from("err"),
+            JsValue::UNDEFINED
+        )
+        .unwrap()
+        .is_null());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_clarity_value_to_native() {
+        // (ok (tuple (amount u100) (memo (some 0x0102)) (sender 'SP000000000000000000002Q6VF78)))
+        let hex = "0x070c0000000306616d6f756e740100000000000000000000000000000064046d656d6f0a020000000201020673656e64657205160000000000000000000000000000000000000000";
+        let native =
+            decode_clarity_value_to_native(JsValue::from(hex), JsValue::UNDEFINED).unwrap();
+        let ok = Reflect::get(&native, &"ok".into()).unwrap();
+        assert_eq!(
+            Reflect::get(&ok, &"amount".into()).unwrap(),
+            JsValue::from(100u128)
+        );
+        let options =
+            js_sys::JSON::parse(r#"{"numbers": "string", "unwrapResponses": true}"#).unwrap();
+        let native = to_json(decode_clarity_value_to_native(JsValue::from(hex), options).unwrap());
+        assert_eq!(
+            native,
+            json!({
+                "amount": "100",
+                "memo": "0x0102",
+                "sender": "SP000000000000000000002Q6VF78",
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_encode_clarity_value() {
+        let value =
+            js_sys::JSON::parse(r#"{"type_id": 10, "value": {"type_id": 13, "data": "x"}}"#)
+                .unwrap();
+        assert_eq!(encode_clarity_value(value).unwrap(), "0x0a0d0000000178");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_encode_transaction() {
+        let decoded =
+            decode_transaction(JsValue::from(TOKEN_TRANSFER_TX), JsValue::UNDEFINED).unwrap();
+        assert_eq!(encode_transaction(decoded).unwrap(), TOKEN_TRANSFER_TX);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_verify_transaction() {
+        let result = to_json(verify_transaction(JsValue::from(TOKEN_TRANSFER_TX)).unwrap());
+        assert_eq!(result["valid"], true);
+        assert_eq!(
+            result["origin"]["public_keys"],
+            json!(["0x03e356007964fc225a44c38352899c41e6293a97f8d8115998ae7e97184704c092"])
+        );
+        assert_eq!(result["sponsor"], json!(null));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_transactions() {
+        let txs = Array::of3(
+            &JsValue::from(TOKEN_TRANSFER_TX),
+            &JsValue::from("0x0000"),
+            &Uint8Array::from(decode_hex(TOKEN_TRANSFER_TX).unwrap().as_ref()).into(),
+        );
+        let results = decode_transactions(txs, JsValue::UNDEFINED).unwrap();
+        let results: Array = results.unchecked_into();
+        let decoded =
+            decode_transaction(JsValue::from(TOKEN_TRANSFER_TX), JsValue::UNDEFINED).unwrap();
+        assert_eq!(to_json(results.get(0)), to_json(decoded.clone()));
+        assert_eq!(to_json(results.get(2)), to_json(decoded));
+        let error: js_sys::Error = results.get(1).unchecked_into();
+        assert_eq!(error.name(), "DeserializeError");
+        assert_eq!(
+            Reflect::get(&error, &"code".into()).unwrap(),
+            JsValue::from("UnexpectedEof")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_transaction_list() {
+        let tx = decode_hex(TOKEN_TRANSFER_TX).unwrap();
+        let mut list = vec![0, 0, 0, 1];
+        list.extend_from_slice(&(tx.len() as u32).to_be_bytes());
+        list.extend_from_slice(&tx);
+        let results =
+            decode_transaction_list(Uint8Array::from(list.as_slice()).into(), JsValue::UNDEFINED)
+                .unwrap();
+        let results = to_json(results);
+        assert_eq!(
+            results[0]["tx_id"],
+            "0xd59d655128d67e33c6e9c20c7cca309b91da352358639092e13ce74fc0a6a5d4"
+        );
+        assert!(decode_transaction_list(JsValue::from("0x00000001"), JsValue::UNDEFINED).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_microblock_stream() {
+        let stream =
+            Uint8Array::from(&include_bytes!("../tests/fixtures/microblock-stream.bin")[..]);
+        let parent_block_hash = JsValue::from(format!("0x{}", "07".repeat(32)));
+        let result = to_json(
+            decode_microblock_stream(
+                stream.clone().into(),
+                parent_block_hash,
+                JsValue::from("0x79b000887626b294a914501a4cd226b58b235983"),
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(result["valid"], true);
+        assert_eq!(result["microblocks"].as_array().unwrap().len(), 3);
+        let result = to_json(
+            decode_microblock_stream(
+                stream.into(),
+                JsValue::from(format!("0x{}", "00".repeat(32))),
+                JsValue::NULL,
+                JsValue::UNDEFINED,
+            )
+            .unwrap(),
+        );
+        assert_eq!(result["valid"], false);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_tx_merkle_proof() {
+        let block = Uint8Array::from(&include_bytes!("../tests/fixtures/nakamoto-block.bin")[..]);
+        let txid = "0x1578f4cbec524cefaebb3cb599fdf003cbce27be0eb7e8bfb864ffa4715fb0d3";
+        let proof = get_tx_merkle_proof(block.into(), JsValue::from(txid), "nakamoto").unwrap();
+        assert_eq!(
+            to_json(proof.clone()),
+            json!({
+                "tx_index": 0,
+                "merkle_root": "0x080d35f6c5c02929a00fca1cc6f00a1c3828d905eb61e002ffd4e48f1ecef29d",
+                "path": [{
+                    "order": "right",
+                    "hash": "0x3dd37793e6e0e5253c1daac0cbf4d1375036f211327f106b158bea7f6451fefd",
+                }],
+            })
+        );
+        assert!(verify_tx_merkle_proof(JsValue::from(txid), proof.clone()).unwrap());
+        let other_txid = JsValue::from(format!("0x{}", "00".repeat(32)));
+        assert!(!verify_tx_merkle_proof(other_txid, proof).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_verify_nakamoto_block_signer_signatures() {
+        let block = Uint8Array::from(&include_bytes!("../tests/fixtures/nakamoto-block.bin")[..]);
+        // a reward set without any of the block's signers
+        let reward_set = js_sys::JSON::parse(&format!(
+            r#"[{{"signing_key": "0x02{}", "weight": 8}}]"#,
+            "11".repeat(32)
+        ))
+        .unwrap();
+        let result =
+            to_json(verify_nakamoto_block_signer_signatures(block.into(), reward_set).unwrap());
+        assert_eq!(result["threshold_met"], false);
+        assert_eq!(result["total_weight"], 8);
+        assert_eq!(
+            result["error"],
+            "Signature 0 is from a signer not in the reward set"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_verify_nakamoto_block_miner_signature() {
+        let block = Uint8Array::from(&include_bytes!("../tests/fixtures/nakamoto-block.bin")[..]);
+        let result = to_json(
+            verify_nakamoto_block_miner_signature(
+                block.into(),
+                JsValue::from("0x37e79a837b4071a1fc6c1b49208e7d2141a25905"),
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result,
+            json!({
+                "valid": true,
+                "miner_public_key": "0x035e291c2a379e7e47809859466378c0f036db49ce1382bcd72b97794aeb9e5324",
+            })
+        );
+    }
+}
@@ -0,0 +1,53 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {
+  decodeNakamotoBlock,
+  decodeNakamotoBlockAsync,
+  decodeStacksBlock,
+  decodeStacksBlockAsync,
+  decodeTransaction,
+  decodeTransactionAsync,
+  DeserializeError,
+} from '../index';
+
+const coinbaseTx = '0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000';
+
+test('decodeTransactionAsync', async () => {
+  const promise = decodeTransactionAsync(coinbaseTx);
+  expect(promise).toBeInstanceOf(Promise);
+  expect(await promise).toEqual(decodeTransaction(coinbaseTx));
+  await expect(decodeTransactionAsync(Buffer.from(coinbaseTx.slice(2), 'hex'))).resolves.toEqual(
+    decodeTransaction(coinbaseTx)
+  );
+});
+
+test('decodeTransactionAsync rejects malformed input', async () => {
+  const error = await decodeTransactionAsync('0x0000').catch(e => e);
+  expect(error).toBeInstanceOf(DeserializeError);
+  expect(error.code).toBe('UnexpectedEof');
+  expect(error.message).toBe('Failed to decode transaction: Unexpected end of input (at byte offset 2)');
+});
+
+test('decodeNakamotoBlockAsync', async () => {
+  const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+  const block = await decodeNakamotoBlockAsync(blockBuffer, true);
+  expect(block.merkle_root_valid).toBe(true);
+  expect(block).toEqual(decodeNakamotoBlock(blockBuffer, true));
+  const options = { numbers: 'bigint' } as const;
+  expect(await decodeNakamotoBlockAsync(blockBuffer, false, options)).toEqual(
+    decodeNakamotoBlock(blockBuffer, false, options)
+  );
+  await expect(decodeNakamotoBlockAsync(blockBuffer.subarray(0, 100))).rejects.toThrow(
+    'Failed to decode Nakamoto block: Unexpected end of input (at `header`'
+  );
+});
+
+test('decodeStacksBlockAsync', async () => {
+  // header with no transactions
+  const blockHex =
+    '00' + '0000000000000001' + '0000000000000001' + '00'.repeat(80) + '11'.repeat(32) + '22'.repeat(32) +
+    '0000' + '33'.repeat(32) + '44'.repeat(32) + '55'.repeat(20) + '00000000';
+  const block = await decodeStacksBlockAsync(blockHex, true);
+  expect(block.merkle_root_valid).toBe(false);
+  expect(block).toEqual(decodeStacksBlock(blockHex, true));
+});
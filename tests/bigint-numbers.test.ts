@@ -0,0 +1,62 @@
+import {
+  decodeClarityValue,
+  decodeClarityValueAtPath,
+  decodeClarityValueList,
+  decodePostConditions,
+  decodeTransaction,
+  decodeTransactions,
+  encodeClarityValue,
+  encodeTransaction,
+  BigIntNumbers,
+  ClarityTypeID,
+  DecodedTxSpendingConditionSingleSig,
+  TxPayloadTokenTransfer,
+} from '../index.js';
+
+// STX token transfer of 2000000 uSTX, nonce 47, fee 10000
+const tokenTransferTx = '0x00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000';
+
+test('decode Clarity int and uint as bigint', () => {
+  // u2000
+  expect(decodeClarityValue('0x01000000000000000000000000000007d0', { numbers: 'bigint' }).value).toBe(2000n);
+  // -1
+  expect(decodeClarityValue('0x00ffffffffffffffffffffffffffffffff', { numbers: 'bigint' }).value).toBe(-1n);
+  // u340282366920938463463374607431768211455
+  expect(decodeClarityValue('0x01ffffffffffffffffffffffffffffffff', { numbers: 'bigint' }).value).toBe(2n ** 128n - 1n);
+  // Default is still string-quoted
+  expect(decodeClarityValue('0x01000000000000000000000000000007d0').value).toBe('2000');
+  expect(() => decodeClarityValue('0x01000000000000000000000000000007d0', { numbers: 'number' as any })).toThrow(
+    'Unknown numbers format `number`'
+  );
+});
+
+test('decode nested Clarity values as bigint', () => {
+  // (list u5 -3)
+  const hex = encodeClarityValue({
+    type_id: ClarityTypeID.List,
+    list: [{ type_id: ClarityTypeID.UInt, value: 5n }, { type_id: ClarityTypeID.Int, value: -3n }],
+  });
+  expect(hex).toBe('0x0b00000002010000000000000000000000000000000500fffffffffffffffffffffffffffffffd');
+  const decoded = decodeClarityValue(hex, { numbers: 'bigint' });
+  expect(decoded.type_id === ClarityTypeID.List && decoded.list.map(v => 'value' in v && v.value)).toEqual([5n, -3n]);
+  expect(decodeClarityValueAtPath(hex, '[1]', { numbers: 'bigint' })?.value).toBe(-3n);
+  const list = decodeClarityValueList('0x00000001' + hex.slice(2), true, { numbers: 'bigint' });
+  expect(list[0].type_id === ClarityTypeID.List && list[0].list[0]).toMatchObject({ value: 5n });
+});
+
+test('decode transaction amounts as bigint', () => {
+  const decoded = decodeTransaction(tokenTransferTx, { numbers: 'bigint' });
+  const origin = decoded.auth.origin_condition as BigIntNumbers<DecodedTxSpendingConditionSingleSig>;
+  expect(origin.nonce).toBe(47n);
+  expect(origin.tx_fee).toBe(10000n);
+  expect((decoded.payload as BigIntNumbers<TxPayloadTokenTransfer>).amount).toBe(2000000n);
+  expect(encodeTransaction(decoded)).toBe(tokenTransferTx);
+
+  const [batchDecoded] = decodeTransactions([tokenTransferTx], { numbers: 'bigint' });
+  expect(batchDecoded).toEqual(decoded);
+});
+
+test('decode post-condition amounts as bigint', () => {
+  const decoded = decodePostConditions('020000000100021642779fa5c48120aa60c18eb05a164bad77bf2cdd0100000000002ab980', { numbers: 'bigint' });
+  expect(decoded.post_conditions[0]).toMatchObject({ amount: 2800000n });
+});
@@ -0,0 +1,53 @@
+import { decodeClarityValue, decodeClarityValueAtPath, ClarityValueUInt, DeserializeError } from '../index.js';
+
+// (ok (tuple (items (list u1 u2 u3)) (name "a")))
+const hex = '0x070c00000002056974656d730b00000003010000000000000000000000000000000101000000000000000000000000000000020100000000000000000000000000000003046e616d650d0000000161';
+
+test('decode clarity value at path', () => {
+  const item = decodeClarityValueAtPath<ClarityValueUInt>(hex, 'ok.items[1]');
+  expect(item).toEqual({
+    repr: 'u2',
+    hex: '0x0100000000000000000000000000000002',
+    type_id: 1,
+    value: '2',
+  });
+  expect(decodeClarityValueAtPath(hex, 'ok.items.1')).toEqual(item);
+  expect(decodeClarityValueAtPath(hex, ['ok', 'items', 1])).toEqual(item);
+  expect(decodeClarityValueAtPath(Buffer.from(hex.slice(2), 'hex'), 'ok.items[1]')).toEqual(item);
+});
+
+test('decode clarity value at path - formats', () => {
+  expect(decodeClarityValueAtPath(hex, 'ok.items', { format: 'repr' })).toBe('(list u1 u2 u3)');
+  expect(decodeClarityValueAtPath(hex, 'ok.name', { format: 'hex' })).toBe('0x0d0000000161');
+  expect(decodeClarityValueAtPath(hex, 'ok.name', { format: 'object' })).toEqual(
+    decodeClarityValue('0x0d0000000161')
+  );
+  expect(decodeClarityValueAtPath(hex, '')).toEqual(decodeClarityValue(hex));
+});
+
+test('decode clarity value at path - missing values', () => {
+  expect(decodeClarityValueAtPath(hex, 'err')).toBeNull();
+  expect(decodeClarityValueAtPath(hex, 'ok.missing')).toBeNull();
+  expect(decodeClarityValueAtPath(hex, 'ok.items[3]')).toBeNull();
+  expect(decodeClarityValueAtPath(hex, 'ok.name.length', { format: 'repr' })).toBeNull();
+});
+
+test('decode clarity value at path - errors', () => {
+  expect(() => decodeClarityValueAtPath(hex, 'ok..name')).toThrow('Invalid path `ok..name`');
+  expect(() => decodeClarityValueAtPath(hex, ['ok', -1])).toThrow('Invalid list index in path: -1');
+  expect(() => decodeClarityValueAtPath(hex, 'ok', { format: 'json' as any })).toThrow('Unknown format `json`');
+
+  // (list u1 <bad type prefix>): items after the selected one are not read
+  const badList = '0x0b00000002010000000000000000000000000000000163';
+  expect(decodeClarityValueAtPath(badList, '[0]', { format: 'repr' })).toBe('u1');
+  let error: DeserializeError | undefined;
+  try {
+    decodeClarityValueAtPath(badList, '[1]');
+  } catch (e) {
+    error = e as DeserializeError;
+  }
+  expect(error).toBeInstanceOf(DeserializeError);
+  expect(error?.code).toBe('BadTypePrefix');
+  expect(error?.path).toBe('list[1]');
+  expect(error?.offset).toBe(22);
+});
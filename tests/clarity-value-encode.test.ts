@@ -0,0 +1,54 @@
+import { ClarityTypeID, decodeClarityValue, encodeClarityValue } from '../index.js';
+
+const ALICE_TUPLE_HEX = '0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479';
+
+test('encode clarity value - decode round trip', () => {
+  const decoded = decodeClarityValue(ALICE_TUPLE_HEX);
+  expect(encodeClarityValue(decoded)).toBe(ALICE_TUPLE_HEX);
+});
+
+test('encode clarity value - round trip all types', () => {
+  const inputs = [
+    '0x00fffffffffffffffffffffffffffffffe',
+    '0x0100000000000000000000000000000001',
+    '0x03',
+    '0x04',
+    '0x09',
+    '0x0a03',
+    '0x070100000000000000000000000000000001',
+    '0x080100000000000000000000000000000001',
+    '0x0200000003010203',
+    '0x0b000000020309',
+    '0x0c00000000',
+    '0x0d0000000568656c6c6f',
+    '0x0e000000066f6720e2ad90',
+    '0x0516a13dce8114be0f707f94470a2e5e86eb402f2923',
+    '0x0616a13dce8114be0f707f94470a2e5e86eb402f29230474657374',
+  ];
+  for (const input of inputs) {
+    expect(encodeClarityValue(decodeClarityValue(input))).toBe(input);
+  }
+});
+
+test('encode clarity value - without repr or hex', () => {
+  const hex = encodeClarityValue({
+    type_id: ClarityTypeID.Tuple,
+    data: {
+      amount: { type_id: ClarityTypeID.UInt, value: 2000 },
+      memo: { type_id: ClarityTypeID.OptionalSome, value: { type_id: ClarityTypeID.Buffer, buffer: '0x01' } },
+      to: { type_id: ClarityTypeID.PrincipalStandard, address: 'SP2GKVKM12JZ0YW3ZJH3GMBJYGVNM0BS94ERA45AM' },
+    },
+  });
+  expect(decodeClarityValue(hex).repr).toBe(
+    "(tuple (amount u2000) (memo (some 0x01)) (to 'SP2GKVKM12JZ0YW3ZJH3GMBJYGVNM0BS94ERA45AM))"
+  );
+});
+
+test('encode clarity value - errors', () => {
+  expect(() => encodeClarityValue({ type_id: 99 } as any)).toThrow('Bad Clarity type_id: 99');
+  expect(() => encodeClarityValue({ type_id: ClarityTypeID.UInt, value: '-1' })).toThrow();
+  expect(() => encodeClarityValue({ type_id: ClarityTypeID.StringAscii, data: 'og ⭐' })).toThrow();
+  expect(() =>
+    encodeClarityValue({ type_id: ClarityTypeID.PrincipalStandard, address: 'not-an-address' })
+  ).toThrow();
+});
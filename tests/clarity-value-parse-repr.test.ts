@@ -0,0 +1,38 @@
+import { decodeClarityValueToRepr, parseClarityRepr } from '../index.js';
+
+const ALICE_TUPLE_HEX = '0x0c00000007066163746976650307616464726573730516142a7f9b4d4c7d2fdbe69c0b6733a484f37bbc3b05616c6961730d00000005416c6963650762616c616e636501000000000000000000000000000007d00470696e670700000000000000000000000000000000fa0a7075626c69635f6b65790a020000002102d4dada83bff981f0cb7ebafcfc6fc7cb5e078b9ee2302a93aae19fb90f872e5804746167730b000000030e0000000b636f6e7472696275746f720e000000066f6720e2ad900e00000007636c6172697479';
+
+test('parse clarity repr - repr round trip', () => {
+  const inputs = [
+    ALICE_TUPLE_HEX,
+    '0x00fffffffffffffffffffffffffffffffe',
+    '0x0100000000000000000000000000000001',
+    '0x0200000003010203',
+    '0x070100000000000000000000000000000001',
+    '0x080616a13dce8114be0f707f94470a2e5e86eb402f29230474657374',
+    '0x0b000000020a0909',
+    '0x0d000000040a22095c',
+  ];
+  for (const input of inputs) {
+    expect(parseClarityRepr(decodeClarityValueToRepr(input))).toBe(input);
+  }
+});
+
+test('parse clarity repr - unicode escapes', () => {
+  // Escapes hold the UTF-8 bytes of one char, as written by decodeClarityValueToRepr
+  expect(parseClarityRepr('u"og \\u{e2ad90}"')).toBe('0x0e000000066f6720e2ad90');
+  expect(parseClarityRepr('u"\\u{c3a9}"')).toBe('0x0e00000002c3a9');
+  expect(() => parseClarityRepr('u"og \\u{2b50}"')).toThrow(/Invalid unicode escape/);
+});
+
+test('parse clarity repr - nesting depth', () => {
+  const nested = (depth: number) => '(some '.repeat(depth) + '(list)' + ')'.repeat(depth);
+  expect(parseClarityRepr(nested(15))).toBe('0x' + '0a'.repeat(15) + '0b00000000');
+  expect(() => parseClarityRepr(nested(16))).toThrow(/TypeSignatureTooDeep/);
+});
+
+test('parse clarity repr - errors include position', () => {
+  expect(() => parseClarityRepr('(tuple\n  (a u1)\n  (b u-1))')).toThrow(/line 3, column 6/);
+  expect(() => parseClarityRepr('(list u1 u2')).toThrow(/Unclosed `\(` at line 1, column 1/);
+  expect(() => parseClarityRepr('(tuple (a u1) (a u2))')).toThrow(/Duplicate tuple field `a`/);
+});
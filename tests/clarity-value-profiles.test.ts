@@ -0,0 +1,72 @@
+import { decodeClarityValue, decodeTransaction, TxPayloadTypeID } from '../index.js';
+
+// (ok (tuple (items (list u1 u2)) (name "a")))
+const hex = '0x070c00000002056974656d730b0000000201000000000000000000000000000000010100000000000000000000000000000002046e616d650d0000000161';
+
+test('minimal profile skips repr and hex', () => {
+  expect(decodeClarityValue(hex, { profile: 'minimal' })).toEqual({
+    type_id: 7,
+    value: {
+      type_id: 12,
+      data: {
+        items: { type_id: 11, list: [{ type_id: 1, value: '1' }, { type_id: 1, value: '2' }] },
+        name: { type_id: 13, data: 'a' },
+      },
+    },
+  });
+  expect(decodeClarityValue(hex, { profile: 'full' })).toEqual(decodeClarityValue(hex));
+});
+
+test('json profile', () => {
+  expect(decodeClarityValue(hex, { profile: 'json' })).toEqual({
+    type: '(response (tuple (items (list 2 uint)) (name (string-ascii 1))) UnknownType)',
+    value: {
+      type: '(tuple (items (list 2 uint)) (name (string-ascii 1)))',
+      value: {
+        items: { type: '(list 2 uint)', value: [{ type: 'uint', value: '1' }, { type: 'uint', value: '2' }] },
+        name: { type: '(string-ascii 1)', value: 'a' },
+      },
+    },
+    success: true,
+  });
+  // (err none)
+  expect(decodeClarityValue('0x0809', { profile: 'json' })).toEqual({
+    type: '(response UnknownType (optional none))',
+    value: { type: '(optional none)', value: null },
+    success: false,
+  });
+  // 'SP000000000000000000002Q6VF78.pox-4
+  expect(decodeClarityValue('0x0616000000000000000000000000000000000000000005706f782d34', { profile: 'json' })).toEqual({
+    type: 'principal',
+    value: 'SP000000000000000000002Q6VF78.pox-4',
+  });
+  // 0x0102
+  expect(decodeClarityValue('0x02000000020102', { profile: 'json', numbers: 'bigint' })).toEqual({
+    type: '(buff 2)',
+    value: '0x0102',
+  });
+  expect(() => decodeClarityValue(hex, { profile: 'compact' as any })).toThrow('Unknown profile `compact`');
+});
+
+test('transaction function args profiles', () => {
+  const tx = '0x0000000001040089f5fd1f719e4449c980de38e3504be6770a2698000000000000014500000000000001f400008b510c9e20dc22040953d9d7eabf2038008fa4d89a5a6cb78bb9d513e75cd0df3924af9ce3b5f185705bc2f6ba3071710ec6a8803ed6da4addc40a05a01ee0f503020000000102021689f5fd1f719e4449c980de38e3504be6770a269816a6a7a70f41adbe8eae708ed7ec2cbf41a272182014626974636f696e2d6d6f6e6b6579732d6c61627314626974636f696e2d6d6f6e6b6579732d6c61627301000000000000000000000000000008ba1002162bcf9762d5b90bc36dc1b4759b1727690f92ddd30e6d61726b6574706c6163652d76340a6c6973742d6173736574000000040616a6a7a70f41adbe8eae708ed7ec2cbf41a272182014626974636f696e2d6d6f6e6b6579732d6c61627301000000000000000000000000000008ba010000000000000000000000000c84588001000000000000000000000000000000c8';
+
+  const json = decodeTransaction(tx, { profile: 'json' });
+  if (json.payload.type_id !== TxPayloadTypeID.ContractCall) {
+    throw new Error('Expected a contract call');
+  }
+  expect(json.payload.function_args).toEqual([
+    { type: 'principal', value: 'SP2KAF9RF86PVX3NEE27DFV1CQX0T4WGR41X3S45C.bitcoin-monkeys-labs' },
+    { type: 'uint', value: '2234' },
+    { type: 'uint', value: '210000000' },
+    { type: 'uint', value: '200' },
+  ]);
+
+  const minimal = decodeTransaction(tx, { profile: 'minimal' });
+  if (minimal.payload.type_id !== TxPayloadTypeID.ContractCall) {
+    throw new Error('Expected a contract call');
+  }
+  expect(minimal.payload.function_args[1]).toEqual({ type_id: 1, value: '2234' });
+  expect(minimal.payload.function_args_buffer).toBe(json.payload.function_args_buffer);
+  expect(minimal.post_conditions[0]).toMatchObject({ asset_value: { type_id: 1, value: '2234' } });
+});
@@ -0,0 +1,53 @@
+import { decodeClarityValue } from '../index.js';
+
+test('strict decode accepts canonical values', () => {
+  // (list none (some u1))
+  const hex = '0x0b00000002090a0100000000000000000000000000000001';
+  expect(decodeClarityValue(hex, { strict: true })).toEqual(decodeClarityValue(hex));
+});
+
+test('strict decode rejects out-of-order and duplicate tuple fields', () => {
+  // (tuple (b true) (a true))
+  expect(decodeClarityValue('0x0c00000002016203016103').repr).toBe('(tuple (a true) (b true))');
+  expect(() => decodeClarityValue('0x0c00000002016203016103', { strict: true })).toThrow(
+    'Non-canonical Clarity value: tuple field `a` is out of order after `b` (at byte offset 8)'
+  );
+  // (tuple (a true) (a false))
+  expect(() => decodeClarityValue('0x0c00000002016103016104', { strict: true })).toThrow(
+    'Non-canonical Clarity value: duplicate tuple field `a` (at byte offset 8)'
+  );
+  // (tuple)
+  expect(decodeClarityValue('0x0c00000000').repr).toBe('(tuple)');
+  expect(() => decodeClarityValue('0x0c00000000', { strict: true })).toThrow(
+    'Non-canonical Clarity value: empty tuples are not allowed (at byte offset 0)'
+  );
+});
+
+test('decode rejects principal versions of 32 or more', () => {
+  for (const options of [{}, { strict: true }]) {
+    expect(() => decodeClarityValue('0x05ff0000000000000000000000000000000000000000', options)).toThrow(
+      'Invalid principal version 255, must be less than 32 (at byte offset 1)'
+    );
+  }
+});
+
+test('strict decode rejects invalid strings', () => {
+  expect(() => decodeClarityValue('0x0d000000026107', { strict: true })).toThrow(
+    'Non-canonical Clarity value: invalid string-ascii character 0x07 (at byte offset 6)'
+  );
+  expect(() => decodeClarityValue('0x0e0000000361ff62', { strict: true })).toThrow(
+    'Non-canonical Clarity value: invalid UTF-8 in string-utf8 (at byte offset 6)'
+  );
+});
+
+test('strict decode rejects heterogeneous lists and trailing bytes', () => {
+  // (list true u1)
+  expect(() => decodeClarityValue('0x0b00000002030100000000000000000000000000000001', { strict: true })).toThrow(
+    'Non-canonical Clarity value: Invalid list'
+  );
+  // lenient decoding ignores the trailing bytes, the root hex is still the whole input
+  expect(decodeClarityValue('0x0300')).toEqual({ repr: 'true', hex: '0x0300', type_id: 3, value: true });
+  expect(() => decodeClarityValue('0x0300', { strict: true })).toThrow(
+    'Non-canonical Clarity value: trailing bytes (at byte offset 1)'
+  );
+});
@@ -0,0 +1,38 @@
+import { decodeClarityValueToNative, encodeClarityValue, ClarityTypeID } from '../index.js';
+
+// (ok (tuple (amount u100) (memo (some 0x0102)) (sender 'SP000000000000000000002Q6VF78)))
+const hex = '0x070c0000000306616d6f756e740100000000000000000000000000000064046d656d6f0a020000000201020673656e64657205160000000000000000000000000000000000000000';
+
+test('decode Clarity value to native JS values', () => {
+  expect(decodeClarityValueToNative(hex)).toEqual({
+    ok: { amount: 100n, memo: '0x0102', sender: 'SP000000000000000000002Q6VF78' },
+  });
+  expect(decodeClarityValueToNative(Buffer.from(hex.slice(2), 'hex'))).toEqual(decodeClarityValueToNative(hex));
+});
+
+test('decode Clarity value to native JS values with options', () => {
+  expect(decodeClarityValueToNative(hex, { numbers: 'string', buffers: 'uint8array', unwrapResponses: true })).toEqual({
+    amount: '100',
+    memo: new Uint8Array([1, 2]),
+    sender: 'SP000000000000000000002Q6VF78',
+  });
+  expect(() => decodeClarityValueToNative(hex, { buffers: 'base64' as any })).toThrow('Unknown buffers format `base64`');
+});
+
+test('decode nested lists, optionals and err responses to native JS values', () => {
+  const encoded = encodeClarityValue({
+    type_id: ClarityTypeID.ResponseError,
+    value: {
+      type_id: ClarityTypeID.List,
+      list: [
+        { type_id: ClarityTypeID.Int, value: '-3' },
+        { type_id: ClarityTypeID.OptionalNone },
+        { type_id: ClarityTypeID.StringUtf8, data: 'hé' },
+        { type_id: ClarityTypeID.PrincipalContract, address: 'SP000000000000000000002Q6VF78', contract_name: 'pox-4' },
+      ],
+    },
+  });
+  expect(decodeClarityValueToNative(encoded)).toEqual({
+    err: [-3n, null, 'hé', 'SP000000000000000000002Q6VF78.pox-4'],
+  });
+});
@@ -0,0 +1,26 @@
+import { decodeClarityValueToTypeName } from '../index.js';
+
+test('decode clarity value to type name - list of optionals', () => {
+  // (list none (some u1))
+  const typeName = decodeClarityValueToTypeName('0x0b00000002090a0100000000000000000000000000000001');
+  expect(typeName).toBe('(list 2 (optional uint))');
+});
+
+test('decode clarity value to type name - list of responses', () => {
+  // (list (ok 0x01) (err u1) (ok 0x0203))
+  const typeName = decodeClarityValueToTypeName('0x0b00000003070200000001010801000000000000000000000000000000010702000000020203');
+  expect(typeName).toBe('(list 3 (response (buff 2) uint))');
+});
+
+test('decode clarity value to type name - empty list', () => {
+  // (tuple (a u1) (b (list)))
+  const typeName = decodeClarityValueToTypeName('0x0c000000020161010000000000000000000000000000000101620b00000000');
+  expect(typeName).toBe('(tuple (a uint) (b (list 0 UnknownType)))');
+});
+
+test('decode clarity value to type name - heterogeneous list', () => {
+  // (list 1 u1)
+  expect(() => decodeClarityValueToTypeName('0x0b0000000200000000000000000000000000000000010100000000000000000000000000000001')).toThrow(
+    'Error inferring Clarity type: Invalid list: item 1 does not match the list type: types int and uint are incompatible'
+  );
+});
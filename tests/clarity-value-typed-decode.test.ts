@@ -0,0 +1,52 @@
+import { ClarityTypeID, ClarityValueList, ClarityValueOptional, ClarityValueUInt, decodeClarityValueWithType } from '../index.js';
+
+// (list none (some u1))
+const listHex = '0x0b00000002090a0100000000000000000000000000000001';
+
+test('decode clarity value with type signature string', () => {
+  const val = decodeClarityValueWithType<ClarityValueList<ClarityValueOptional<ClarityValueUInt>>>(listHex, '(list 2 (optional uint))');
+  expect(val).toEqual({
+    repr: '(list none (some u1))',
+    hex: listHex,
+    type_id: ClarityTypeID.List,
+    type: '(list 2 (optional uint))',
+    list: [
+      { repr: 'none', hex: '0x09', type_id: ClarityTypeID.OptionalNone, value: null, type: '(optional uint)' },
+      {
+        repr: '(some u1)',
+        hex: '0x0a0100000000000000000000000000000001',
+        type_id: ClarityTypeID.OptionalSome,
+        type: '(optional uint)',
+        value: { repr: 'u1', hex: '0x0100000000000000000000000000000001', type_id: ClarityTypeID.UInt, value: '1', type: 'uint' },
+      },
+    ],
+  });
+});
+
+test('decode clarity value with ABI type', () => {
+  const abiType = { list: { type: { optional: 'uint128' as const }, length: 10 } };
+  const val = decodeClarityValueWithType(listHex, abiType);
+  expect(val.type).toBe('(list 10 (optional uint))');
+  expect(decodeClarityValueWithType(listHex, JSON.stringify(abiType)).type).toBe('(list 10 (optional uint))');
+
+  // (err u1)
+  const errVal = decodeClarityValueWithType('0x080100000000000000000000000000000001', {
+    response: { ok: { tuple: [{ name: 'amount', type: 'uint128' }, { name: 'memo', type: { buffer: { length: 34 } } }] }, error: 'uint128' },
+  });
+  expect(errVal.type).toBe('(response (tuple (amount uint) (memo (buff 34))) uint)');
+});
+
+test('decode clarity value with type - mismatches', () => {
+  expect(() => decodeClarityValueWithType(listHex, '(list 1 (optional uint))')).toThrow(
+    'Type mismatch at `value`: list length 2 exceeds maximum 1'
+  );
+  expect(() => decodeClarityValueWithType(listHex, { list: { type: { optional: 'int128' }, length: 2 } })).toThrow(
+    'Type mismatch at `value[1].some`: expected int, found u1'
+  );
+  expect(() => decodeClarityValueWithType('0x080100000000000000000000000000000001', '(response bool none)')).toThrow(
+    'Type mismatch at `value.err`: expected UnknownType, found u1'
+  );
+  expect(() => decodeClarityValueWithType(listHex, '(list 2 (optional foo))')).toThrow(
+    'Error parsing type signature: Unknown type `foo` at column 19'
+  );
+});
@@ -0,0 +1,71 @@
+import { DeserializeError, decodeClarityValue, decodeClarityValueList, decodePostConditions, decodeTransaction } from '../index';
+
+function catchError(fn: () => unknown): DeserializeError {
+  try {
+    fn();
+  } catch (error) {
+    return error as DeserializeError;
+  }
+  throw new Error('Expected function to throw');
+}
+
+test('truncated transaction', () => {
+  // version, chain ID, auth type, then the hash mode and first 5 signer bytes of a singlesig spending condition
+  const error = catchError(() => decodeTransaction('0x000000000104000000000000'));
+  expect(error).toBeInstanceOf(DeserializeError);
+  expect(error).toBeInstanceOf(Error);
+  expect(error.name).toBe('DeserializeError');
+  expect(error.code).toBe('UnexpectedEof');
+  expect(error.offset).toBe(12);
+  expect(error.path).toBe('auth.origin_condition');
+  expect(error.message).toBe(
+    'Failed to decode transaction: Unexpected end of input (at `auth.origin_condition`, byte offset 12)'
+  );
+});
+
+test('bad type prefix in a contract call argument', () => {
+  const tx =
+    '0x0000000001' +
+    // standard auth, singlesig p2pkh, zeroed signer, nonce, fee, and signature
+    '0400' + '00'.repeat(36) + '00' + '00'.repeat(65) +
+    // anchor mode any, post-condition mode deny, no post-conditions
+    '030200000000' +
+    // contract call to SP000000000000000000002Q6VF78.pox-4 `f`
+    '0216' + '00'.repeat(20) + '05706f782d340166' +
+    // (u1 (tuple (amount <bad type prefix>)))
+    '00000002' + '01' + '00'.repeat(15) + '01' + '0c0000000106616d6f756e7463';
+  const error = catchError(() => decodeTransaction(tx));
+  expect(error.code).toBe('BadTypePrefix');
+  expect(error.path).toBe('payload.function_args[1].tuple.amount');
+  expect(error.offset).toBe((tx.length - 2) / 2 - 1);
+});
+
+test('Clarity value errors', () => {
+  // (some (list true <bad type prefix>))
+  let error = catchError(() => decodeClarityValue('0x0a0b0000000203ff'));
+  expect(error.code).toBe('BadTypePrefix');
+  expect(error.path).toBe('some.list[1]');
+  expect(error.offset).toBe(7);
+
+  // buffer with a length over the maximum value size
+  error = catchError(() => decodeClarityValue('0x02ffffffff'));
+  expect(error.code).toBe('IllegalLength');
+  expect(error.offset).toBe(0);
+
+  error = catchError(() => decodeClarityValue('0x0300', { strict: true }));
+  expect(error.code).toBe('NonCanonical');
+  expect(error.offset).toBe(1);
+
+  // the second list item is truncated, offsets include the u32 length prefix
+  error = catchError(() => decodeClarityValueList('0x000000020301'));
+  expect(error.code).toBe('UnexpectedEof');
+  expect(error.path).toBe('[1]');
+  expect(error.offset).toBe(6);
+});
+
+test('post-condition errors', () => {
+  const error = catchError(() => decodePostConditions('0x010000000107'));
+  expect(error.code).toBe('InvalidValue');
+  expect(error.path).toBe('post_conditions[0]');
+  expect(error.offset).toBe(6);
+});
@@ -0,0 +1,79 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import { decodeMicroblock, decodeMicroblockStream } from '../index';
+
+// A synthetic stream of 3 microblocks, each containing one transaction, built on parent block 0x0707..07
+const streamBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/microblock-stream.bin'));
+const parentBlockHash = '0x' + '07'.repeat(32);
+const microblockPubkeyHash = '0x79b000887626b294a914501a4cd226b58b235983';
+const microblockHashes = [
+  '0xccc76d181a505e55e4ca25289209093568f7a3de1ed1c20b24c323503734916c',
+  '0x480ba529ac66c59ce17ae1608a9f094161d8f915de90b77a02bfada9292da862',
+  '0x0b30c6eb16888252760367b9c447ddfcd5fe4a2c84f770742e76fdbadd725436',
+];
+
+describe('Microblock decoding', () => {
+  test('decode single microblock', () => {
+    // Skip the u32 stream length prefix and take the first microblock
+    const microblockBuffer = streamBuffer.subarray(4, 4 + (streamBuffer.length - 4) / 3);
+    const microblock = decodeMicroblock(microblockBuffer, true);
+    expect(microblock.block_hash).toBe(microblockHashes[0]);
+    expect(microblock.signer_pubkey_hash).toBe(microblockPubkeyHash);
+    expect(microblock.merkle_root_valid).toBe(true);
+    expect(microblock.header.sequence).toBe(0);
+    expect(microblock.header.prev_block).toBe(parentBlockHash);
+    expect(microblock.txs).toHaveLength(1);
+  });
+
+  test('decode and verify microblock stream', () => {
+    const result = decodeMicroblockStream(streamBuffer, parentBlockHash, microblockPubkeyHash);
+    expect(result.valid).toBe(true);
+    expect(result.errors).toEqual([]);
+    expect(result.microblocks.map(m => m.block_hash)).toEqual(microblockHashes);
+    expect(result.microblocks.map(m => m.header.sequence)).toEqual([0, 1, 2]);
+    expect(result.microblocks[1].header.prev_block).toBe(microblockHashes[0]);
+    expect(result.microblocks[2].header.prev_block).toBe(microblockHashes[1]);
+  });
+
+  test('decode microblock transactions with output options', () => {
+    const microblockBuffer = streamBuffer.subarray(4, 4 + (streamBuffer.length - 4) / 3);
+    const tx = decodeMicroblock(microblockBuffer).txs[0];
+    const bigintTx = decodeMicroblock(microblockBuffer, false, { numbers: 'bigint' }).txs[0];
+    expect(bigintTx.auth.origin_condition.nonce).toBe(BigInt(tx.auth.origin_condition.nonce));
+
+    const result = decodeMicroblockStream(streamBuffer, null, null, { numbers: 'bigint' });
+    expect(result.microblocks[0].txs[0]).toEqual(bigintTx);
+  });
+
+  test('stream not matching the anchor block', () => {
+    const result = decodeMicroblockStream(streamBuffer, '0x' + '00'.repeat(32), '0x' + '00'.repeat(20));
+    expect(result.valid).toBe(false);
+    expect(result.errors).toEqual([
+      `Microblock 0 prev_block ${parentBlockHash} does not match the parent block 0x${'00'.repeat(32)}`,
+      `Microblock 0 is signed by ${microblockPubkeyHash}, expected 0x${'00'.repeat(20)}`,
+      `Microblock 1 is signed by ${microblockPubkeyHash}, expected 0x${'00'.repeat(20)}`,
+      `Microblock 2 is signed by ${microblockPubkeyHash}, expected 0x${'00'.repeat(20)}`,
+    ]);
+  });
+
+  test('stream with a missing microblock', () => {
+    const microblockLength = (streamBuffer.length - 4) / 3;
+    const gapStream = Buffer.concat([
+      Buffer.from([0, 0, 0, 2]),
+      streamBuffer.subarray(4, 4 + microblockLength),
+      streamBuffer.subarray(4 + microblockLength * 2),
+    ]);
+    const result = decodeMicroblockStream(gapStream);
+    expect(result.valid).toBe(false);
+    expect(result.errors).toEqual([
+      'Microblock 1 has sequence 2, expected 1',
+      `Microblock 1 prev_block ${microblockHashes[1]} does not match the hash of microblock 0`,
+    ]);
+  });
+
+  test('stream with an oversized microblock count', () => {
+    // A count larger than the input can hold fails to decode instead of allocating for it
+    const oversizedStream = Buffer.concat([Buffer.from([0xff, 0xff, 0xff, 0xff]), streamBuffer.subarray(4)]);
+    expect(() => decodeMicroblockStream(oversizedStream)).toThrow(/^Failed to decode microblock stream: /);
+  });
+});
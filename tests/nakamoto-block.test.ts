@@ -1,6 +1,41 @@
 import * as fs from 'fs';
 import * as path from 'path';
-import { decodeNakamotoBlock, decodeStacksBlock } from '../index';
+import {
+  decodeNakamotoBlock,
+  decodeStacksBlock,
+  getTxMerkleProof,
+  verifyNakamotoBlockMinerSignature,
+  verifyNakamotoBlockSignerSignatures,
+  verifyTxMerkleProof,
+} from '../index';
+
+// Signing keys recovered from the fixture block's signer signatures, in reward set order
+const fixtureSignerKeys = [
+  '0x02254a34747123978819f2a90506f76cb057fe3fbff6d8721a0d9cf8e9412d0e60',
+  '0x023d6e4adbd5e7bedd5a1e1b85940e1e8c6c34924fd0d584e5e15d84c8572083d9',
+  '0x0244869db071d334ff8e5cd94956ae7b60a4abd41f83f3c9d66ab314718151d94d',
+  '0x024f164c6e73df283d34d7d9cc86553a82dce76045ba7dfbf4de0004f89eabb8e0',
+  '0x025588e24e2bf387fe8cc7bccba1aac7fe599b96724892431e992a40d06e8fe220',
+  '0x0268e6f499fca2912488e89fc8b6734cafbe24a4ecbcd3312e4eb27ed8e5cfb4f3',
+  '0x027af6b78b484b2c129dab7e490e8438f9a6eb4890071335a5781d483073866b9a',
+  '0x02844807121921880119fe05ae47fccb4945a4bb2f840fe7de66e6f32640bc8169',
+  '0x02877ce29ba35458b827a6ea18510b9058ae4c30e2c33d288f2982c13497caec6e',
+  '0x02879477ec9ed8a56c0b26ba4b452246608d6e1b02ae1fa405a2c97ee8af5da202',
+  '0x029e1245f007bd8f76d5ce67e759acd21f8b0f2538a80713468f7524bf3fff6136',
+  '0x02a89cb3164d3dabcb5cf8796bfc28b91fade8c71a64b5a76d94e79cd27cfa8895',
+  '0x02c54d8b1ba4b7207f78f861c60f8a67433c264a11ac9b6b7773476e9f6c008e49',
+  '0x0302328212d5e430a8a880f8e2365a8f976ee50490ff030c106866c0b789eae91a',
+  '0x0321129d7a3e14cce66abef68b9a3d31d998f14e9a18b09d66aa1110fc604a3b1f',
+  '0x034df3feda207a1cd4f31ae2b58f136a0d382d23419ef8d06569fa538202ba8aed',
+  '0x03632c3412fc3db64c4efd21cac47714f2408752c9eeec70c2364f814623da77f4',
+  '0x03815f036a90512671911fd66cca53dd88d421beaa3253083635f267dc614ee888',
+  '0x038265445ac9fa77d96bf6f3eee74e287deb10aeef2f2e3f96581a6bb765de8240',
+  '0x038b1879ce76e0332b6420144abf959479f206c2e64b07dead643e2885136610ed',
+  '0x038cb1e945144ca7669b0f33656b8379bcb3c17795b4d8665e42dea76eb3f86d2f',
+  '0x03b3b78738abbdc573cdcefd8200b1bca999e2f2fd8ecdf70c64ced1e4105437b7',
+  '0x03b42ff50ea79b0dcee6a8e90610fb9f3cd00ce29769cc278dee2e446ab105722a',
+  '0x03cef32afac202346ac76a28e81e77ed497c3f22ce20ac54b496950b4ef0b74b2e',
+];
 
 describe('Nakamoto block decoding', () => {
   it('should decode a Nakamoto block', () => {
@@ -40,11 +75,81 @@ describe('Nakamoto block decoding', () => {
     expect(result.block_id).toBe(result.header.index_block_hash);
   });
 
+  it('should decode the block transactions with output options', () => {
+    const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+    const tx = decodeNakamotoBlock(blockBuffer).txs[0];
+    const bigintTx = decodeNakamotoBlock(blockBuffer, false, { numbers: 'bigint' }).txs[0];
+    expect(bigintTx.auth.origin_condition.nonce).toBe(BigInt(tx.auth.origin_condition.nonce));
+    expect(bigintTx.auth.origin_condition.tx_fee).toBe(BigInt(tx.auth.origin_condition.tx_fee));
+    expect(() => decodeNakamotoBlock(blockBuffer, false, { profile: 'compact' as any })).toThrow(
+      'Unknown profile `compact`'
+    );
+  });
+
   it('should handle invalid block data gracefully', () => {
     expect(() => {
       decodeNakamotoBlock('deadbeef');
     }).toThrow();
   });
+
+  it('should verify the tx merkle root', () => {
+    const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+    expect(decodeNakamotoBlock(blockBuffer)).not.toHaveProperty('merkle_root_valid');
+    expect(decodeNakamotoBlock(blockBuffer, true).merkle_root_valid).toBe(true);
+
+    // flip a bit of the header tx_merkle_root
+    const tampered = Buffer.from(blockBuffer);
+    tampered[1 + 8 + 8 + 20 + 32] ^= 1;
+    expect(decodeNakamotoBlock(tampered, true).merkle_root_valid).toBe(false);
+  });
+
+  it('should produce a tx merkle inclusion proof', () => {
+    const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+    const txid = '0x1578f4cbec524cefaebb3cb599fdf003cbce27be0eb7e8bfb864ffa4715fb0d3';
+    const proof = getTxMerkleProof(blockBuffer, txid, 'nakamoto');
+    expect(proof).toEqual({
+      tx_index: 0,
+      merkle_root: '0x080d35f6c5c02929a00fca1cc6f00a1c3828d905eb61e002ffd4e48f1ecef29d',
+      path: [{ order: 'right', hash: '0x3dd37793e6e0e5253c1daac0cbf4d1375036f211327f106b158bea7f6451fefd' }],
+    });
+    expect(verifyTxMerkleProof(txid, proof)).toBe(true);
+    expect(verifyTxMerkleProof('0x' + '00'.repeat(32), proof)).toBe(false);
+    expect(() => getTxMerkleProof(blockBuffer, '0x' + '00'.repeat(32), 'nakamoto')).toThrow('not found in block');
+  });
+
+  it('should verify signer signatures against a reward set', () => {
+    const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+    const rewardSet = fixtureSignerKeys.map(signing_key => ({ signing_key, weight: 2 }));
+    // a signer that did not sign
+    rewardSet.push({ signing_key: '0x02' + '11'.repeat(32), weight: 8 });
+
+    const result = verifyNakamotoBlockSignerSignatures(blockBuffer, rewardSet);
+    expect(result.threshold_met).toBe(true);
+    expect(result.total_weight).toBe(56);
+    expect(result.threshold).toBe(40);
+    expect(result.signed_weight).toBe(48);
+    expect(result.signers).toHaveLength(24);
+    expect(result.signers[0]).toEqual({ index: 0, signing_key: fixtureSignerKeys[0], weight: 2 });
+    expect(result.error).toBeNull();
+
+    rewardSet[24].weight = 100;
+    expect(verifyNakamotoBlockSignerSignatures(blockBuffer, rewardSet).threshold_met).toBe(false);
+
+    const missingSigner = verifyNakamotoBlockSignerSignatures(blockBuffer, rewardSet.slice(1));
+    expect(missingSigner.threshold_met).toBe(false);
+    expect(missingSigner.error).toBe('Signature 0 is from a signer not in the reward set');
+  });
+
+  it('should verify the miner signature', () => {
+    const blockBuffer = fs.readFileSync(path.join(__dirname, 'fixtures/nakamoto-block.bin'));
+    // hash160 of the key that signed the block's tenure change tx
+    const result = verifyNakamotoBlockMinerSignature(blockBuffer, '0x37e79a837b4071a1fc6c1b49208e7d2141a25905');
+    expect(result).toEqual({
+      valid: true,
+      miner_public_key: '0x035e291c2a379e7e47809859466378c0f036db49ce1382bcd72b97794aeb9e5324',
+    });
+    expect(verifyNakamotoBlockMinerSignature(blockBuffer, '0x' + '00'.repeat(20)).valid).toBe(false);
+  });
 });
 
 describe('Stacks 2.x block decoding', () => {
@@ -102,6 +207,11 @@ describe('Stacks 2.x block decoding', () => {
     expect(result.header.block_hash).toMatch(/^0x[0-9a-f]{64}$/);
     expect(result.block_hash).toMatch(/^0x[0-9a-f]{64}$/);
     expect(result.block_hash).toBe(result.header.block_hash);
+
+    // an empty block has an all-zeros merkle root
+    expect(decodeStacksBlock(blockHex, true).merkle_root_valid).toBe(false);
+    const emptyRootBlockHex = blockHex.replace('33'.repeat(32), '00'.repeat(32));
+    expect(decodeStacksBlock(emptyRootBlockHex, true).merkle_root_valid).toBe(true);
   });
 
   it('should handle invalid block data gracefully', () => {
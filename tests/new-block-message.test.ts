@@ -0,0 +1,112 @@
+import { ClarityTypeID, DeserializeError, decodeNewBlockMessage, decodeTransaction, TxPayloadTypeID } from '../index';
+
+// STX token transfer of 2000000 uSTX
+const rawTx = '0x00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000';
+
+function newBlockMessage(events: unknown[]): string {
+  return JSON.stringify({
+    block_hash: '0x' + '11'.repeat(32),
+    block_height: 150000,
+    transactions: [
+      { txid: '0xb81f2fdcc66926d846a4cfdad426a66a2a5a183208bffcbc1e0c1a453e662171', tx_index: 0, status: 'success', raw_tx: rawTx, raw_result: '0x0703' },
+      // burnchain STX transfer operation
+      { txid: '0x' + '22'.repeat(32), tx_index: 1, status: 'success', raw_tx: '0x00', raw_result: '0x0703' },
+    ],
+    events,
+    matured_miner_rewards: [],
+  });
+}
+
+test('decode new block message', () => {
+  const message = decodeNewBlockMessage(newBlockMessage([
+    {
+      txid: '0x' + '22'.repeat(32),
+      event_index: 0,
+      committed: true,
+      type: 'stx_transfer_event',
+      stx_transfer_event: { sender: 'SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B', recipient: 'SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92', amount: '100', memo: '' },
+    },
+    {
+      txid: '0x' + '22'.repeat(32),
+      event_index: 1,
+      committed: true,
+      type: 'contract_event',
+      // (tuple (a u1) (b (some "x")))
+      contract_event: { contract_identifier: 'SP000000000000000000002Q6VF78.pox-4', topic: 'print', raw_value: '0x0c000000020161010000000000000000000000000000000101620a0d0000000178' },
+    },
+    {
+      txid: '0x' + '22'.repeat(32),
+      event_index: 2,
+      committed: true,
+      type: 'nft_mint_event',
+      nft_mint_event: { asset_identifier: 'SP000000000000000000002Q6VF78.nft::token', recipient: 'SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B', raw_value: '0x0100000000000000000000000000000001' },
+    },
+  ]));
+
+  expect(message.block_height).toBe(150000);
+  expect(message.matured_miner_rewards).toEqual([]);
+
+  const [tx, burnOp] = message.transactions;
+  expect(tx.decoded_tx).toEqual(decodeTransaction(rawTx));
+  expect(tx.decoded_tx?.payload.type_id).toBe(TxPayloadTypeID.TokenTransfer);
+  expect(tx.decoded_result).toEqual({ repr: '(ok true)', hex: '0x0703', type_id: ClarityTypeID.ResponseOk, value: { repr: 'true', hex: '0x03', type_id: ClarityTypeID.BoolTrue, value: true } });
+  expect(burnOp.decoded_tx).toBeNull();
+  expect(burnOp.raw_tx).toBe('0x00');
+
+  const [stxTransfer, contractEvent, nftMint] = message.events;
+  expect(stxTransfer.stx_transfer_event).toEqual({ sender: 'SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B', recipient: 'SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92', amount: '100', memo: '' });
+  expect(contractEvent.contract_event?.decoded_value.repr).toBe('(tuple (a u1) (b (some "x")))');
+  expect(contractEvent.contract_event?.topic).toBe('print');
+  expect(nftMint.nft_mint_event?.decoded_value).toEqual({ repr: 'u1', hex: '0x0100000000000000000000000000000001', type_id: ClarityTypeID.UInt, value: '1' });
+});
+
+test('decode new block message with output options', () => {
+  const message = decodeNewBlockMessage(newBlockMessage([
+    {
+      txid: '0x' + '22'.repeat(32),
+      event_index: 0,
+      committed: true,
+      type: 'nft_mint_event',
+      nft_mint_event: { asset_identifier: 'SP000000000000000000002Q6VF78.nft::token', recipient: 'SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B', raw_value: '0x0100000000000000000000000000000001' },
+    },
+  ]), { profile: 'json', numbers: 'bigint' });
+  expect(message.transactions[0].decoded_result).toEqual({ type: '(response bool UnknownType)', value: { type: 'bool', value: true }, success: true });
+  expect(message.events[0].nft_mint_event?.decoded_value).toEqual({ type: 'uint', value: 1n });
+  const decodedTx = message.transactions[0].decoded_tx;
+  if (decodedTx?.payload.type_id !== TxPayloadTypeID.TokenTransfer) {
+    throw new Error('Expected a token transfer');
+  }
+  expect(decodedTx.payload.amount).toBe(2000000n);
+});
+
+test('decode new block message keeps the values JSON.parse returns', () => {
+  const json = '{"z": {"b": 1, "a": 2, "10": 3}, "__proto__": {"polluted": true}, "big": 9007199254740993, "float": 0.30000000000000004, "transactions": [], "events": [{"type": "burn", "tx": {"__proto__": null}}]}';
+  const message = decodeNewBlockMessage(json) as any;
+  const parsed = JSON.parse(json);
+  expect(Object.keys(message)).toEqual(Object.keys(parsed));
+  expect(Object.keys(message.z)).toEqual(['10', 'b', 'a']);
+  expect(message.big).toBe(parsed.big);
+  expect(message.float).toBe(parsed.float);
+  expect(Object.getPrototypeOf(message)).toBe(Object.prototype);
+  expect(Object.getOwnPropertyDescriptor(message, '__proto__')?.value).toEqual({ polluted: true });
+  expect(message.polluted).toBeUndefined();
+  expect(Object.getOwnPropertyDescriptor(message.events[0].tx, '__proto__')?.value).toBeNull();
+  expect(Object.getPrototypeOf(message.events[0].tx)).toBe(Object.prototype);
+});
+
+test('decode new block message errors', () => {
+  let error: DeserializeError | undefined;
+  try {
+    decodeNewBlockMessage(newBlockMessage([
+      { txid: '0x' + '22'.repeat(32), event_index: 0, committed: true, type: 'contract_event', contract_event: { contract_identifier: 'SP000000000000000000002Q6VF78.pox-4', topic: 'print', raw_value: '0x01' } },
+    ]));
+  } catch (e) {
+    error = e as DeserializeError;
+  }
+  expect(error).toBeInstanceOf(DeserializeError);
+  expect(error?.code).toBe('UnexpectedEof');
+  expect(error?.path).toBe('events[0].contract_event.raw_value');
+  expect(error?.message).toMatch(/^Failed to decode new block message: /);
+
+  expect(() => decodeNewBlockMessage('{"transactions": [')).toThrow('Error parsing new block message JSON');
+});
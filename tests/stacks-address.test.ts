@@ -1,4 +1,17 @@
-import { decodeClarityValueToPrincipal, stacksAddressFromParts } from '../index.js';
+import {
+  decodeClarityValueToPrincipal,
+  multisigToStacksAddress,
+  publicKeyToStacksAddress,
+  stacksAddressFromParts,
+  TxSpendingConditionMultiSigHashMode,
+  TxSpendingConditionSingleSigHashMode,
+} from '../index.js';
+
+const publicKeys = [
+  '0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798',
+  '02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5',
+  '02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9',
+];
 
 test('stacks address from bytes and hash160 hex', () => {
   let address = stacksAddressFromParts(26, '0xcd1f5bc9aa49e7417cee3e5dba1a92567da41af6');
@@ -15,3 +28,36 @@ test('stacks address from clarity value', () => {
   const address = decodeClarityValueToPrincipal(inputBytes);
   expect(address).toBe('SP2GKVKM12JZ0YW3ZJH3GMBJYGVNM0BS94ERA45AM');
 });
+
+test('stacks address from public key', () => {
+  expect(publicKeyToStacksAddress(publicKeys[0], TxSpendingConditionSingleSigHashMode.P2PKH, 'mainnet'))
+    .toBe('SP1THWXQ8368SDN2MJGE4BMDKMCHZ2GSVTS1X0BPM');
+  expect(publicKeyToStacksAddress(Buffer.from(publicKeys[0], 'hex'), TxSpendingConditionSingleSigHashMode.P2WPKH, 'testnet'))
+    .toBe('SN2YFXDS8PP22AFAZ7XRBSDW0X7QJ32K8YGY8YW81');
+  // signer of a mainnet token transfer, recovered from its signature
+  expect(publicKeyToStacksAddress('0x03e356007964fc225a44c38352899c41e6293a97f8d8115998ae7e97184704c092', TxSpendingConditionSingleSigHashMode.P2PKH, 'mainnet'))
+    .toBe('SP2R0VR6CFDF533VX3S1XDSFCQN9E1K8C5WS5Q9JC');
+});
+
+test('stacks address from multisig public keys', () => {
+  expect(multisigToStacksAddress(publicKeys, 2, TxSpendingConditionMultiSigHashMode.P2SH, 'mainnet'))
+    .toBe('SMAZR1TMWWZBGQ8WQKG8F1QTVDSJ1V5RVGMRGMFW');
+  expect(multisigToStacksAddress(publicKeys, 2, TxSpendingConditionMultiSigHashMode.P2SHNonSequential, 'mainnet'))
+    .toBe('SMAZR1TMWWZBGQ8WQKG8F1QTVDSJ1V5RVGMRGMFW');
+  expect(multisigToStacksAddress(publicKeys, 2, TxSpendingConditionMultiSigHashMode.P2WSH, 'testnet'))
+    .toBe('SN34NXXY925X5CNRW5QE49SFXHEH9THCRJC52J7J4');
+  expect(() => multisigToStacksAddress(publicKeys, 4, TxSpendingConditionMultiSigHashMode.P2SH, 'mainnet'))
+    .toThrow('Invalid number of required signatures 4 for 3 public keys');
+});
+
+test('stacks address number arguments must be integers in range', () => {
+  expect(() => stacksAddressFromParts(26.5, '0xcd1f5bc9aa49e7417cee3e5dba1a92567da41af6')).toThrow('`version` must be an integer 0-255');
+  expect(() => publicKeyToStacksAddress(publicKeys[0], 0.5 as TxSpendingConditionSingleSigHashMode, 'mainnet'))
+    .toThrow('`hashMode` must be an integer 0-255');
+  for (const signaturesRequired of [1.5, 2.7, -1, NaN, Infinity]) {
+    expect(() => multisigToStacksAddress(publicKeys, signaturesRequired, TxSpendingConditionMultiSigHashMode.P2SH, 'mainnet'))
+      .toThrow('`signaturesRequired` must be an integer 0-65535');
+  }
+  expect(() => multisigToStacksAddress(publicKeys, 2, 256 as TxSpendingConditionMultiSigHashMode, 'mainnet'))
+    .toThrow('`hashMode` must be an integer 0-255');
+});
@@ -0,0 +1,111 @@
+import { ClarityTypeID, DeserializeError, decodeSip010PrintMemo, decodeSip010TransferArgs, decodeTokenEvent, TokenEventName } from '../index';
+
+const sender = 'SP2JXKMSH007NPYAQHKJPQMAQYAD90NQGTVJVQ02B';
+const recipient = 'SPTDVH38C1EQWPV5YABG00NEAPTN9XEXMJHSEW92';
+
+test('decode ft token events', () => {
+  const transfer = decodeTokenEvent({
+    type: 'ft_transfer_event',
+    ft_transfer_event: { asset_identifier: 'SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex', sender, recipient, amount: '1000' },
+  });
+  expect(transfer).toEqual({
+    name: TokenEventName.FtTransfer,
+    asset: {
+      contract_id: 'SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex',
+      contract_address: 'SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9',
+      contract_name: 'token-alex',
+      asset_name: 'alex',
+    },
+    sender,
+    recipient,
+    amount: '1000',
+  });
+
+  const burn = decodeTokenEvent({
+    type: 'ft_burn_event',
+    ft_burn_event: { asset_identifier: 'SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex', sender, amount: '7' },
+  });
+  expect(burn?.name).toBe(TokenEventName.FtBurn);
+  expect(burn).not.toHaveProperty('recipient');
+
+  expect(decodeTokenEvent({ type: 'stx_transfer_event', stx_transfer_event: { sender, recipient, amount: '1' } })).toBeNull();
+  expect(() => decodeTokenEvent({
+    type: 'ft_mint_event',
+    ft_mint_event: { asset_identifier: 'token-alex', recipient, amount: '1' },
+  })).toThrow('Error decoding token event: Invalid asset identifier: token-alex');
+});
+
+test('decode nft token events', () => {
+  const mint = decodeTokenEvent({
+    type: 'nft_mint_event',
+    nft_mint_event: { asset_identifier: 'SP000000000000000000002Q6VF78.nft::token', recipient, raw_value: '0x0100000000000000000000000000000001' },
+  });
+  expect(mint?.name).toBe(TokenEventName.NftMint);
+  if (mint?.name !== TokenEventName.NftMint) {
+    throw new Error('Expected an NFT mint');
+  }
+  expect(mint.asset.asset_name).toBe('token');
+  expect(mint.recipient).toBe(recipient);
+  expect(mint.value).toEqual({ repr: 'u1', hex: '0x0100000000000000000000000000000001', type_id: ClarityTypeID.UInt, value: '1' });
+});
+
+test('decode token events with output options', () => {
+  const transfer = decodeTokenEvent({
+    type: 'ft_transfer_event',
+    ft_transfer_event: { asset_identifier: 'SP3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3PA0KBR9.token-alex::alex', sender, recipient, amount: '1000' },
+  }, { numbers: 'bigint' });
+  if (transfer?.name !== TokenEventName.FtTransfer) {
+    throw new Error('Expected an FT transfer');
+  }
+  expect(transfer.amount).toBe(1000n);
+
+  const event = {
+    type: 'nft_burn_event',
+    nft_burn_event: { asset_identifier: 'SP000000000000000000002Q6VF78.nft::token', sender, raw_value: '0x0100000000000000000000000000000001' },
+  };
+  const burn = decodeTokenEvent(event, { profile: 'json' });
+  if (burn?.name !== TokenEventName.NftBurn) {
+    throw new Error('Expected an NFT burn');
+  }
+  expect(burn.value).toEqual({ type: 'uint', value: '1' });
+  const minimal = decodeTokenEvent(event, { profile: 'minimal', numbers: 'bigint' });
+  if (minimal?.name !== TokenEventName.NftBurn) {
+    throw new Error('Expected an NFT burn');
+  }
+  expect(minimal.value).toEqual({ type_id: ClarityTypeID.UInt, value: 1n });
+  expect(() => decodeTokenEvent(event, { profile: 'compact' as any })).toThrow('Unknown profile `compact`');
+});
+
+test('decode SIP-010 transfer args', () => {
+  // (transfer u500 'SP2JX... 'SPTDV... (some 0x0000 "exchange-id 42"))
+  const args = '0x0000000401000000000000000000000000000001f40516a5d9d331000f5b79578ce56bd157f29a9056f0d6051634ddc468605d7e5b65f2970002ae55b554f5dda40a0200000010000065786368616e67652d6964203432';
+  expect(decodeSip010TransferArgs(args)).toEqual({
+    amount: '500',
+    sender,
+    recipient,
+    memo: 'exchange-id 42',
+    memo_hex: '0x000065786368616e67652d6964203432',
+  });
+
+  // (transfer u500 'SP2JX... 'SPTDV... none)
+  const noMemo = decodeSip010TransferArgs(Buffer.from('0000000401000000000000000000000000000001f40516a5d9d331000f5b79578ce56bd157f29a9056f0d6051634ddc468605d7e5b65f2970002ae55b554f5dda409', 'hex'));
+  expect(noMemo.memo).toBeNull();
+  expect(noMemo.memo_hex).toBeNull();
+
+  expect(() => decodeSip010TransferArgs('0x0000000101000000000000000000000000000001f4')).toThrow('Expected 4 SIP-010 transfer arguments, got 1');
+  let error: DeserializeError | undefined;
+  try {
+    decodeSip010TransferArgs('0x0000000101');
+  } catch (e) {
+    error = e as DeserializeError;
+  }
+  expect(error).toBeInstanceOf(DeserializeError);
+  expect(error?.path).toBe('[0]');
+});
+
+test('decode SIP-010 print memo', () => {
+  // 0x68656c6c6f0a ("hello\n")
+  expect(decodeSip010PrintMemo('0x0a020000000668656c6c6f0a')).toEqual({ memo: 'hello', memo_hex: '0x68656c6c6f0a' });
+  expect(decodeSip010PrintMemo('0x020000000668656c6c6f0a')?.memo).toBe('hello');
+  expect(decodeSip010PrintMemo('0x0100000000000000000000000000000001')).toBeNull();
+});
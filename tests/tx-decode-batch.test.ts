@@ -0,0 +1,62 @@
+import { decodeTransaction, decodeTransactionList, decodeTransactions, DecodedTxResult, DeserializeError } from '../index';
+
+const txs = [
+  '0x0000000001040089f5fd1f719e4449c980de38e3504be6770a2698000000000000014500000000000001f400008b510c9e20dc22040953d9d7eabf2038008fa4d89a5a6cb78bb9d513e75cd0df3924af9ce3b5f185705bc2f6ba3071710ec6a8803ed6da4addc40a05a01ee0f503020000000102021689f5fd1f719e4449c980de38e3504be6770a269816a6a7a70f41adbe8eae708ed7ec2cbf41a272182014626974636f696e2d6d6f6e6b6579732d6c61627314626974636f696e2d6d6f6e6b6579732d6c61627301000000000000000000000000000008ba1002162bcf9762d5b90bc36dc1b4759b1727690f92ddd30e6d61726b6574706c6163652d76340a6c6973742d6173736574000000040616a6a7a70f41adbe8eae708ed7ec2cbf41a272182014626974636f696e2d6d6f6e6b6579732d6c61627301000000000000000000000000000008ba010000000000000000000000000c84588001000000000000000000000000000000c8',
+  '0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000',
+];
+
+function frameTxs(txBuffers: Buffer[]): Buffer {
+  const count = Buffer.alloc(4);
+  count.writeUInt32BE(txBuffers.length);
+  const parts = [count];
+  for (const tx of txBuffers) {
+    const len = Buffer.alloc(4);
+    len.writeUInt32BE(tx.length);
+    parts.push(len, tx);
+  }
+  return Buffer.concat(parts);
+}
+
+test('decodeTransactions matches decodeTransaction', () => {
+  const inputs = [txs[0], Buffer.from(txs[1].slice(2), 'hex')];
+  const results = decodeTransactions(inputs);
+  expect(results).toEqual(txs.map(tx => decodeTransaction(tx)));
+  expect(decodeTransactions([])).toEqual([]);
+});
+
+test('decodeTransactions returns per-item errors', () => {
+  const results = decodeTransactions([txs[0], '0x0000', txs[1]]);
+  expect((results[0] as DecodedTxResult).tx_id).toBe(decodeTransaction(txs[0]).tx_id);
+  expect(results[1]).toBeInstanceOf(DeserializeError);
+  expect((results[1] as DeserializeError).code).toBe('UnexpectedEof');
+  expect((results[1] as DeserializeError).message).toBe(
+    'Failed to decode transaction: Unexpected end of input (at byte offset 2)'
+  );
+  expect((results[2] as DecodedTxResult).tx_id).toBe(decodeTransaction(txs[1]).tx_id);
+  expect(() => decodeTransactions([1 as any])).toThrow('Array items must be hex strings or Buffers');
+});
+
+test('decodeTransactionList', () => {
+  const txBuffers = txs.map(tx => Buffer.from(tx.slice(2), 'hex'));
+  const results = decodeTransactionList(frameTxs([...txBuffers, Buffer.from('00', 'hex')]));
+  expect(results.slice(0, 2)).toEqual(txs.map(tx => decodeTransaction(tx)));
+  expect(results[2]).toBeInstanceOf(DeserializeError);
+
+  // the second transaction's length prefix points past the end of the input
+  const truncated = frameTxs(txBuffers).subarray(0, 8 + txBuffers[0].length + 10);
+  expect(() => decodeTransactionList(truncated)).toThrow(
+    'Failed to decode transaction list: Unexpected end of input (at `[1]`'
+  );
+
+  const trailing = Buffer.concat([frameTxs(txBuffers), Buffer.from('00', 'hex')]);
+  expect(() => decodeTransactionList(trailing)).toThrow(
+    'Failed to decode transaction list: 1 trailing bytes after the last transaction'
+  );
+
+  // a count larger than the input can hold fails to decode instead of allocating for it
+  const oversized = frameTxs(txBuffers);
+  oversized.writeUInt32BE(0xffffffff, 0);
+  expect(() => decodeTransactionList(oversized)).toThrow(
+    'Failed to decode transaction list: Unexpected end of input (at `[2]`'
+  );
+});
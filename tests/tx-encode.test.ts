@@ -0,0 +1,72 @@
+import {
+  AnchorModeID,
+  decodeTransaction,
+  DecodedTxSpendingConditionSingleSig,
+  encodeTransaction,
+  PostConditionAuthFlag,
+  PostConditionModeID,
+  TransactionVersion,
+  TxPayloadTokenTransfer,
+  TxPayloadTypeID,
+} from '../index.js';
+
+test('encode tx - decode round trip', () => {
+  const inputs = [
+    '0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000',
+    '0x00000000010400d27548439b50c8d8ca86756151bd6e07059510a0000000000000002f00000000000027100000ffdf728bc8d1423ba751e164a153b160c48980e727c98971816096534ee6b3a40e81b31786a547cc64fcb52d592939ec5a5886d8ec8053a91e22e76cb9ef4d4403020000000000051634ddc468605d7e5b65f2970002ae55b554f5dda400000000001e848000000000000000000000000000000000000000000000000000000000000000000000',
+    '0x80000000000400000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000030200000000060205706f782d320000003b3b3b20506f5820746573746e657420636f6e7374616e74730a3b3b204d696e2f6d6178206e756d626572206f6620726577617264206379636c6573',
+    '0x8080000000040055a0a92720d20398211cd4c7663d65d018efcc1f00000000000000030000000000000000010118da31f542913e8c56961b87ee4794924e655a28a2034e37ef4823eeddf074747285bd6efdfbd84eecdf62cffa7c1864e683c688f4c105f4db7429066735b4e2010200000000050000000000000000000000000000000000000000000000000000000000000000061aba27f99e007c7f605a8305e318c1abde3cd220ac0b68656c6c6f5f776f726c64',
+    '0x808000000004001dc27eba0247f8cc9575e7d45e50a0bc7e72427d000000000000001d000000000000000000011dc72b6dfd9b36e414a2709e3b01eb5bbdd158f9bc77cd2ca6c3c8b0c803613e2189f6dacf709b34e8182e99d3a1af15812b75e59357d9c255c772695998665f010200000000076f2ff2c4517ab683bf2d588727f09603cc3e9328b9c500e21a939ead57c0560af8a3a132bd7d56566f2ff2c4517ab683bf2d588727f09603cc3e932828dcefb98f6b221eef731cabec7538314441c1e0ff06b44c22085d41aae447c1000000010014ff3cb19986645fd7e71282ad9fea07d540a60e',
+  ];
+  for (const input of inputs) {
+    expect(encodeTransaction(decodeTransaction(input))).toBe(input);
+  }
+});
+
+test('encode tx - strip signature', () => {
+  const decoded = decodeTransaction('0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000');
+  const origin = decoded.auth.origin_condition as DecodedTxSpendingConditionSingleSig;
+  origin.signature = '0x' + '00'.repeat(65);
+  const unsigned = decodeTransaction(encodeTransaction(decoded));
+  expect((unsigned.auth.origin_condition as DecodedTxSpendingConditionSingleSig).signature).toBe(origin.signature);
+  expect(unsigned.payload).toEqual(decoded.payload);
+});
+
+test('encode tx - synthesize token transfer', () => {
+  const encoded = encodeTransaction({
+    version: TransactionVersion.Testnet,
+    chain_id: 0x80000000,
+    auth: {
+      type_id: PostConditionAuthFlag.Standard,
+      origin_condition: {
+        hash_mode: 0,
+        signer: { address: 'ST2RS0YJZ2QH5VYXQ91X06B9QYR90BNGJETA5616X' },
+        nonce: '5',
+        tx_fee: '180',
+        key_encoding: 0,
+        signature: '0x' + '00'.repeat(65),
+      },
+    },
+    anchor_mode: AnchorModeID.Any,
+    post_condition_mode: PostConditionModeID.Deny,
+    post_conditions: [],
+    payload: {
+      type_id: TxPayloadTypeID.TokenTransfer,
+      recipient: { type_id: 5, address: 'ST3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3M29079Z' },
+      amount: '1000',
+      memo_hex: '0x68656c6c6f',
+    },
+  } as any);
+  const decoded = decodeTransaction(encoded);
+  const payload = decoded.payload as TxPayloadTokenTransfer;
+  expect(payload.amount).toBe('1000');
+  expect(payload.memo_hex).toBe('0x68656c6c6f' + '00'.repeat(29));
+  expect(payload.recipient.address).toBe('ST3K8BC0PPEVCV7NZ6QSRWPQ2JE9E5B6N3M29079Z');
+  expect(decoded.auth.origin_condition.signer.address).toBe('ST2RS0YJZ2QH5VYXQ91X06B9QYR90BNGJETA5616X');
+});
+
+test('encode tx - invalid input', () => {
+  const decoded = decodeTransaction('0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000');
+  (decoded.payload as any).type_id = 99;
+  expect(() => encodeTransaction(decoded)).toThrow(/Unknown payload type_id 99/);
+});
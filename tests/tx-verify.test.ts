@@ -0,0 +1,32 @@
+import { decodeTransaction, encodeTransaction, verifyTransaction, DecodedTxSpendingConditionSingleSig } from '../index.js';
+
+test('verify tx - single-sig', () => {
+  const result = verifyTransaction('0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000');
+  expect(result.valid).toBe(true);
+  expect(result.origin).toEqual({
+    valid: true,
+    public_keys: ['0x03e356007964fc225a44c38352899c41e6293a97f8d8115998ae7e97184704c092'],
+    error: null,
+  });
+  expect(result.sponsor).toBeNull();
+});
+
+test('verify tx - sponsored', () => {
+  const result = verifyTransaction('0x000000000105001f6000287c9ed40ba39b27fc4257ff30cda4e91d000000000000005c0000000000000000000188f35fb5444ebdfcfced4b267aa76e60b90fc38ff7f7c8d770fc21407a26650a78aded3af77a5f0d7ca2ba7dabeff0579ec1d891e715c55237cfd5737e42378000661506d48705f932af21abcff23046b216886e840000000000000a1a000000000007a12000003fe006c2b46c5c6a16bc0af6f065c63d6e6c5a40e51fec26a9768caab0acd7ca42401d9c8030f120d2005388c1d04c602a3a9177eb26534a4fd6a3897686b1490302000000010002161f6000287c9ed40ba39b27fc4257ff30cda4e91d0100000000bda999040216debc095099629badb11b9d5335e874d12f1f1d450e73656e642d6d616e792d6d656d6f0973656e642d6d616e79000000010b0000001e0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162c34acd88561d6905f635e8eb59a36395b8c42a1047573747801000000000000000000000000002e640b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f1818ba832287fc71375c3e78bae2c8c64f6bb01047573747801000000000000000000000000008d51b10c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516267248c930110d411248d9ff16e63a8ddfa0c641047573747801000000000000000000000000121d39d80c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e73e15214dab160f2b78c935279e99a4a8ce83fc047573747801000000000000000000000000003beba90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166176366f88666f63ee0280db409fc8bcdc5eccbd047573747801000000000000000000000000056fb9430c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cb8de306c17412fc869c8337fa7b6b6f3a17cc160475737478010000000000000000000000000014018b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516477b4de6bfcf52f8cfec6854720b7a6982193eb704757374780100000000000000000000000005f518e20c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051668210b7f12b4ee43f374da9a59a51ecc8d0d89230475737478010000000000000000000000000029a8780c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05163b8e6ef49d23e432100aeca022c89555b7209aad04757374780100000000000000000000000010152ebb0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051694401aeb5474061ac390f723cb1826feb1b90448047573747801000000000000000000000000482a34380c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051686e0feea7bd59ae51dd0d2e47c7220406348e73804757374780100000000000000000000000000c938550c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516a52961a0d54cb3b9ce9a7092440dff4573cff58c04757374780100000000000000000000000000f19d340c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516130b7aee04f3eee627e103d3c33aea1d5339087d047573747801000000000000000000000000032aadd60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165db3d733033684784ebfa931b8e5f228bb247c4d04757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051600da0575e398c7b3d64f3df9d32ec57b9c0af3f704757374780100000000000000000000000000adf7280c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051607b7982ea3aaff67270d86e62436b0d08ea8d17804757374780100000000000000000000000010bc307b0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fd048b0a4e906580e4a5281ebef59f2d60585ac4047573747801000000000000000000000000076efb580c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05164ecd67a6d4ca15c0cb9cf7cc0bc0594ba10ce4ee047573747801000000000000000000000000016a6c4c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516f6641fafd4cdb742214558a43a5effeeb6061d76047573747801000000000000000000000000002a5b460c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166af9a2a4e295c9b23b7304f4bd14082b7101e7c304757374780100000000000000000000000002b299d90c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05166c317c39e65133aa7a414eb75e516dc59536f55004757374780100000000000000000000000018b0beba0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516e13a94997d076788fa000588d7bfb053b2cfa3c3047573747801000000000000000000000000003b2c660c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05165b7ee1d697120fbe6a761df99cddb46774cf1ca50475737478010000000000000000000000000005da110c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051688e4220b60f2222e115d8a8b1e46b09aed368854047573747801000000000000000000000000056d4c270c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051609973469a88ffcbf70651d8078442b9eedd47f590475737478010000000000000000000000000002e6410c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05162bbf6ea8291431ec60fcb86c066ff375071ce7d8047573747801000000000000000000000000001e57d60c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516fbd72ce502f8a46db5a6e473a4e2c8830bc1a43904757374780100000000000000000000000001a593880c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f051667a3c0f1b06075e9f7b9fa4d3e5d6492041c295a0475737478010000000000000000000000000005cb390c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f0516cba8da4a454ae7ad02cb71fb523ddd720d73ad3a0475737478010000000000000000000000000176f41c0c00000003046d656d6f0200000017726577617264206379636c6520233236207061796f757402746f05169bb88a9c3db00f126f08f380d28b348458ebb6f7047573747801000000000000000000000000025c3d73');
+  expect(result.valid).toBe(true);
+  expect(result.origin.public_keys).toEqual(['0x02d524efe02a947e73ed1418b5b89a47e9dcb8361255f64a419415d35623af11ec']);
+  expect(result.sponsor).toEqual({
+    valid: true,
+    public_keys: ['0x02ec053f88651c60a3855a1f977276a374b9d2f6b895f8be26e3221021752235e9'],
+    error: null,
+  });
+});
+
+test('verify tx - modified fee', () => {
+  const decoded = decodeTransaction('0x00000000010400b00de0cc7b5e518f7d1e43d6e5ecbd52e0cd0c2f0000000000001ddc00000000000000000001827095db6a9de80e51323bc96a926874999472634fbdfd5adc36df48e2d6b01c7505244fba66e8812b761a2ea1f1e98253139db1ccd1c7ffaa14208dd489bf69010200000000040000000000000000000000000000000000000000000000000000000000000000');
+  const origin = decoded.auth.origin_condition as DecodedTxSpendingConditionSingleSig;
+  origin.tx_fee = (BigInt(origin.tx_fee) + 1n).toString();
+  const result = verifyTransaction(encodeTransaction(decoded));
+  expect(result.valid).toBe(false);
+  expect(result.origin.error).toBe('Signer hash does not equal hash of public key(s)');
+});